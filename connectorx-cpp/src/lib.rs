@@ -366,3 +366,120 @@ pub unsafe extern "C" fn connectorx_iter_next(
 pub unsafe extern "C" fn connectorx_set_thread_num(num: usize) {
     set_global_num_thread(num);
 }
+
+#[repr(C)]
+pub struct CXCatalogColumn {
+    name: *const c_char,
+    source_type: *const c_char,
+    // null if the source type cannot be loaded into arrow
+    arrow_type: *const FFI_ArrowSchema,
+    nullable: bool,
+}
+
+#[repr(C)]
+pub struct CXCatalogTable {
+    schema: *const c_char,
+    name: *const c_char,
+    is_view: bool,
+    columns: CXSlice<CXCatalogColumn>,
+}
+
+#[repr(C)]
+pub struct CXCatalog {
+    schemas: CXSlice<*const c_char>,
+    tables: CXSlice<CXCatalogTable>,
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn free_catalog(catalog: *mut CXCatalog) {
+    let res = Box::from_raw(catalog);
+
+    get_vec::<_>(res.schemas.ptr, res.schemas.len, res.schemas.capacity)
+        .into_iter()
+        .for_each(|schema| free_str(schema));
+
+    get_vec::<_>(res.tables.ptr, res.tables.len, res.tables.capacity)
+        .into_iter()
+        .for_each(|table| {
+            free_str(table.schema);
+            free_str(table.name);
+            get_vec::<_>(table.columns.ptr, table.columns.len, table.columns.capacity)
+                .into_iter()
+                .for_each(|col| {
+                    free_str(col.name);
+                    free_str(col.source_type);
+                    if !col.arrow_type.is_null() {
+                        Arc::from_raw(col.arrow_type);
+                    }
+                })
+        });
+}
+
+/// List the schemas of the database of `conn` and the tables of `schema`, or of every schema if
+/// `schema` is null. Returns null if `conn` or `schema` is not valid UTF-8, `conn` is not a valid
+/// connection string or the catalog cannot be read from the database. A returned catalog is
+/// released with `free_catalog`.
+///
+/// # Safety
+///
+/// `conn` must point to a nul-terminated string and `schema` must be null or point to one.
+#[no_mangle]
+pub unsafe extern "C" fn connectorx_get_catalog(
+    conn: *const c_char,
+    schema: *const c_char,
+) -> *mut CXCatalog {
+    let read_catalog = || -> Option<Catalog> {
+        let conn_str = unsafe { CStr::from_ptr(conn) }.to_str().ok()?;
+        let schema_str = if schema.is_null() {
+            None
+        } else {
+            Some(unsafe { CStr::from_ptr(schema) }.to_str().ok()?)
+        };
+        let source_conn = SourceConn::try_from(conn_str).ok()?;
+        get_catalog(&source_conn, schema_str).ok()
+    };
+    let catalog = match read_catalog() {
+        Some(catalog) => catalog,
+        None => return std::ptr::null_mut(),
+    };
+
+    let to_c_str = |s: String| -> *const c_char {
+        CString::new(s).expect("new CString error").into_raw() as *const c_char
+    };
+
+    let schemas: Vec<*const c_char> = catalog.schemas.into_iter().map(to_c_str).collect();
+    let tables: Vec<CXCatalogTable> = catalog
+        .tables
+        .into_iter()
+        .map(|table| {
+            let columns: Vec<CXCatalogColumn> = table
+                .columns
+                .into_iter()
+                .map(|col| CXCatalogColumn {
+                    name: to_c_str(col.name),
+                    source_type: to_c_str(col.source_type),
+                    arrow_type: match col.arrow_type {
+                        Some(dt) => Arc::into_raw(Arc::new(
+                            FFI_ArrowSchema::try_from(&dt).expect("export schema c"),
+                        )),
+                        None => std::ptr::null(),
+                    },
+                    nullable: col.nullable,
+                })
+                .collect();
+            CXCatalogTable {
+                schema: to_c_str(table.schema),
+                name: to_c_str(table.name),
+                is_view: table.kind == TableKind::View,
+                columns: CXSlice::<_>::new_from_vec(columns),
+            }
+        })
+        .collect();
+
+    let res = Box::new(CXCatalog {
+        schemas: CXSlice::<_>::new_from_vec(schemas),
+        tables: CXSlice::<_>::new_from_vec(tables),
+    });
+
+    Box::into_raw(res)
+}
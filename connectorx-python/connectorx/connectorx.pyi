@@ -16,6 +16,22 @@ class _DataframeInfos(TypedDict):
     headers: list[_Header]
     block_infos: list[PandasBlockInfo]
 
+class _CatalogColumn(TypedDict):
+    name: str
+    source_type: str
+    arrow_type: str | None
+    nullable: bool
+
+class _CatalogTable(TypedDict):
+    schema: str
+    name: str
+    kind: Literal["table", "view"]
+    columns: list[_CatalogColumn]
+
+class _Catalog(TypedDict):
+    schemas: list[str]
+    tables: list[_CatalogTable]
+
 _ArrowInfos = tuple[list[_Header], list[list[tuple[_ArrowArrayPtr, _ArrowSchemaPtr]]]]
 
 @overload
@@ -26,6 +42,7 @@ def read_sql(
     queries: list[str] | None,
     partition_query: dict[str, Any] | None,
     pre_execution_queries: list[str] | None,
+    post_execution_queries: list[str] | None,
     **kwargs
 ) -> _DataframeInfos: ...
 @overload
@@ -36,6 +53,7 @@ def read_sql(
     queries: list[str] | None,
     partition_query: dict[str, Any] | None,
     pre_execution_queries: list[str] | None,
+    post_execution_queries: list[str] | None,
     **kwargs
 ) -> _ArrowInfos: ...
 def partition_sql(conn: str, partition_query: dict[str, Any]) -> list[str]: ...
@@ -45,3 +63,4 @@ def get_meta(
     protocol: Literal["csv", "binary", "cursor", "simple", "text"] | None,
     query: str,
 ) -> _DataframeInfos: ...
+def get_catalog(conn: str, schema: str | None) -> _Catalog: ...
@@ -44,6 +44,113 @@ def test_arrow(postgres_url: str) -> None:
     df.sort_values(by="test_int", inplace=True, ignore_index=True)
     assert_frame_equal(df, expected, check_names=True)
 
+def test_arrow_dictionary_columns(postgres_url: str) -> None:
+    import pyarrow as pa
+
+    query = "SELECT test_int, test_str FROM test_table"
+    table = read_sql(
+        postgres_url,
+        query,
+        return_type="arrow",
+        dictionary_columns=["test_str"],
+    )
+    assert table.schema.field("test_int").type == pa.int32()
+    assert table.schema.field("test_str").type == pa.dictionary(pa.int32(), pa.string())
+
+    table = table.sort_by("test_int")
+    assert table.column("test_str").to_pylist() == ["a", "str1", "str2", "b", "c", None]
+
+
+def test_arrow_view_types(postgres_url: str) -> None:
+    import pyarrow as pa
+
+    query = "SELECT test_int, test_str, test_str::bytea AS test_bytes FROM test_table"
+    table = read_sql(
+        postgres_url,
+        query,
+        return_type="arrow",
+        view_types=True,
+    )
+    assert table.schema.field("test_str").type == pa.string_view()
+    assert table.schema.field("test_bytes").type == pa.binary_view()
+
+    table = table.sort_by("test_int")
+    assert table.column("test_str").to_pylist() == ["a", "str1", "str2", "b", "c", None]
+
+
+def test_arrow_extension_types(postgres_url: str) -> None:
+    import uuid
+    import pyarrow as pa
+
+    query = "SELECT test_uuid, test_jsonb FROM test_types"
+    table = read_sql(
+        postgres_url,
+        query,
+        return_type="arrow",
+        extension_types=True,
+    )
+    uuid_type = table.schema.field("test_uuid").type
+    assert getattr(uuid_type, "storage_type", uuid_type) == pa.binary(16)
+    json_type = table.schema.field("test_jsonb").type
+    assert getattr(json_type, "storage_type", json_type) == pa.string()
+
+    # pyarrow with the `arrow.uuid` extension registered returns `uuid.UUID` values already
+    values = [
+        v if v is None or isinstance(v, uuid.UUID) else uuid.UUID(bytes=v)
+        for v in table.column("test_uuid").to_pylist()
+    ]
+    expected = read_sql(postgres_url, query, return_type="arrow")
+    assert values == [
+        None if v is None else uuid.UUID(v)
+        for v in expected.column("test_uuid").to_pylist()
+    ]
+
+
+def test_arrow_timestamp_options(postgres_url: str) -> None:
+    import pyarrow as pa
+
+    query = "SELECT test_timestamp, test_timestamptz FROM test_types"
+    table = read_sql(
+        postgres_url,
+        query,
+        return_type="arrow",
+        timestamp_unit="ms",
+        time_zone="Europe/Berlin",
+        naive_time_zone="UTC",
+    )
+    assert table.schema.field("test_timestamp").type == pa.timestamp("ms", tz="UTC")
+    assert table.schema.field("test_timestamptz").type == pa.timestamp(
+        "ms", tz="Europe/Berlin"
+    )
+
+    # the instants are unchanged, only truncated toward zero to the unit
+    expected = read_sql(postgres_url, query, return_type="arrow")
+    for name in ["test_timestamp", "test_timestamptz"]:
+        assert table.column(name).cast(pa.int64()).to_pylist() == [
+            None if v is None else int(v / 1000)
+            for v in expected.column(name).cast(pa.int64()).to_pylist()
+        ]
+
+
+def test_arrow_maps(postgres_url: str) -> None:
+    import pyarrow as pa
+
+    query = (
+        "SELECT 'a=>1, b=>NULL'::hstore AS test_hstore, "
+        "'{\"x\": 1, \"y\": null}'::jsonb AS test_json"
+    )
+    table = read_sql(
+        postgres_url,
+        query,
+        return_type="arrow",
+        json_maps="int64",
+    )
+    assert table.schema.field("test_hstore").type == pa.map_(pa.string(), pa.string())
+    assert table.schema.field("test_json").type == pa.map_(pa.string(), pa.int64())
+    assert table.column("test_hstore").to_pylist() == [[("a", "1"), ("b", None)]]
+    assert table.column("test_json").to_pylist() == [[("x", 1), ("y", None)]]
+
+
 def test_arrow_stream(postgres_url: str) -> None:
     import pyarrow as pa
     query = "SELECT * FROM test_table"
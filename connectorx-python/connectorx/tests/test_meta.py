@@ -4,7 +4,7 @@ import pandas as pd
 import pytest
 from pandas.testing import assert_frame_equal
 
-from .. import get_meta
+from .. import get_catalog, get_meta
 
 
 @pytest.fixture(scope="module")  # type: ignore
@@ -31,4 +31,19 @@ def test_get_meta(postgres_url: str) -> None:
             ),
         },
     )
-    assert_frame_equal(df, expected, check_names=True)
\ No newline at end of file
+    assert_frame_equal(df, expected, check_names=True)
+
+
+def test_get_catalog(postgres_url: str) -> None:
+    catalog = get_catalog(postgres_url, "public")
+    assert catalog["schemas"] == ["public"]
+    table = next(t for t in catalog["tables"] if t["name"] == "test_table")
+    assert table["schema"] == "public"
+    assert table["kind"] == "table"
+    assert [(c["name"], c["source_type"], c["arrow_type"]) for c in table["columns"]] == [
+        ("test_int", "Int4", "Int32"),
+        ("test_nullint", "Int4", "Int32"),
+        ("test_str", "Text", "Utf8"),
+        ("test_float", "Float8", "Float64"),
+        ("test_bool", "Bool", "Boolean"),
+    ]
@@ -280,6 +280,35 @@ def test_postgres_with_partition(postgres_url: str) -> None:
     assert_frame_equal(df, expected, check_names=True)
 
 
+@pytest.mark.parametrize("row_count", ["estimate", "none"])
+def test_postgres_without_count(postgres_url: str, row_count: str) -> None:
+    query = "SELECT * FROM test_table"
+    df = read_sql(
+        postgres_url,
+        query,
+        partition_on="test_int",
+        partition_range=(0, 2000),
+        partition_num=3,
+        row_count=row_count,
+    )
+    expected = pd.DataFrame(
+        index=range(6),
+        data={
+            "test_int": pd.Series([0, 1, 2, 3, 4, 1314], dtype="Int64"),
+            "test_nullint": pd.Series([5, 3, None, 7, 9, 2], dtype="Int64"),
+            "test_str": pd.Series(
+                ["a", "str1", "str2", "b", "c", None], dtype="object"
+            ),
+            "test_float": pd.Series([3.1, None, 2.2, 3, 7.8, -10], dtype="float64"),
+            "test_bool": pd.Series(
+                [None, True, False, False, None, True], dtype="boolean"
+            ),
+        },
+    )
+    df.sort_values(by="test_int", inplace=True, ignore_index=True)
+    assert_frame_equal(df, expected, check_names=True)
+
+
 def test_postgres_with_partition_without_partition_range(postgres_url: str) -> None:
     query = "SELECT * FROM test_table where test_float > 3"
     df = read_sql(
@@ -1141,3 +1170,14 @@ def test_postgres_vector_types(postgres_url: str) -> None:
         },
     )
     assert_frame_equal(df[['binary_vector', 'sparse_vector']], expected[['binary_vector', 'sparse_vector']], check_names=True)
+
+
+def test_postgres_progress_callback_error(postgres_url: str) -> None:
+    def callback(rows_done, total_rows, partitions_done, total_partitions, throughput):
+        raise RuntimeError("stop")
+
+    query = "SELECT * FROM test_table"
+    with pytest.raises(RuntimeError, match="stop"):
+        read_sql(postgres_url, query, progress_callback=callback)
+    with pytest.raises(RuntimeError, match="stop"):
+        read_sql(postgres_url, query, return_type="arrow", progress_callback=callback)
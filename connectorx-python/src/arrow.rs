@@ -66,7 +66,11 @@ impl PyRecordBatchIterator {
         mut slf: PyRefMut<'py, Self>,
         py: Python<'py>,
     ) -> PyResult<Option<Py<PyRecordBatch>>> {
-        match slf.0.next_batch() {
+        let batch = slf
+            .0
+            .try_next_batch()
+            .map_err(ConnectorXPythonError::from)?;
+        match batch {
             Some(rb) => {
                 let wrapped = PyRecordBatch(Some(rb));
                 let py_obj = Py::new(py, wrapped)?;
@@ -85,10 +89,21 @@ pub fn write_arrow<'py>(
     origin_query: Option<String>,
     queries: &[CXQuery<String>],
     pre_execution_queries: Option<&[String]>,
+    post_execution_queries: Option<&[String]>,
+    progress: Option<ProgressCallback>,
+    destination: ArrowDestination,
 ) -> Bound<'py, PyAny> {
     let ptrs = py.allow_threads(
         || -> Result<(Vec<String>, Vec<Vec<(uintptr_t, uintptr_t)>>), ConnectorXPythonError> {
-            let destination = get_arrow(source_conn, origin_query, queries, pre_execution_queries)?;
+            let (destination, _) = get_arrow_into(
+                destination,
+                source_conn,
+                origin_query,
+                queries,
+                pre_execution_queries,
+                post_execution_queries,
+                progress,
+            )?;
             let rbs = destination.arrow()?;
             Ok(to_ptrs(rbs))
         },
@@ -104,15 +119,17 @@ pub fn get_arrow_rb_iter<'py>(
     origin_query: Option<String>,
     queries: &[CXQuery<String>],
     pre_execution_queries: Option<&[String]>,
+    post_execution_queries: Option<&[String]>,
     batch_size: usize,
 ) -> Bound<'py, PyAny> {
-    let mut arrow_iter: Box<dyn RecordBatchIterator> = new_record_batch_iter(
+    let mut arrow_iter: Box<dyn RecordBatchIterator> = try_new_record_batch_iter(
         source_conn,
         origin_query,
         queries,
         batch_size,
         pre_execution_queries,
-    );
+        post_execution_queries,
+    )?;
 
     arrow_iter.prepare();
     let py_rb_iter = PyRecordBatchIterator(arrow_iter);
@@ -123,12 +140,14 @@ pub fn get_arrow_rb_iter<'py>(
 
 pub fn to_ptrs_rb(rb: RecordBatch) -> Vec<(uintptr_t, uintptr_t)> {
     let mut cols = vec![];
+    let schema = rb.schema();
 
-    for array in rb.columns().into_iter() {
+    // export the fields rather than the data types to keep their metadata, e.g. extension types
+    for (array, field) in rb.columns().iter().zip(schema.fields()) {
         let data = array.to_data();
         let array_ptr = Arc::new(arrow::ffi::FFI_ArrowArray::new(&data));
         let schema_ptr = Arc::new(
-            arrow::ffi::FFI_ArrowSchema::try_from(data.data_type()).expect("export schema c"),
+            arrow::ffi::FFI_ArrowSchema::try_from(field.as_ref()).expect("export schema c"),
         );
         cols.push((
             Arc::into_raw(array_ptr) as uintptr_t,
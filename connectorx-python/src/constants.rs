@@ -1,6 +1,11 @@
 // PyString buffer size in MB
 pub const PYSTRING_BUFFER_SIZE: usize = 4;
 
+// Rows of the first chunk a pandas partition allocates when the rows are not counted in advance
+pub const PANDAS_CHUNK_ROWS: usize = 1 << 16;
+// Chunks double in size until they hold this many rows
+pub const PANDAS_MAX_CHUNK_ROWS: usize = 1 << 22;
+
 #[cfg(not(debug_assertions))]
 pub const J4RS_BASE_PATH: &str = "./target/release";
 #[cfg(debug_assertions)]
@@ -1,5 +1,8 @@
+use arrow::datatypes::{DataType, TimeUnit};
 use connectorx::{
+    destinations::arrow::ArrowDestination,
     partition::{partition, PartitionQuery},
+    progress::{Progress, ProgressCallback},
     source_router::parse_source,
     sql::CXQuery,
 };
@@ -8,7 +11,9 @@ use pyo3::prelude::*;
 use pyo3::{exceptions::PyValueError, PyResult};
 
 use crate::errors::ConnectorXPythonError;
+use crate::pandas::RowCount;
 use pyo3::types::PyDict;
+use std::sync::{Arc, Mutex};
 
 #[derive(FromPyObject)]
 #[pyo3(from_item_all)]
@@ -32,6 +37,62 @@ impl Into<PartitionQuery> for PyPartitionQuery {
     }
 }
 
+/// The python callable `progress_callback` in kwargs, if any. The first exception raised by the
+/// callable stops further calls and is raised once the load is over.
+struct PyProgressCallback {
+    callback: Option<PyObject>,
+    error: Arc<Mutex<Option<PyErr>>>,
+}
+
+impl PyProgressCallback {
+    fn new(kwargs: Option<&Bound<PyDict>>) -> Self {
+        let callback = kwargs
+            .and_then(|dict| dict.get_item("progress_callback").ok().flatten())
+            .filter(|obj| !obj.is_none())
+            .map(|obj| obj.unbind());
+        Self {
+            callback,
+            error: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Wrap the callable into a `ProgressCallback`.
+    /// It is invoked as `f(rows_done, total_rows, partitions_done, total_partitions, throughput)`.
+    fn callback(&self, py: Python) -> Option<ProgressCallback> {
+        let callback = self.callback.as_ref()?.clone_ref(py);
+        let error = self.error.clone();
+        Some(Arc::new(move |p: Progress| {
+            let mut error = error.lock().unwrap();
+            if error.is_some() {
+                return;
+            }
+            Python::with_gil(|py| {
+                if let Err(e) = callback.call1(
+                    py,
+                    (
+                        p.rows_done,
+                        p.total_rows,
+                        p.partitions_done,
+                        p.total_partitions,
+                        p.throughput(),
+                    ),
+                ) {
+                    *error = Some(e);
+                }
+            })
+        }))
+    }
+
+    /// Raise the exception of the callable, if any, otherwise return the result of the load.
+    fn check<T, E: Into<PyErr>>(&self, result: Result<T, E>) -> PyResult<T> {
+        let value = result.map_err(Into::into)?;
+        match self.error.lock().unwrap().take() {
+            Some(e) => Err(e),
+            None => Ok(value),
+        }
+    }
+}
+
 pub fn read_sql<'py>(
     py: Python<'py>,
     conn: &str,
@@ -40,6 +101,7 @@ pub fn read_sql<'py>(
     queries: Option<Vec<String>>,
     partition_query: Option<PyPartitionQuery>,
     pre_execution_queries: Option<Vec<String>>,
+    post_execution_queries: Option<Vec<String>>,
     kwargs: Option<&Bound<PyDict>>,
 ) -> PyResult<Bound<'py, PyAny>> {
     let source_conn = parse_source(conn, protocol).map_err(|e| ConnectorXPythonError::from(e))?;
@@ -60,20 +122,128 @@ pub fn read_sql<'py>(
     };
 
     match return_type {
-        "pandas" => Ok(crate::pandas::write_pandas(
-            py,
-            &source_conn,
-            origin_query,
-            &queries,
-            pre_execution_queries.as_deref(),
-        )?),
-        "arrow" => Ok(crate::arrow::write_arrow(
-            py,
-            &source_conn,
-            origin_query,
-            &queries,
-            pre_execution_queries.as_deref(),
-        )?),
+        "pandas" => {
+            let row_count = match kwargs
+                .and_then(|dict| dict.get_item("row_count").ok().flatten())
+                .filter(|obj| !obj.is_none())
+            {
+                Some(obj) => obj
+                    .extract::<String>()?
+                    .parse::<RowCount>()
+                    .map_err(|e| PyValueError::new_err(e.to_string()))?,
+                None => RowCount::Exact,
+            };
+
+            let progress = PyProgressCallback::new(kwargs);
+            progress.check(crate::pandas::write_pandas(
+                py,
+                &source_conn,
+                origin_query,
+                &queries,
+                pre_execution_queries.as_deref(),
+                post_execution_queries.as_deref(),
+                progress.callback(py),
+                row_count,
+            ))
+        }
+        "arrow" => {
+            let dictionary_columns = kwargs
+                .and_then(|dict| dict.get_item("dictionary_columns").ok().flatten())
+                .filter(|obj| !obj.is_none())
+                .map(|obj| obj.extract::<Vec<String>>())
+                .transpose()?
+                .unwrap_or_default();
+            let view_types = kwargs
+                .and_then(|dict| dict.get_item("view_types").ok().flatten())
+                .map(|obj| obj.is_truthy())
+                .transpose()?
+                .unwrap_or(false);
+            let extension_types = kwargs
+                .and_then(|dict| dict.get_item("extension_types").ok().flatten())
+                .map(|obj| obj.is_truthy())
+                .transpose()?
+                .unwrap_or(false);
+
+            let timestamp_unit = kwargs
+                .and_then(|dict| dict.get_item("timestamp_unit").ok().flatten())
+                .filter(|obj| !obj.is_none())
+                .map(|obj| obj.extract::<String>())
+                .transpose()?
+                .map(|unit| match unit.as_str() {
+                    "s" => Ok(TimeUnit::Second),
+                    "ms" => Ok(TimeUnit::Millisecond),
+                    "us" => Ok(TimeUnit::Microsecond),
+                    "ns" => Ok(TimeUnit::Nanosecond),
+                    _ => Err(PyValueError::new_err(format!(
+                        "timestamp_unit must be one of 's', 'ms', 'us' or 'ns', got '{}'",
+                        unit
+                    ))),
+                })
+                .transpose()?;
+            let time_zone = kwargs
+                .and_then(|dict| dict.get_item("time_zone").ok().flatten())
+                .filter(|obj| !obj.is_none())
+                .map(|obj| obj.extract::<String>())
+                .transpose()?;
+            let session_time_zone = kwargs
+                .and_then(|dict| dict.get_item("session_time_zone").ok().flatten())
+                .map(|obj| obj.is_truthy())
+                .transpose()?
+                .unwrap_or(false);
+            let naive_time_zone = kwargs
+                .and_then(|dict| dict.get_item("naive_time_zone").ok().flatten())
+                .filter(|obj| !obj.is_none())
+                .map(|obj| obj.extract::<String>())
+                .transpose()?;
+            let json_maps = kwargs
+                .and_then(|dict| dict.get_item("json_maps").ok().flatten())
+                .filter(|obj| !obj.is_none())
+                .map(|obj| obj.extract::<String>())
+                .transpose()?
+                .map(|values| match values.as_str() {
+                    "bool" => Ok(DataType::Boolean),
+                    "int64" => Ok(DataType::Int64),
+                    "float64" => Ok(DataType::Float64),
+                    "string" => Ok(DataType::Utf8),
+                    _ => Err(PyValueError::new_err(format!(
+                        "json_maps must be one of 'bool', 'int64', 'float64' or 'string', got '{}'",
+                        values
+                    ))),
+                })
+                .transpose()?;
+
+            let mut destination = ArrowDestination::new();
+            destination.set_dictionary_columns(&dictionary_columns);
+            destination.set_view_types(view_types);
+            destination.set_extension_types(extension_types);
+            if let Some(unit) = timestamp_unit {
+                destination.set_timestamp_unit(unit);
+            }
+            if let Some(time_zone) = time_zone {
+                destination.set_time_zone(time_zone);
+            }
+            destination.set_session_time_zone(session_time_zone);
+            if let Some(time_zone) = naive_time_zone {
+                destination.set_naive_time_zone(time_zone);
+            }
+            if let Some(values) = json_maps {
+                destination
+                    .set_json_maps(values)
+                    .map_err(ConnectorXPythonError::from)?;
+            }
+
+            let progress = PyProgressCallback::new(kwargs);
+            progress.check(crate::arrow::write_arrow(
+                py,
+                &source_conn,
+                origin_query,
+                &queries,
+                pre_execution_queries.as_deref(),
+                post_execution_queries.as_deref(),
+                progress.callback(py),
+                destination,
+            ))
+        }
         "arrow_stream" => {
             let batch_size = kwargs
                 .and_then(|dict| dict.get_item("batch_size").ok().flatten())
@@ -86,6 +256,7 @@ pub fn read_sql<'py>(
                 origin_query,
                 &queries,
                 pre_execution_queries.as_deref(),
+                post_execution_queries.as_deref(),
                 batch_size,
             )?)
         }
@@ -1,4 +1,4 @@
-use pyo3::exceptions::PyRuntimeError;
+use pyo3::exceptions::{PyRuntimeError, PyTimeoutError};
 use pyo3::PyErr;
 use thiserror::Error;
 
@@ -53,9 +53,33 @@ pub enum ConnectorXPythonError {
     Other(#[from] anyhow::Error),
 }
 
+impl ConnectorXPythonError {
+    /// Whether the error is raised because the statement or connect timeout elapsed.
+    pub fn is_timeout(&self) -> bool {
+        use connectorx::errors::ConnectorXOutError;
+        match self {
+            ConnectorXPythonError::ConnectorXOutError(e) => {
+                matches!(e, ConnectorXOutError::Timeout(_))
+            }
+            ConnectorXPythonError::MsSQLSourceError(e) => e.is_timeout(),
+            ConnectorXPythonError::PostgresSourceError(e) => e.is_timeout(),
+            ConnectorXPythonError::MySQLSourceError(e) => e.is_timeout(),
+            ConnectorXPythonError::SQLiteSourceError(e) => e.is_timeout(),
+            ConnectorXPythonError::OracleSourceError(e) => e.is_timeout(),
+            ConnectorXPythonError::BigQuerySourceError(e) => e.is_timeout(),
+            ConnectorXPythonError::TrinoSourceError(e) => e.is_timeout(),
+            _ => false,
+        }
+    }
+}
+
 impl From<ConnectorXPythonError> for PyErr {
     fn from(e: ConnectorXPythonError) -> PyErr {
-        PyRuntimeError::new_err(format!("{}", e))
+        if e.is_timeout() {
+            PyTimeoutError::new_err(format!("{}", e))
+        } else {
+            PyRuntimeError::new_err(format!("{}", e))
+        }
     }
 }
 
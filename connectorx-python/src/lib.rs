@@ -5,10 +5,15 @@ mod errors;
 pub mod pandas;
 
 use crate::constants::J4RS_BASE_PATH;
-use ::connectorx::{fed_dispatcher::run, partition::partition, source_router::parse_source};
+use ::connectorx::{
+    catalog::{self, TableKind},
+    fed_dispatcher::run,
+    partition::partition,
+    source_router::parse_source,
+};
 use pyo3::exceptions::PyRuntimeError;
 use pyo3::prelude::*;
-use pyo3::types::PyDict;
+use pyo3::types::{PyDict, PyList};
 use pyo3::{wrap_pyfunction, PyResult};
 use std::collections::HashMap;
 use std::env;
@@ -35,6 +40,7 @@ fn connectorx(_: Python, m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_wrapped(wrap_pyfunction!(read_sql2))?;
     m.add_wrapped(wrap_pyfunction!(partition_sql))?;
     m.add_wrapped(wrap_pyfunction!(get_meta))?;
+    m.add_wrapped(wrap_pyfunction!(get_catalog))?;
     m.add_class::<pandas::PandasBlockInfo>()?;
     m.add_class::<arrow::PyRecordBatch>()?;
     m.add_class::<arrow::PyRecordBatchIterator>()?;
@@ -42,7 +48,7 @@ fn connectorx(_: Python, m: &Bound<'_, PyModule>) -> PyResult<()> {
 }
 
 #[pyfunction]
-#[pyo3(signature = (conn, return_type, protocol=None, queries=None, partition_query=None, pre_execution_queries=None, *, **kwargs))]
+#[pyo3(signature = (conn, return_type, protocol=None, queries=None, partition_query=None, pre_execution_queries=None, post_execution_queries=None, *, **kwargs))]
 pub fn read_sql<'py>(
     py: Python<'py>,
     conn: &str,
@@ -51,6 +57,7 @@ pub fn read_sql<'py>(
     queries: Option<Vec<String>>,
     partition_query: Option<cx_read_sql::PyPartitionQuery>,
     pre_execution_queries: Option<Vec<String>>,
+    post_execution_queries: Option<Vec<String>>,
     kwargs: Option<&Bound<PyDict>>,
 ) -> PyResult<Bound<'py, PyAny>> {
     cx_read_sql::read_sql(
@@ -61,6 +68,7 @@ pub fn read_sql<'py>(
         queries,
         partition_query,
         pre_execution_queries,
+        post_execution_queries,
         kwargs,
     )
 }
@@ -112,3 +120,46 @@ pub fn get_meta<'py>(
     pandas::get_meta::get_meta(py, conn, protocol.unwrap_or("binary"), query)
         .map_err(|e| From::from(e))
 }
+
+#[pyfunction]
+#[pyo3(signature = (conn, schema=None))]
+pub fn get_catalog<'py>(
+    py: Python<'py>,
+    conn: &str,
+    schema: Option<&str>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let source_conn =
+        parse_source(conn, None).map_err(|e| crate::errors::ConnectorXPythonError::from(e))?;
+    let catalog = py
+        .allow_threads(|| catalog::get_catalog(&source_conn, schema))
+        .map_err(|e| crate::errors::ConnectorXPythonError::from(e))?;
+
+    let tables = PyList::empty_bound(py);
+    for table in catalog.tables {
+        let columns = PyList::empty_bound(py);
+        for column in table.columns {
+            let dict = PyDict::new_bound(py);
+            dict.set_item("name", column.name)?;
+            dict.set_item("source_type", column.source_type)?;
+            dict.set_item("arrow_type", column.arrow_type.map(|dt| dt.to_string()))?;
+            dict.set_item("nullable", column.nullable)?;
+            columns.append(dict)?;
+        }
+        let dict = PyDict::new_bound(py);
+        dict.set_item("schema", table.schema)?;
+        dict.set_item("name", table.name)?;
+        dict.set_item(
+            "kind",
+            match table.kind {
+                TableKind::Table => "table",
+                TableKind::View => "view",
+            },
+        )?;
+        dict.set_item("columns", columns)?;
+        tables.append(dict)?;
+    }
+    let dict = PyDict::new_bound(py);
+    dict.set_item("schemas", catalog.schemas)?;
+    dict.set_item("tables", tables)?;
+    Ok(dict.into_any())
+}
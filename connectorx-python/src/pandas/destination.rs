@@ -6,6 +6,7 @@ use super::{
     pystring::PyString,
     typesystem::{PandasArrayType, PandasBlockType, PandasTypeSystem},
 };
+use crate::constants::{PANDAS_CHUNK_ROWS, PANDAS_MAX_CHUNK_ROWS};
 use crate::errors::{ConnectorXPythonError, Result};
 use anyhow::anyhow;
 use connectorx::prelude::*;
@@ -14,17 +15,55 @@ use itertools::Itertools;
 use numpy::{PyArray1, PyArray2};
 use pyo3::{
     prelude::*,
-    types::{IntoPyDict, PyList, PyTuple},
+    types::{IntoPyDict, PyList, PySlice, PyTuple},
 };
 use std::{
     collections::HashMap,
     mem::transmute,
+    str::FromStr,
     sync::{
         atomic::{AtomicUsize, Ordering},
-        Arc,
+        Arc, Mutex,
     },
 };
 
+/// How [`PandasDestination`] learns the number of rows to allocate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RowCount {
+    /// Count the rows with `COUNT(*)` queries and allocate the result up front.
+    #[default]
+    Exact,
+    /// Size the buffers from the row estimate of the source and grow them as needed.
+    Estimate,
+    /// Do not count, grow the buffers as partitions deliver rows.
+    Skip,
+}
+
+impl FromStr for RowCount {
+    type Err = ConnectorXPythonError;
+
+    #[throws(ConnectorXPythonError)]
+    fn from_str(s: &str) -> Self {
+        match s {
+            "exact" => RowCount::Exact,
+            "estimate" => RowCount::Estimate,
+            "none" => RowCount::Skip,
+            _ => throw!(anyhow!(
+                "row_count should be 'exact', 'estimate' or 'none', got '{}'",
+                s
+            )),
+        }
+    }
+}
+
+/// Rows written by a partition into buffers it allocated itself.
+struct PandasChunk {
+    partition: usize,
+    seq: usize,
+    blocks: Vec<Py<PyAny>>,
+    nrows: usize,
+}
+
 #[pyclass]
 pub struct PandasBlockInfo {
     dt: PandasBlockType,
@@ -46,6 +85,9 @@ pub struct PandasDestination<'py> {
     names: Vec<String>,
     block_datas: Vec<Bound<'py, PyAny>>, // either 2d array for normal blocks, or two 1d arrays for extension blocks
     block_infos: Vec<PandasBlockInfo>,
+    row_count: RowCount,
+    chunk_rows: Option<usize>, // rows of the first chunk of each partition if the rows are not counted
+    chunks: Mutex<Vec<PandasChunk>>,
 }
 
 impl<'py> PandasDestination<'py> {
@@ -56,10 +98,25 @@ impl<'py> PandasDestination<'py> {
             names: vec![],
             block_datas: vec![],
             block_infos: vec![],
+            row_count: RowCount::Exact,
+            chunk_rows: None,
+            chunks: Mutex::new(vec![]),
         }
     }
 
-    pub fn result(self, py: Python<'py>) -> Result<Bound<'py, PyAny>> {
+    pub fn set_row_count(&mut self, row_count: RowCount) {
+        self.row_count = row_count;
+    }
+
+    pub fn row_count(&self) -> RowCount {
+        self.row_count
+    }
+
+    pub fn result(mut self, py: Python<'py>) -> Result<Bound<'py, PyAny>> {
+        if self.chunk_rows.is_some() {
+            self.compact(py)?;
+        }
+
         #[throws(ConnectorXPythonError)]
         fn to_list<T: IntoPy<PyObject>>(py: Python<'_>, arr: Vec<T>) -> Bound<PyList> {
             let list = PyList::empty_bound(py);
@@ -80,42 +137,72 @@ impl<'py> PandasDestination<'py> {
         Ok(result.into_any())
     }
 
+    /// Concatenate the chunks written by the partitions into the result blocks, dropping
+    /// the rows allocated but not written.
     #[throws(ConnectorXPythonError)]
-    fn allocate_array<T: numpy::Element + 'py>(
-        &mut self,
-        py: Python<'py>,
-        dt: PandasBlockType,
-        placement: Vec<usize>,
-    ) {
-        // has to use `zeros` instead of `new` for String type initialization
-        let data = PyArray2::<T>::zeros_bound(py, [placement.len(), self.nrow], false);
-        let block_info = PandasBlockInfo {
-            dt,
-            cids: placement,
-        };
+    fn compact(&mut self, py: Python<'py>) {
+        let mut chunks = std::mem::take(
+            &mut *self
+                .chunks
+                .lock()
+                .map_err(|e| anyhow!("mutex poisoned {}", e))?,
+        );
+        chunks.sort_by_key(|chunk| (chunk.partition, chunk.seq));
+        self.nrow = chunks.iter().map(|chunk| chunk.nrows).sum();
+
+        let numpy = py.import_bound("numpy")?;
+        for (idx, block) in self.block_infos.iter().enumerate() {
+            let mut parts = vec![self.block_datas[idx].clone()];
+            for chunk in &chunks {
+                let rows = PySlice::new_bound(py, 0, chunk.nrows as isize, 1);
+                let data = chunk.blocks[idx].bind(py);
+                parts.push(if is_masked(block.dt) {
+                    PyTuple::new_bound(
+                        py,
+                        vec![
+                            data.get_item(0)?.get_item(&rows)?,
+                            data.get_item(1)?.get_item(&rows)?,
+                        ],
+                    )
+                    .into_any()
+                } else {
+                    data.get_item((PySlice::full_bound(py), rows))?
+                });
+            }
 
-        self.block_datas.push(data.into_any());
-        self.block_infos.push(block_info);
+            self.block_datas[idx] = if is_masked(block.dt) {
+                let mut datas = vec![];
+                let mut masks = vec![];
+                for part in parts {
+                    datas.push(part.get_item(0)?);
+                    masks.push(part.get_item(1)?);
+                }
+                let data = numpy.call_method1("concatenate", (datas,))?;
+                let mask = numpy.call_method1("concatenate", (masks,))?;
+                PyTuple::new_bound(py, vec![data, mask]).into_any()
+            } else {
+                numpy.call_method1("concatenate", (parts, 1))?
+            };
+        }
     }
 
+    /// Same as `allocate_py`, but only sets up the block layout: each partition allocates
+    /// chunks of rows on demand, starting from `chunk_rows` rows and doubling as it fills them.
     #[throws(ConnectorXPythonError)]
-    fn allocate_masked_array<T: numpy::Element + 'py>(
+    pub fn allocate_chunked_py<S: AsRef<str>>(
         &mut self,
         py: Python<'py>,
-        dt: PandasBlockType,
-        placement: Vec<usize>,
+        chunk_rows: Option<usize>,
+        names: &[S],
+        schema: &[PandasTypeSystem],
+        data_order: DataOrder,
     ) {
-        for pos in placement {
-            let block_info = PandasBlockInfo {
-                dt,
-                cids: vec![pos],
-            };
-            let data = PyArray1::<T>::zeros_bound(py, self.nrow, false);
-            let mask = PyArray1::<bool>::zeros_bound(py, self.nrow, false);
-            let obj = PyTuple::new_bound(py, vec![data.as_any(), mask.as_any()]);
-            self.block_datas.push(obj.into_any());
-            self.block_infos.push(block_info);
-        }
+        self.chunk_rows = Some(
+            chunk_rows
+                .unwrap_or(PANDAS_CHUNK_ROWS)
+                .clamp(1, PANDAS_MAX_CHUNK_ROWS),
+        );
+        self.allocate_py(py, 0, names, schema, data_order)?;
     }
 
     #[throws(ConnectorXPythonError)]
@@ -142,43 +229,195 @@ impl<'py> PandasDestination<'py> {
             .for_each(|(i, dt)| block_indices.entry((*dt).into()).or_default().push(i));
 
         for (dt, placement) in block_indices {
-            match dt {
-                PandasBlockType::Boolean(true) => {
-                    self.allocate_masked_array::<bool>(py, dt, placement)?;
+            if is_masked(dt) {
+                // extension blocks hold a single column
+                for pos in placement {
+                    self.block_infos.push(PandasBlockInfo {
+                        dt,
+                        cids: vec![pos],
+                    });
                 }
-                PandasBlockType::Boolean(false) => {
-                    self.allocate_array::<bool>(py, dt, placement)?;
+            } else {
+                self.block_infos.push(PandasBlockInfo {
+                    dt,
+                    cids: placement,
+                });
+            }
+        }
+        self.block_datas = allocate_blocks(py, &self.block_infos, self.nrow);
+    }
+}
+
+fn is_masked(dt: PandasBlockType) -> bool {
+    matches!(
+        dt,
+        PandasBlockType::Boolean(true) | PandasBlockType::Int64(true)
+    )
+}
+
+fn allocate_blocks<'py>(
+    py: Python<'py>,
+    block_infos: &[PandasBlockInfo],
+    nrow: usize,
+) -> Vec<Bound<'py, PyAny>> {
+    fn array<'py, T: numpy::Element + 'py>(
+        py: Python<'py>,
+        ncols: usize,
+        nrow: usize,
+    ) -> Bound<'py, PyAny> {
+        // has to use `zeros` instead of `new` for String type initialization
+        PyArray2::<T>::zeros_bound(py, [ncols, nrow], false).into_any()
+    }
+
+    fn masked_array<'py, T: numpy::Element + 'py>(
+        py: Python<'py>,
+        nrow: usize,
+    ) -> Bound<'py, PyAny> {
+        let data = PyArray1::<T>::zeros_bound(py, nrow, false);
+        let mask = PyArray1::<bool>::zeros_bound(py, nrow, false);
+        PyTuple::new_bound(py, vec![data.as_any(), mask.as_any()]).into_any()
+    }
+
+    block_infos
+        .iter()
+        .map(|block| {
+            let ncols = block.cids.len();
+            match block.dt {
+                PandasBlockType::Boolean(true) => masked_array::<bool>(py, nrow),
+                PandasBlockType::Boolean(false) => array::<bool>(py, ncols, nrow),
+                PandasBlockType::Int64(true) => masked_array::<i64>(py, nrow),
+                PandasBlockType::Int64(false) => array::<i64>(py, ncols, nrow),
+                PandasBlockType::Float64 => array::<f64>(py, ncols, nrow),
+                PandasBlockType::BooleanArray
+                | PandasBlockType::Float64Array
+                | PandasBlockType::Int64Array => {
+                    array::<super::pandas_columns::PyList>(py, ncols, nrow)
                 }
-                PandasBlockType::Int64(true) => {
-                    self.allocate_masked_array::<i64>(py, dt, placement)?;
+                PandasBlockType::String => array::<PyString>(py, ncols, nrow),
+                PandasBlockType::DateTime => array::<i64>(py, ncols, nrow),
+                PandasBlockType::Bytes => array::<PyBytes>(py, ncols, nrow),
+            }
+        })
+        .collect()
+}
+
+/// Split the blocks into columns and each column into `counts` partitions.
+#[throws(ConnectorXPythonError)]
+fn partition_blocks(
+    block_infos: &[PandasBlockInfo],
+    block_datas: &[Bound<'_, PyAny>],
+    ncols: usize,
+    counts: usize,
+) -> Vec<Vec<Box<dyn PandasColumnObject>>> {
+    let mut partitioned_columns: Vec<Vec<Box<dyn PandasColumnObject>>> =
+        (0..ncols).map(|_| Vec::new()).collect();
+
+    for (idx, block) in block_infos.iter().enumerate() {
+        let buf = &block_datas[idx];
+        match block.dt {
+            PandasBlockType::Boolean(_) => {
+                let bblock = BooleanBlock::extract_block(buf)?;
+
+                let bcols = bblock.split()?;
+                for (&cid, bcol) in block.cids.iter().zip_eq(bcols) {
+                    partitioned_columns[cid] = bcol
+                        .partition(counts)
+                        .into_iter()
+                        .map(|c| Box::new(c) as _)
+                        .collect()
                 }
-                PandasBlockType::Int64(false) => {
-                    self.allocate_array::<i64>(py, dt, placement)?;
+            }
+            PandasBlockType::Float64 => {
+                let fblock = Float64Block::extract_block(buf)?;
+                let fcols = fblock.split()?;
+                for (&cid, fcol) in block.cids.iter().zip_eq(fcols) {
+                    partitioned_columns[cid] = fcol
+                        .partition(counts)
+                        .into_iter()
+                        .map(|c| Box::new(c) as _)
+                        .collect()
                 }
-                PandasBlockType::Float64 => {
-                    self.allocate_array::<f64>(py, dt, placement)?;
+            }
+            PandasBlockType::BooleanArray => {
+                let bblock = ArrayBlock::<bool>::extract_block(buf)?;
+                let bcols = bblock.split()?;
+                for (&cid, bcol) in block.cids.iter().zip_eq(bcols) {
+                    partitioned_columns[cid] = bcol
+                        .partition(counts)
+                        .into_iter()
+                        .map(|c| Box::new(c) as _)
+                        .collect()
                 }
-                PandasBlockType::BooleanArray => {
-                    self.allocate_array::<super::pandas_columns::PyList>(py, dt, placement)?;
+            }
+            PandasBlockType::Float64Array => {
+                let fblock = ArrayBlock::<f64>::extract_block(buf)?;
+                let fcols = fblock.split()?;
+                for (&cid, fcol) in block.cids.iter().zip_eq(fcols) {
+                    partitioned_columns[cid] = fcol
+                        .partition(counts)
+                        .into_iter()
+                        .map(|c| Box::new(c) as _)
+                        .collect()
                 }
-                PandasBlockType::Float64Array => {
-                    self.allocate_array::<super::pandas_columns::PyList>(py, dt, placement)?;
+            }
+            PandasBlockType::Int64Array => {
+                let fblock = ArrayBlock::<i64>::extract_block(buf)?;
+                let fcols = fblock.split()?;
+                for (&cid, fcol) in block.cids.iter().zip_eq(fcols) {
+                    partitioned_columns[cid] = fcol
+                        .partition(counts)
+                        .into_iter()
+                        .map(|c| Box::new(c) as _)
+                        .collect()
                 }
-                PandasBlockType::Int64Array => {
-                    self.allocate_array::<super::pandas_columns::PyList>(py, dt, placement)?;
+            }
+            PandasBlockType::Int64(_) => {
+                let ublock = Int64Block::extract_block(buf)?;
+                let ucols = ublock.split()?;
+                for (&cid, ucol) in block.cids.iter().zip_eq(ucols) {
+                    partitioned_columns[cid] = ucol
+                        .partition(counts)
+                        .into_iter()
+                        .map(|c| Box::new(c) as _)
+                        .collect()
                 }
-                PandasBlockType::String => {
-                    self.allocate_array::<PyString>(py, dt, placement)?;
+            }
+            PandasBlockType::String => {
+                let sblock = StringBlock::extract_block(buf)?;
+                let scols = sblock.split()?;
+                for (&cid, scol) in block.cids.iter().zip_eq(scols) {
+                    partitioned_columns[cid] = scol
+                        .partition(counts)
+                        .into_iter()
+                        .map(|c| Box::new(c) as _)
+                        .collect()
                 }
-                PandasBlockType::DateTime => {
-                    self.allocate_array::<i64>(py, dt, placement)?;
+            }
+            PandasBlockType::Bytes => {
+                let bblock = BytesBlock::extract_block(buf)?;
+                let bcols = bblock.split()?;
+                for (&cid, bcol) in block.cids.iter().zip_eq(bcols) {
+                    partitioned_columns[cid] = bcol
+                        .partition(counts)
+                        .into_iter()
+                        .map(|c| Box::new(c) as _)
+                        .collect()
                 }
-                PandasBlockType::Bytes => {
-                    self.allocate_array::<PyBytes>(py, dt, placement)?;
+            }
+            PandasBlockType::DateTime => {
+                let dblock = DateTimeBlock::extract_block(buf)?;
+                let dcols = dblock.split()?;
+                for (&cid, dcol) in block.cids.iter().zip_eq(dcols) {
+                    partitioned_columns[cid] = dcol
+                        .partition(counts)
+                        .into_iter()
+                        .map(|c| Box::new(c) as _)
+                        .collect()
                 }
-            };
+            }
         }
     }
+    partitioned_columns
 }
 
 impl<'py> Destination for PandasDestination<'py> {
@@ -191,7 +430,7 @@ impl<'py> Destination for PandasDestination<'py> {
     type Error = ConnectorXPythonError;
 
     fn needs_count(&self) -> bool {
-        true
+        self.row_count == RowCount::Exact
     }
 
     #[allow(unreachable_code)]
@@ -208,131 +447,49 @@ impl<'py> Destination for PandasDestination<'py> {
 
     #[throws(ConnectorXPythonError)]
     fn partition(&mut self, counts: usize) -> Vec<Self::Partition<'_>> {
-        let mut partitioned_columns: Vec<Vec<Box<dyn PandasColumnObject>>> =
-            (0..self.schema.len()).map(|_| Vec::new()).collect();
-
-        for (idx, block) in self.block_infos.iter().enumerate() {
-            let buf = &self.block_datas[idx];
-            match block.dt {
-                PandasBlockType::Boolean(_) => {
-                    let bblock = BooleanBlock::extract_block(buf)?;
-
-                    let bcols = bblock.split()?;
-                    for (&cid, bcol) in block.cids.iter().zip_eq(bcols) {
-                        partitioned_columns[cid] = bcol
-                            .partition(counts)
-                            .into_iter()
-                            .map(|c| Box::new(c) as _)
-                            .collect()
-                    }
-                }
-                PandasBlockType::Float64 => {
-                    let fblock = Float64Block::extract_block(buf)?;
-                    let fcols = fblock.split()?;
-                    for (&cid, fcol) in block.cids.iter().zip_eq(fcols) {
-                        partitioned_columns[cid] = fcol
-                            .partition(counts)
-                            .into_iter()
-                            .map(|c| Box::new(c) as _)
-                            .collect()
-                    }
-                }
-                PandasBlockType::BooleanArray => {
-                    let bblock = ArrayBlock::<bool>::extract_block(buf)?;
-                    let bcols = bblock.split()?;
-                    for (&cid, bcol) in block.cids.iter().zip_eq(bcols) {
-                        partitioned_columns[cid] = bcol
-                            .partition(counts)
-                            .into_iter()
-                            .map(|c| Box::new(c) as _)
-                            .collect()
-                    }
-                }
-                PandasBlockType::Float64Array => {
-                    let fblock = ArrayBlock::<f64>::extract_block(buf)?;
-                    let fcols = fblock.split()?;
-                    for (&cid, fcol) in block.cids.iter().zip_eq(fcols) {
-                        partitioned_columns[cid] = fcol
-                            .partition(counts)
-                            .into_iter()
-                            .map(|c| Box::new(c) as _)
-                            .collect()
-                    }
-                }
-                PandasBlockType::Int64Array => {
-                    let fblock = ArrayBlock::<i64>::extract_block(buf)?;
-                    let fcols = fblock.split()?;
-                    for (&cid, fcol) in block.cids.iter().zip_eq(fcols) {
-                        partitioned_columns[cid] = fcol
-                            .partition(counts)
-                            .into_iter()
-                            .map(|c| Box::new(c) as _)
-                            .collect()
-                    }
-                }
-                PandasBlockType::Int64(_) => {
-                    let ublock = Int64Block::extract_block(buf)?;
-                    let ucols = ublock.split()?;
-                    for (&cid, ucol) in block.cids.iter().zip_eq(ucols) {
-                        partitioned_columns[cid] = ucol
-                            .partition(counts)
-                            .into_iter()
-                            .map(|c| Box::new(c) as _)
-                            .collect()
-                    }
-                }
-                PandasBlockType::String => {
-                    let sblock = StringBlock::extract_block(buf)?;
-                    let scols = sblock.split()?;
-                    for (&cid, scol) in block.cids.iter().zip_eq(scols) {
-                        partitioned_columns[cid] = scol
-                            .partition(counts)
-                            .into_iter()
-                            .map(|c| Box::new(c) as _)
-                            .collect()
-                    }
-                }
-                PandasBlockType::Bytes => {
-                    let bblock = BytesBlock::extract_block(buf)?;
-                    let bcols = bblock.split()?;
-                    for (&cid, bcol) in block.cids.iter().zip_eq(bcols) {
-                        partitioned_columns[cid] = bcol
-                            .partition(counts)
-                            .into_iter()
-                            .map(|c| Box::new(c) as _)
-                            .collect()
-                    }
-                }
-                PandasBlockType::DateTime => {
-                    let dblock = DateTimeBlock::extract_block(buf)?;
-                    let dcols = dblock.split()?;
-                    for (&cid, dcol) in block.cids.iter().zip_eq(dcols) {
-                        partitioned_columns[cid] = dcol
-                            .partition(counts)
-                            .into_iter()
-                            .map(|c| Box::new(c) as _)
-                            .collect()
-                    }
-                }
-            }
-        }
+        let mut partitioned_columns = match self.chunk_rows {
+            // partitions allocate their own chunks when the rows are not counted
+            Some(_) => (0..self.schema.len()).map(|_| Vec::new()).collect(),
+            None => partition_blocks(
+                &self.block_infos,
+                &self.block_datas,
+                self.schema.len(),
+                counts,
+            )?,
+        };
 
         let mut par_destinations = vec![];
         let glob_row = Arc::new(AtomicUsize::new(0));
-        for _ in 0..counts {
+        for partition in 0..counts {
             let mut columns = Vec::with_capacity(partitioned_columns.len());
-            for (i, partitions) in partitioned_columns.iter_mut().enumerate() {
-                columns.push(
-                    partitions
-                        .pop()
-                        .ok_or_else(|| anyhow!("empty partition for {}th column", i))?,
-                );
-            }
+            let chunks = match self.chunk_rows {
+                Some(chunk_rows) => Some(PartitionChunks {
+                    partition,
+                    block_infos: &self.block_infos,
+                    chunks: &self.chunks,
+                    next_rows: chunk_rows,
+                    blocks: vec![],
+                    capacity: 0,
+                    nrows: 0,
+                    seq: 0,
+                }),
+                None => {
+                    for (i, partitions) in partitioned_columns.iter_mut().enumerate() {
+                        columns.push(
+                            partitions
+                                .pop()
+                                .ok_or_else(|| anyhow!("empty partition for {}th column", i))?,
+                        );
+                    }
+                    None
+                }
+            };
 
             par_destinations.push(PandasPartitionDestination::new(
                 columns,
                 &self.schema[..],
                 Arc::clone(&glob_row),
+                chunks,
             ));
         }
 
@@ -343,12 +500,25 @@ impl<'py> Destination for PandasDestination<'py> {
         self.schema.as_ref()
     }
 }
+/// The chunks of a partition that allocates its buffers on demand.
+struct PartitionChunks<'py> {
+    partition: usize,
+    block_infos: &'py [PandasBlockInfo],
+    chunks: &'py Mutex<Vec<PandasChunk>>,
+    next_rows: usize,
+    blocks: Vec<Py<PyAny>>, // blocks of the current chunk
+    capacity: usize,
+    nrows: usize,
+    seq: usize,
+}
+
 pub struct PandasPartitionDestination<'py> {
     columns: Vec<Box<dyn PandasColumnObject + 'py>>,
     schema: &'py [PandasTypeSystem],
     seq: usize,
     glob_row: Arc<AtomicUsize>,
     cur_row: usize,
+    chunks: Option<PartitionChunks<'py>>,
 }
 
 impl<'py> PandasPartitionDestination<'py> {
@@ -356,6 +526,7 @@ impl<'py> PandasPartitionDestination<'py> {
         columns: Vec<Box<dyn PandasColumnObject + 'py>>,
         schema: &'py [PandasTypeSystem],
         glob_row: Arc<AtomicUsize>,
+        chunks: Option<PartitionChunks<'py>>,
     ) -> Self {
         Self {
             columns,
@@ -363,9 +534,61 @@ impl<'py> PandasPartitionDestination<'py> {
             seq: 0,
             glob_row,
             cur_row: 0,
+            chunks,
         }
     }
 
+    /// Hand the current chunk over to the destination once its columns are flushed.
+    #[throws(ConnectorXPythonError)]
+    fn seal_chunk(&mut self) {
+        for col in &mut self.columns {
+            col.finalize()?;
+        }
+        if let Some(chunks) = self.chunks.as_mut() {
+            if !chunks.blocks.is_empty() {
+                chunks
+                    .chunks
+                    .lock()
+                    .map_err(|e| anyhow!("mutex poisoned {}", e))?
+                    .push(PandasChunk {
+                        partition: chunks.partition,
+                        seq: chunks.seq,
+                        blocks: std::mem::take(&mut chunks.blocks),
+                        nrows: chunks.nrows,
+                    });
+                chunks.seq += 1;
+            }
+        }
+    }
+
+    /// Seal the current chunk and allocate a new one that holds at least `n` rows.
+    #[throws(ConnectorXPythonError)]
+    fn grow(&mut self, n: usize) {
+        self.seal_chunk()?;
+        let ncols = self.ncols();
+        let chunks = self
+            .chunks
+            .as_mut()
+            .ok_or_else(|| anyhow!("partition does not allocate chunks"))?;
+        let capacity = chunks.next_rows.max(n);
+        chunks.next_rows = chunks
+            .next_rows
+            .max((capacity * 2).min(PANDAS_MAX_CHUNK_ROWS));
+
+        let (blocks, columns) = Python::with_gil(|py| -> Result<_> {
+            let blocks = allocate_blocks(py, chunks.block_infos, capacity);
+            let columns = partition_blocks(chunks.block_infos, &blocks, ncols, 1)?;
+            Ok((
+                blocks.into_iter().map(Bound::unbind).collect(),
+                columns.into_iter().flatten().collect(),
+            ))
+        })?;
+        chunks.blocks = blocks;
+        chunks.capacity = capacity;
+        chunks.nrows = 0;
+        self.columns = columns;
+    }
+
     fn loc(&mut self) -> (usize, usize) {
         let (row, col) = (
             self.cur_row + self.seq / self.ncols(),
@@ -385,10 +608,7 @@ impl<'py> DestinationPartition<'py> for PandasPartitionDestination<'py> {
     }
 
     fn finalize(&mut self) -> Result<()> {
-        for col in &mut self.columns {
-            col.finalize()?;
-        }
-        Ok(())
+        self.seal_chunk()
     }
 
     #[throws(ConnectorXPythonError)]
@@ -396,7 +616,22 @@ impl<'py> DestinationPartition<'py> for PandasPartitionDestination<'py> {
         if n == 0 {
             return self.cur_row;
         }
-        self.cur_row = self.glob_row.fetch_add(n, Ordering::Relaxed);
+        match self
+            .chunks
+            .as_ref()
+            .map(|chunks| chunks.nrows + n > chunks.capacity)
+        {
+            None => self.cur_row = self.glob_row.fetch_add(n, Ordering::Relaxed),
+            Some(full) => {
+                if full {
+                    self.grow(n)?;
+                }
+                if let Some(chunks) = self.chunks.as_mut() {
+                    self.cur_row = chunks.nrows;
+                    chunks.nrows += n;
+                }
+            }
+        }
         self.seq = 0;
         self.cur_row
     }
@@ -1,7 +1,12 @@
-use super::{destination::PandasDestination, typesystem::PandasTypeSystem};
+use super::{
+    destination::{PandasDestination, RowCount},
+    typesystem::PandasTypeSystem,
+};
+use crate::constants::PANDAS_MAX_CHUNK_ROWS;
 use crate::errors::ConnectorXPythonError;
 use connectorx::errors::Result as CXResult;
 use connectorx::prelude::*;
+use connectorx::progress::ProgressTracker;
 use itertools::Itertools;
 use log::debug;
 use pyo3::prelude::*;
@@ -13,6 +18,7 @@ pub struct PandasDispatcher<'py, S, TP> {
     dst: PandasDestination<'py>,
     queries: Vec<CXQuery<String>>,
     origin_query: Option<String>,
+    progress: Option<ProgressCallback>,
     _phantom: PhantomData<TP>,
 }
 
@@ -37,6 +43,7 @@ where
             dst,
             queries: queries.iter().map(Into::into).collect(),
             origin_query,
+            progress: None,
             _phantom: PhantomData,
         }
     }
@@ -45,6 +52,14 @@ where
         self.src.set_pre_execution_queries(pre_execution_queries);
     }
 
+    pub fn set_post_execution_queries(&mut self, post_execution_queries: Option<&[String]>) {
+        self.src.set_post_execution_queries(post_execution_queries);
+    }
+
+    pub fn set_progress_callback(&mut self, callback: Option<ProgressCallback>) {
+        self.progress = callback;
+    }
+
     /// Start the data loading process.
     pub fn run(mut self, py: Python<'py>) -> Result<Bound<'py, PyAny>, TP::Error> {
         debug!("Run dispatcher");
@@ -68,9 +83,12 @@ where
             // return None if cannot derive total count
             debug!("Try get row rounts for entire result");
             self.src.result_rows()?
+        } else if self.dst.row_count() == RowCount::Estimate {
+            debug!("Estimate row counts for entire result");
+            self.src.estimate_rows()?
         } else {
             debug!("Do not need counts in advance");
-            Some(0)
+            None
         };
         let mut src_partitions: Vec<S::Partition> = self.src.partition()?;
         if self.dst.needs_count() && total_rows.is_none() {
@@ -87,18 +105,38 @@ where
                 .collect();
             total_rows = Some(part_rows.iter().sum());
         }
-        let total_rows = total_rows.ok_or_else(ConnectorXError::CountError)?;
 
-        debug!(
-            "Allocate destination memory: {}x{}",
-            total_rows,
-            src_schema.len()
-        );
-        self.dst
-            .allocate_py(py, total_rows, &names, &dst_schema, dorder)?;
+        let expected_rows = if self.dst.needs_count() {
+            let total_rows = total_rows.ok_or_else(ConnectorXError::CountError)?;
+            debug!(
+                "Allocate destination memory: {}x{}",
+                total_rows,
+                src_schema.len()
+            );
+            self.dst
+                .allocate_py(py, total_rows, &names, &dst_schema, dorder)?;
+            Some(total_rows)
+        } else {
+            // give each partition a share of the estimate, with some headroom; a wildly wrong
+            // estimate must not allocate huge chunks
+            let nparts = src_partitions.len().max(1);
+            let chunk_rows =
+                total_rows.map(|n| (n / nparts + n / nparts / 8 + 1).min(PANDAS_MAX_CHUNK_ROWS));
+            debug!(
+                "Allocate destination memory on demand, starting from {:?} rows per partition",
+                chunk_rows
+            );
+            self.dst
+                .allocate_chunked_py(py, chunk_rows, &names, &dst_schema, dorder)?;
+            None
+        };
 
         debug!("Create destination partition");
         let dst_partitions = self.dst.partition(self.queries.len())?;
+        let tracker = self
+            .progress
+            .take()
+            .map(|callback| ProgressTracker::new(callback, expected_rows, dst_partitions.len()));
 
         #[cfg(all(not(feature = "branch"), not(feature = "fptr")))]
         compile_error!("branch or fptr, pick one");
@@ -133,6 +171,9 @@ where
                         DataOrder::RowMajor => loop {
                             let (n, is_last) = parser.fetch_next()?;
                             dst.aquire_row(n)?;
+                            if let Some(tracker) = &tracker {
+                                tracker.add_rows(n);
+                            }
                             for _ in 0..n {
                                 #[allow(clippy::needless_range_loop)]
                                 for col in 0..dst.ncols() {
@@ -153,6 +194,9 @@ where
                         DataOrder::ColumnMajor => loop {
                             let (n, is_last) = parser.fetch_next()?;
                             dst.aquire_row(n)?;
+                            if let Some(tracker) = &tracker {
+                                tracker.add_rows(n);
+                            }
                             #[allow(clippy::needless_range_loop)]
                             for col in 0..dst.ncols() {
                                 for _ in 0..n {
@@ -173,7 +217,12 @@ where
 
                     debug!("Finalize partition {}", i);
                     dst.finalize()?;
+                    drop(parser);
+                    src.finish()?;
                     debug!("Partition {} finished", i);
+                    if let Some(tracker) = &tracker {
+                        tracker.finish_partition();
+                    }
                     Ok(())
                 })?;
             Ok(())
@@ -21,7 +21,7 @@ use connectorx::{
         sqlite::SQLiteSource,
         trino::TrinoSource,
     },
-    sql::CXQuery,
+    sql::{tag_queries, CXQuery},
 };
 use fehler::throws;
 use log::debug;
@@ -40,7 +40,7 @@ pub fn get_meta<'py>(
 ) -> Bound<'py, PyAny> {
     let source_conn = SourceConn::try_from(conn)?;
     let destination = PandasDestination::new();
-    let queries = &[CXQuery::Naked(query)];
+    let queries = &tag_queries(&[CXQuery::Naked(query)], source_conn.tag.as_deref());
 
     match source_conn.ty {
         SourceType::Postgres => {
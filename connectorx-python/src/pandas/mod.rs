@@ -6,7 +6,9 @@ mod pystring;
 mod transports;
 mod typesystem;
 
-pub use self::destination::{PandasBlockInfo, PandasDestination, PandasPartitionDestination};
+pub use self::destination::{
+    PandasBlockInfo, PandasDestination, PandasPartitionDestination, RowCount,
+};
 use self::dispatcher::PandasDispatcher;
 pub use self::transports::{
     BigQueryPandasTransport, MsSQLPandasTransport, MysqlPandasTransport, OraclePandasTransport,
@@ -25,7 +27,7 @@ use connectorx::{
             SimpleProtocol,
         },
     },
-    sql::CXQuery,
+    sql::{tag_queries, CXQuery},
 };
 use fehler::throws;
 use log::debug;
@@ -41,10 +43,15 @@ pub fn write_pandas<'a, 'py: 'a>(
     origin_query: Option<String>,
     queries: &[CXQuery<String>],
     pre_execution_queries: Option<&[String]>,
+    post_execution_queries: Option<&[String]>,
+    progress: Option<ProgressCallback>,
+    row_count: RowCount,
 ) -> Bound<'py, PyAny> {
-    let destination = PandasDestination::new();
+    let mut destination = PandasDestination::new();
+    destination.set_row_count(row_count);
     let protocol = source_conn.proto.as_str();
     debug!("Protocol: {}", protocol);
+    let queries = &tag_queries(queries, source_conn.tag.as_deref());
 
     match source_conn.ty {
         SourceType::Postgres => {
@@ -63,6 +70,8 @@ pub fn write_pandas<'a, 'py: 'a>(
                         sb, destination, queries, origin_query
                     );
                     dispatcher.set_pre_execution_queries(pre_execution_queries);
+                    dispatcher.set_post_execution_queries(post_execution_queries);
+                    dispatcher.set_progress_callback(progress);
                     dispatcher.run(py)?
                 }
                 ("csv", None) => {
@@ -75,6 +84,8 @@ pub fn write_pandas<'a, 'py: 'a>(
                         sb, destination, queries, origin_query
                     );
                     dispatcher.set_pre_execution_queries(pre_execution_queries);
+                    dispatcher.set_post_execution_queries(post_execution_queries);
+                    dispatcher.set_progress_callback(progress);
                     dispatcher.run(py)?
                 }
                 ("binary", Some(tls_conn)) => {
@@ -89,6 +100,8 @@ pub fn write_pandas<'a, 'py: 'a>(
                             PostgresPandasTransport<PgBinaryProtocol, MakeTlsConnector>,
                         >::new(sb, destination, queries, origin_query);
                     dispatcher.set_pre_execution_queries(pre_execution_queries);
+                    dispatcher.set_post_execution_queries(post_execution_queries);
+                    dispatcher.set_progress_callback(progress);
                     dispatcher.run(py)?
                 }
                 ("binary", None) => {
@@ -104,6 +117,8 @@ pub fn write_pandas<'a, 'py: 'a>(
                         sb, destination, queries, origin_query
                     );
                     dispatcher.set_pre_execution_queries(pre_execution_queries);
+                    dispatcher.set_post_execution_queries(post_execution_queries);
+                    dispatcher.set_progress_callback(progress);
                     dispatcher.run(py)?
                 }
                 ("cursor", Some(tls_conn)) => {
@@ -118,6 +133,8 @@ pub fn write_pandas<'a, 'py: 'a>(
                             PostgresPandasTransport<CursorProtocol, MakeTlsConnector>,
                         >::new(sb, destination, queries, origin_query);
                     dispatcher.set_pre_execution_queries(pre_execution_queries);
+                    dispatcher.set_post_execution_queries(post_execution_queries);
+                    dispatcher.set_progress_callback(progress);
                     dispatcher.run(py)?
                 }
                 ("cursor", None) => {
@@ -130,6 +147,8 @@ pub fn write_pandas<'a, 'py: 'a>(
                         sb, destination, queries, origin_query
                     );
                     dispatcher.set_pre_execution_queries(pre_execution_queries);
+                    dispatcher.set_post_execution_queries(post_execution_queries);
+                    dispatcher.set_progress_callback(progress);
                     dispatcher.run(py)?
                 }
                 ("simple", Some(tls_conn)) => {
@@ -144,6 +163,8 @@ pub fn write_pandas<'a, 'py: 'a>(
                             PostgresPandasTransport<SimpleProtocol, MakeTlsConnector>,
                         >::new(sb, destination, queries, origin_query);
                     dispatcher.set_pre_execution_queries(pre_execution_queries);
+                    dispatcher.set_post_execution_queries(post_execution_queries);
+                    dispatcher.set_progress_callback(progress);
                     dispatcher.run(py)?
                 }
                 ("simple", None) => {
@@ -156,6 +177,8 @@ pub fn write_pandas<'a, 'py: 'a>(
                         sb, destination, queries, origin_query
                     );
                     dispatcher.set_pre_execution_queries(pre_execution_queries);
+                    dispatcher.set_post_execution_queries(post_execution_queries);
+                    dispatcher.set_progress_callback(progress);
                     dispatcher.run(py)?
                 }
                 _ => unimplemented!("{} protocol not supported", protocol),
@@ -165,12 +188,15 @@ pub fn write_pandas<'a, 'py: 'a>(
             // remove the first "sqlite://" manually since url.path is not correct for windows
             let path = &source_conn.conn.as_str()[9..];
             let source = SQLiteSource::new(path, queries.len())?;
-            let dispatcher = PandasDispatcher::<_, SqlitePandasTransport>::new(
+            let mut dispatcher = PandasDispatcher::<_, SqlitePandasTransport>::new(
                 source,
                 destination,
                 queries,
                 origin_query,
             );
+            dispatcher.set_pre_execution_queries(pre_execution_queries);
+            dispatcher.set_post_execution_queries(post_execution_queries);
+            dispatcher.set_progress_callback(progress);
             dispatcher.run(py)?
         }
         SourceType::MySQL => match protocol {
@@ -185,6 +211,8 @@ pub fn write_pandas<'a, 'py: 'a>(
                         origin_query,
                     );
                 dispatcher.set_pre_execution_queries(pre_execution_queries);
+                dispatcher.set_post_execution_queries(post_execution_queries);
+                dispatcher.set_progress_callback(progress);
                 dispatcher.run(py)?
             }
             "text" => {
@@ -197,6 +225,8 @@ pub fn write_pandas<'a, 'py: 'a>(
                     origin_query,
                 );
                 dispatcher.set_pre_execution_queries(pre_execution_queries);
+                dispatcher.set_post_execution_queries(post_execution_queries);
+                dispatcher.set_progress_callback(progress);
                 dispatcher.run(py)?
             }
             _ => unimplemented!("{} protocol not supported", protocol),
@@ -204,44 +234,56 @@ pub fn write_pandas<'a, 'py: 'a>(
         SourceType::MsSQL => {
             let rt = Arc::new(tokio::runtime::Runtime::new().expect("Failed to create runtime"));
             let source = MsSQLSource::new(rt, &source_conn.conn[..], queries.len())?;
-            let dispatcher = PandasDispatcher::<_, MsSQLPandasTransport>::new(
+            let mut dispatcher = PandasDispatcher::<_, MsSQLPandasTransport>::new(
                 source,
                 destination,
                 queries,
                 origin_query,
             );
+            dispatcher.set_pre_execution_queries(pre_execution_queries);
+            dispatcher.set_post_execution_queries(post_execution_queries);
+            dispatcher.set_progress_callback(progress);
             dispatcher.run(py)?
         }
         SourceType::Oracle => {
             let source = OracleSource::new(&source_conn.conn[..], queries.len())?;
-            let dispatcher = PandasDispatcher::<_, OraclePandasTransport>::new(
+            let mut dispatcher = PandasDispatcher::<_, OraclePandasTransport>::new(
                 source,
                 destination,
                 queries,
                 origin_query,
             );
+            dispatcher.set_pre_execution_queries(pre_execution_queries);
+            dispatcher.set_post_execution_queries(post_execution_queries);
+            dispatcher.set_progress_callback(progress);
             dispatcher.run(py)?
         }
         SourceType::BigQuery => {
             let rt = Arc::new(tokio::runtime::Runtime::new().expect("Failed to create runtime"));
             let source = BigQuerySource::new(rt, &source_conn.conn[..])?;
-            let dispatcher = PandasDispatcher::<_, BigQueryPandasTransport>::new(
+            let mut dispatcher = PandasDispatcher::<_, BigQueryPandasTransport>::new(
                 source,
                 destination,
                 queries,
                 origin_query,
             );
+            dispatcher.set_pre_execution_queries(pre_execution_queries);
+            dispatcher.set_post_execution_queries(post_execution_queries);
+            dispatcher.set_progress_callback(progress);
             dispatcher.run(py)?
         }
         SourceType::Trino => {
             let rt = Arc::new(tokio::runtime::Runtime::new().expect("Failed to create runtime"));
             let source = TrinoSource::new(rt, &source_conn.conn[..])?;
-            let dispatcher = PandasDispatcher::<TrinoSource, TrinoPandasTransport>::new(
+            let mut dispatcher = PandasDispatcher::<TrinoSource, TrinoPandasTransport>::new(
                 source,
                 destination,
                 queries,
                 origin_query,
             );
+            dispatcher.set_pre_execution_queries(pre_execution_queries);
+            dispatcher.set_post_execution_queries(post_execution_queries);
+            dispatcher.set_progress_callback(progress);
             dispatcher.run(py)?
         }
         _ => unimplemented!("{:?} not implemented!", source_conn.ty),
@@ -1,7 +1,7 @@
 use crate::errors::ConnectorXPythonError;
 use crate::pandas::destination::PandasDestination;
 use crate::pandas::typesystem::PandasTypeSystem;
-use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, Utc};
 use connectorx::{
     impl_transport,
     sources::mysql::{BinaryProtocol, MySQLSource, MySQLTypeSystem, TextProtocol},
@@ -33,7 +33,7 @@ impl_transport!(
         { UInt24[u32]                => I64[i64]                | conversion none }
         { ULongLong[u64]             => F64[f64]                | conversion auto }
         { Date[NaiveDate]            => DateTime[DateTime<Utc>] | conversion option }
-        { Time[NaiveTime]            => String[String]          | conversion option }
+        { Time[Duration]             => String[String]          | conversion option }
         { Year[i16]                  => I64[i64]                | conversion none}
         { Datetime[NaiveDateTime]    => DateTime[DateTime<Utc>] | conversion option }
         { Timestamp[NaiveDateTime]   => DateTime[DateTime<Utc>] | conversion none }
@@ -69,7 +69,7 @@ impl_transport!(
         { UInt24[u32]                => I64[i64]                | conversion none }
         { ULongLong[u64]             => F64[f64]                | conversion auto }
         { Date[NaiveDate]            => DateTime[DateTime<Utc>] | conversion option }
-        { Time[NaiveTime]            => String[String]          | conversion option }
+        { Time[Duration]             => String[String]          | conversion option }
         { Datetime[NaiveDateTime]    => DateTime[DateTime<Utc>] | conversion option }
         { Timestamp[NaiveDateTime]   => DateTime[DateTime<Utc>] | conversion none }
         { Year[i16]                  => I64[i64]                | conversion none}
@@ -96,9 +96,29 @@ impl<'py, P> TypeConversion<NaiveDate, DateTime<Utc>> for MysqlPandasTransport<'
     }
 }
 
-impl<'py, P> TypeConversion<NaiveTime, String> for MysqlPandasTransport<'py, P> {
-    fn convert(val: NaiveTime) -> String {
-        val.to_string()
+// formatted as a time of the day for values within a day, e.g. `23:59:59.5`, and as hours
+// beyond it, e.g. `-838:59:59`
+impl<'py, P> TypeConversion<Duration, String> for MysqlPandasTransport<'py, P> {
+    fn convert(val: Duration) -> String {
+        let sign = if val < Duration::zero() { "-" } else { "" };
+        let val = val.abs();
+        let secs = val.num_seconds();
+        let micros = (val - Duration::seconds(secs))
+            .num_microseconds()
+            .unwrap_or(0);
+        let fraction = match micros {
+            0 => String::new(),
+            m if m % 1000 == 0 => format!(".{:03}", m / 1000),
+            m => format!(".{:06}", m),
+        };
+        format!(
+            "{}{:02}:{:02}:{:02}{}",
+            sign,
+            secs / 3600,
+            secs / 60 % 60,
+            secs % 60,
+            fraction
+        )
     }
 }
 
@@ -8,7 +8,7 @@ use connectorx::{
         BinaryProtocol, CSVProtocol, CursorProtocol, PostgresSource, PostgresTypeSystem,
         SimpleProtocol,
     },
-    typesystem::TypeConversion,
+    typesystem::{List, TypeConversion},
 };
 use postgres::NoTls;
 use postgres_openssl::MakeTlsConnector;
@@ -36,13 +36,13 @@ macro_rules! impl_postgres_transport {
                 { Int4[i32]                                     => I64[i64]                               | conversion auto }
                 { Int8[i64]                                     => I64[i64]                               | conversion auto }
                 { UInt4[u32]                                    => I64[i64]                               | conversion auto }
-                { BoolArray[Vec<Option<bool>>]                  => BoolArray[Vec<bool>]                   | conversion option }
-                { Int2Array[Vec<Option<i16>>]                   => I64Array[Vec<i64>]                     | conversion option }
-                { Int4Array[Vec<Option<i32>>]                   => I64Array[Vec<i64>]                     | conversion option }
-                { Int8Array[Vec<Option<i64>>]                   => I64Array[Vec<i64>]                     | conversion option }
-                { Float4Array[Vec<Option<f32>>]                 => F64Array[Vec<f64>]                     | conversion option }
-                { Float8Array[Vec<Option<f64>>]                 => F64Array[Vec<f64>]                     | conversion option }
-                { NumericArray[Vec<Option<Decimal>>]            => F64Array[Vec<f64>]                     | conversion option }
+                { BoolArray[List<bool>]                         => BoolArray[Vec<bool>]                   | conversion option }
+                { Int2Array[List<i16>]                          => I64Array[Vec<i64>]                     | conversion option }
+                { Int4Array[List<i32>]                          => I64Array[Vec<i64>]                     | conversion option }
+                { Int8Array[List<i64>]                          => I64Array[Vec<i64>]                     | conversion option }
+                { Float4Array[List<f32>]                        => F64Array[Vec<f64>]                     | conversion option }
+                { Float8Array[List<f64>]                        => F64Array[Vec<f64>]                     | conversion option }
+                { NumericArray[List<Decimal>]                   => F64Array[Vec<f64>]                     | conversion option }
                 { Vector[Vector]                                => F64Array[Vec<f64>]                     | conversion option   }
                 { HalfVec[HalfVector]                           => F64Array[Vec<f64>]                     | conversion option   }
                 { Bit[Bit]                                      => Bytes[Vec<u8>]                         | conversion option   }
@@ -116,11 +116,11 @@ impl<'py, P, C> TypeConversion<HashMap<String, Option<String>>, String>
     }
 }
 
-impl<'py, P, C> TypeConversion<Vec<Option<Decimal>>, Vec<f64>>
-    for PostgresPandasTransport<'py, P, C>
-{
-    fn convert(val: Vec<Option<Decimal>>) -> Vec<f64> {
-        val.into_iter()
+// the elements of multidimensional arrays are flattened in row-major order
+impl<'py, P, C> TypeConversion<List<Decimal>, Vec<f64>> for PostgresPandasTransport<'py, P, C> {
+    fn convert(val: List<Decimal>) -> Vec<f64> {
+        val.values
+            .into_iter()
             .map(|v| match v {
                 Some(v) => v
                     .to_f64()
@@ -133,11 +133,10 @@ impl<'py, P, C> TypeConversion<Vec<Option<Decimal>>, Vec<f64>>
     }
 }
 
-impl<'py, P, C> TypeConversion<Vec<Option<bool>>, Vec<bool>>
-    for PostgresPandasTransport<'py, P, C>
-{
-    fn convert(val: Vec<Option<bool>>) -> Vec<bool> {
-        val.into_iter()
+impl<'py, P, C> TypeConversion<List<bool>, Vec<bool>> for PostgresPandasTransport<'py, P, C> {
+    fn convert(val: List<bool>) -> Vec<bool> {
+        val.values
+            .into_iter()
             .map(|v| match v {
                 Some(v) => v,
                 None => {
@@ -148,9 +147,10 @@ impl<'py, P, C> TypeConversion<Vec<Option<bool>>, Vec<bool>>
     }
 }
 
-impl<'py, P, C> TypeConversion<Vec<Option<f32>>, Vec<f64>> for PostgresPandasTransport<'py, P, C> {
-    fn convert(val: Vec<Option<f32>>) -> Vec<f64> {
-        val.into_iter()
+impl<'py, P, C> TypeConversion<List<f32>, Vec<f64>> for PostgresPandasTransport<'py, P, C> {
+    fn convert(val: List<f32>) -> Vec<f64> {
+        val.values
+            .into_iter()
             .map(|v| match v {
                 Some(v) => v as f64,
                 None => {
@@ -161,9 +161,10 @@ impl<'py, P, C> TypeConversion<Vec<Option<f32>>, Vec<f64>> for PostgresPandasTra
     }
 }
 
-impl<'py, P, C> TypeConversion<Vec<Option<f64>>, Vec<f64>> for PostgresPandasTransport<'py, P, C> {
-    fn convert(val: Vec<Option<f64>>) -> Vec<f64> {
-        val.into_iter()
+impl<'py, P, C> TypeConversion<List<f64>, Vec<f64>> for PostgresPandasTransport<'py, P, C> {
+    fn convert(val: List<f64>) -> Vec<f64> {
+        val.values
+            .into_iter()
             .map(|v| match v {
                 Some(v) => v,
                 None => {
@@ -174,9 +175,10 @@ impl<'py, P, C> TypeConversion<Vec<Option<f64>>, Vec<f64>> for PostgresPandasTra
     }
 }
 
-impl<'py, P, C> TypeConversion<Vec<Option<i16>>, Vec<i64>> for PostgresPandasTransport<'py, P, C> {
-    fn convert(val: Vec<Option<i16>>) -> Vec<i64> {
-        val.into_iter()
+impl<'py, P, C> TypeConversion<List<i16>, Vec<i64>> for PostgresPandasTransport<'py, P, C> {
+    fn convert(val: List<i16>) -> Vec<i64> {
+        val.values
+            .into_iter()
             .map(|v| match v {
                 Some(v) => v as i64,
                 None => {
@@ -187,9 +189,10 @@ impl<'py, P, C> TypeConversion<Vec<Option<i16>>, Vec<i64>> for PostgresPandasTra
     }
 }
 
-impl<'py, P, C> TypeConversion<Vec<Option<i32>>, Vec<i64>> for PostgresPandasTransport<'py, P, C> {
-    fn convert(val: Vec<Option<i32>>) -> Vec<i64> {
-        val.into_iter()
+impl<'py, P, C> TypeConversion<List<i32>, Vec<i64>> for PostgresPandasTransport<'py, P, C> {
+    fn convert(val: List<i32>) -> Vec<i64> {
+        val.values
+            .into_iter()
             .map(|v| match v {
                 Some(v) => v as i64,
                 None => {
@@ -200,9 +203,10 @@ impl<'py, P, C> TypeConversion<Vec<Option<i32>>, Vec<i64>> for PostgresPandasTra
     }
 }
 
-impl<'py, P, C> TypeConversion<Vec<Option<i64>>, Vec<i64>> for PostgresPandasTransport<'py, P, C> {
-    fn convert(val: Vec<Option<i64>>) -> Vec<i64> {
-        val.into_iter()
+impl<'py, P, C> TypeConversion<List<i64>, Vec<i64>> for PostgresPandasTransport<'py, P, C> {
+    fn convert(val: List<i64>) -> Vec<i64> {
+        val.values
+            .into_iter()
             .map(|v| match v {
                 Some(v) => v,
                 None => {
@@ -7,6 +7,7 @@ use connectorx::{
     sources::trino::{TrinoSource, TrinoTypeSystem},
     typesystem::TypeConversion,
 };
+use uuid::Uuid;
 
 #[allow(dead_code)]
 pub struct TrinoPandasTransport<'py>(&'py ());
@@ -29,6 +30,7 @@ impl_transport!(
         { Real[f32]                  => F64[f64]                | conversion auto }
         { Varchar[String]            => String[String]          | conversion auto }
         { Char[String]               => String[String]          | conversion none }
+        { Uuid[Uuid]                 => String[String]          | conversion option }
     }
 );
 
@@ -48,6 +50,12 @@ impl<'py> TypeConversion<NaiveTime, String> for TrinoPandasTransport<'py> {
     }
 }
 
+impl<'py> TypeConversion<Uuid, String> for TrinoPandasTransport<'py> {
+    fn convert(val: Uuid) -> String {
+        val.to_string()
+    }
+}
+
 impl<'py> TypeConversion<NaiveDateTime, DateTime<Utc>> for TrinoPandasTransport<'py> {
     fn convert(val: NaiveDateTime) -> DateTime<Utc> {
         DateTime::from_naive_utc_and_offset(val, Utc)
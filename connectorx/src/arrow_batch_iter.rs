@@ -1,9 +1,11 @@
 use crate::prelude::*;
+use anyhow::anyhow;
 use arrow::record_batch::RecordBatch;
 use itertools::Itertools;
-use log::debug;
 use rayon::prelude::*;
 use std::marker::PhantomData;
+use std::thread::JoinHandle;
+use tracing::debug;
 
 pub fn set_global_num_thread(num: usize) {
     rayon::ThreadPoolBuilder::new()
@@ -32,6 +34,7 @@ where
     dorder: DataOrder,
     src_schema: Vec<S::TypeSystem>,
     dst_schema: Vec<ArrowStreamTypeSystem>,
+    worker: Option<JoinHandle<Result<(), TP::Error>>>,
     _phantom: PhantomData<TP>,
 }
 
@@ -61,6 +64,7 @@ where
             dorder,
             src_schema,
             dst_schema,
+            worker: None,
             _phantom: PhantomData,
         })
     }
@@ -72,7 +76,7 @@ where
         let dst_partitions = self.dst_parts.take().unwrap();
         let dorder = self.dorder;
 
-        std::thread::spawn(move || -> Result<(), TP::Error> {
+        self.worker = Some(std::thread::spawn(move || -> Result<(), TP::Error> {
             let schemas: Vec<_> = src_schema
                 .iter()
                 .zip_eq(&dst_schema)
@@ -125,6 +129,8 @@ where
 
                     debug!("Finalize partition {}", i);
                     dst.finalize()?;
+                    drop(parser);
+                    src.finish()?;
                     debug!("Partition {} finished", i);
                     Ok(())
                 })?;
@@ -132,7 +138,7 @@ where
             debug!("Writing finished");
 
             Ok(())
-        });
+        }));
     }
 }
 
@@ -157,6 +163,12 @@ pub trait RecordBatchIterator: Send {
     fn get_schema(&self) -> (RecordBatch, &[String]);
     fn prepare(&mut self);
     fn next_batch(&mut self) -> Option<RecordBatch>;
+
+    /// Same as [`RecordBatchIterator::next_batch`], but once all the batches are returned, returns
+    /// the error that stopped the load early, if any.
+    fn try_next_batch(&mut self) -> Result<Option<RecordBatch>, ConnectorXOutError> {
+        Ok(self.next_batch())
+    }
 }
 
 impl<'a, S, TP> RecordBatchIterator for ArrowBatchIter<S, TP>
@@ -168,6 +180,7 @@ where
             S = S,
             D = ArrowStreamDestination,
         > + std::marker::Send,
+    ConnectorXOutError: From<TP::Error>,
 {
     fn get_schema(&self) -> (RecordBatch, &[String]) {
         (self.dst.empty_batch(), self.dst.names())
@@ -180,4 +193,16 @@ where
     fn next_batch(&mut self) -> Option<RecordBatch> {
         self.next()
     }
+
+    fn try_next_batch(&mut self) -> Result<Option<RecordBatch>, ConnectorXOutError> {
+        if let Some(rb) = self.next() {
+            return Ok(Some(rb));
+        }
+        // the batches run out once the worker is done, whether it succeeded or not
+        match self.worker.take().map(|worker| worker.join()) {
+            Some(Ok(Err(e))) => Err(ConnectorXOutError::from(e).classify_timeout()),
+            Some(Err(_)) => Err(anyhow!("the worker writing the record batches panicked").into()),
+            _ => Ok(None),
+        }
+    }
 }
@@ -0,0 +1,415 @@
+//! List the schemas, tables, views and columns of a database, along with the arrow type every
+//! column is loaded as. The column types are derived the same way a load does it: the source
+//! describes a `SELECT *` of the table with its own typesystem, and the arrow transport of the
+//! source converts them with `convert_typesystem`.
+
+#[cfg(feature = "src_mysql")]
+use crate::sources::mysql::BinaryProtocol as MySQLBinaryProtocol;
+#[cfg(feature = "src_postgres")]
+use crate::sources::postgres::{rewrite_tls_args, CursorProtocol};
+use crate::{
+    destinations::arrow::FNewField,
+    get_arrow::get_arrow,
+    prelude::*,
+    sql::{CXParam, CXQuery},
+};
+use anyhow::anyhow;
+use arrow::{array::StringArray, datatypes::DataType, record_batch::RecordBatch};
+use fehler::{throw, throws};
+#[cfg(feature = "src_postgres")]
+use postgres::NoTls;
+#[cfg(feature = "src_postgres")]
+use postgres_openssl::MakeTlsConnector;
+#[allow(unused_imports)]
+use std::sync::Arc;
+use tracing::{debug, info_span};
+
+/// Whether a [`CatalogTable`] stores its rows or is a view over other tables.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableKind {
+    Table,
+    View,
+}
+
+#[derive(Debug, Clone)]
+pub struct CatalogColumn {
+    pub name: String,
+    /// The type of the column in the typesystem of the source, e.g. `Int4` for Postgres.
+    pub source_type: String,
+    /// The arrow type of the column, `None` if ConnectorX cannot load the source type into arrow.
+    pub arrow_type: Option<DataType>,
+    pub nullable: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct CatalogTable {
+    pub schema: String,
+    pub name: String,
+    pub kind: TableKind,
+    /// Empty if the source cannot describe a column of the table.
+    pub columns: Vec<CatalogColumn>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Catalog {
+    pub schemas: Vec<String>,
+    pub tables: Vec<CatalogTable>,
+}
+
+/// List the schemas of `source_conn`, and the tables and views with their columns, either of
+/// `schema` or of every schema that is not a system one. Oracle lists the current schema if no
+/// `schema` is given. A table only shows up if the user of the connection can read it.
+#[allow(unreachable_code, unreachable_patterns, unused_variables, unused_mut)]
+#[throws(ConnectorXOutError)]
+pub fn get_catalog(source_conn: &SourceConn, schema: Option<&str>) -> Catalog {
+    let _span = info_span!("get_catalog", source = ?source_conn.ty).entered();
+
+    // the catalog queries bind the schema as a parameter, which not every protocol supports
+    let mut source_conn = source_conn.clone();
+    match source_conn.ty {
+        SourceType::Postgres => source_conn.set_protocol("cursor"),
+        SourceType::MySQL => source_conn.set_protocol("binary"),
+        _ => {}
+    }
+
+    let schemas = match schema {
+        Some(schema) => vec![schema.to_string()],
+        None => {
+            let query = schemas_query(&source_conn)?;
+            fetch_strings(&source_conn, &query, 1)?.remove(0)
+        }
+    };
+
+    let query = match tables_query(&source_conn, schema, &schemas)? {
+        Some(query) => query,
+        None => {
+            return Catalog {
+                schemas,
+                tables: vec![],
+            }
+        }
+    };
+    let mut listed = fetch_strings(&source_conn, &query, 3)?.into_iter();
+    let (table_schemas, names, kinds) = (
+        listed.next().unwrap_or_default(),
+        listed.next().unwrap_or_default(),
+        listed.next().unwrap_or_default(),
+    );
+    debug!(ntables = names.len(), "Listed tables");
+
+    let queries: Vec<CXQuery<String>> = table_schemas
+        .iter()
+        .zip(&names)
+        .map(|(schema, name)| select_all(&source_conn.ty, schema, name))
+        .collect();
+    let columns = describe(&source_conn, &queries)?;
+
+    let tables = table_schemas
+        .into_iter()
+        .zip(names)
+        .zip(kinds)
+        .zip(columns)
+        .map(|(((schema, name), kind), columns)| CatalogTable {
+            schema,
+            name,
+            // `BASE TABLE`, `VIEW`, `SYSTEM VIEW`, `FOREIGN`, ... depending on the database
+            kind: match kind.to_uppercase().contains("VIEW") {
+                true => TableKind::View,
+                false => TableKind::Table,
+            },
+            columns,
+        })
+        .collect();
+
+    Catalog { schemas, tables }
+}
+
+#[throws(ConnectorXOutError)]
+fn schemas_query(source_conn: &SourceConn) -> CXQuery<String> {
+    let query = match source_conn.ty {
+        SourceType::Postgres => {
+            "SELECT schema_name::text FROM information_schema.schemata \
+             WHERE schema_name NOT IN ('pg_catalog', 'information_schema') \
+             AND schema_name NOT LIKE 'pg\\_%' ORDER BY 1"
+        }
+        SourceType::MySQL => {
+            "SELECT schema_name FROM information_schema.schemata \
+             WHERE schema_name NOT IN ('mysql', 'information_schema', 'performance_schema', 'sys') \
+             ORDER BY 1"
+        }
+        SourceType::SQLite => "SELECT name FROM pragma_database_list ORDER BY seq",
+        SourceType::MsSQL => {
+            "SELECT schema_name FROM information_schema.schemata \
+             WHERE schema_name NOT IN ('sys', 'INFORMATION_SCHEMA', 'guest') \
+             AND schema_name NOT LIKE 'db[_]%' ORDER BY 1"
+        }
+        SourceType::Oracle => "SELECT SYS_CONTEXT('USERENV', 'CURRENT_SCHEMA') FROM dual",
+        SourceType::BigQuery => "SELECT schema_name FROM INFORMATION_SCHEMA.SCHEMATA ORDER BY 1",
+        SourceType::Trino => {
+            "SELECT schema_name FROM information_schema.schemata \
+             WHERE schema_name <> 'information_schema' ORDER BY 1"
+        }
+        _ => throw!(ConnectorXOutError::SourceNotSupport(format!(
+            "{:?}",
+            source_conn.ty
+        ))),
+    };
+    CXQuery::naked(query)
+}
+
+/// The query listing `(schema, name, type)` of the tables in `schema`, or in all of `schemas`.
+/// Returns `None` if there is no schema to look into.
+#[throws(ConnectorXOutError)]
+fn tables_query(
+    source_conn: &SourceConn,
+    schema: Option<&str>,
+    schemas: &[String],
+) -> Option<CXQuery<String>> {
+    let param = |schema: &str| vec![CXParam::from(schema)];
+    let query = match source_conn.ty {
+        SourceType::Postgres => {
+            let query = CXQuery::naked(format!(
+                "SELECT table_schema::text, table_name::text, table_type::text \
+                 FROM information_schema.tables \
+                 WHERE table_schema NOT IN ('pg_catalog', 'information_schema') {} ORDER BY 1, 2",
+                schema.map_or("", |_| "AND table_schema = $1")
+            ));
+            query.bind(schema.map(param).unwrap_or_default())
+        }
+        SourceType::MySQL => {
+            let query = CXQuery::naked(format!(
+                "SELECT table_schema, table_name, table_type FROM information_schema.tables \
+                 WHERE table_schema NOT IN \
+                 ('mysql', 'information_schema', 'performance_schema', 'sys') {} ORDER BY 1, 2",
+                schema.map_or("", |_| "AND table_schema = ?")
+            ));
+            query.bind(schema.map(param).unwrap_or_default())
+        }
+        SourceType::MsSQL => {
+            let query = CXQuery::naked(format!(
+                "SELECT table_schema, table_name, table_type FROM information_schema.tables \
+                 {} ORDER BY 1, 2",
+                schema.map_or("", |_| "WHERE table_schema = @P1")
+            ));
+            query.bind(schema.map(param).unwrap_or_default())
+        }
+        SourceType::Trino => {
+            let query = CXQuery::naked(format!(
+                "SELECT table_schema, table_name, table_type FROM information_schema.tables \
+                 WHERE table_schema <> 'information_schema' {} ORDER BY 1, 2",
+                schema.map_or("", |_| "AND table_schema = ?")
+            ));
+            query.bind(schema.map(param).unwrap_or_default())
+        }
+        // every attached database of sqlite has a `sqlite_master` of its own
+        SourceType::SQLite => {
+            let selects: Vec<String> = schemas
+                .iter()
+                .map(|s| {
+                    format!(
+                        "SELECT ? AS table_schema, name, type FROM {}.sqlite_master \
+                         WHERE type IN ('table', 'view') AND name NOT LIKE 'sqlite\\_%' ESCAPE '\\'",
+                        quote_identifier(&source_conn.ty, s)
+                    )
+                })
+                .collect();
+            if selects.is_empty() {
+                return None;
+            }
+            CXQuery::naked(format!("{} ORDER BY 1, 2", selects.join(" UNION ALL ")))
+                .bind(schemas.iter().map(|s| CXParam::from(s.as_str())).collect())
+        }
+        SourceType::Oracle => {
+            let schema = schemas
+                .first()
+                .ok_or_else(|| anyhow!("Cannot get the current schema of oracle"))?;
+            CXQuery::naked(
+                "SELECT owner, table_name, 'BASE TABLE' FROM all_tables WHERE owner = :1 \
+                 UNION ALL SELECT owner, view_name, 'VIEW' FROM all_views WHERE owner = :2 \
+                 ORDER BY 1, 2",
+            )
+            .bind(vec![
+                CXParam::from(schema.as_str()),
+                CXParam::from(schema.as_str()),
+            ])
+        }
+        // the tables are listed per dataset
+        SourceType::BigQuery => {
+            let selects: Vec<String> = schemas
+                .iter()
+                .map(|s| {
+                    format!(
+                        "SELECT table_schema, table_name, table_type FROM {}.INFORMATION_SCHEMA.TABLES",
+                        quote_identifier(&source_conn.ty, s)
+                    )
+                })
+                .collect();
+            if selects.is_empty() {
+                return None;
+            }
+            CXQuery::naked(format!("{} ORDER BY 1, 2", selects.join(" UNION ALL ")))
+        }
+        _ => throw!(ConnectorXOutError::SourceNotSupport(format!(
+            "{:?}",
+            source_conn.ty
+        ))),
+    };
+    Some(query)
+}
+
+fn quote_identifier(ty: &SourceType, ident: &str) -> String {
+    match ty {
+        SourceType::MySQL => format!("`{}`", ident.replace('`', "``")),
+        SourceType::BigQuery => format!("`{}`", ident.replace('`', "\\`")),
+        SourceType::MsSQL => format!("[{}]", ident.replace(']', "]]")),
+        _ => format!("\"{}\"", ident.replace('"', "\"\"")),
+    }
+}
+
+fn select_all(ty: &SourceType, schema: &str, name: &str) -> CXQuery<String> {
+    CXQuery::naked(format!(
+        "SELECT * FROM {}.{}",
+        quote_identifier(ty, schema),
+        quote_identifier(ty, name)
+    ))
+}
+
+/// Load `query` and return its first `ncols` columns, which must be strings.
+#[throws(ConnectorXOutError)]
+fn fetch_strings(
+    source_conn: &SourceConn,
+    query: &CXQuery<String>,
+    ncols: usize,
+) -> Vec<Vec<String>> {
+    let batches: Vec<RecordBatch> =
+        get_arrow(source_conn, None, std::slice::from_ref(query), None)?.arrow()?;
+
+    let mut columns = vec![vec![]; ncols];
+    for batch in &batches {
+        for (cidx, values) in columns.iter_mut().enumerate() {
+            let array = batch
+                .column(cidx)
+                .as_any()
+                .downcast_ref::<StringArray>()
+                .ok_or_else(|| anyhow!("Catalog column {} is not a string", cidx))?;
+            values.extend(array.iter().map(|v| v.unwrap_or_default().to_string()));
+        }
+    }
+    columns
+}
+
+#[allow(unreachable_code, unreachable_patterns, unused_variables, unused_mut)]
+#[throws(ConnectorXOutError)]
+fn describe(source_conn: &SourceConn, queries: &[CXQuery<String>]) -> Vec<Vec<CatalogColumn>> {
+    if queries.is_empty() {
+        return vec![];
+    }
+
+    match source_conn.ty {
+        #[cfg(feature = "src_postgres")]
+        SourceType::Postgres => {
+            let (config, tls) = rewrite_tls_args(&source_conn.conn)?;
+            match tls {
+                Some(tls_conn) => describe_columns::<
+                    _,
+                    PostgresArrowTransport<CursorProtocol, MakeTlsConnector>,
+                >(
+                    PostgresSource::<CursorProtocol, MakeTlsConnector>::new(config, tls_conn, 1)?,
+                    queries,
+                )?,
+                None => describe_columns::<_, PostgresArrowTransport<CursorProtocol, NoTls>>(
+                    PostgresSource::<CursorProtocol, NoTls>::new(config, NoTls, 1)?,
+                    queries,
+                )?,
+            }
+        }
+        #[cfg(feature = "src_mysql")]
+        SourceType::MySQL => describe_columns::<_, MySQLArrowTransport<MySQLBinaryProtocol>>(
+            MySQLSource::<MySQLBinaryProtocol>::new(&source_conn.conn[..], 1)?,
+            queries,
+        )?,
+        #[cfg(feature = "src_sqlite")]
+        SourceType::SQLite => {
+            // remove the first "sqlite://" manually since url.path is not correct for windows
+            let path = &source_conn.conn.as_str()[9..];
+            describe_columns::<_, SQLiteArrowTransport>(SQLiteSource::new(path, 1)?, queries)?
+        }
+        #[cfg(feature = "src_mssql")]
+        SourceType::MsSQL => {
+            let rt = Arc::new(tokio::runtime::Runtime::new().expect("Failed to create runtime"));
+            describe_columns::<_, MsSQLArrowTransport>(
+                MsSQLSource::new(rt, &source_conn.conn[..], 1)?,
+                queries,
+            )?
+        }
+        #[cfg(feature = "src_oracle")]
+        SourceType::Oracle => describe_columns::<_, OracleArrowTransport>(
+            OracleSource::new(&source_conn.conn[..], 1)?,
+            queries,
+        )?,
+        #[cfg(feature = "src_bigquery")]
+        SourceType::BigQuery => {
+            let rt = Arc::new(tokio::runtime::Runtime::new().expect("Failed to create runtime"));
+            describe_columns::<_, BigQueryArrowTransport>(
+                BigQuerySource::new(rt, &source_conn.conn[..])?,
+                queries,
+            )?
+        }
+        #[cfg(feature = "src_trino")]
+        SourceType::Trino => {
+            let rt = Arc::new(tokio::runtime::Runtime::new().expect("Failed to create runtime"));
+            describe_columns::<_, TrinoArrowTransport>(
+                TrinoSource::new(rt, &source_conn.conn[..])?,
+                queries,
+            )?
+        }
+        _ => throw!(ConnectorXOutError::SourceNotSupport(format!(
+            "{:?}",
+            source_conn.ty
+        ))),
+    }
+}
+
+// one source describes all the tables, so they share its connection pool
+fn describe_columns<S, TP>(
+    mut source: S,
+    queries: &[CXQuery<String>],
+) -> Result<Vec<Vec<CatalogColumn>>, ConnectorXOutError>
+where
+    S: Source,
+    TP: Transport<TSS = S::TypeSystem, TSD = ArrowTypeSystem, S = S, D = ArrowDestination>,
+    ConnectorXOutError: From<TP::Error>,
+{
+    queries
+        .iter()
+        .map(|query| {
+            source.set_queries(std::slice::from_ref(query));
+            // a column type the source does not know must not abort the listing of the other
+            // tables
+            if let Err(e) = source.fetch_metadata() {
+                debug!(query = %query, error = ?e, "Cannot describe the columns, skip them");
+                return Ok(vec![]);
+            }
+            source
+                .names()
+                .into_iter()
+                .zip(source.schema())
+                .map(|(name, ts)| {
+                    let field = match TP::convert_typesystem(ts) {
+                        Ok(dt) => Some(Realize::<FNewField>::realize(dt)?(name.as_str())),
+                        Err(e) => {
+                            debug!(column = name.as_str(), error = %e, "No arrow type");
+                            None
+                        }
+                    };
+                    Ok(CatalogColumn {
+                        source_type: ts.type_name().to_string(),
+                        arrow_type: field.map(|f| f.data_type().clone()),
+                        nullable: ts.is_nullable(),
+                        name,
+                    })
+                })
+                .collect()
+        })
+        .collect()
+}
@@ -11,9 +11,45 @@ pub const DEFAULT_ARROW_DECIMAL_SCALE: i8 = 10;
 pub const DEFAULT_ARROW_DECIMAL: ArrowDataType =
     ArrowDataType::Decimal128(DEFAULT_ARROW_DECIMAL_PRECISION, DEFAULT_ARROW_DECIMAL_SCALE);
 
+/// Metadata key of an arrow field holding the type of the column in the source.
+#[cfg(feature = "dst_arrow")]
+pub const SOURCE_TYPE_KEY: &str = "connectorx.source_type";
+
+/// Metadata keys of an arrow field describing the column in the source, set with
+/// `ArrowDestination::set_source_metadata`.
+#[cfg(feature = "dst_arrow")]
+pub const SOURCE_NULLABLE_KEY: &str = "connectorx.source_nullable";
+#[cfg(feature = "dst_arrow")]
+pub const SOURCE_PRECISION_KEY: &str = "connectorx.precision";
+#[cfg(feature = "dst_arrow")]
+pub const SOURCE_SCALE_KEY: &str = "connectorx.scale";
+#[cfg(feature = "dst_arrow")]
+pub const SOURCE_LENGTH_KEY: &str = "connectorx.length";
+
+/// Metadata keys of an arrow schema holding the kind of the source and the query it was loaded with.
+#[cfg(feature = "dst_arrow")]
+pub const SOURCE_KIND_KEY: &str = "connectorx.source";
+#[cfg(feature = "dst_arrow")]
+pub const SOURCE_QUERY_KEY: &str = "connectorx.query";
+
+/// Metadata keys of an arrow field holding its extension type, see
+/// https://arrow.apache.org/docs/format/CanonicalExtensions.html
+#[cfg(feature = "dst_arrow")]
+pub const EXTENSION_TYPE_NAME_KEY: &str = "ARROW:extension:name";
+#[cfg(feature = "dst_arrow")]
+pub const EXTENSION_TYPE_METADATA_KEY: &str = "ARROW:extension:metadata";
+
+/// Metadata key of an arrow field holding the length in bits of a bit string column loaded as
+/// `FixedSizeBinary`, whose width is only the number of whole bytes.
+#[cfg(feature = "dst_arrow")]
+pub const BIT_LENGTH_KEY: &str = "connectorx.bit_length";
+
 #[cfg(feature = "dst_arrow")]
 pub(crate) const SECONDS_IN_DAY: i64 = 86_400;
 
+/// Minimum time between two invocations of a progress callback.
+pub const PROGRESS_REPORT_INTERVAL: std::time::Duration = std::time::Duration::from_millis(500);
+
 #[allow(dead_code)]
 const KILO: usize = 1 << 10;
 
@@ -51,3 +87,12 @@ pub const MYSQL_JDBC_DRIVER: &str = "com.mysql.cj.jdbc.Driver";
 pub const DUCKDB_JDBC_DRIVER: &str = "org.duckdb.DuckDBDriver";
 
 pub const CONNECTORX_PROTOCOL: &str = "cxprotocol";
+
+/// Connection string parameter holding the label that tags every query issued.
+pub const CONNECTORX_TAG: &str = "cxtag";
+
+/// Connection string parameter bounding the time to connect, in seconds.
+pub const CONNECT_TIMEOUT: &str = "connect_timeout";
+
+/// Connection string parameter bounding the time a query may run, in milliseconds.
+pub const STATEMENT_TIMEOUT: &str = "statement_timeout";
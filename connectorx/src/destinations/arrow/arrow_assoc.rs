@@ -1,28 +1,62 @@
 use super::{
     errors::{ArrowDestinationError, Result},
-    typesystem::{DateTimeWrapperMicro, NaiveDateTimeWrapperMicro, NaiveTimeWrapperMicro},
+    typesystem::{
+        DateTimeWrapperMicro, DictionaryWrapper, NaiveDateTimeWrapperMicro, NaiveTimeWrapperMicro,
+    },
 };
 use crate::{
     constants::{DEFAULT_ARROW_DECIMAL, DEFAULT_ARROW_DECIMAL_SCALE, SECONDS_IN_DAY},
+    destinations::arrow_maps::{append_string_map, map_type, string_map_builder},
+    destinations::arrow_vectors::{
+        sparse_vector_fields, vector_field, BitColumnBuilder, SparseVectorColumnBuilder,
+        VectorColumnBuilder,
+    },
+    typesystem::{
+        BitString, Composite, DenseVector, FieldType, FieldValue, Interval, List, SparseVec,
+    },
     utils::decimal_to_i128,
 };
+use anyhow::anyhow;
 use arrow::array::{
-    ArrayBuilder, BooleanBuilder, Date32Builder, Decimal128Builder, Float32Builder, Float64Builder,
-    Int16Builder, Int32Builder, Int64Builder, LargeBinaryBuilder, LargeListBuilder, StringBuilder,
-    Time64MicrosecondBuilder, Time64NanosecondBuilder, TimestampMicrosecondBuilder,
-    TimestampNanosecondBuilder, UInt16Builder, UInt32Builder, UInt64Builder,
+    make_builder, ArrayBuilder, ArrayRef, BinaryBuilder, BinaryViewBuilder, BooleanArray,
+    BooleanBuilder, Date32Array, Date32Builder, Decimal128Array, Decimal128Builder,
+    DurationMicrosecondBuilder, FixedSizeBinaryBuilder, Float32Array, Float32Builder, Float64Array,
+    Float64Builder, Int16Array, Int16Builder, Int32Array, Int32Builder, Int64Array, Int64Builder,
+    IntervalMonthDayNanoArray, IntervalMonthDayNanoBuilder, LargeBinaryArray, LargeBinaryBuilder,
+    LargeListArray, MapBuilder, NullBufferBuilder, StringArray, StringBuilder,
+    StringDictionaryBuilder, StringViewBuilder, StructArray, Time64MicrosecondArray,
+    Time64MicrosecondBuilder, Time64NanosecondBuilder, TimestampMicrosecondArray,
+    TimestampMicrosecondBuilder, TimestampNanosecondBuilder, UInt16Builder, UInt32Builder,
+    UInt64Builder,
 };
-use arrow::datatypes::Field;
-use arrow::datatypes::{DataType as ArrowDataType, TimeUnit};
-use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Timelike, Utc};
-use fehler::throws;
+use arrow::buffer::{NullBuffer, OffsetBuffer};
+use arrow::datatypes::{
+    DataType as ArrowDataType, Float16Type, Float32Type, Int32Type, IntervalMonthDayNano,
+    IntervalUnit, TimeUnit,
+};
+use arrow::datatypes::{Field, Fields};
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, NaiveTime, Timelike, Utc};
+use fehler::{throw, throws};
+use half::f16;
 use rust_decimal::Decimal;
+use serde_json::Value;
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock};
+use uuid::Uuid;
 
 /// Associate arrow builder with native type
 pub trait ArrowAssoc {
     type Builder: ArrayBuilder + Send;
 
     fn builder(nrows: usize) -> Self::Builder;
+
+    /// Same as `builder`, but the builders of list columns nest their arrays to `depth`, which
+    /// is shared by all the builders of the column.
+    fn builder_with_depth(nrows: usize, _depth: &ListDepth) -> Self::Builder {
+        Self::builder(nrows)
+    }
+
     fn append(builder: &mut Self::Builder, value: Self) -> Result<()>;
     fn field(header: &str) -> Field;
 }
@@ -115,16 +149,263 @@ impl ArrowAssoc for Option<Decimal> {
     }
 }
 
-impl ArrowAssoc for &str {
-    type Builder = StringBuilder;
+/// Builder of the string columns, `Utf8` by default, `Utf8View` if the destination asks for
+/// view types or `Dictionary(Int32, Utf8)` for the columns it asks to dictionary-encode.
+pub enum Utf8ColumnBuilder {
+    Utf8(StringBuilder),
+    View(StringViewBuilder),
+    Dictionary(Box<StringDictionaryBuilder<Int32Type>>),
+}
+
+impl Utf8ColumnBuilder {
+    pub fn view(nrows: usize) -> Self {
+        Utf8ColumnBuilder::View(StringViewBuilder::with_capacity(nrows))
+    }
+
+    pub fn dictionary(nrows: usize) -> Self {
+        Utf8ColumnBuilder::Dictionary(Box::new(StringDictionaryBuilder::with_capacity(
+            nrows, 64, 1024,
+        )))
+    }
+
+    #[throws(ArrowDestinationError)]
+    fn append_value(&mut self, value: &str) {
+        match self {
+            Utf8ColumnBuilder::Utf8(builder) => builder.append_value(value),
+            Utf8ColumnBuilder::View(builder) => builder.append_value(value),
+            Utf8ColumnBuilder::Dictionary(builder) => {
+                builder.append(value)?;
+            }
+        }
+    }
+
+    fn append_null(&mut self) {
+        match self {
+            Utf8ColumnBuilder::Utf8(builder) => builder.append_null(),
+            Utf8ColumnBuilder::View(builder) => builder.append_null(),
+            Utf8ColumnBuilder::Dictionary(builder) => builder.append_null(),
+        }
+    }
+}
+
+impl ArrayBuilder for Utf8ColumnBuilder {
+    fn len(&self) -> usize {
+        match self {
+            Utf8ColumnBuilder::Utf8(builder) => builder.len(),
+            Utf8ColumnBuilder::View(builder) => builder.len(),
+            Utf8ColumnBuilder::Dictionary(builder) => builder.len(),
+        }
+    }
+
+    fn finish(&mut self) -> ArrayRef {
+        match self {
+            Utf8ColumnBuilder::Utf8(builder) => ArrayBuilder::finish(builder),
+            Utf8ColumnBuilder::View(builder) => ArrayBuilder::finish(builder),
+            Utf8ColumnBuilder::Dictionary(builder) => ArrayBuilder::finish(builder.as_mut()),
+        }
+    }
+
+    fn finish_cloned(&self) -> ArrayRef {
+        match self {
+            Utf8ColumnBuilder::Utf8(builder) => ArrayBuilder::finish_cloned(builder),
+            Utf8ColumnBuilder::View(builder) => ArrayBuilder::finish_cloned(builder),
+            Utf8ColumnBuilder::Dictionary(builder) => ArrayBuilder::finish_cloned(builder.as_ref()),
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn into_box_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+}
+
+macro_rules! impl_utf8_assoc {
+    ($T:ty) => {
+        impl<'r> ArrowAssoc for $T {
+            type Builder = Utf8ColumnBuilder;
+
+            fn builder(nrows: usize) -> Self::Builder {
+                Utf8ColumnBuilder::Utf8(StringBuilder::with_capacity(1024, nrows))
+            }
+
+            #[throws(ArrowDestinationError)]
+            fn append(builder: &mut Self::Builder, value: Self) {
+                builder.append_value(value.as_ref())?;
+            }
+
+            fn field(header: &str) -> Field {
+                Field::new(header, ArrowDataType::Utf8, false)
+            }
+        }
+
+        impl<'r> ArrowAssoc for Option<$T> {
+            type Builder = Utf8ColumnBuilder;
+
+            fn builder(nrows: usize) -> Self::Builder {
+                Utf8ColumnBuilder::Utf8(StringBuilder::with_capacity(1024, nrows))
+            }
+
+            #[throws(ArrowDestinationError)]
+            fn append(builder: &mut Self::Builder, value: Self) {
+                match value {
+                    Some(s) => builder.append_value(s.as_ref())?,
+                    None => builder.append_null(),
+                }
+            }
+
+            fn field(header: &str) -> Field {
+                Field::new(header, ArrowDataType::Utf8, true)
+            }
+        }
+    };
+}
+
+impl_utf8_assoc!(&'r str);
+impl_utf8_assoc!(String);
+
+/// Builder of the JSON columns, strings by default or maps if the destination asks for JSON
+/// objects as maps, see [`map_type`].
+pub enum JsonColumnBuilder {
+    Text(Utf8ColumnBuilder),
+    Map(MapBuilder<StringBuilder, Box<dyn ArrayBuilder>>),
+}
+
+impl JsonColumnBuilder {
+    pub fn view(nrows: usize) -> Self {
+        JsonColumnBuilder::Text(Utf8ColumnBuilder::view(nrows))
+    }
+
+    /// A builder of the map type `ty` returned by `map_type`.
+    pub fn map(ty: &ArrowDataType, nrows: usize) -> Self {
+        let values = match ty {
+            ArrowDataType::Map(entries, _) => match entries.data_type() {
+                ArrowDataType::Struct(fields) => fields[1].data_type().clone(),
+                _ => unreachable!("the entries of a map are structs"),
+            },
+            _ => unreachable!("{} is not a map", ty),
+        };
+        JsonColumnBuilder::Map(MapBuilder::with_capacity(
+            None,
+            StringBuilder::new(),
+            make_builder(&values, nrows),
+            nrows,
+        ))
+    }
+
+    #[throws(ArrowDestinationError)]
+    fn append_value(&mut self, value: Value) {
+        match (self, value) {
+            (JsonColumnBuilder::Text(builder), value) => {
+                builder.append_value(&value.to_string())?
+            }
+            (JsonColumnBuilder::Map(builder), Value::Object(object)) => {
+                for (key, value) in object {
+                    builder.keys().append_value(key);
+                    append_map_value(builder.values().as_any_mut(), value)?;
+                }
+                builder.append(true)?;
+            }
+            (JsonColumnBuilder::Map(_), value) => {
+                throw!(anyhow!("cannot load {} into a map", value))
+            }
+        }
+    }
+
+    #[throws(ArrowDestinationError)]
+    fn append_null(&mut self) {
+        match self {
+            JsonColumnBuilder::Text(builder) => builder.append_null(),
+            JsonColumnBuilder::Map(builder) => builder.append(false)?,
+        }
+    }
+}
+
+// the values of the maps of JSON objects all have the type of the map, or are null
+#[throws(ArrowDestinationError)]
+fn append_map_value(builder: &mut dyn Any, value: Value) {
+    if let Some(builder) = builder.downcast_mut::<BooleanBuilder>() {
+        match value {
+            Value::Null => builder.append_null(),
+            Value::Bool(v) => builder.append_value(v),
+            v => throw!(anyhow!("cannot load {} into a map of booleans", v)),
+        }
+    } else if let Some(builder) = builder.downcast_mut::<Int64Builder>() {
+        match value {
+            Value::Null => builder.append_null(),
+            Value::Number(v) if v.is_i64() => builder.append_option(v.as_i64()),
+            v => throw!(anyhow!("cannot load {} into a map of integers", v)),
+        }
+    } else if let Some(builder) = builder.downcast_mut::<Float64Builder>() {
+        match value {
+            Value::Null => builder.append_null(),
+            Value::Number(v) => builder.append_option(v.as_f64()),
+            v => throw!(anyhow!("cannot load {} into a map of floats", v)),
+        }
+    } else if let Some(builder) = builder.downcast_mut::<StringBuilder>() {
+        match value {
+            Value::Null => builder.append_null(),
+            Value::String(v) => builder.append_value(v),
+            v => throw!(anyhow!("cannot load {} into a map of strings", v)),
+        }
+    } else {
+        throw!(anyhow!("unsupported type of the values of a map"));
+    }
+}
+
+impl ArrayBuilder for JsonColumnBuilder {
+    fn len(&self) -> usize {
+        match self {
+            JsonColumnBuilder::Text(builder) => builder.len(),
+            JsonColumnBuilder::Map(builder) => builder.len(),
+        }
+    }
+
+    fn finish(&mut self) -> ArrayRef {
+        match self {
+            JsonColumnBuilder::Text(builder) => ArrayBuilder::finish(builder),
+            JsonColumnBuilder::Map(builder) => ArrayBuilder::finish(builder),
+        }
+    }
+
+    fn finish_cloned(&self) -> ArrayRef {
+        match self {
+            JsonColumnBuilder::Text(builder) => ArrayBuilder::finish_cloned(builder),
+            JsonColumnBuilder::Map(builder) => ArrayBuilder::finish_cloned(builder),
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn into_box_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+}
+
+impl ArrowAssoc for Value {
+    type Builder = JsonColumnBuilder;
 
     fn builder(nrows: usize) -> Self::Builder {
-        StringBuilder::with_capacity(1024, nrows)
+        JsonColumnBuilder::Text(Utf8ColumnBuilder::Utf8(StringBuilder::with_capacity(
+            1024, nrows,
+        )))
     }
 
     #[throws(ArrowDestinationError)]
     fn append(builder: &mut Self::Builder, value: Self) {
-        builder.append_value(value);
+        builder.append_value(value)?;
     }
 
     fn field(header: &str) -> Field {
@@ -132,18 +413,20 @@ impl ArrowAssoc for &str {
     }
 }
 
-impl ArrowAssoc for Option<&str> {
-    type Builder = StringBuilder;
+impl ArrowAssoc for Option<Value> {
+    type Builder = JsonColumnBuilder;
 
     fn builder(nrows: usize) -> Self::Builder {
-        StringBuilder::with_capacity(1024, nrows)
+        JsonColumnBuilder::Text(Utf8ColumnBuilder::Utf8(StringBuilder::with_capacity(
+            1024, nrows,
+        )))
     }
 
     #[throws(ArrowDestinationError)]
     fn append(builder: &mut Self::Builder, value: Self) {
         match value {
-            Some(s) => builder.append_value(s),
-            None => builder.append_null(),
+            Some(v) => builder.append_value(v)?,
+            None => builder.append_null()?,
         }
     }
 
@@ -152,16 +435,117 @@ impl ArrowAssoc for Option<&str> {
     }
 }
 
-impl ArrowAssoc for String {
-    type Builder = StringBuilder;
+impl ArrowAssoc for HashMap<String, Option<String>> {
+    type Builder = MapBuilder<StringBuilder, StringBuilder>;
+
+    fn builder(nrows: usize) -> Self::Builder {
+        string_map_builder(nrows)
+    }
+
+    fn append(builder: &mut Self::Builder, value: Self) -> Result<()> {
+        Ok(append_string_map(builder, Some(value))?)
+    }
+
+    fn field(header: &str) -> Field {
+        Field::new(header, map_type(ArrowDataType::Utf8), false)
+    }
+}
+
+impl ArrowAssoc for Option<HashMap<String, Option<String>>> {
+    type Builder = MapBuilder<StringBuilder, StringBuilder>;
+
+    fn builder(nrows: usize) -> Self::Builder {
+        string_map_builder(nrows)
+    }
+
+    fn append(builder: &mut Self::Builder, value: Self) -> Result<()> {
+        Ok(append_string_map(builder, value)?)
+    }
+
+    fn field(header: &str) -> Field {
+        Field::new(header, map_type(ArrowDataType::Utf8), true)
+    }
+}
+
+/// Builder of the UUID columns, hyphenated strings by default or `FixedSizeBinary(16)` if the
+/// destination asks for extension types.
+pub enum UuidColumnBuilder {
+    Text(Utf8ColumnBuilder),
+    Binary(FixedSizeBinaryBuilder),
+}
+
+impl UuidColumnBuilder {
+    pub fn view(nrows: usize) -> Self {
+        UuidColumnBuilder::Text(Utf8ColumnBuilder::view(nrows))
+    }
+
+    pub fn binary(nrows: usize) -> Self {
+        UuidColumnBuilder::Binary(FixedSizeBinaryBuilder::with_capacity(nrows, 16))
+    }
+
+    #[throws(ArrowDestinationError)]
+    fn append_value(&mut self, value: Uuid) {
+        match self {
+            UuidColumnBuilder::Text(builder) => {
+                builder.append_value(value.hyphenated().encode_lower(&mut Uuid::encode_buffer()))?
+            }
+            UuidColumnBuilder::Binary(builder) => builder.append_value(value.as_bytes())?,
+        }
+    }
+
+    fn append_null(&mut self) {
+        match self {
+            UuidColumnBuilder::Text(builder) => builder.append_null(),
+            UuidColumnBuilder::Binary(builder) => builder.append_null(),
+        }
+    }
+}
+
+impl ArrayBuilder for UuidColumnBuilder {
+    fn len(&self) -> usize {
+        match self {
+            UuidColumnBuilder::Text(builder) => builder.len(),
+            UuidColumnBuilder::Binary(builder) => builder.len(),
+        }
+    }
+
+    fn finish(&mut self) -> ArrayRef {
+        match self {
+            UuidColumnBuilder::Text(builder) => ArrayBuilder::finish(builder),
+            UuidColumnBuilder::Binary(builder) => ArrayBuilder::finish(builder),
+        }
+    }
+
+    fn finish_cloned(&self) -> ArrayRef {
+        match self {
+            UuidColumnBuilder::Text(builder) => ArrayBuilder::finish_cloned(builder),
+            UuidColumnBuilder::Binary(builder) => ArrayBuilder::finish_cloned(builder),
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn into_box_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+}
+
+impl ArrowAssoc for Uuid {
+    type Builder = UuidColumnBuilder;
 
     fn builder(nrows: usize) -> Self::Builder {
-        StringBuilder::with_capacity(1024, nrows)
+        UuidColumnBuilder::Text(<String as ArrowAssoc>::builder(nrows))
     }
 
     #[throws(ArrowDestinationError)]
-    fn append(builder: &mut Self::Builder, value: String) {
-        builder.append_value(value.as_str());
+    fn append(builder: &mut Self::Builder, value: Self) {
+        builder.append_value(value)?;
     }
 
     fn field(header: &str) -> Field {
@@ -169,17 +553,17 @@ impl ArrowAssoc for String {
     }
 }
 
-impl ArrowAssoc for Option<String> {
-    type Builder = StringBuilder;
+impl ArrowAssoc for Option<Uuid> {
+    type Builder = UuidColumnBuilder;
 
     fn builder(nrows: usize) -> Self::Builder {
-        StringBuilder::with_capacity(1024, nrows)
+        UuidColumnBuilder::Text(<String as ArrowAssoc>::builder(nrows))
     }
 
     #[throws(ArrowDestinationError)]
     fn append(builder: &mut Self::Builder, value: Self) {
         match value {
-            Some(s) => builder.append_value(s.as_str()),
+            Some(v) => builder.append_value(v)?,
             None => builder.append_null(),
         }
     }
@@ -189,6 +573,45 @@ impl ArrowAssoc for Option<String> {
     }
 }
 
+impl ArrowAssoc for DictionaryWrapper {
+    type Builder = StringDictionaryBuilder<Int32Type>;
+
+    fn builder(nrows: usize) -> Self::Builder {
+        StringDictionaryBuilder::with_capacity(nrows, 64, 1024)
+    }
+
+    #[throws(ArrowDestinationError)]
+    fn append(builder: &mut Self::Builder, value: Self) {
+        builder.append(value.0)?;
+    }
+
+    fn field(header: &str) -> Field {
+        Field::new_dictionary(header, ArrowDataType::Int32, ArrowDataType::Utf8, false)
+    }
+}
+
+impl ArrowAssoc for Option<DictionaryWrapper> {
+    type Builder = StringDictionaryBuilder<Int32Type>;
+
+    fn builder(nrows: usize) -> Self::Builder {
+        StringDictionaryBuilder::with_capacity(nrows, 64, 1024)
+    }
+
+    #[throws(ArrowDestinationError)]
+    fn append(builder: &mut Self::Builder, value: Self) {
+        match value {
+            Some(s) => {
+                builder.append(s.0)?;
+            }
+            None => builder.append_null(),
+        }
+    }
+
+    fn field(header: &str) -> Field {
+        Field::new_dictionary(header, ArrowDataType::Int32, ArrowDataType::Utf8, true)
+    }
+}
+
 impl ArrowAssoc for DateTime<Utc> {
     type Builder = TimestampNanosecondBuilder;
 
@@ -493,169 +916,587 @@ impl ArrowAssoc for NaiveTimeWrapperMicro {
     }
 }
 
-impl ArrowAssoc for Option<Vec<u8>> {
-    type Builder = LargeBinaryBuilder;
+#[throws(ArrowDestinationError)]
+fn duration_to_arrow(d: Duration) -> i64 {
+    d.num_microseconds()
+        .ok_or_else(|| anyhow!("duration {} is out of the range of microseconds", d))?
+}
+
+impl ArrowAssoc for Option<Duration> {
+    type Builder = DurationMicrosecondBuilder;
 
     fn builder(nrows: usize) -> Self::Builder {
-        LargeBinaryBuilder::with_capacity(1024, nrows)
+        DurationMicrosecondBuilder::with_capacity(nrows)
     }
 
-    fn append(builder: &mut Self::Builder, value: Self) -> Result<()> {
-        match value {
-            Some(v) => builder.append_value(v),
-            None => builder.append_null(),
-        };
+    fn append(builder: &mut Self::Builder, value: Option<Duration>) -> Result<()> {
+        builder.append_option(value.map(duration_to_arrow).transpose()?);
         Ok(())
     }
 
     fn field(header: &str) -> Field {
-        Field::new(header, ArrowDataType::LargeBinary, true)
+        Field::new(header, ArrowDataType::Duration(TimeUnit::Microsecond), true)
     }
 }
 
-impl ArrowAssoc for Vec<u8> {
-    type Builder = LargeBinaryBuilder;
+impl ArrowAssoc for Duration {
+    type Builder = DurationMicrosecondBuilder;
 
     fn builder(nrows: usize) -> Self::Builder {
-        LargeBinaryBuilder::with_capacity(1024, nrows)
+        DurationMicrosecondBuilder::with_capacity(nrows)
     }
 
-    fn append(builder: &mut Self::Builder, value: Self) -> Result<()> {
-        builder.append_value(value);
+    fn append(builder: &mut Self::Builder, value: Duration) -> Result<()> {
+        builder.append_value(duration_to_arrow(value)?);
         Ok(())
     }
 
     fn field(header: &str) -> Field {
-        Field::new(header, ArrowDataType::LargeBinary, false)
+        Field::new(
+            header,
+            ArrowDataType::Duration(TimeUnit::Microsecond),
+            false,
+        )
     }
 }
 
-impl ArrowAssoc for Option<Vec<Option<Decimal>>> {
-    type Builder = LargeListBuilder<Decimal128Builder>;
+fn interval_to_arrow(i: Interval) -> IntervalMonthDayNano {
+    IntervalMonthDayNano::new(i.months, i.days, i.nanoseconds)
+}
+
+impl ArrowAssoc for Option<Interval> {
+    type Builder = IntervalMonthDayNanoBuilder;
 
     fn builder(nrows: usize) -> Self::Builder {
-        LargeListBuilder::with_capacity(
-            Decimal128Builder::with_capacity(nrows).with_data_type(DEFAULT_ARROW_DECIMAL),
-            nrows,
-        )
+        IntervalMonthDayNanoBuilder::with_capacity(nrows)
     }
 
-    fn append(builder: &mut Self::Builder, value: Self) -> Result<()> {
-        match value {
-            Some(vals) => {
-                let mut list = vec![];
-
-                for val in vals {
-                    match val {
-                        Some(v) => {
-                            list.push(Some(decimal_to_i128(
-                                v,
-                                DEFAULT_ARROW_DECIMAL_SCALE as u32,
-                            )?));
-                        }
-                        None => list.push(None),
-                    }
-                }
-
-                builder.append_value(list);
-            }
-            None => builder.append_null(),
-        };
+    fn append(builder: &mut Self::Builder, value: Option<Interval>) -> Result<()> {
+        builder.append_option(value.map(interval_to_arrow));
         Ok(())
     }
 
     fn field(header: &str) -> Field {
         Field::new(
             header,
-            ArrowDataType::LargeList(std::sync::Arc::new(Field::new_list_field(
-                DEFAULT_ARROW_DECIMAL,
-                true,
-            ))),
+            ArrowDataType::Interval(IntervalUnit::MonthDayNano),
             true,
         )
     }
 }
 
-impl ArrowAssoc for Vec<Option<Decimal>> {
-    type Builder = LargeListBuilder<Decimal128Builder>;
+impl ArrowAssoc for Interval {
+    type Builder = IntervalMonthDayNanoBuilder;
 
     fn builder(nrows: usize) -> Self::Builder {
-        LargeListBuilder::with_capacity(
-            Decimal128Builder::with_capacity(nrows).with_data_type(DEFAULT_ARROW_DECIMAL),
-            nrows,
-        )
+        IntervalMonthDayNanoBuilder::with_capacity(nrows)
     }
 
-    fn append(builder: &mut Self::Builder, vals: Self) -> Result<()> {
-        let mut list = vec![];
-
-        for val in vals {
-            match val {
-                Some(v) => {
-                    list.push(Some(decimal_to_i128(
-                        v,
-                        DEFAULT_ARROW_DECIMAL_SCALE as u32,
-                    )?));
-                }
-                None => list.push(None),
-            }
-        }
-
-        builder.append_value(list);
+    fn append(builder: &mut Self::Builder, value: Interval) -> Result<()> {
+        builder.append_value(interval_to_arrow(value));
         Ok(())
     }
 
     fn field(header: &str) -> Field {
         Field::new(
             header,
-            ArrowDataType::LargeList(std::sync::Arc::new(Field::new_list_field(
-                DEFAULT_ARROW_DECIMAL,
-                false,
-            ))),
+            ArrowDataType::Interval(IntervalUnit::MonthDayNano),
             false,
         )
     }
 }
 
-macro_rules! impl_arrow_array_assoc {
-    ($T:ty, $AT:expr, $B:ident) => {
-        impl ArrowAssoc for $T {
-            type Builder = LargeListBuilder<$B>;
+/// Builder of the binary columns, `LargeBinary` by default or `BinaryView` if the destination
+/// asks for view types.
+pub enum BinaryColumnBuilder {
+    Large(LargeBinaryBuilder),
+    View(BinaryViewBuilder),
+}
 
-            fn builder(nrows: usize) -> Self::Builder {
-                LargeListBuilder::with_capacity($B::new(), nrows)
-            }
+impl BinaryColumnBuilder {
+    pub fn view(nrows: usize) -> Self {
+        BinaryColumnBuilder::View(BinaryViewBuilder::with_capacity(nrows))
+    }
 
-            #[throws(ArrowDestinationError)]
-            fn append(builder: &mut Self::Builder, value: Self) {
-                builder.append_value(value);
-            }
+    fn append_value(&mut self, value: &[u8]) {
+        match self {
+            BinaryColumnBuilder::Large(builder) => builder.append_value(value),
+            BinaryColumnBuilder::View(builder) => builder.append_value(value),
+        }
+    }
 
-            fn field(header: &str) -> Field {
-                Field::new(
-                    header,
-                    ArrowDataType::LargeList(std::sync::Arc::new(Field::new_list_field($AT, true))),
-                    false,
-                )
-            }
+    fn append_null(&mut self) {
+        match self {
+            BinaryColumnBuilder::Large(builder) => builder.append_null(),
+            BinaryColumnBuilder::View(builder) => builder.append_null(),
         }
+    }
+}
 
-        impl ArrowAssoc for Option<$T> {
-            type Builder = LargeListBuilder<$B>;
+impl ArrayBuilder for BinaryColumnBuilder {
+    fn len(&self) -> usize {
+        match self {
+            BinaryColumnBuilder::Large(builder) => builder.len(),
+            BinaryColumnBuilder::View(builder) => builder.len(),
+        }
+    }
 
-            fn builder(nrows: usize) -> Self::Builder {
-                LargeListBuilder::with_capacity($B::new(), nrows)
-            }
+    fn finish(&mut self) -> ArrayRef {
+        match self {
+            BinaryColumnBuilder::Large(builder) => ArrayBuilder::finish(builder),
+            BinaryColumnBuilder::View(builder) => ArrayBuilder::finish(builder),
+        }
+    }
 
-            #[throws(ArrowDestinationError)]
-            fn append(builder: &mut Self::Builder, value: Self) {
-                builder.append_option(value);
+    fn finish_cloned(&self) -> ArrayRef {
+        match self {
+            BinaryColumnBuilder::Large(builder) => ArrayBuilder::finish_cloned(builder),
+            BinaryColumnBuilder::View(builder) => ArrayBuilder::finish_cloned(builder),
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn into_box_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+}
+
+macro_rules! impl_binary_assoc {
+    ($T:ty) => {
+        impl<'r> ArrowAssoc for $T {
+            type Builder = BinaryColumnBuilder;
+
+            fn builder(nrows: usize) -> Self::Builder {
+                BinaryColumnBuilder::Large(LargeBinaryBuilder::with_capacity(1024, nrows))
+            }
+
+            #[throws(ArrowDestinationError)]
+            fn append(builder: &mut Self::Builder, value: Self) {
+                builder.append_value(value.as_ref());
+            }
+
+            fn field(header: &str) -> Field {
+                Field::new(header, ArrowDataType::LargeBinary, false)
+            }
+        }
+
+        impl<'r> ArrowAssoc for Option<$T> {
+            type Builder = BinaryColumnBuilder;
+
+            fn builder(nrows: usize) -> Self::Builder {
+                BinaryColumnBuilder::Large(LargeBinaryBuilder::with_capacity(1024, nrows))
+            }
+
+            #[throws(ArrowDestinationError)]
+            fn append(builder: &mut Self::Builder, value: Self) {
+                match value {
+                    Some(v) => builder.append_value(v.as_ref()),
+                    None => builder.append_null(),
+                }
+            }
+
+            fn field(header: &str) -> Field {
+                Field::new(header, ArrowDataType::LargeBinary, true)
+            }
+        }
+    };
+}
+
+impl_binary_assoc!(&'r [u8]);
+impl_binary_assoc!(Vec<u8>);
+
+/// Elements of the list columns, appended to the builder of their arrow type.
+pub trait ArrowListElement: Sized {
+    type Builder: ArrayBuilder + Send;
+
+    fn builder(capacity: usize) -> Self::Builder;
+    fn append(builder: &mut Self::Builder, value: Option<Self>) -> Result<()>;
+    fn data_type() -> ArrowDataType;
+}
+
+macro_rules! impl_list_element {
+    ($T:ty, $AT:expr, $B:ident) => {
+        impl_list_element!($T, $AT, $B, |v| v);
+    };
+    ($T:ty, $AT:expr, $B:ident, $convert:expr) => {
+        impl ArrowListElement for $T {
+            type Builder = $B;
+
+            fn builder(capacity: usize) -> Self::Builder {
+                $B::with_capacity(capacity)
+            }
+
+            #[throws(ArrowDestinationError)]
+            fn append(builder: &mut Self::Builder, value: Option<Self>) {
+                builder.append_option(value.map($convert));
+            }
+
+            fn data_type() -> ArrowDataType {
+                $AT
+            }
+        }
+    };
+}
+
+impl_list_element!(bool, ArrowDataType::Boolean, BooleanBuilder);
+impl_list_element!(i16, ArrowDataType::Int16, Int16Builder);
+impl_list_element!(i32, ArrowDataType::Int32, Int32Builder);
+impl_list_element!(i64, ArrowDataType::Int64, Int64Builder);
+impl_list_element!(u16, ArrowDataType::UInt16, UInt16Builder);
+impl_list_element!(u32, ArrowDataType::UInt32, UInt32Builder);
+impl_list_element!(u64, ArrowDataType::UInt64, UInt64Builder);
+impl_list_element!(f32, ArrowDataType::Float32, Float32Builder);
+impl_list_element!(f64, ArrowDataType::Float64, Float64Builder);
+impl_list_element!(
+    NaiveDate,
+    ArrowDataType::Date32,
+    Date32Builder,
+    naive_date_to_arrow
+);
+// the timestamps and times are in microseconds like the columns of the same types, which holds
+// the infinite timestamps as well
+impl_list_element!(
+    NaiveDateTime,
+    ArrowDataType::Timestamp(TimeUnit::Microsecond, None),
+    TimestampMicrosecondBuilder,
+    |v: NaiveDateTime| v.and_utc().timestamp_micros()
+);
+impl_list_element!(
+    NaiveTime,
+    ArrowDataType::Time64(TimeUnit::Microsecond),
+    Time64MicrosecondBuilder,
+    |v: NaiveTime| v.num_seconds_from_midnight() as i64 * 1_000_000 + v.nanosecond() as i64 / 1000
+);
+impl_list_element!(
+    Interval,
+    ArrowDataType::Interval(IntervalUnit::MonthDayNano),
+    IntervalMonthDayNanoBuilder,
+    |v: Interval| IntervalMonthDayNano::new(v.months, v.days, v.nanoseconds)
+);
+
+impl ArrowListElement for DateTime<Utc> {
+    type Builder = TimestampMicrosecondBuilder;
+
+    fn builder(capacity: usize) -> Self::Builder {
+        TimestampMicrosecondBuilder::with_capacity(capacity).with_timezone("+00:00")
+    }
+
+    #[throws(ArrowDestinationError)]
+    fn append(builder: &mut Self::Builder, value: Option<Self>) {
+        builder.append_option(value.map(|v| v.timestamp_micros()));
+    }
+
+    fn data_type() -> ArrowDataType {
+        ArrowDataType::Timestamp(TimeUnit::Microsecond, Some("+00:00".into()))
+    }
+}
+
+impl ArrowListElement for Decimal {
+    type Builder = Decimal128Builder;
+
+    fn builder(capacity: usize) -> Self::Builder {
+        Decimal128Builder::with_capacity(capacity).with_data_type(DEFAULT_ARROW_DECIMAL)
+    }
+
+    #[throws(ArrowDestinationError)]
+    fn append(builder: &mut Self::Builder, value: Option<Self>) {
+        match value {
+            Some(v) => {
+                builder.append_value(decimal_to_i128(v, DEFAULT_ARROW_DECIMAL_SCALE as u32)?)
+            }
+            None => builder.append_null(),
+        }
+    }
+
+    fn data_type() -> ArrowDataType {
+        DEFAULT_ARROW_DECIMAL
+    }
+}
+
+// strings, uuids and json values are all kept as strings in lists
+macro_rules! impl_string_list_element {
+    ($T:ty, $to_str:expr) => {
+        impl ArrowListElement for $T {
+            type Builder = StringBuilder;
+
+            fn builder(capacity: usize) -> Self::Builder {
+                StringBuilder::with_capacity(capacity, capacity * 8)
+            }
+
+            #[throws(ArrowDestinationError)]
+            fn append(builder: &mut Self::Builder, value: Option<Self>) {
+                builder.append_option(value.as_ref().map($to_str));
+            }
+
+            fn data_type() -> ArrowDataType {
+                ArrowDataType::Utf8
+            }
+        }
+    };
+}
+
+impl_string_list_element!(String, |v: &String| v.clone());
+impl_string_list_element!(Uuid, |v: &Uuid| v.to_string());
+impl_string_list_element!(Value, |v: &Value| v.to_string());
+
+impl ArrowListElement for Vec<u8> {
+    type Builder = BinaryBuilder;
+
+    fn builder(capacity: usize) -> Self::Builder {
+        BinaryBuilder::with_capacity(capacity, capacity * 8)
+    }
+
+    #[throws(ArrowDestinationError)]
+    fn append(builder: &mut Self::Builder, value: Option<Self>) {
+        builder.append_option(value);
+    }
+
+    fn data_type() -> ArrowDataType {
+        ArrowDataType::Binary
+    }
+}
+
+/// The number of dimensions of the arrays of a list column. It is fixed once for the whole load,
+/// by the first array that is not empty or, if none was appended yet, to one by the first batch
+/// that is finished.
+pub type ListDepth = Arc<OnceLock<usize>>;
+
+/// Builder of the list columns. A multidimensional array is nested into one list per dimension,
+/// so all the arrays of a column that are not empty need the same number of dimensions.
+pub struct ListColumnBuilder<B> {
+    values: B,
+    // the offsets of every level of lists, the outermost first
+    offsets: Vec<Vec<i64>>,
+    nulls: NullBufferBuilder,
+    ndims: ListDepth,
+}
+
+impl<B: ArrayBuilder> ListColumnBuilder<B> {
+    fn new(values: B, nrows: usize, ndims: ListDepth) -> Self {
+        let mut offsets = Vec::with_capacity(nrows + 1);
+        offsets.push(0);
+        ListColumnBuilder {
+            values,
+            offsets: vec![offsets],
+            nulls: NullBufferBuilder::new(nrows),
+            ndims,
+        }
+    }
+
+    fn append<T>(&mut self, value: Option<List<T>>) -> Result<()>
+    where
+        T: ArrowListElement<Builder = B>,
+    {
+        let list = match value {
+            Some(list) => list,
+            None => {
+                self.nulls.append_null();
+                self.push_lists(0, 1, 0);
+                return Ok(());
+            }
+        };
+        self.nulls.append_non_null();
+        // an empty array has no dimensions and is an empty list at any depth
+        if list.dims.is_empty() {
+            self.push_lists(0, 1, 0);
+            return Ok(());
+        }
+        let ndims = *self.ndims.get_or_init(|| list.dims.len());
+        if ndims != list.dims.len() {
+            throw!(anyhow!(
+                "cannot load an array of {} dimensions into a column of {} dimensions",
+                list.dims.len(),
+                ndims
+            ));
+        }
+        if list.values.len() != list.dims.iter().product::<usize>() {
+            throw!(anyhow!(
+                "array of dimensions {:?} has {} elements",
+                list.dims,
+                list.values.len()
+            ));
+        }
+        while self.offsets.len() < ndims {
+            self.offsets.push(vec![0]);
+        }
+        let mut nlists = 1;
+        for (level, &len) in list.dims.iter().enumerate() {
+            self.push_lists(level, nlists, len);
+            nlists *= len;
+        }
+        for value in list.values {
+            T::append(&mut self.values, value)?;
+        }
+        Ok(())
+    }
+
+    // append `n` lists of `len` items to a level
+    fn push_lists(&mut self, level: usize, n: usize, len: usize) {
+        let offsets = &mut self.offsets[level];
+        let mut last = *offsets.last().unwrap_or(&0);
+        for _ in 0..n {
+            last += len as i64;
+            offsets.push(last);
+        }
+    }
+
+    fn build(
+        values: ArrayRef,
+        mut offsets: Vec<Vec<i64>>,
+        nulls: Option<NullBuffer>,
+        ndims: usize,
+    ) -> ArrayRef {
+        // the lists of a batch with empty arrays only have no items at the inner levels
+        while offsets.len() < ndims {
+            offsets.push(vec![0]);
+        }
+        let mut array = values;
+        let mut nulls = nulls;
+        for (level, offsets) in offsets.into_iter().enumerate().rev() {
+            let field = Arc::new(Field::new_list_field(array.data_type().clone(), true));
+            // only the arrays themselves are nullable, not the lists of their dimensions
+            let level_nulls = if level == 0 { nulls.take() } else { None };
+            array = Arc::new(LargeListArray::new(
+                field,
+                OffsetBuffer::new(offsets.into()),
+                array,
+                level_nulls,
+            ));
+        }
+        array
+    }
+}
+
+impl<B: ArrayBuilder> ArrayBuilder for ListColumnBuilder<B> {
+    fn len(&self) -> usize {
+        self.offsets[0].len() - 1
+    }
+
+    fn finish(&mut self) -> ArrayRef {
+        let offsets = std::mem::replace(&mut self.offsets, vec![vec![0]]);
+        let ndims = *self.ndims.get_or_init(|| 1);
+        Self::build(self.values.finish(), offsets, self.nulls.finish(), ndims)
+    }
+
+    fn finish_cloned(&self) -> ArrayRef {
+        Self::build(
+            self.values.finish_cloned(),
+            self.offsets.clone(),
+            self.nulls.finish_cloned(),
+            self.ndims.get().copied().unwrap_or(1),
+        )
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn into_box_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+}
+
+fn list_field<T: ArrowListElement>(header: &str, nullable: bool) -> Field {
+    Field::new(
+        header,
+        ArrowDataType::LargeList(Arc::new(Field::new_list_field(T::data_type(), true))),
+        nullable,
+    )
+}
+
+impl<T: ArrowListElement> ArrowAssoc for List<T> {
+    type Builder = ListColumnBuilder<T::Builder>;
+
+    fn builder(nrows: usize) -> Self::Builder {
+        ListColumnBuilder::new(T::builder(nrows), nrows, ListDepth::default())
+    }
+
+    fn builder_with_depth(nrows: usize, depth: &ListDepth) -> Self::Builder {
+        ListColumnBuilder::new(T::builder(nrows), nrows, Arc::clone(depth))
+    }
+
+    fn append(builder: &mut Self::Builder, value: Self) -> Result<()> {
+        builder.append(Some(value))
+    }
+
+    fn field(header: &str) -> Field {
+        list_field::<T>(header, false)
+    }
+}
+
+impl<T: ArrowListElement> ArrowAssoc for Option<List<T>> {
+    type Builder = ListColumnBuilder<T::Builder>;
+
+    fn builder(nrows: usize) -> Self::Builder {
+        ListColumnBuilder::new(T::builder(nrows), nrows, ListDepth::default())
+    }
+
+    fn builder_with_depth(nrows: usize, depth: &ListDepth) -> Self::Builder {
+        ListColumnBuilder::new(T::builder(nrows), nrows, Arc::clone(depth))
+    }
+
+    fn append(builder: &mut Self::Builder, value: Self) -> Result<()> {
+        builder.append(value)
+    }
+
+    fn field(header: &str) -> Field {
+        list_field::<T>(header, true)
+    }
+}
+
+macro_rules! impl_vector_assoc {
+    ($T:ty, $AT:ty) => {
+        impl ArrowAssoc for DenseVector<$T> {
+            type Builder = VectorColumnBuilder<$AT>;
+
+            fn builder(nrows: usize) -> Self::Builder {
+                VectorColumnBuilder::variable(nrows)
+            }
+
+            #[throws(ArrowDestinationError)]
+            fn append(builder: &mut Self::Builder, value: Self) {
+                builder.append_value(&value.0)?;
+            }
+
+            fn field(header: &str) -> Field {
+                Field::new(
+                    header,
+                    ArrowDataType::LargeList(vector_field::<$AT>()),
+                    false,
+                )
+            }
+        }
+
+        impl ArrowAssoc for Option<DenseVector<$T>> {
+            type Builder = VectorColumnBuilder<$AT>;
+
+            fn builder(nrows: usize) -> Self::Builder {
+                VectorColumnBuilder::variable(nrows)
+            }
+
+            #[throws(ArrowDestinationError)]
+            fn append(builder: &mut Self::Builder, value: Self) {
+                match value {
+                    Some(v) => builder.append_value(&v.0)?,
+                    None => builder.append_null(),
+                }
             }
 
             fn field(header: &str) -> Field {
                 Field::new(
                     header,
-                    ArrowDataType::LargeList(std::sync::Arc::new(Field::new_list_field($AT, true))),
+                    ArrowDataType::LargeList(vector_field::<$AT>()),
                     true,
                 )
             }
@@ -663,13 +1504,403 @@ macro_rules! impl_arrow_array_assoc {
     };
 }
 
-impl_arrow_array_assoc!(Vec<Option<bool>>, ArrowDataType::Boolean, BooleanBuilder);
-impl_arrow_array_assoc!(Vec<Option<String>>, ArrowDataType::Utf8, StringBuilder);
-impl_arrow_array_assoc!(Vec<Option<i16>>, ArrowDataType::Int16, Int16Builder);
-impl_arrow_array_assoc!(Vec<Option<i32>>, ArrowDataType::Int32, Int32Builder);
-impl_arrow_array_assoc!(Vec<Option<i64>>, ArrowDataType::Int64, Int64Builder);
-impl_arrow_array_assoc!(Vec<Option<u16>>, ArrowDataType::UInt16, UInt16Builder);
-impl_arrow_array_assoc!(Vec<Option<u32>>, ArrowDataType::UInt32, UInt32Builder);
-impl_arrow_array_assoc!(Vec<Option<u64>>, ArrowDataType::UInt64, UInt64Builder);
-impl_arrow_array_assoc!(Vec<Option<f32>>, ArrowDataType::Float32, Float32Builder);
-impl_arrow_array_assoc!(Vec<Option<f64>>, ArrowDataType::Float64, Float64Builder);
+impl_vector_assoc!(f32, Float32Type);
+impl_vector_assoc!(f16, Float16Type);
+
+impl ArrowAssoc for SparseVec {
+    type Builder = SparseVectorColumnBuilder;
+
+    fn builder(nrows: usize) -> Self::Builder {
+        SparseVectorColumnBuilder::new(nrows)
+    }
+
+    #[throws(ArrowDestinationError)]
+    fn append(builder: &mut Self::Builder, value: Self) {
+        builder.append(Some(value));
+    }
+
+    fn field(header: &str) -> Field {
+        Field::new(header, ArrowDataType::Struct(sparse_vector_fields()), false)
+    }
+}
+
+impl ArrowAssoc for Option<SparseVec> {
+    type Builder = SparseVectorColumnBuilder;
+
+    fn builder(nrows: usize) -> Self::Builder {
+        SparseVectorColumnBuilder::new(nrows)
+    }
+
+    #[throws(ArrowDestinationError)]
+    fn append(builder: &mut Self::Builder, value: Self) {
+        builder.append(value);
+    }
+
+    fn field(header: &str) -> Field {
+        Field::new(header, ArrowDataType::Struct(sparse_vector_fields()), true)
+    }
+}
+
+impl ArrowAssoc for BitString {
+    type Builder = BitColumnBuilder;
+
+    fn builder(nrows: usize) -> Self::Builder {
+        BitColumnBuilder::large(nrows)
+    }
+
+    #[throws(ArrowDestinationError)]
+    fn append(builder: &mut Self::Builder, value: Self) {
+        builder.append_value(&value.0)?;
+    }
+
+    fn field(header: &str) -> Field {
+        Field::new(header, ArrowDataType::LargeBinary, false)
+    }
+}
+
+impl ArrowAssoc for Option<BitString> {
+    type Builder = BitColumnBuilder;
+
+    fn builder(nrows: usize) -> Self::Builder {
+        BitColumnBuilder::large(nrows)
+    }
+
+    #[throws(ArrowDestinationError)]
+    fn append(builder: &mut Self::Builder, value: Self) {
+        match value {
+            Some(v) => builder.append_value(&v.0)?,
+            None => builder.append_null(),
+        }
+    }
+
+    fn field(header: &str) -> Field {
+        Field::new(header, ArrowDataType::LargeBinary, true)
+    }
+}
+
+/// The arrow type of a composite type with `fields`. Times and timestamps are kept in
+/// microseconds and instants in UTC.
+pub(crate) fn composite_type(fields: &[(String, FieldType)]) -> ArrowDataType {
+    ArrowDataType::Struct(
+        fields
+            .iter()
+            .map(|(name, ty)| Field::new(name, field_type(ty), true))
+            .collect(),
+    )
+}
+
+fn field_type(ty: &FieldType) -> ArrowDataType {
+    match ty {
+        FieldType::Bool => ArrowDataType::Boolean,
+        FieldType::Int16 => ArrowDataType::Int16,
+        FieldType::Int32 => ArrowDataType::Int32,
+        FieldType::Int64 => ArrowDataType::Int64,
+        FieldType::Float32 => ArrowDataType::Float32,
+        FieldType::Float64 => ArrowDataType::Float64,
+        FieldType::Decimal => DEFAULT_ARROW_DECIMAL,
+        FieldType::Text | FieldType::Uuid | FieldType::Json => ArrowDataType::Utf8,
+        FieldType::Binary => ArrowDataType::LargeBinary,
+        FieldType::Date => ArrowDataType::Date32,
+        FieldType::Time => ArrowDataType::Time64(TimeUnit::Microsecond),
+        FieldType::Timestamp => ArrowDataType::Timestamp(TimeUnit::Microsecond, None),
+        FieldType::TimestampTz => {
+            ArrowDataType::Timestamp(TimeUnit::Microsecond, Some("+00:00".into()))
+        }
+        FieldType::Interval => ArrowDataType::Interval(IntervalUnit::MonthDayNano),
+        FieldType::List(item) => {
+            ArrowDataType::LargeList(Arc::new(Field::new_list_field(field_type(item), true)))
+        }
+        FieldType::Composite(fields) => composite_type(fields),
+    }
+}
+
+// whether `value` can be stored in a field of type `ty`, see `field_type`
+#[throws(ArrowDestinationError)]
+fn check_field(ty: &ArrowDataType, value: &FieldValue) {
+    match (ty, value) {
+        (ArrowDataType::Boolean, FieldValue::Bool(_))
+        | (ArrowDataType::Int16, FieldValue::Int16(_))
+        | (ArrowDataType::Int32, FieldValue::Int32(_))
+        | (ArrowDataType::Int64, FieldValue::Int64(_))
+        | (ArrowDataType::Float32, FieldValue::Float32(_))
+        | (ArrowDataType::Float64, FieldValue::Float64(_))
+        | (ArrowDataType::Utf8, FieldValue::Text(_))
+        | (ArrowDataType::Utf8, FieldValue::Uuid(_))
+        | (ArrowDataType::Utf8, FieldValue::Json(_))
+        | (ArrowDataType::LargeBinary, FieldValue::Binary(_))
+        | (ArrowDataType::Date32, FieldValue::Date(_))
+        | (ArrowDataType::Time64(_), FieldValue::Time(_))
+        | (ArrowDataType::Timestamp(_, None), FieldValue::Timestamp(_))
+        | (ArrowDataType::Timestamp(_, Some(_)), FieldValue::TimestampTz(_))
+        | (ArrowDataType::Interval(_), FieldValue::Interval(_)) => {}
+        // decimals that do not fit are rejected here so that finishing the column cannot fail
+        (ArrowDataType::Decimal128(_, scale), FieldValue::Decimal(v)) => {
+            decimal_to_i128(*v, *scale as u32)?;
+        }
+        (ArrowDataType::LargeList(item), FieldValue::List(values)) => {
+            for value in values.iter().flatten() {
+                check_field(item.data_type(), value)?;
+            }
+        }
+        (ArrowDataType::Struct(fields), FieldValue::Composite(Composite(values)))
+            if fields.len() == values.len() =>
+        {
+            for (field, value) in fields.iter().zip(values) {
+                if let Some(value) = value {
+                    check_field(field.data_type(), value)?;
+                }
+            }
+        }
+        _ => throw!(anyhow!(
+            "cannot load {:?} into a field of type {}",
+            value,
+            ty
+        )),
+    }
+}
+
+// the array of type `ty` holding `values`, which have all passed `check_field`
+fn field_array(ty: &ArrowDataType, values: Vec<Option<FieldValue>>) -> ArrayRef {
+    macro_rules! collect {
+        ($A:ty, $pattern:pat => $value:expr) => {
+            Arc::new(
+                values
+                    .into_iter()
+                    .map(|v| match v {
+                        Some($pattern) => Some($value),
+                        _ => None,
+                    })
+                    .collect::<$A>(),
+            )
+        };
+    }
+
+    match ty {
+        ArrowDataType::Boolean => collect!(BooleanArray, FieldValue::Bool(v) => v),
+        ArrowDataType::Int16 => collect!(Int16Array, FieldValue::Int16(v) => v),
+        ArrowDataType::Int32 => collect!(Int32Array, FieldValue::Int32(v) => v),
+        ArrowDataType::Int64 => collect!(Int64Array, FieldValue::Int64(v) => v),
+        ArrowDataType::Float32 => collect!(Float32Array, FieldValue::Float32(v) => v),
+        ArrowDataType::Float64 => collect!(Float64Array, FieldValue::Float64(v) => v),
+        ArrowDataType::Decimal128(_, scale) => Arc::new(
+            values
+                .into_iter()
+                .map(|v| match v {
+                    Some(FieldValue::Decimal(v)) => decimal_to_i128(v, *scale as u32).ok(),
+                    _ => None,
+                })
+                .collect::<Decimal128Array>()
+                .with_data_type(ty.clone()),
+        ),
+        ArrowDataType::Utf8 => Arc::new(
+            values
+                .into_iter()
+                .map(|v| match v {
+                    Some(FieldValue::Text(v)) => Some(v),
+                    Some(FieldValue::Uuid(v)) => Some(v.to_string()),
+                    Some(FieldValue::Json(v)) => Some(v.to_string()),
+                    _ => None,
+                })
+                .collect::<StringArray>(),
+        ),
+        ArrowDataType::LargeBinary => collect!(LargeBinaryArray, FieldValue::Binary(v) => v),
+        ArrowDataType::Date32 => {
+            collect!(Date32Array, FieldValue::Date(v) => naive_date_to_arrow(v))
+        }
+        ArrowDataType::Time64(_) => collect!(
+            Time64MicrosecondArray,
+            FieldValue::Time(v) => v.num_seconds_from_midnight() as i64 * 1_000_000
+                + (v.nanosecond() as i64) / 1000
+        ),
+        ArrowDataType::Timestamp(_, None) => collect!(
+            TimestampMicrosecondArray,
+            FieldValue::Timestamp(v) => v.and_utc().timestamp_micros()
+        ),
+        ArrowDataType::Timestamp(_, Some(tz)) => Arc::new(
+            values
+                .into_iter()
+                .map(|v| match v {
+                    Some(FieldValue::TimestampTz(v)) => Some(v.timestamp_micros()),
+                    _ => None,
+                })
+                .collect::<TimestampMicrosecondArray>()
+                .with_timezone(tz.as_ref()),
+        ),
+        ArrowDataType::Interval(_) => collect!(
+            IntervalMonthDayNanoArray,
+            FieldValue::Interval(v) => interval_to_arrow(v)
+        ),
+        ArrowDataType::LargeList(item) => {
+            let mut offsets = Vec::with_capacity(values.len() + 1);
+            offsets.push(0);
+            let mut nulls = NullBufferBuilder::new(values.len());
+            let mut items = vec![];
+            for value in values {
+                match value {
+                    Some(FieldValue::List(list)) => {
+                        items.extend(list);
+                        nulls.append_non_null();
+                    }
+                    _ => nulls.append_null(),
+                }
+                offsets.push(items.len() as i64);
+            }
+            Arc::new(LargeListArray::new(
+                Arc::clone(item),
+                OffsetBuffer::new(offsets.into()),
+                field_array(item.data_type(), items),
+                nulls.finish(),
+            ))
+        }
+        ArrowDataType::Struct(fields) => {
+            let mut nulls = NullBufferBuilder::new(values.len());
+            let mut columns = vec![Vec::with_capacity(values.len()); fields.len()];
+            for value in values {
+                match value {
+                    Some(FieldValue::Composite(Composite(values))) => {
+                        for (column, value) in columns.iter_mut().zip(values) {
+                            column.push(value);
+                        }
+                        nulls.append_non_null();
+                    }
+                    _ => {
+                        for column in columns.iter_mut() {
+                            column.push(None);
+                        }
+                        nulls.append_null();
+                    }
+                }
+            }
+            struct_array(fields, columns, nulls.finish())
+        }
+        _ => unreachable!("{} is not the type of a field", ty),
+    }
+}
+
+fn struct_array(
+    fields: &Fields,
+    columns: Vec<Vec<Option<FieldValue>>>,
+    nulls: Option<NullBuffer>,
+) -> ArrayRef {
+    let columns = fields
+        .iter()
+        .zip(columns)
+        .map(|(field, values)| field_array(field.data_type(), values))
+        .collect();
+    Arc::new(StructArray::new(fields.clone(), columns, nulls))
+}
+
+/// Builder of the struct columns, which are loaded from composite values. The values of every
+/// field are kept until the column is finished since the types of the fields are only known
+/// from the schema, see [`composite_type`].
+pub struct StructColumnBuilder {
+    fields: Fields,
+    columns: Vec<Vec<Option<FieldValue>>>,
+    nulls: NullBufferBuilder,
+}
+
+impl StructColumnBuilder {
+    pub fn new(fields: Fields, nrows: usize) -> Self {
+        let columns = vec![Vec::with_capacity(nrows); fields.len()];
+        StructColumnBuilder {
+            fields,
+            columns,
+            nulls: NullBufferBuilder::new(nrows),
+        }
+    }
+
+    #[throws(ArrowDestinationError)]
+    fn append(&mut self, value: Option<Composite>) {
+        match value {
+            Some(Composite(values)) => {
+                if values.len() != self.fields.len() {
+                    throw!(anyhow!(
+                        "cannot load a composite value of {} fields into a column of {} fields",
+                        values.len(),
+                        self.fields.len()
+                    ));
+                }
+                for ((field, column), value) in
+                    self.fields.iter().zip(&mut self.columns).zip(values)
+                {
+                    if let Some(value) = &value {
+                        check_field(field.data_type(), value)?;
+                    }
+                    column.push(value);
+                }
+                self.nulls.append_non_null();
+            }
+            None => {
+                for column in self.columns.iter_mut() {
+                    column.push(None);
+                }
+                self.nulls.append_null();
+            }
+        }
+    }
+}
+
+impl ArrayBuilder for StructColumnBuilder {
+    fn len(&self) -> usize {
+        self.nulls.len()
+    }
+
+    fn finish(&mut self) -> ArrayRef {
+        let columns = self.columns.iter_mut().map(std::mem::take).collect();
+        struct_array(&self.fields, columns, self.nulls.finish())
+    }
+
+    fn finish_cloned(&self) -> ArrayRef {
+        struct_array(
+            &self.fields,
+            self.columns.clone(),
+            self.nulls.finish_cloned(),
+        )
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn into_box_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+}
+
+// the fields of the struct columns are set by the destination from the description of the
+// columns, the builders of the typesystem have none
+impl ArrowAssoc for Composite {
+    type Builder = StructColumnBuilder;
+
+    fn builder(nrows: usize) -> Self::Builder {
+        StructColumnBuilder::new(Fields::empty(), nrows)
+    }
+
+    fn append(builder: &mut Self::Builder, value: Self) -> Result<()> {
+        builder.append(Some(value))
+    }
+
+    fn field(header: &str) -> Field {
+        Field::new(header, ArrowDataType::Struct(Fields::empty()), false)
+    }
+}
+
+impl ArrowAssoc for Option<Composite> {
+    type Builder = StructColumnBuilder;
+
+    fn builder(nrows: usize) -> Self::Builder {
+        StructColumnBuilder::new(Fields::empty(), nrows)
+    }
+
+    fn append(builder: &mut Self::Builder, value: Self) -> Result<()> {
+        builder.append(value)
+    }
+
+    fn field(header: &str) -> Field {
+        Field::new(header, ArrowDataType::Struct(Fields::empty()), true)
+    }
+}
@@ -1,4 +1,4 @@
-use super::arrow_assoc::ArrowAssoc;
+use super::arrow_assoc::{ArrowAssoc, ListDepth};
 use super::Builder;
 use crate::errors::Result;
 use crate::typesystem::{ParameterizedFunc, ParameterizedOn};
@@ -9,7 +9,7 @@ use arrow::datatypes::Field;
 pub struct FNewBuilder;
 
 impl ParameterizedFunc for FNewBuilder {
-    type Function = fn(nrows: usize) -> Builder;
+    type Function = fn(nrows: usize, depth: &ListDepth) -> Builder;
 }
 
 impl<T> ParameterizedOn<T> for FNewBuilder
@@ -17,11 +17,11 @@ where
     T: ArrowAssoc,
 {
     fn parameterize() -> Self::Function {
-        fn imp<T>(nrows: usize) -> Builder
+        fn imp<T>(nrows: usize, depth: &ListDepth) -> Builder
         where
             T: ArrowAssoc,
         {
-            Box::new(T::builder(nrows)) as Builder
+            Box::new(T::builder_with_depth(nrows, depth)) as Builder
         }
         imp::<T>
     }
@@ -8,17 +8,40 @@ pub mod typesystem;
 pub use self::errors::{ArrowDestinationError, Result};
 pub use self::typesystem::ArrowTypeSystem;
 use super::{Consume, Destination, DestinationPartition};
-use crate::constants::RECORD_BATCH_SIZE;
+use crate::constants::{
+    EXTENSION_TYPE_METADATA_KEY, EXTENSION_TYPE_NAME_KEY, RECORD_BATCH_SIZE, SOURCE_KIND_KEY,
+    SOURCE_LENGTH_KEY, SOURCE_NULLABLE_KEY, SOURCE_PRECISION_KEY, SOURCE_QUERY_KEY,
+    SOURCE_SCALE_KEY, SOURCE_TYPE_KEY,
+};
 use crate::data_order::DataOrder;
+use crate::destinations::arrow_maps::map_type;
+use crate::destinations::arrow_vectors::{
+    fixed_size_bits_field, fixed_size_vector_field, BitColumnBuilder, VectorColumnBuilder,
+};
+use crate::source_router::SourceType;
+use crate::sources::ColumnDescription;
+use crate::sql::CXQuery;
 use crate::typesystem::{Realize, TypeAssoc, TypeSystem};
 use anyhow::anyhow;
-use arrow::{datatypes::Schema, record_batch::RecordBatch};
-use arrow_assoc::ArrowAssoc;
+use arrow::{
+    array::timezone::Tz,
+    compute::cast,
+    datatypes::{DataType, Field, Float16Type, Float32Type, Schema, TimeUnit},
+    error::ArrowError,
+    record_batch::RecordBatch,
+};
+use arrow_assoc::{
+    composite_type, ArrowAssoc, BinaryColumnBuilder, JsonColumnBuilder, ListDepth,
+    StructColumnBuilder, Utf8ColumnBuilder, UuidColumnBuilder,
+};
 use fehler::{throw, throws};
-use funcs::{FFinishBuilder, FNewBuilder, FNewField};
+pub(crate) use funcs::FNewField;
+use funcs::{FFinishBuilder, FNewBuilder};
 use itertools::Itertools;
+use log::warn;
 use std::{
     any::Any,
+    collections::HashMap,
     sync::{Arc, Mutex},
 };
 
@@ -34,12 +57,57 @@ use {
 type Builder = Box<dyn Any + Send>;
 type Builders = Vec<Builder>;
 
+/// Field metadata of the canonical extension type `name`, which takes no parameters.
+fn extension_metadata(name: &str) -> HashMap<String, String> {
+    HashMap::from([
+        (EXTENSION_TYPE_NAME_KEY.to_string(), name.to_string()),
+        (EXTENSION_TYPE_METADATA_KEY.to_string(), String::new()),
+    ])
+}
+
+/// Field metadata describing the column in the source, see [`ArrowDestination::set_source_metadata`].
+/// `nullable` is used if the source does not know whether the column is nullable.
+pub(crate) fn source_metadata(
+    column: &ColumnDescription,
+    nullable: bool,
+) -> HashMap<String, String> {
+    let mut metadata = HashMap::from([
+        (SOURCE_TYPE_KEY.to_string(), column.source_type.clone()),
+        (
+            SOURCE_NULLABLE_KEY.to_string(),
+            column.nullable.unwrap_or(nullable).to_string(),
+        ),
+    ]);
+    if let Some(precision) = column.precision {
+        metadata.insert(SOURCE_PRECISION_KEY.to_string(), precision.to_string());
+    }
+    if let Some(scale) = column.scale {
+        metadata.insert(SOURCE_SCALE_KEY.to_string(), scale.to_string());
+    }
+    if let Some(length) = column.length {
+        metadata.insert(SOURCE_LENGTH_KEY.to_string(), length.to_string());
+    }
+    metadata
+}
+
 pub struct ArrowDestination {
     schema: Vec<ArrowTypeSystem>,
     names: Vec<String>,
     data: Arc<Mutex<Vec<RecordBatch>>>,
     arrow_schema: Arc<Schema>,
+    list_depths: Vec<ListDepth>,
     batch_size: usize,
+    dictionary_columns: Vec<String>,
+    view_types: bool,
+    extension_types: bool,
+    source_metadata: bool,
+    columns: Vec<ColumnDescription>,
+    schema_metadata: HashMap<String, String>,
+    timestamp_unit: Option<TimeUnit>,
+    time_zone: Option<String>,
+    session_time_zone: bool,
+    naive_time_zone: Option<String>,
+    json_maps: Option<DataType>,
 }
 
 impl Default for ArrowDestination {
@@ -49,7 +117,19 @@ impl Default for ArrowDestination {
             names: vec![],
             data: Arc::new(Mutex::new(vec![])),
             arrow_schema: Arc::new(Schema::empty()),
+            list_depths: vec![],
             batch_size: RECORD_BATCH_SIZE,
+            dictionary_columns: vec![],
+            view_types: false,
+            extension_types: false,
+            source_metadata: false,
+            columns: vec![],
+            schema_metadata: HashMap::new(),
+            timestamp_unit: None,
+            time_zone: None,
+            session_time_zone: false,
+            naive_time_zone: None,
+            json_maps: None,
         }
     }
 }
@@ -65,8 +145,148 @@ impl ArrowDestination {
             names: vec![],
             data: Arc::new(Mutex::new(vec![])),
             arrow_schema: Arc::new(Schema::empty()),
+            list_depths: vec![],
             batch_size,
+            dictionary_columns: vec![],
+            view_types: false,
+            extension_types: false,
+            source_metadata: false,
+            columns: vec![],
+            schema_metadata: HashMap::new(),
+            timestamp_unit: None,
+            time_zone: None,
+            session_time_zone: false,
+            naive_time_zone: None,
+            json_maps: None,
+        }
+    }
+
+    /// Load the string columns named `columns` as `Dictionary(Int32, Utf8)`, e.g. low-cardinality
+    /// columns that are known to be categorical. Enum columns are dictionary-encoded anyway.
+    pub fn set_dictionary_columns<S: AsRef<str>>(&mut self, columns: &[S]) {
+        self.dictionary_columns = columns.iter().map(|c| c.as_ref().to_string()).collect();
+    }
+
+    /// Load the string and binary columns as `Utf8View` and `BinaryView` instead of `Utf8` and
+    /// `LargeBinary`.
+    pub fn set_view_types(&mut self, view_types: bool) {
+        self.view_types = view_types;
+    }
+
+    /// Load UUIDs as `FixedSizeBinary(16)` with the canonical `arrow.uuid` extension type instead
+    /// of hyphenated strings, and tag JSON columns with the `arrow.json` extension type.
+    pub fn set_extension_types(&mut self, extension_types: bool) {
+        self.extension_types = extension_types;
+    }
+
+    /// Attach the type of each column as declared in the source (e.g. `numeric(12,2)`) to the
+    /// metadata of its field, together with its nullability, precision, scale and length if
+    /// known. The schema metadata records the kind of the source and the query.
+    pub fn set_source_metadata(&mut self, source_metadata: bool) {
+        self.source_metadata = source_metadata;
+    }
+
+    /// Load the timestamps in `unit` instead of the unit of the transport, e.g. microseconds for
+    /// Postgres and nanoseconds for most other sources. Values are truncated to the unit.
+    pub fn set_timestamp_unit(&mut self, unit: TimeUnit) {
+        self.timestamp_unit = Some(unit);
+    }
+
+    /// Label the timestamps with a time zone with `time_zone`, a name such as `Europe/Berlin`
+    /// or an offset such as `+01:00`. The instants are kept, only the zone they are shown in
+    /// changes.
+    pub fn set_time_zone<S: Into<String>>(&mut self, time_zone: S) {
+        self.time_zone = Some(time_zone.into());
+    }
+
+    /// Label the timestamps with a time zone with the time zone of the source session if the
+    /// source reports it. `set_time_zone` takes precedence. A session time zone that arrow
+    /// does not know, e.g. `localtime`, is replaced by UTC.
+    pub fn set_session_time_zone(&mut self, session_time_zone: bool) {
+        self.session_time_zone = session_time_zone;
+    }
+
+    /// Interpret the timestamps without a time zone as local times in `time_zone` and load them
+    /// as instants labeled with it.
+    pub fn set_naive_time_zone<S: Into<String>>(&mut self, time_zone: S) {
+        self.naive_time_zone = Some(time_zone.into());
+    }
+
+    /// Load the JSON columns as `Map(Utf8, values)`, for JSON objects whose values are all of
+    /// the same type, one of `Boolean`, `Int64`, `Float64` and `Utf8`. Null values are kept,
+    /// the load fails on any other value.
+    #[throws(ArrowDestinationError)]
+    pub fn set_json_maps(&mut self, values: DataType) {
+        if !matches!(
+            values,
+            DataType::Boolean | DataType::Int64 | DataType::Float64 | DataType::Utf8
+        ) {
+            throw!(anyhow!("cannot load JSON objects into maps of {}", values));
+        }
+        self.json_maps = Some(values);
+    }
+
+    /// The type of the timestamp column `dt` of type `Timestamp(unit, tz)` by default, after
+    /// applying the unit and time zone options.
+    #[throws(ArrowDestinationError)]
+    fn timestamp_type(
+        &self,
+        dt: ArrowTypeSystem,
+        unit: TimeUnit,
+        tz: Option<Arc<str>>,
+        column: Option<&ColumnDescription>,
+    ) -> DataType {
+        let time_zone = match dt {
+            ArrowTypeSystem::DateTimeTz(_) | ArrowTypeSystem::DateTimeTzMicro(_) => {
+                self.time_zone.clone().or_else(|| {
+                    column
+                        .filter(|_| self.session_time_zone)
+                        .and_then(|c| c.time_zone.clone())
+                        // the session may use a time zone that arrow does not know, e.g.
+                        // `localtime` or a POSIX spec
+                        .map(|time_zone| match time_zone.parse::<Tz>() {
+                            Ok(_) => time_zone,
+                            Err(e) => {
+                                warn!("unknown session time zone {}: {}, use UTC", time_zone, e);
+                                String::from("UTC")
+                            }
+                        })
+                })
+            }
+            _ => self.naive_time_zone.clone(),
+        };
+        let tz = match time_zone {
+            Some(time_zone) => {
+                // fail early rather than when the first batch is cast
+                time_zone.parse::<Tz>()?;
+                Some(time_zone.into())
+            }
+            None => tz,
+        };
+        DataType::Timestamp(self.timestamp_unit.unwrap_or(unit), tz)
+    }
+
+    /// Record the kind of the source and the query in the schema metadata if source metadata
+    /// is requested. The original query is preferred over the partitioned ones.
+    pub(crate) fn set_query_metadata(
+        &mut self,
+        source: &SourceType,
+        origin_query: Option<&str>,
+        queries: &[CXQuery<String>],
+    ) {
+        if !self.source_metadata {
+            return;
         }
+        let query = match origin_query {
+            Some(query) => query.to_string(),
+            None => queries.iter().map(|q| q.as_str()).join(";\n"),
+        };
+        self.schema_metadata.insert(
+            SOURCE_KIND_KEY.to_string(),
+            format!("{:?}", source).to_lowercase(),
+        );
+        self.schema_metadata
+            .insert(SOURCE_QUERY_KEY.to_string(), query);
     }
 }
 
@@ -80,6 +300,25 @@ impl Destination for ArrowDestination {
         false
     }
 
+    // the fields of the struct columns are only known from the description of the columns
+    fn needs_column_descriptions(&self, schema: &[ArrowTypeSystem]) -> bool {
+        self.source_metadata
+            || self.session_time_zone
+            || schema.iter().any(|dt| {
+                matches!(
+                    dt,
+                    ArrowTypeSystem::Struct(_)
+                        | ArrowTypeSystem::Float32Vector(_)
+                        | ArrowTypeSystem::Float16Vector(_)
+                        | ArrowTypeSystem::BitString(_)
+                )
+            })
+    }
+
+    fn set_column_descriptions(&mut self, columns: Vec<ColumnDescription>) {
+        self.columns = columns;
+    }
+
     #[throws(ArrowDestinationError)]
     fn allocate<S: AsRef<str>>(
         &mut self,
@@ -98,13 +337,89 @@ impl Destination for ArrowDestination {
         // parse the metadata
         self.schema = schema.to_vec();
         self.names = names.iter().map(|n| n.as_ref().to_string()).collect();
+        if let Some(name) = self
+            .dictionary_columns
+            .iter()
+            .find(|c| !self.names.contains(c))
+        {
+            throw!(anyhow!("cannot dictionary-encode unknown column {}", name))
+        }
         let fields = self
             .schema
             .iter()
             .zip_eq(&self.names)
-            .map(|(&dt, h)| Ok(Realize::<FNewField>::realize(dt)?(h.as_str())))
+            .enumerate()
+            .map(|(i, (&dt, h))| {
+                let field = Realize::<FNewField>::realize(dt)?(h.as_str());
+                let field = match dt {
+                    ArrowTypeSystem::LargeUtf8(_) if self.dictionary_columns.contains(h) => field
+                        .with_data_type(DataType::Dictionary(
+                            Box::new(DataType::Int32),
+                            Box::new(DataType::Utf8),
+                        )),
+                    ArrowTypeSystem::Json(_) if self.json_maps.is_some() => {
+                        field.with_data_type(map_type(self.json_maps.clone().unwrap()))
+                    }
+                    ArrowTypeSystem::LargeUtf8(_)
+                    | ArrowTypeSystem::Json(_)
+                    | ArrowTypeSystem::Uuid(_)
+                        if self.view_types =>
+                    {
+                        field.with_data_type(DataType::Utf8View)
+                    }
+                    ArrowTypeSystem::LargeBinary(_) | ArrowTypeSystem::BitString(_)
+                        if self.view_types =>
+                    {
+                        field.with_data_type(DataType::BinaryView)
+                    }
+                    // the timestamps are converted when a batch is finished as well
+                    _ => match field.data_type().clone() {
+                        DataType::Timestamp(unit, tz) => {
+                            let ty = self.timestamp_type(dt, unit, tz, self.columns.get(i))?;
+                            field.with_data_type(ty)
+                        }
+                        _ => field,
+                    },
+                };
+                let field = match dt {
+                    ArrowTypeSystem::Struct(_) => {
+                        let fields = self
+                            .columns
+                            .get(i)
+                            .and_then(|c| c.fields.as_ref())
+                            .ok_or_else(|| anyhow!("the fields of column {} are unknown", h))?;
+                        field.with_data_type(composite_type(fields))
+                    }
+                    // vectors and bit strings of a known length are loaded as fixed-size types
+                    ArrowTypeSystem::Float32Vector(_) | ArrowTypeSystem::Float16Vector(_) => {
+                        fixed_size_vector_field(field, self.columns.get(i))
+                    }
+                    ArrowTypeSystem::BitString(_) => {
+                        fixed_size_bits_field(field, self.columns.get(i))
+                    }
+                    ArrowTypeSystem::Uuid(_) if self.extension_types => field
+                        .with_data_type(DataType::FixedSizeBinary(16))
+                        .with_metadata(extension_metadata("arrow.uuid")),
+                    ArrowTypeSystem::Json(_)
+                        if self.extension_types && self.json_maps.is_none() =>
+                    {
+                        field.with_metadata(extension_metadata("arrow.json"))
+                    }
+                    _ => field,
+                };
+                Ok(match self.columns.get(i) {
+                    Some(column) if self.source_metadata => {
+                        let mut metadata = field.metadata().clone();
+                        metadata.extend(source_metadata(column, field.is_nullable()));
+                        field.with_metadata(metadata)
+                    }
+                    _ => field,
+                })
+            })
             .collect::<Result<Vec<_>>>()?;
-        self.arrow_schema = Arc::new(Schema::new(fields));
+        self.arrow_schema =
+            Arc::new(Schema::new(fields).with_metadata(self.schema_metadata.clone()));
+        self.list_depths = self.schema.iter().map(|_| ListDepth::default()).collect();
     }
 
     #[throws(ArrowDestinationError)]
@@ -115,6 +430,7 @@ impl Destination for ArrowDestination {
                 self.schema.clone(),
                 Arc::clone(&self.data),
                 Arc::clone(&self.arrow_schema),
+                self.list_depths.clone(),
                 self.batch_size,
             )?);
         }
@@ -149,6 +465,21 @@ impl ArrowDestination {
 
             // Arrow stores data by columns, therefore need to be Zero-copied by column
             for (i, col) in chunk.columns().iter().enumerate() {
+                // polars has no half floats, the half vectors are loaded as vectors of Float32
+                let col = match col.data_type() {
+                    DataType::FixedSizeList(item, dim)
+                        if item.data_type() == &DataType::Float16 =>
+                    {
+                        let item = item.as_ref().clone().with_data_type(DataType::Float32);
+                        cast(col, &DataType::FixedSizeList(Arc::new(item), *dim))?
+                    }
+                    DataType::LargeList(item) if item.data_type() == &DataType::Float16 => {
+                        let item = item.as_ref().clone().with_data_type(DataType::Float32);
+                        cast(col, &DataType::LargeList(Arc::new(item)))?
+                    }
+                    _ => col.clone(),
+                };
+
                 // Convert to ArrayData (arrow-rs)
                 let array = col.to_data();
 
@@ -199,11 +530,13 @@ impl ArrowDestination {
     }
 
     pub fn empty_batch(&self) -> RecordBatch {
-        RecordBatch::new_empty(self.arrow_schema.clone())
+        RecordBatch::new_empty(self.arrow_schema())
     }
 
+    /// The schema of the loaded record batches, with the list columns nested to the depth of
+    /// their arrays once it is known.
     pub fn arrow_schema(&self) -> Arc<Schema> {
-        self.arrow_schema.clone()
+        nested_schema(&self.arrow_schema, &self.list_depths)
     }
 
     pub fn names(&self) -> &[String] {
@@ -216,8 +549,10 @@ pub struct ArrowPartitionWriter {
     builders: Option<Builders>,
     current_row: usize,
     current_col: usize,
+    nbytes: usize,
     data: Arc<Mutex<Vec<RecordBatch>>>,
     arrow_schema: Arc<Schema>,
+    list_depths: Vec<ListDepth>,
     batch_size: usize,
 }
 
@@ -229,6 +564,7 @@ impl ArrowPartitionWriter {
         schema: Vec<ArrowTypeSystem>,
         data: Arc<Mutex<Vec<RecordBatch>>>,
         arrow_schema: Arc<Schema>,
+        list_depths: Vec<ListDepth>,
         batch_size: usize,
     ) -> Self {
         let mut pw = ArrowPartitionWriter {
@@ -236,8 +572,10 @@ impl ArrowPartitionWriter {
             builders: None,
             current_row: 0,
             current_col: 0,
+            nbytes: 0,
             data,
             arrow_schema,
+            list_depths,
             batch_size,
         };
         pw.allocate()?;
@@ -249,7 +587,56 @@ impl ArrowPartitionWriter {
         let builders = self
             .schema
             .iter()
-            .map(|dt| Ok(Realize::<FNewBuilder>::realize(*dt)?(self.batch_size)))
+            .zip(self.arrow_schema.fields())
+            .zip(&self.list_depths)
+            // columns loaded as view, dictionary, extension or fixed-size types keep the typesystem
+            // types of their default layout, only their builders differ, and the struct columns
+            // get their fields
+            .map(|((dt, field), depth)| match (dt, field.data_type()) {
+                (ArrowTypeSystem::Uuid(_), DataType::Utf8View) => {
+                    Ok(Box::new(UuidColumnBuilder::view(self.batch_size)) as Builder)
+                }
+                (ArrowTypeSystem::Uuid(_), DataType::FixedSizeBinary(_)) => {
+                    Ok(Box::new(UuidColumnBuilder::binary(self.batch_size)) as Builder)
+                }
+                (ArrowTypeSystem::Json(_), DataType::Utf8View) => {
+                    Ok(Box::new(JsonColumnBuilder::view(self.batch_size)) as Builder)
+                }
+                (ArrowTypeSystem::Json(_), ty @ DataType::Map(..)) => {
+                    Ok(Box::new(JsonColumnBuilder::map(ty, self.batch_size)) as Builder)
+                }
+                (ArrowTypeSystem::BitString(_), DataType::BinaryView) => {
+                    Ok(Box::new(BitColumnBuilder::view(self.batch_size)) as Builder)
+                }
+                (ArrowTypeSystem::BitString(_), DataType::FixedSizeBinary(width)) => {
+                    Ok(Box::new(BitColumnBuilder::fixed(*width, self.batch_size)) as Builder)
+                }
+                (ArrowTypeSystem::Float32Vector(_), DataType::FixedSizeList(_, dim)) => {
+                    let builder = VectorColumnBuilder::<Float32Type>::fixed(*dim, self.batch_size);
+                    Ok(Box::new(builder) as Builder)
+                }
+                (ArrowTypeSystem::Float16Vector(_), DataType::FixedSizeList(_, dim)) => {
+                    let builder = VectorColumnBuilder::<Float16Type>::fixed(*dim, self.batch_size);
+                    Ok(Box::new(builder) as Builder)
+                }
+                (ArrowTypeSystem::LargeUtf8(_), DataType::Dictionary(..)) => {
+                    Ok(Box::new(Utf8ColumnBuilder::dictionary(self.batch_size)) as Builder)
+                }
+                (_, DataType::Utf8View) => {
+                    Ok(Box::new(Utf8ColumnBuilder::view(self.batch_size)) as Builder)
+                }
+                (_, DataType::BinaryView) => {
+                    Ok(Box::new(BinaryColumnBuilder::view(self.batch_size)) as Builder)
+                }
+                (ArrowTypeSystem::Struct(_), DataType::Struct(fields)) => {
+                    let builder = StructColumnBuilder::new(fields.clone(), self.batch_size);
+                    Ok(Box::new(builder) as Builder)
+                }
+                _ => Ok(Realize::<FNewBuilder>::realize(*dt)?(
+                    self.batch_size,
+                    depth,
+                )),
+            })
             .collect::<Result<Vec<_>>>()?;
         self.builders.replace(builders);
     }
@@ -265,7 +652,29 @@ impl ArrowPartitionWriter {
             .zip(self.schema.iter())
             .map(|(builder, &dt)| Realize::<FFinishBuilder>::realize(dt)?(builder))
             .collect::<std::result::Result<Vec<_>, crate::errors::ConnectorXError>>()?;
-        let rb = RecordBatch::try_new(Arc::clone(&self.arrow_schema), columns)?;
+        // timestamps are converted to the requested unit and time zone
+        let schema = nested_schema(&self.arrow_schema, &self.list_depths);
+        let columns = columns
+            .into_iter()
+            .zip(schema.fields())
+            .zip(&self.schema)
+            .map(|((column, field), dt)| {
+                if column.data_type() == field.data_type() {
+                    return Ok(column);
+                }
+                let column = match (dt, column.data_type()) {
+                    // instants are built without a time zone, a cast to a time zone would take
+                    // them as local times
+                    (ArrowTypeSystem::DateTimeTz(_), DataType::Timestamp(unit, None)) => {
+                        cast(&column, &DataType::Timestamp(*unit, Some("+00:00".into())))?
+                    }
+                    _ => column,
+                };
+                cast(&column, field.data_type())
+            })
+            .collect::<std::result::Result<Vec<_>, ArrowError>>()?;
+        let rb = RecordBatch::try_new(schema, columns)?;
+        self.nbytes += rb.get_array_memory_size();
         {
             let mut guard = self
                 .data
@@ -299,11 +708,15 @@ impl<'a> DestinationPartition<'a> for ArrowPartitionWriter {
     fn ncols(&self) -> usize {
         self.schema.len()
     }
+
+    fn nbytes(&self) -> usize {
+        self.nbytes
+    }
 }
 
 impl<'a, T> Consume<T> for ArrowPartitionWriter
 where
-    T: TypeAssoc<<Self as DestinationPartition<'a>>::TypeSystem> + ArrowAssoc + 'static,
+    T: TypeAssoc<<Self as DestinationPartition<'a>>::TypeSystem> + ArrowAssoc,
 {
     type Error = ArrowDestinationError;
 
@@ -338,3 +751,29 @@ where
         }
     }
 }
+
+// The schema with the list columns nested into one list per dimension of their arrays, which is
+// only known from the arrays themselves, see `ListDepth`.
+fn nested_schema(schema: &Arc<Schema>, depths: &[ListDepth]) -> Arc<Schema> {
+    let fields = schema
+        .fields()
+        .iter()
+        .enumerate()
+        .map(
+            |(i, field)| match (field.data_type(), depths.get(i).and_then(|d| d.get())) {
+                (DataType::LargeList(_), Some(&depth)) if depth > 1 => {
+                    let ty = (1..depth).fold(field.data_type().clone(), |ty, _| {
+                        DataType::LargeList(Arc::new(Field::new_list_field(ty, true)))
+                    });
+                    Arc::new(field.as_ref().clone().with_data_type(ty))
+                }
+                _ => Arc::clone(field),
+            },
+        )
+        .collect::<Vec<_>>();
+    if fields[..] == schema.fields()[..] {
+        Arc::clone(schema)
+    } else {
+        Arc::new(Schema::new(fields).with_metadata(schema.metadata().clone()))
+    }
+}
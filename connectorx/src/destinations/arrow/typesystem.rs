@@ -1,6 +1,15 @@
+use crate::errors::{ConnectorXError, Result};
 use crate::impl_typesystem;
-use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use crate::typesystem::{
+    BitString, Composite, DenseVector, Interval, List, SparseVec, TypeAssoc, TypeSystem,
+};
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use fehler::throw;
+use half::f16;
 use rust_decimal::Decimal;
+use serde_json::Value;
+use std::collections::HashMap;
+use uuid::Uuid;
 
 #[derive(Debug, Clone, Copy)]
 pub struct DateTimeWrapperMicro(pub DateTime<Utc>);
@@ -11,6 +20,10 @@ pub struct NaiveTimeWrapperMicro(pub NaiveTime);
 #[derive(Debug, Clone, Copy)]
 pub struct NaiveDateTimeWrapperMicro(pub NaiveDateTime);
 
+/// A string loaded into a dictionary-encoded column, e.g. the label of an enum.
+#[derive(Debug, Clone)]
+pub struct DictionaryWrapper(pub String);
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum ArrowTypeSystem {
     Int16(bool),
@@ -24,7 +37,10 @@ pub enum ArrowTypeSystem {
     Decimal(bool),
     Boolean(bool),
     LargeUtf8(bool),
+    Dictionary(bool),
+    Json(bool),
     LargeBinary(bool),
+    Uuid(bool),
     Date32(bool),
     Date64(bool),
     Date64Micro(bool),
@@ -32,6 +48,8 @@ pub enum ArrowTypeSystem {
     Time64Micro(bool),
     DateTimeTz(bool),
     DateTimeTzMicro(bool),
+    Duration(bool),
+    Interval(bool),
     BoolArray(bool),
     Utf8Array(bool),
     Int16Array(bool),
@@ -43,6 +61,20 @@ pub enum ArrowTypeSystem {
     Float32Array(bool),
     Float64Array(bool),
     DecimalArray(bool),
+    BinaryArray(bool),
+    Date32Array(bool),
+    Date64Array(bool),
+    Time64Array(bool),
+    DateTimeTzArray(bool),
+    UuidArray(bool),
+    JsonArray(bool),
+    IntervalArray(bool),
+    Struct(bool),
+    Map(bool),
+    Float32Vector(bool),
+    Float16Vector(bool),
+    SparseVector(bool),
+    BitString(bool),
 }
 
 impl_typesystem! {
@@ -59,7 +91,10 @@ impl_typesystem! {
         { Decimal         => Decimal                   }
         { Boolean         => bool                      }
         { LargeUtf8       => String                    }
+        { Dictionary      => DictionaryWrapper         }
+        { Json            => Value                     }
         { LargeBinary     => Vec<u8>                   }
+        { Uuid            => Uuid                      }
         { Date32          => NaiveDate                 }
         { Date64          => NaiveDateTime             }
         { Date64Micro     => NaiveDateTimeWrapperMicro }
@@ -67,16 +102,65 @@ impl_typesystem! {
         { Time64Micro     => NaiveTimeWrapperMicro     }
         { DateTimeTz      => DateTime<Utc>             }
         { DateTimeTzMicro => DateTimeWrapperMicro      }
-        { BoolArray       => Vec<Option<bool>>         }
-        { Utf8Array       => Vec<Option<String>>       }
-        { Int16Array      => Vec<Option<i16>>          }
-        { Int32Array      => Vec<Option<i32>>          }
-        { Int64Array      => Vec<Option<i64>>          }
-        { UInt16Array     => Vec<Option<u16>>          }
-        { UInt32Array     => Vec<Option<u32>>          }
-        { UInt64Array     => Vec<Option<u64>>          }
-        { Float32Array    => Vec<Option<f32>>          }
-        { Float64Array    => Vec<Option<f64>>          }
-        { DecimalArray    => Vec<Option<Decimal>>      }
+        { Duration        => Duration                  }
+        { Interval        => Interval                  }
+        { BoolArray       => List<bool>                }
+        { Utf8Array       => List<String>              }
+        { Int16Array      => List<i16>                 }
+        { Int32Array      => List<i32>                 }
+        { Int64Array      => List<i64>                 }
+        { UInt16Array     => List<u16>                 }
+        { UInt32Array     => List<u32>                 }
+        { UInt64Array     => List<u64>                 }
+        { Float32Array    => List<f32>                 }
+        { Float64Array    => List<f64>                 }
+        { DecimalArray    => List<Decimal>             }
+        { BinaryArray     => List<Vec<u8>>             }
+        { Date32Array     => List<NaiveDate>           }
+        { Date64Array     => List<NaiveDateTime>       }
+        { Time64Array     => List<NaiveTime>           }
+        { DateTimeTzArray => List<DateTime<Utc>>       }
+        { UuidArray       => List<Uuid>                }
+        { JsonArray       => List<Value>               }
+        { IntervalArray   => List<Interval>            }
+        { Struct          => Composite                 }
+        { Map             => HashMap<String, Option<String>> }
+        { Float32Vector   => DenseVector<f32>          }
+        { Float16Vector   => DenseVector<f16>          }
+        { SparseVector    => SparseVec                 }
+        { BitString       => BitString                 }
     }
 }
+
+// Borrowed strings and bytes are appended to the builders of `String` and `Vec<u8>` without being
+// copied into an owned value first, so they are associated with the same types.
+macro_rules! impl_borrowed_assoc {
+    ($T:ty, $V:ident) => {
+        impl<'r> TypeAssoc<ArrowTypeSystem> for $T {
+            fn check(ts: ArrowTypeSystem) -> Result<()> {
+                match ts {
+                    ArrowTypeSystem::$V(false) => Ok(()),
+                    _ => throw!(ConnectorXError::TypeCheckFailed(
+                        ts.type_name().to_string(),
+                        std::any::type_name::<$T>()
+                    )),
+                }
+            }
+        }
+
+        impl<'r> TypeAssoc<ArrowTypeSystem> for Option<$T> {
+            fn check(ts: ArrowTypeSystem) -> Result<()> {
+                match ts {
+                    ArrowTypeSystem::$V(true) => Ok(()),
+                    _ => throw!(ConnectorXError::TypeCheckFailed(
+                        ts.type_name().to_string(),
+                        std::any::type_name::<$T>()
+                    )),
+                }
+            }
+        }
+    };
+}
+
+impl_borrowed_assoc!(&'r str, LargeUtf8);
+impl_borrowed_assoc!(&'r [u8], LargeBinary);
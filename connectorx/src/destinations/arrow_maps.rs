@@ -0,0 +1,41 @@
+//! The map columns with string keys, built the same way by the arrow and arrow stream
+//! destinations.
+
+use arrow::array::{MapBuilder, StringBuilder};
+use arrow::datatypes::{DataType, Field, Fields};
+use arrow::error::ArrowError;
+use fehler::throws;
+use itertools::Itertools;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// The type of the map columns with string keys and values of type `values`, as built by
+/// `MapBuilder`.
+pub(crate) fn map_type(values: DataType) -> DataType {
+    let entries = DataType::Struct(Fields::from(vec![
+        Field::new("keys", DataType::Utf8, false),
+        Field::new("values", values, true),
+    ]));
+    DataType::Map(Arc::new(Field::new("entries", entries, false)), false)
+}
+
+pub(crate) fn string_map_builder(nrows: usize) -> MapBuilder<StringBuilder, StringBuilder> {
+    MapBuilder::with_capacity(None, StringBuilder::new(), StringBuilder::new(), nrows)
+}
+
+/// Append `value` to a map column of strings. The entries are sorted by key since the order of
+/// a `HashMap` is arbitrary.
+#[throws(ArrowError)]
+pub(crate) fn append_string_map(
+    builder: &mut MapBuilder<StringBuilder, StringBuilder>,
+    value: Option<HashMap<String, Option<String>>>,
+) {
+    let is_valid = value.is_some();
+    if let Some(map) = value {
+        for (key, value) in map.into_iter().sorted_unstable_by(|a, b| a.0.cmp(&b.0)) {
+            builder.keys().append_value(key);
+            builder.values().append_option(value);
+        }
+    }
+    builder.append(is_valid)?;
+}
@@ -0,0 +1,312 @@
+//! The vector and bit string columns, e.g. of pgvector, built the same way by the arrow and arrow
+//! stream destinations. Vectors of a known dimension are loaded as `FixedSizeList` and bit strings
+//! of a known length as `FixedSizeBinary`.
+
+use crate::constants::BIT_LENGTH_KEY;
+use crate::sources::ColumnDescription;
+use crate::typesystem::SparseVec;
+use arrow::array::{
+    ArrayBuilder, ArrayRef, ArrowPrimitiveType, BinaryViewBuilder, FixedSizeBinaryBuilder,
+    FixedSizeListBuilder, Float32Builder, Int32Builder, LargeBinaryBuilder, LargeListBuilder,
+    ListBuilder, NullBufferBuilder, PrimitiveBuilder, StructArray,
+};
+use arrow::buffer::NullBuffer;
+use arrow::datatypes::{DataType, Field, Fields, Float32Type, Int32Type};
+use arrow::error::ArrowError;
+use fehler::{throw, throws};
+use std::any::Any;
+use std::sync::Arc;
+
+pub(crate) fn vector_field<T: ArrowPrimitiveType>() -> Arc<Field> {
+    Arc::new(Field::new_list_field(T::DATA_TYPE, false))
+}
+
+/// The field of a dense vector column `field`, a `FixedSizeList` if the dimension of the column
+/// is known.
+pub(crate) fn fixed_size_vector_field(field: Field, column: Option<&ColumnDescription>) -> Field {
+    match (field.data_type(), column.and_then(|c| c.dimension)) {
+        (DataType::LargeList(item), Some(dim)) => {
+            let ty = DataType::FixedSizeList(item.clone(), dim as i32);
+            field.with_data_type(ty)
+        }
+        _ => field,
+    }
+}
+
+/// The field of a bit string column `field`, a `FixedSizeBinary` holding the whole bytes of the
+/// strings if their length is known. The length in bits is kept in the metadata of the field.
+pub(crate) fn fixed_size_bits_field(field: Field, column: Option<&ColumnDescription>) -> Field {
+    match column.and_then(|c| c.length) {
+        Some(len) => {
+            let mut metadata = field.metadata().clone();
+            metadata.insert(BIT_LENGTH_KEY.to_string(), len.to_string());
+            field
+                .with_data_type(DataType::FixedSizeBinary(len.div_ceil(8) as i32))
+                .with_metadata(metadata)
+        }
+        None => field,
+    }
+}
+
+/// Builder of the dense vector columns, `LargeList` by default or `FixedSizeList` if the
+/// dimension of the column is known. The elements of the vectors are not nullable.
+pub enum VectorColumnBuilder<T: ArrowPrimitiveType> {
+    Variable(LargeListBuilder<PrimitiveBuilder<T>>),
+    Fixed(FixedSizeListBuilder<PrimitiveBuilder<T>>),
+}
+
+impl<T: ArrowPrimitiveType> VectorColumnBuilder<T> {
+    pub fn variable(nrows: usize) -> Self {
+        let builder = LargeListBuilder::with_capacity(PrimitiveBuilder::new(), nrows);
+        VectorColumnBuilder::Variable(builder.with_field(vector_field::<T>()))
+    }
+
+    pub fn fixed(dim: i32, nrows: usize) -> Self {
+        let values = PrimitiveBuilder::with_capacity(nrows * dim as usize);
+        let builder = FixedSizeListBuilder::with_capacity(values, dim, nrows);
+        VectorColumnBuilder::Fixed(builder.with_field(vector_field::<T>()))
+    }
+
+    #[throws(ArrowError)]
+    pub(crate) fn append_value(&mut self, value: &[T::Native]) {
+        match self {
+            VectorColumnBuilder::Variable(builder) => {
+                builder.values().append_slice(value);
+                builder.append(true);
+            }
+            VectorColumnBuilder::Fixed(builder) => {
+                if value.len() != builder.value_length() as usize {
+                    throw!(ArrowError::InvalidArgumentError(format!(
+                        "cannot load a vector of dimension {} into a column of dimension {}",
+                        value.len(),
+                        builder.value_length()
+                    )));
+                }
+                builder.values().append_slice(value);
+                builder.append(true);
+            }
+        }
+    }
+
+    pub(crate) fn append_null(&mut self) {
+        match self {
+            VectorColumnBuilder::Variable(builder) => builder.append(false),
+            VectorColumnBuilder::Fixed(builder) => {
+                // a null vector still takes its slots, zeros since the elements are not nullable
+                let dim = builder.value_length() as usize;
+                builder.values().append_value_n(T::Native::default(), dim);
+                builder.append(false);
+            }
+        }
+    }
+}
+
+impl<T: ArrowPrimitiveType> ArrayBuilder for VectorColumnBuilder<T> {
+    fn len(&self) -> usize {
+        match self {
+            VectorColumnBuilder::Variable(builder) => builder.len(),
+            VectorColumnBuilder::Fixed(builder) => builder.len(),
+        }
+    }
+
+    fn finish(&mut self) -> ArrayRef {
+        match self {
+            VectorColumnBuilder::Variable(builder) => ArrayBuilder::finish(builder),
+            VectorColumnBuilder::Fixed(builder) => ArrayBuilder::finish(builder),
+        }
+    }
+
+    fn finish_cloned(&self) -> ArrayRef {
+        match self {
+            VectorColumnBuilder::Variable(builder) => ArrayBuilder::finish_cloned(builder),
+            VectorColumnBuilder::Fixed(builder) => ArrayBuilder::finish_cloned(builder),
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn into_box_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+}
+
+pub(crate) fn sparse_vector_fields() -> Fields {
+    Fields::from(vec![
+        Field::new(
+            "indices",
+            DataType::List(vector_field::<Int32Type>()),
+            false,
+        ),
+        Field::new(
+            "values",
+            DataType::List(vector_field::<Float32Type>()),
+            false,
+        ),
+        Field::new("dim", DataType::Int32, false),
+    ])
+}
+
+/// Builder of the sparse vector columns, a struct of the indices and the values of the nonzero
+/// elements and of the dimension of every vector.
+pub struct SparseVectorColumnBuilder {
+    indices: ListBuilder<Int32Builder>,
+    values: ListBuilder<Float32Builder>,
+    dims: Int32Builder,
+    nulls: NullBufferBuilder,
+}
+
+impl SparseVectorColumnBuilder {
+    pub fn new(nrows: usize) -> Self {
+        SparseVectorColumnBuilder {
+            indices: ListBuilder::with_capacity(Int32Builder::new(), nrows)
+                .with_field(vector_field::<Int32Type>()),
+            values: ListBuilder::with_capacity(Float32Builder::new(), nrows)
+                .with_field(vector_field::<Float32Type>()),
+            dims: Int32Builder::with_capacity(nrows),
+            nulls: NullBufferBuilder::new(nrows),
+        }
+    }
+
+    pub(crate) fn append(&mut self, value: Option<SparseVec>) {
+        // a null vector is empty, the fields are not nullable
+        let value = match value {
+            Some(value) => {
+                self.nulls.append_non_null();
+                value
+            }
+            None => {
+                self.nulls.append_null();
+                SparseVec::default()
+            }
+        };
+        self.indices.values().append_slice(&value.indices);
+        self.indices.append(true);
+        self.values.values().append_slice(&value.values);
+        self.values.append(true);
+        self.dims.append_value(value.dim);
+    }
+
+    fn build(columns: Vec<ArrayRef>, nulls: Option<NullBuffer>) -> ArrayRef {
+        Arc::new(StructArray::new(sparse_vector_fields(), columns, nulls))
+    }
+}
+
+impl ArrayBuilder for SparseVectorColumnBuilder {
+    fn len(&self) -> usize {
+        self.dims.len()
+    }
+
+    fn finish(&mut self) -> ArrayRef {
+        let columns = vec![
+            ArrayBuilder::finish(&mut self.indices),
+            ArrayBuilder::finish(&mut self.values),
+            ArrayBuilder::finish(&mut self.dims),
+        ];
+        Self::build(columns, self.nulls.finish())
+    }
+
+    fn finish_cloned(&self) -> ArrayRef {
+        let columns = vec![
+            ArrayBuilder::finish_cloned(&self.indices),
+            ArrayBuilder::finish_cloned(&self.values),
+            ArrayBuilder::finish_cloned(&self.dims),
+        ];
+        Self::build(columns, self.nulls.finish_cloned())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn into_box_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+}
+
+/// Builder of the bit string columns, `LargeBinary` by default, `BinaryView` if the destination
+/// asks for view types or `FixedSizeBinary` holding the whole bytes of the strings if their
+/// length is known.
+pub enum BitColumnBuilder {
+    Large(LargeBinaryBuilder),
+    View(BinaryViewBuilder),
+    Fixed(FixedSizeBinaryBuilder),
+}
+
+impl BitColumnBuilder {
+    pub fn large(nrows: usize) -> Self {
+        BitColumnBuilder::Large(LargeBinaryBuilder::with_capacity(1024, nrows))
+    }
+
+    pub fn view(nrows: usize) -> Self {
+        BitColumnBuilder::View(BinaryViewBuilder::with_capacity(nrows))
+    }
+
+    pub fn fixed(width: i32, nrows: usize) -> Self {
+        BitColumnBuilder::Fixed(FixedSizeBinaryBuilder::with_capacity(nrows, width))
+    }
+
+    #[throws(ArrowError)]
+    pub(crate) fn append_value(&mut self, value: &[u8]) {
+        match self {
+            BitColumnBuilder::Large(builder) => builder.append_value(value),
+            BitColumnBuilder::View(builder) => builder.append_value(value),
+            BitColumnBuilder::Fixed(builder) => builder.append_value(value)?,
+        }
+    }
+
+    pub(crate) fn append_null(&mut self) {
+        match self {
+            BitColumnBuilder::Large(builder) => builder.append_null(),
+            BitColumnBuilder::View(builder) => builder.append_null(),
+            BitColumnBuilder::Fixed(builder) => builder.append_null(),
+        }
+    }
+}
+
+impl ArrayBuilder for BitColumnBuilder {
+    fn len(&self) -> usize {
+        match self {
+            BitColumnBuilder::Large(builder) => builder.len(),
+            BitColumnBuilder::View(builder) => builder.len(),
+            BitColumnBuilder::Fixed(builder) => builder.len(),
+        }
+    }
+
+    fn finish(&mut self) -> ArrayRef {
+        match self {
+            BitColumnBuilder::Large(builder) => ArrayBuilder::finish(builder),
+            BitColumnBuilder::View(builder) => ArrayBuilder::finish(builder),
+            BitColumnBuilder::Fixed(builder) => ArrayBuilder::finish(builder),
+        }
+    }
+
+    fn finish_cloned(&self) -> ArrayRef {
+        match self {
+            BitColumnBuilder::Large(builder) => ArrayBuilder::finish_cloned(builder),
+            BitColumnBuilder::View(builder) => ArrayBuilder::finish_cloned(builder),
+            BitColumnBuilder::Fixed(builder) => ArrayBuilder::finish_cloned(builder),
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn into_box_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+}
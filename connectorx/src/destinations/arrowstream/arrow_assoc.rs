@@ -1,17 +1,25 @@
 use super::errors::{ArrowDestinationError, Result};
 use crate::constants::{DEFAULT_ARROW_DECIMAL, DEFAULT_ARROW_DECIMAL_SCALE, SECONDS_IN_DAY};
+use crate::destinations::arrow_maps::{append_string_map, map_type, string_map_builder};
+use crate::destinations::arrow_vectors::{
+    sparse_vector_fields, vector_field, BitColumnBuilder, SparseVectorColumnBuilder,
+    VectorColumnBuilder,
+};
+use crate::typesystem::{BitString, DenseVector, SparseVec};
 use crate::utils::decimal_to_i128;
+use anyhow::anyhow;
 use arrow::array::{
-    ArrayBuilder, BooleanBuilder, Date32Builder, Date64Builder, Decimal128Builder, Float32Builder,
-    Float64Builder, Int32Builder, Int64Builder, LargeBinaryBuilder, LargeListBuilder,
-    StringBuilder, Time64NanosecondBuilder, TimestampNanosecondBuilder, UInt32Builder,
-    UInt64Builder,
+    ArrayBuilder, BooleanBuilder, Date32Builder, Date64Builder, Decimal128Builder,
+    DurationMicrosecondBuilder, Float32Builder, Float64Builder, Int32Builder, Int64Builder,
+    LargeBinaryBuilder, LargeListBuilder, MapBuilder, StringBuilder, Time64NanosecondBuilder,
+    TimestampNanosecondBuilder, UInt32Builder, UInt64Builder,
 };
-use arrow::datatypes::Field;
-use arrow::datatypes::{DataType as ArrowDataType, TimeUnit};
-use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Timelike, Utc};
+use arrow::datatypes::{DataType as ArrowDataType, Field, Float16Type, Float32Type, TimeUnit};
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, NaiveTime, Timelike, Utc};
 use fehler::throws;
+use half::f16;
 use rust_decimal::Decimal;
+use std::collections::HashMap;
 /// Associate arrow builder with native type
 pub trait ArrowAssoc {
     type Builder: ArrayBuilder + Send;
@@ -349,6 +357,50 @@ impl ArrowAssoc for NaiveTime {
     }
 }
 
+#[throws(ArrowDestinationError)]
+fn duration_to_arrow(d: Duration) -> i64 {
+    d.num_microseconds()
+        .ok_or_else(|| anyhow!("duration {} is out of the range of microseconds", d))?
+}
+
+impl ArrowAssoc for Option<Duration> {
+    type Builder = DurationMicrosecondBuilder;
+
+    fn builder(nrows: usize) -> Self::Builder {
+        DurationMicrosecondBuilder::with_capacity(nrows)
+    }
+
+    fn append(builder: &mut Self::Builder, value: Option<Duration>) -> Result<()> {
+        builder.append_option(value.map(duration_to_arrow).transpose()?);
+        Ok(())
+    }
+
+    fn field(header: &str) -> Field {
+        Field::new(header, ArrowDataType::Duration(TimeUnit::Microsecond), true)
+    }
+}
+
+impl ArrowAssoc for Duration {
+    type Builder = DurationMicrosecondBuilder;
+
+    fn builder(nrows: usize) -> Self::Builder {
+        DurationMicrosecondBuilder::with_capacity(nrows)
+    }
+
+    fn append(builder: &mut Self::Builder, value: Duration) -> Result<()> {
+        builder.append_value(duration_to_arrow(value)?);
+        Ok(())
+    }
+
+    fn field(header: &str) -> Field {
+        Field::new(
+            header,
+            ArrowDataType::Duration(TimeUnit::Microsecond),
+            false,
+        )
+    }
+}
+
 impl ArrowAssoc for Option<Vec<u8>> {
     type Builder = LargeBinaryBuilder;
 
@@ -433,3 +485,158 @@ macro_rules! impl_arrow_array_assoc {
 }
 
 impl_arrow_array_assoc!(Vec<Option<f32>>, ArrowDataType::Float32, Float32Builder);
+
+macro_rules! impl_vector_assoc {
+    ($T:ty, $AT:ty) => {
+        impl ArrowAssoc for DenseVector<$T> {
+            type Builder = VectorColumnBuilder<$AT>;
+
+            fn builder(nrows: usize) -> Self::Builder {
+                VectorColumnBuilder::variable(nrows)
+            }
+
+            #[throws(ArrowDestinationError)]
+            fn append(builder: &mut Self::Builder, value: Self) {
+                builder.append_value(&value.0)?;
+            }
+
+            fn field(header: &str) -> Field {
+                Field::new(
+                    header,
+                    ArrowDataType::LargeList(vector_field::<$AT>()),
+                    false,
+                )
+            }
+        }
+
+        impl ArrowAssoc for Option<DenseVector<$T>> {
+            type Builder = VectorColumnBuilder<$AT>;
+
+            fn builder(nrows: usize) -> Self::Builder {
+                VectorColumnBuilder::variable(nrows)
+            }
+
+            #[throws(ArrowDestinationError)]
+            fn append(builder: &mut Self::Builder, value: Self) {
+                match value {
+                    Some(v) => builder.append_value(&v.0)?,
+                    None => builder.append_null(),
+                }
+            }
+
+            fn field(header: &str) -> Field {
+                Field::new(
+                    header,
+                    ArrowDataType::LargeList(vector_field::<$AT>()),
+                    true,
+                )
+            }
+        }
+    };
+}
+
+impl_vector_assoc!(f32, Float32Type);
+impl_vector_assoc!(f16, Float16Type);
+
+impl ArrowAssoc for SparseVec {
+    type Builder = SparseVectorColumnBuilder;
+
+    fn builder(nrows: usize) -> Self::Builder {
+        SparseVectorColumnBuilder::new(nrows)
+    }
+
+    #[throws(ArrowDestinationError)]
+    fn append(builder: &mut Self::Builder, value: Self) {
+        builder.append(Some(value));
+    }
+
+    fn field(header: &str) -> Field {
+        Field::new(header, ArrowDataType::Struct(sparse_vector_fields()), false)
+    }
+}
+
+impl ArrowAssoc for Option<SparseVec> {
+    type Builder = SparseVectorColumnBuilder;
+
+    fn builder(nrows: usize) -> Self::Builder {
+        SparseVectorColumnBuilder::new(nrows)
+    }
+
+    #[throws(ArrowDestinationError)]
+    fn append(builder: &mut Self::Builder, value: Self) {
+        builder.append(value);
+    }
+
+    fn field(header: &str) -> Field {
+        Field::new(header, ArrowDataType::Struct(sparse_vector_fields()), true)
+    }
+}
+
+impl ArrowAssoc for BitString {
+    type Builder = BitColumnBuilder;
+
+    fn builder(nrows: usize) -> Self::Builder {
+        BitColumnBuilder::large(nrows)
+    }
+
+    #[throws(ArrowDestinationError)]
+    fn append(builder: &mut Self::Builder, value: Self) {
+        builder.append_value(&value.0)?;
+    }
+
+    fn field(header: &str) -> Field {
+        Field::new(header, ArrowDataType::LargeBinary, false)
+    }
+}
+
+impl ArrowAssoc for Option<BitString> {
+    type Builder = BitColumnBuilder;
+
+    fn builder(nrows: usize) -> Self::Builder {
+        BitColumnBuilder::large(nrows)
+    }
+
+    #[throws(ArrowDestinationError)]
+    fn append(builder: &mut Self::Builder, value: Self) {
+        match value {
+            Some(v) => builder.append_value(&v.0)?,
+            None => builder.append_null(),
+        }
+    }
+
+    fn field(header: &str) -> Field {
+        Field::new(header, ArrowDataType::LargeBinary, true)
+    }
+}
+
+impl ArrowAssoc for HashMap<String, Option<String>> {
+    type Builder = MapBuilder<StringBuilder, StringBuilder>;
+
+    fn builder(nrows: usize) -> Self::Builder {
+        string_map_builder(nrows)
+    }
+
+    fn append(builder: &mut Self::Builder, value: Self) -> Result<()> {
+        Ok(append_string_map(builder, Some(value))?)
+    }
+
+    fn field(header: &str) -> Field {
+        Field::new(header, map_type(ArrowDataType::Utf8), false)
+    }
+}
+
+impl ArrowAssoc for Option<HashMap<String, Option<String>>> {
+    type Builder = MapBuilder<StringBuilder, StringBuilder>;
+
+    fn builder(nrows: usize) -> Self::Builder {
+        string_map_builder(nrows)
+    }
+
+    fn append(builder: &mut Self::Builder, value: Self) -> Result<()> {
+        Ok(append_string_map(builder, value)?)
+    }
+
+    fn field(header: &str) -> Field {
+        Field::new(header, map_type(ArrowDataType::Utf8), true)
+    }
+}
@@ -10,9 +10,16 @@ pub use self::typesystem::ArrowTypeSystem;
 use super::{Consume, Destination, DestinationPartition};
 use crate::constants::RECORD_BATCH_SIZE;
 use crate::data_order::DataOrder;
+use crate::destinations::arrow_vectors::{
+    fixed_size_bits_field, fixed_size_vector_field, BitColumnBuilder, VectorColumnBuilder,
+};
+use crate::sources::ColumnDescription;
 use crate::typesystem::{Realize, TypeAssoc, TypeSystem};
 use anyhow::anyhow;
-use arrow::{datatypes::Schema, record_batch::RecordBatch};
+use arrow::{
+    datatypes::{DataType, Float16Type, Float32Type, Schema},
+    record_batch::RecordBatch,
+};
 use arrow_assoc::ArrowAssoc;
 use fehler::{throw, throws};
 use funcs::{FFinishBuilder, FNewBuilder, FNewField};
@@ -33,6 +40,7 @@ pub struct ArrowDestination {
     names: Vec<String>,
     arrow_schema: Arc<Schema>,
     batch_size: usize,
+    columns: Vec<ColumnDescription>,
     sender: Option<Sender<RecordBatch>>,
     receiver: Receiver<RecordBatch>,
 }
@@ -45,6 +53,7 @@ impl Default for ArrowDestination {
             names: vec![],
             arrow_schema: Arc::new(Schema::empty()),
             batch_size: RECORD_BATCH_SIZE,
+            columns: vec![],
             sender: Some(tx),
             receiver: rx,
         }
@@ -63,6 +72,7 @@ impl ArrowDestination {
             names: vec![],
             arrow_schema: Arc::new(Schema::empty()),
             batch_size,
+            columns: vec![],
             sender: Some(tx),
             receiver: rx,
         }
@@ -79,6 +89,23 @@ impl Destination for ArrowDestination {
         false
     }
 
+    // the dimension of the vectors and the length of the bit strings are only known from the
+    // description of the columns
+    fn needs_column_descriptions(&self, schema: &[ArrowTypeSystem]) -> bool {
+        schema.iter().any(|dt| {
+            matches!(
+                dt,
+                ArrowTypeSystem::Float32Vector(_)
+                    | ArrowTypeSystem::Float16Vector(_)
+                    | ArrowTypeSystem::BitString(_)
+            )
+        })
+    }
+
+    fn set_column_descriptions(&mut self, columns: Vec<ColumnDescription>) {
+        self.columns = columns;
+    }
+
     #[throws(ArrowDestinationError)]
     fn allocate<S: AsRef<str>>(
         &mut self,
@@ -101,7 +128,20 @@ impl Destination for ArrowDestination {
             .schema
             .iter()
             .zip_eq(&self.names)
-            .map(|(&dt, h)| Ok(Realize::<FNewField>::realize(dt)?(h.as_str())))
+            .enumerate()
+            .map(|(i, (&dt, h))| {
+                let field = Realize::<FNewField>::realize(dt)?(h.as_str());
+                // vectors and bit strings of a known length are loaded as fixed-size types
+                Ok(match dt {
+                    ArrowTypeSystem::Float32Vector(_) | ArrowTypeSystem::Float16Vector(_) => {
+                        fixed_size_vector_field(field, self.columns.get(i))
+                    }
+                    ArrowTypeSystem::BitString(_) => {
+                        fixed_size_bits_field(field, self.columns.get(i))
+                    }
+                    _ => field,
+                })
+            })
             .collect::<Result<Vec<_>>>()?;
         self.arrow_schema = Arc::new(Schema::new(fields));
     }
@@ -171,6 +211,7 @@ pub struct ArrowPartitionWriter {
     builders: Option<Builders>,
     current_row: usize,
     current_col: usize,
+    nbytes: usize,
     arrow_schema: Arc<Schema>,
     batch_size: usize,
     sender: Option<Sender<RecordBatch>>,
@@ -191,6 +232,7 @@ impl ArrowPartitionWriter {
             builders: None,
             current_row: 0,
             current_col: 0,
+            nbytes: 0,
             arrow_schema,
             batch_size,
             sender: Some(sender),
@@ -204,7 +246,23 @@ impl ArrowPartitionWriter {
         let builders = self
             .schema
             .iter()
-            .map(|dt| Ok(Realize::<FNewBuilder>::realize(*dt)?(self.batch_size)))
+            .zip(self.arrow_schema.fields())
+            // columns loaded as fixed-size types keep the typesystem types of their default
+            // layout, only their builders differ
+            .map(|(dt, field)| match (dt, field.data_type()) {
+                (ArrowTypeSystem::BitString(_), DataType::FixedSizeBinary(width)) => {
+                    Ok(Box::new(BitColumnBuilder::fixed(*width, self.batch_size)) as Builder)
+                }
+                (ArrowTypeSystem::Float32Vector(_), DataType::FixedSizeList(_, dim)) => {
+                    let builder = VectorColumnBuilder::<Float32Type>::fixed(*dim, self.batch_size);
+                    Ok(Box::new(builder) as Builder)
+                }
+                (ArrowTypeSystem::Float16Vector(_), DataType::FixedSizeList(_, dim)) => {
+                    let builder = VectorColumnBuilder::<Float16Type>::fixed(*dim, self.batch_size);
+                    Ok(Box::new(builder) as Builder)
+                }
+                _ => Ok(Realize::<FNewBuilder>::realize(*dt)?(self.batch_size)),
+            })
             .collect::<Result<Vec<_>>>()?;
         self.builders.replace(builders);
     }
@@ -221,6 +279,7 @@ impl ArrowPartitionWriter {
             .map(|(builder, &dt)| Realize::<FFinishBuilder>::realize(dt)?(builder))
             .collect::<std::result::Result<Vec<_>, crate::errors::ConnectorXError>>()?;
         let rb = RecordBatch::try_new(Arc::clone(&self.arrow_schema), columns)?;
+        self.nbytes += rb.get_array_memory_size();
         self.sender.as_ref().and_then(|s| s.send(rb).ok());
 
         self.current_row = 0;
@@ -249,6 +308,10 @@ impl<'a> DestinationPartition<'a> for ArrowPartitionWriter {
     fn ncols(&self) -> usize {
         self.schema.len()
     }
+
+    fn nbytes(&self) -> usize {
+        self.nbytes
+    }
 }
 
 impl<'a, T> Consume<T> for ArrowPartitionWriter
@@ -1,6 +1,9 @@
 use crate::impl_typesystem;
-use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use crate::typesystem::{BitString, DenseVector, SparseVec};
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use half::f16;
 use rust_decimal::Decimal;
+use std::collections::HashMap;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum ArrowTypeSystem {
@@ -17,8 +20,14 @@ pub enum ArrowTypeSystem {
     Date32(bool),
     Date64(bool),
     Time64(bool),
+    Duration(bool),
     DateTimeTz(bool),
     Float32Array(bool),
+    Float32Vector(bool),
+    Float16Vector(bool),
+    SparseVector(bool),
+    BitString(bool),
+    Map(bool),
 }
 
 impl_typesystem! {
@@ -37,7 +46,13 @@ impl_typesystem! {
         { Date32          => NaiveDate          }
         { Date64          => NaiveDateTime      }
         { Time64          => NaiveTime          }
+        { Duration        => Duration           }
         { DateTimeTz      => DateTime<Utc>      }
         { Float32Array    => Vec<Option<f32>>   }
+        { Float32Vector   => DenseVector<f32>   }
+        { Float16Vector   => DenseVector<f16>   }
+        { SparseVector    => SparseVec          }
+        { BitString       => BitString          }
+        { Map             => HashMap<String, Option<String>> }
     }
 }
@@ -4,10 +4,15 @@
 #[cfg(feature = "dst_arrow")]
 pub mod arrow;
 #[cfg(feature = "dst_arrow")]
+mod arrow_maps;
+#[cfg(feature = "dst_arrow")]
+mod arrow_vectors;
+#[cfg(feature = "dst_arrow")]
 pub mod arrowstream;
 
 use crate::data_order::DataOrder;
 use crate::errors::ConnectorXError;
+use crate::sources::ColumnDescription;
 use crate::typesystem::{TypeAssoc, TypeSystem};
 
 /// A `Destination` is associated with a `TypeSystem` and a `PartitionDestination`.
@@ -24,6 +29,15 @@ pub trait Destination: Sized {
     /// in order to pre-allocate the buffer.
     fn needs_count(&self) -> bool;
 
+    /// Specify whether the destination wants the columns as declared in the source,
+    /// which are passed to `set_column_descriptions` before `allocate`, e.g. for the
+    /// columns of `schema` whose type is only known from their description.
+    fn needs_column_descriptions(&self, _schema: &[Self::TypeSystem]) -> bool {
+        false
+    }
+
+    fn set_column_descriptions(&mut self, _columns: Vec<ColumnDescription>) {}
+
     /// Construct the `Destination`.
     /// This allocates the memory based on the types of each columns
     /// and the number of rows.
@@ -66,6 +80,12 @@ pub trait DestinationPartition<'a>: Send {
 
     /// Aquire n rows in final destination
     fn aquire_row(&mut self, n: usize) -> Result<usize, Self::Error>;
+
+    /// Number of bytes this `PartitionDestination` has produced so far.
+    /// Destinations that do not track memory usage return 0.
+    fn nbytes(&self) -> usize {
+        0
+    }
 }
 
 /// A type implemented `Consume<T>` means that it can consume a value `T` by adding it to it's own buffer.
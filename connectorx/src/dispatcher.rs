@@ -4,14 +4,18 @@ use crate::{
     data_order::{coordinate, DataOrder},
     destinations::{Destination, DestinationPartition},
     errors::{ConnectorXError, Result as CXResult},
+    progress::{ProgressCallback, ProgressTracker},
+    report::{LoadReport, PartitionReport},
     sources::{PartitionParser, Source, SourcePartition},
-    sql::CXQuery,
+    sql::{redact_query, CXQuery},
     typesystem::Transport,
 };
 use itertools::Itertools;
-use log::debug;
 use rayon::prelude::*;
+use std::any::type_name;
 use std::marker::PhantomData;
+use std::time::Instant;
+use tracing::{debug, debug_span, info_span};
 
 /// A dispatcher takes a `S: Source`, a `D: Destination`, a `TP: Transport` and a vector of `queries` as input to
 /// load data from `S` to `D` using the queries.
@@ -20,6 +24,7 @@ pub struct Dispatcher<'a, S, D, TP> {
     dst: &'a mut D,
     queries: Vec<CXQuery<String>>,
     origin_query: Option<String>,
+    progress: Option<ProgressCallback>,
     _phantom: PhantomData<TP>,
 }
 
@@ -39,6 +44,7 @@ where
             dst,
             queries: queries.iter().map(Into::into).collect(),
             origin_query,
+            progress: None,
             _phantom: PhantomData,
         }
     }
@@ -47,8 +53,36 @@ where
         self.src.set_pre_execution_queries(pre_execution_queries);
     }
 
+    pub fn set_post_execution_queries(&mut self, post_execution_queries: Option<&[String]>) {
+        self.src.set_post_execution_queries(post_execution_queries);
+    }
+
+    /// Set a callback that is invoked periodically during `run` with the rows fetched so far,
+    /// the total rows when known, the finished partitions and the current throughput.
+    pub fn set_progress_callback(&mut self, callback: Option<ProgressCallback>) {
+        self.progress = callback;
+    }
+
     pub fn prepare(
+        self,
+    ) -> Result<
+        (
+            DataOrder,
+            Vec<S::Partition>,
+            Vec<D::Partition<'w>>,
+            Vec<S::TypeSystem>,
+            Vec<D::TypeSystem>,
+        ),
+        TP::Error,
+    > {
+        self.prepare_reported(&mut LoadReport::default())
+    }
+
+    /// Same as `prepare`, but records the time spent on metadata and counting into `report`.
+    #[allow(clippy::type_complexity)]
+    fn prepare_reported(
         mut self,
+        report: &mut LoadReport,
     ) -> Result<
         (
             DataOrder,
@@ -66,14 +100,24 @@ where
         self.src.set_origin_query(self.origin_query);
 
         debug!("Fetching metadata");
+        let start = Instant::now();
+        let metadata_span = info_span!("metadata").entered();
         self.src.fetch_metadata()?;
+        drop(metadata_span);
+        report.metadata_time = start.elapsed();
         let src_schema = self.src.schema();
         let dst_schema = src_schema
             .iter()
             .map(|&s| TP::convert_typesystem(s))
             .collect::<CXResult<Vec<_>>>()?;
         let names = self.src.names();
+        if self.dst.needs_column_descriptions(&dst_schema) {
+            let columns = self.src.describe_columns()?;
+            self.dst.set_column_descriptions(columns);
+        }
 
+        let count_span = info_span!("count", rows = tracing::field::Empty).entered();
+        let start = Instant::now();
         let mut total_rows = if self.dst.needs_count() {
             // return None if cannot derive total count
             debug!("Try get row rounts for entire result");
@@ -98,6 +142,12 @@ where
             total_rows = Some(part_rows.iter().sum());
         }
         let total_rows = total_rows.ok_or_else(ConnectorXError::CountError)?;
+        report.count_time = start.elapsed();
+        if self.dst.needs_count() {
+            report.expected_rows = Some(total_rows);
+            count_span.record("rows", total_rows);
+        }
+        drop(count_span);
 
         debug!(
             "Allocate destination memory: {}x{}",
@@ -120,8 +170,35 @@ where
 
     /// Start the data loading process.
     pub fn run(self) -> Result<(), TP::Error> {
+        self.run_with_report(None)?;
+        Ok(())
+    }
+
+    /// Start the data loading process and return a [`LoadReport`] with the queries issued,
+    /// per-partition row counts, bytes produced and the time spent in each phase. `protocol` is
+    /// the protocol the source was created with, if any, and is recorded in the report.
+    pub fn run_with_report(mut self, protocol: Option<&str>) -> Result<LoadReport, TP::Error> {
+        let queries: Vec<String> = self.queries.iter().map(|q| q.to_string()).collect();
+        let load_span = info_span!(
+            "load",
+            source = type_name::<S>(),
+            query = self.origin_query.as_deref().map(redact_query),
+            partitions = queries.len(),
+            rows = tracing::field::Empty,
+        );
+        let _guard = load_span.enter();
         debug!("Run dispatcher");
-        let (dorder, src_partitions, dst_partitions, src_schema, dst_schema) = self.prepare()?;
+        let start = Instant::now();
+        let progress = self.progress.take();
+        let mut report = LoadReport {
+            protocol: protocol.map(str::to_string),
+            ..Default::default()
+        };
+        let (dorder, src_partitions, dst_partitions, src_schema, dst_schema) =
+            self.prepare_reported(&mut report)?;
+        let tracker = progress.map(|callback| {
+            ProgressTracker::new(callback, report.expected_rows, dst_partitions.len())
+        });
 
         #[cfg(all(not(feature = "branch"), not(feature = "fptr")))]
         compile_error!("branch or fptr, pick one");
@@ -135,71 +212,141 @@ where
 
         debug!("Start writing");
         // parse and write
-        dst_partitions
+        report.partitions = dst_partitions
             .into_par_iter()
             .zip_eq(src_partitions)
             .enumerate()
-            .try_for_each(|(i, (mut dst, mut src))| -> Result<(), TP::Error> {
-                #[cfg(feature = "fptr")]
-                let f: Vec<_> = src_schema
-                    .iter()
-                    .zip_eq(&dst_schema)
-                    .map(|(&src_ty, &dst_ty)| TP::processor(src_ty, dst_ty))
-                    .collect::<CXResult<Vec<_>>>()?;
-
-                let mut parser = src.parser()?;
-
-                match dorder {
-                    DataOrder::RowMajor => loop {
-                        let (n, is_last) = parser.fetch_next()?;
-                        dst.aquire_row(n)?;
-                        for _ in 0..n {
-                            #[allow(clippy::needless_range_loop)]
-                            for col in 0..dst.ncols() {
-                                #[cfg(feature = "fptr")]
-                                f[col](&mut parser, &mut dst)?;
+            .map(
+                |(i, (mut dst, mut src))| -> Result<PartitionReport, TP::Error> {
+                    #[cfg(feature = "fptr")]
+                    let f: Vec<_> = src_schema
+                        .iter()
+                        .zip_eq(&dst_schema)
+                        .map(|(&src_ty, &dst_ty)| TP::processor(src_ty, dst_ty))
+                        .collect::<CXResult<Vec<_>>>()?;
 
-                                #[cfg(feature = "branch")]
-                                {
-                                    let (s1, s2) = schemas[col];
-                                    TP::process(s1, s2, &mut parser, &mut dst)?;
-                                }
+                    // rayon workers do not inherit the current span, so the parent is set explicitly
+                    let partition_span = info_span!(
+                        parent: &load_span,
+                        "partition",
+                        partition = i,
+                        query = redact_query(&queries[i]),
+                        rows = tracing::field::Empty,
+                    );
+                    let _guard = partition_span.enter();
+
+                    let mut part_report = PartitionReport {
+                        index: i,
+                        query: queries[i].clone(),
+                        ..Default::default()
+                    };
+
+                    let fetch_start = Instant::now();
+                    let mut parser = src.parser()?;
+                    part_report.fetch_time += fetch_start.elapsed();
+
+                    match dorder {
+                        DataOrder::RowMajor => loop {
+                            let fetch_start = Instant::now();
+                            let fetch_span = debug_span!(
+                                "fetch_next",
+                                partition = i,
+                                rows = tracing::field::Empty
+                            )
+                            .entered();
+                            let (n, is_last) = parser.fetch_next()?;
+                            fetch_span.record("rows", n);
+                            drop(fetch_span);
+                            part_report.fetch_time += fetch_start.elapsed();
+                            part_report.rows += n;
+                            if let Some(tracker) = &tracker {
+                                tracker.add_rows(n);
                             }
-                        }
-                        if is_last {
-                            break;
-                        }
-                    },
-                    DataOrder::ColumnMajor => loop {
-                        let (n, is_last) = parser.fetch_next()?;
-                        dst.aquire_row(n)?;
-                        #[allow(clippy::needless_range_loop)]
-                        for col in 0..dst.ncols() {
+
+                            let convert_start = Instant::now();
+                            dst.aquire_row(n)?;
                             for _ in 0..n {
-                                #[cfg(feature = "fptr")]
-                                f[col](&mut parser, &mut dst)?;
-                                #[cfg(feature = "branch")]
-                                {
-                                    let (s1, s2) = schemas[col];
-                                    TP::process(s1, s2, &mut parser, &mut dst)?;
+                                #[allow(clippy::needless_range_loop)]
+                                for col in 0..dst.ncols() {
+                                    #[cfg(feature = "fptr")]
+                                    f[col](&mut parser, &mut dst)?;
+
+                                    #[cfg(feature = "branch")]
+                                    {
+                                        let (s1, s2) = schemas[col];
+                                        TP::process(s1, s2, &mut parser, &mut dst)?;
+                                    }
                                 }
                             }
-                        }
-                        if is_last {
-                            break;
-                        }
-                    },
-                }
+                            part_report.convert_time += convert_start.elapsed();
+                            if is_last {
+                                break;
+                            }
+                        },
+                        DataOrder::ColumnMajor => loop {
+                            let fetch_start = Instant::now();
+                            let fetch_span = debug_span!(
+                                "fetch_next",
+                                partition = i,
+                                rows = tracing::field::Empty
+                            )
+                            .entered();
+                            let (n, is_last) = parser.fetch_next()?;
+                            fetch_span.record("rows", n);
+                            drop(fetch_span);
+                            part_report.fetch_time += fetch_start.elapsed();
+                            part_report.rows += n;
+                            if let Some(tracker) = &tracker {
+                                tracker.add_rows(n);
+                            }
 
-                debug!("Finalize partition {}", i);
-                dst.finalize()?;
-                debug!("Partition {} finished", i);
-                Ok(())
-            })?;
+                            let convert_start = Instant::now();
+                            dst.aquire_row(n)?;
+                            #[allow(clippy::needless_range_loop)]
+                            for col in 0..dst.ncols() {
+                                for _ in 0..n {
+                                    #[cfg(feature = "fptr")]
+                                    f[col](&mut parser, &mut dst)?;
+                                    #[cfg(feature = "branch")]
+                                    {
+                                        let (s1, s2) = schemas[col];
+                                        TP::process(s1, s2, &mut parser, &mut dst)?;
+                                    }
+                                }
+                            }
+                            part_report.convert_time += convert_start.elapsed();
+                            if is_last {
+                                break;
+                            }
+                        },
+                    }
+
+                    let convert_start = Instant::now();
+                    info_span!("finalize", partition = i).in_scope(|| dst.finalize())?;
+                    part_report.convert_time += convert_start.elapsed();
+                    drop(parser);
+                    src.finish()?;
+                    part_report.bytes = dst.nbytes();
+                    if let Some(tracker) = &tracker {
+                        tracker.finish_partition();
+                    }
+                    partition_span.record("rows", part_report.rows);
+                    debug!(
+                        bytes = part_report.bytes,
+                        fetch_time = ?part_report.fetch_time,
+                        convert_time = ?part_report.convert_time,
+                        "Partition finished"
+                    );
+                    Ok(part_report)
+                },
+            )
+            .collect::<Result<Vec<_>, TP::Error>>()?;
 
         debug!("Writing finished");
+        report.total_time = start.elapsed();
+        load_span.record("rows", report.total_rows());
 
-        Ok(())
+        Ok(report)
     }
 
     /// Only fetch the metadata (header) of the destination.
@@ -215,7 +362,87 @@ where
             .map(|&s| TP::convert_typesystem(s))
             .collect::<CXResult<Vec<_>>>()?;
         let names = self.src.names();
+        if self.dst.needs_column_descriptions(&dst_schema) {
+            let columns = self.src.describe_columns()?;
+            self.dst.set_column_descriptions(columns);
+        }
         self.dst.allocate(0, &names, &dst_schema, dorder)?;
         Ok(())
     }
 }
+
+/// Run the first of `queries` once as is, see [`Source::raw_partition`], and load each result set
+/// it returns into a destination of its own, which is created by `new_destination`. The result
+/// sets are loaded one after another while their rows are fetched, so the destination must not
+/// need the row count in advance.
+pub fn load_result_sets<S, D, TP>(
+    mut src: S,
+    queries: &[CXQuery<String>],
+    mut new_destination: impl FnMut() -> D,
+) -> Result<Vec<D>, TP::Error>
+where
+    S: Source,
+    D: Destination,
+    TP: Transport<TSS = S::TypeSystem, TSD = D::TypeSystem, S = S, D = D>,
+{
+    let dorder = coordinate(S::DATA_ORDERS, D::DATA_ORDERS)?;
+    src.set_data_order(dorder)?;
+    src.set_queries(queries);
+    let mut partition = src.raw_partition()?;
+
+    let mut destinations = vec![];
+    {
+        let mut parser = partition.parser()?;
+        while let Some((names, src_schema)) = parser.next_result_set()? {
+            debug!(
+                index = destinations.len(),
+                ncols = names.len(),
+                "Load result set"
+            );
+            let dst_schema = src_schema
+                .iter()
+                .map(|&s| TP::convert_typesystem(s))
+                .collect::<CXResult<Vec<_>>>()?;
+            let mut destination = new_destination();
+            destination.allocate(0, &names, &dst_schema, dorder)?;
+
+            let mut dst = destination.partition(1)?.remove(0);
+            #[cfg(feature = "fptr")]
+            let f: Vec<_> = src_schema
+                .iter()
+                .zip_eq(&dst_schema)
+                .map(|(&src_ty, &dst_ty)| TP::processor(src_ty, dst_ty))
+                .collect::<CXResult<Vec<_>>>()?;
+            loop {
+                let (n, is_last) = parser.fetch_next()?;
+                dst.aquire_row(n)?;
+                // the values are produced in the data order of the source
+                let (outer, inner) = match dorder {
+                    DataOrder::RowMajor => (n, dst.ncols()),
+                    DataOrder::ColumnMajor => (dst.ncols(), n),
+                };
+                for i in 0..outer {
+                    for j in 0..inner {
+                        let col = match dorder {
+                            DataOrder::RowMajor => j,
+                            DataOrder::ColumnMajor => i,
+                        };
+                        #[cfg(feature = "fptr")]
+                        f[col](&mut parser, &mut dst)?;
+
+                        #[cfg(feature = "branch")]
+                        TP::process(src_schema[col], dst_schema[col], &mut parser, &mut dst)?;
+                    }
+                }
+                if is_last {
+                    break;
+                }
+            }
+            dst.finalize()?;
+            drop(dst);
+            destinations.push(destination);
+        }
+    }
+    partition.finish()?;
+    Ok(destinations)
+}
@@ -14,6 +14,10 @@ pub enum ConnectorXOutError {
     #[error("Source {0} not supported.")]
     SourceNotSupport(String),
 
+    /// The statement or connect timeout elapsed, the message is the error from the source.
+    #[error("Timed out: {0}")]
+    Timeout(String),
+
     #[error(transparent)]
     IOError(#[from] std::io::Error),
 
@@ -122,16 +126,121 @@ pub enum ConnectorXOutError {
     #[error(transparent)]
     TrinoArrowTransportError(#[from] crate::transports::TrinoArrowTransportError),
 
+    #[cfg(all(feature = "src_postgres", feature = "dst_arrow"))]
+    #[error(transparent)]
+    PostgresArrowStreamTransportError(#[from] crate::transports::PostgresArrowStreamTransportError),
+
+    #[cfg(all(feature = "src_mysql", feature = "dst_arrow"))]
+    #[error(transparent)]
+    MySQLArrowStreamTransportError(#[from] crate::transports::MySQLArrowStreamTransportError),
+
+    #[cfg(all(feature = "src_sqlite", feature = "dst_arrow"))]
+    #[error(transparent)]
+    SQLiteArrowStreamTransportError(#[from] crate::transports::SQLiteArrowStreamTransportError),
+
+    #[cfg(all(feature = "src_mssql", feature = "dst_arrow"))]
+    #[error(transparent)]
+    MsSQLArrowStreamTransportError(#[from] crate::transports::MsSQLArrowStreamTransportError),
+
+    #[cfg(all(feature = "src_oracle", feature = "dst_arrow"))]
+    #[error(transparent)]
+    OracleArrowStreamTransportError(#[from] crate::transports::OracleArrowStreamTransportError),
+
+    #[cfg(all(feature = "src_bigquery", feature = "dst_arrow"))]
+    #[error(transparent)]
+    BigQueryArrowStreamTransportError(#[from] crate::transports::BigQueryArrowStreamTransportError),
+
     /// Any other errors that are too trivial to be put here explicitly.
     #[error(transparent)]
     Other(#[from] anyhow::Error),
 }
 
+impl ConnectorXOutError {
+    /// Turn an error raised because a timeout elapsed into [`ConnectorXOutError::Timeout`],
+    /// leaving other errors untouched.
+    pub fn classify_timeout(self) -> Self {
+        let is_timeout = match &self {
+            #[cfg(feature = "src_postgres")]
+            ConnectorXOutError::PostgresSourceError(e) => e.is_timeout(),
+            #[cfg(all(feature = "src_postgres", feature = "dst_arrow"))]
+            ConnectorXOutError::PostgresArrowTransportError(
+                crate::transports::PostgresArrowTransportError::Source(e),
+            ) => e.is_timeout(),
+            #[cfg(feature = "src_mysql")]
+            ConnectorXOutError::MySQLSourceError(e) => e.is_timeout(),
+            #[cfg(all(feature = "src_mysql", feature = "dst_arrow"))]
+            ConnectorXOutError::MySQLArrowTransportError(
+                crate::transports::MySQLArrowTransportError::Source(e),
+            ) => e.is_timeout(),
+            #[cfg(feature = "src_mssql")]
+            ConnectorXOutError::MsSQLSourceError(e) => e.is_timeout(),
+            #[cfg(all(feature = "src_mssql", feature = "dst_arrow"))]
+            ConnectorXOutError::MsSQLArrowTransportError(
+                crate::transports::MsSQLArrowTransportError::Source(e),
+            ) => e.is_timeout(),
+            #[cfg(feature = "src_sqlite")]
+            ConnectorXOutError::SQLiteSourceError(e) => e.is_timeout(),
+            #[cfg(all(feature = "src_sqlite", feature = "dst_arrow"))]
+            ConnectorXOutError::SQLiteArrowTransportError(
+                crate::transports::SQLiteArrowTransportError::Source(e),
+            ) => e.is_timeout(),
+            #[cfg(feature = "src_oracle")]
+            ConnectorXOutError::OracleSourceError(e) => e.is_timeout(),
+            #[cfg(all(feature = "src_oracle", feature = "dst_arrow"))]
+            ConnectorXOutError::OracleArrowTransportError(
+                crate::transports::OracleArrowTransportError::Source(e),
+            ) => e.is_timeout(),
+            #[cfg(feature = "src_bigquery")]
+            ConnectorXOutError::BigQuerySourceError(e) => e.is_timeout(),
+            #[cfg(all(feature = "src_bigquery", feature = "dst_arrow"))]
+            ConnectorXOutError::BigqueryArrowTransportError(
+                crate::transports::BigQueryArrowTransportError::Source(e),
+            ) => e.is_timeout(),
+            #[cfg(feature = "src_trino")]
+            ConnectorXOutError::TrinoSourceError(e) => e.is_timeout(),
+            #[cfg(all(feature = "src_trino", feature = "dst_arrow"))]
+            ConnectorXOutError::TrinoArrowTransportError(
+                crate::transports::TrinoArrowTransportError::Source(e),
+            ) => e.is_timeout(),
+            #[cfg(all(feature = "src_postgres", feature = "dst_arrow"))]
+            ConnectorXOutError::PostgresArrowStreamTransportError(
+                crate::transports::PostgresArrowStreamTransportError::Source(e),
+            ) => e.is_timeout(),
+            #[cfg(all(feature = "src_mysql", feature = "dst_arrow"))]
+            ConnectorXOutError::MySQLArrowStreamTransportError(
+                crate::transports::MySQLArrowStreamTransportError::Source(e),
+            ) => e.is_timeout(),
+            #[cfg(all(feature = "src_sqlite", feature = "dst_arrow"))]
+            ConnectorXOutError::SQLiteArrowStreamTransportError(
+                crate::transports::SQLiteArrowStreamTransportError::Source(e),
+            ) => e.is_timeout(),
+            #[cfg(all(feature = "src_mssql", feature = "dst_arrow"))]
+            ConnectorXOutError::MsSQLArrowStreamTransportError(
+                crate::transports::MsSQLArrowStreamTransportError::Source(e),
+            ) => e.is_timeout(),
+            #[cfg(all(feature = "src_oracle", feature = "dst_arrow"))]
+            ConnectorXOutError::OracleArrowStreamTransportError(
+                crate::transports::OracleArrowStreamTransportError::Source(e),
+            ) => e.is_timeout(),
+            #[cfg(all(feature = "src_bigquery", feature = "dst_arrow"))]
+            ConnectorXOutError::BigQueryArrowStreamTransportError(
+                crate::transports::BigQueryArrowStreamTransportError::Source(e),
+            ) => e.is_timeout(),
+            _ => false,
+        };
+        if is_timeout {
+            ConnectorXOutError::Timeout(self.to_string())
+        } else {
+            self
+        }
+    }
+}
+
 /// Errors that can be raised from this library.
 #[derive(Error, Debug)]
 pub enum ConnectorXError {
     /// The required type does not same as the schema defined.
-    #[error("Data type unexpected: {0:?} expected, {1} found.")]
+    #[error("Data type unexpected: {0} expected, {1} found.")]
     TypeCheckFailed(String, &'static str),
 
     #[error("Data order not supported {0:?}.")]
@@ -152,6 +261,15 @@ pub enum ConnectorXError {
     #[error("Cannot get total number of rows in advance.")]
     CountError(),
 
+    #[error("No parameter is bound to the placeholder {0}.")]
+    MissingParam(String),
+
+    #[error("Column type {0} is not supported.")]
+    UnsupportedType(String),
+
+    #[error("The result sets of raw queries are not supported by {0}.")]
+    ResultSetsNotSupported(&'static str),
+
     #[error(transparent)]
     SQLParserError(#[from] sqlparser::parser::ParserError),
 
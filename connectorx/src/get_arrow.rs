@@ -7,19 +7,28 @@ use crate::sources::postgres::{
 };
 use crate::{
     arrow_batch_iter::{ArrowBatchIter, RecordBatchIterator},
+    destinations::arrow::{source_metadata, FNewField},
+    partition::{partition, PartitionQuery},
     prelude::*,
-    sql::CXQuery,
+    sql::{tag_queries, CXQuery},
+};
+use anyhow::anyhow;
+use arrow::{
+    datatypes::{Field, Schema},
+    record_batch::RecordBatch,
 };
 use fehler::{throw, throws};
-use log::debug;
+use itertools::Itertools;
 #[cfg(feature = "src_postgres")]
 use postgres::NoTls;
 #[cfg(feature = "src_postgres")]
 use postgres_openssl::MakeTlsConnector;
+use rayon::prelude::*;
+use std::collections::HashMap;
 #[allow(unused_imports)]
 use std::sync::Arc;
+use tracing::{debug, info_span};
 
-#[allow(unreachable_code, unreachable_patterns, unused_variables, unused_mut)]
 #[throws(ConnectorXOutError)]
 pub fn get_arrow(
     source_conn: &SourceConn,
@@ -27,11 +36,80 @@ pub fn get_arrow(
     queries: &[CXQuery<String>],
     pre_execution_queries: Option<&[String]>,
 ) -> ArrowDestination {
-    let mut destination = ArrowDestination::new();
+    let (destination, _) = get_arrow_with_report(
+        source_conn,
+        origin_query,
+        queries,
+        pre_execution_queries,
+        None,
+        None,
+    )?;
+    destination
+}
+
+/// Same as [`get_arrow`], but also runs `post_execution_queries` after the load, reports
+/// the progress to `progress` and returns a [`LoadReport`] describing the load.
+#[throws(ConnectorXOutError)]
+pub fn get_arrow_with_report(
+    source_conn: &SourceConn,
+    origin_query: Option<String>,
+    queries: &[CXQuery<String>],
+    pre_execution_queries: Option<&[String]>,
+    post_execution_queries: Option<&[String]>,
+    progress: Option<ProgressCallback>,
+) -> (ArrowDestination, LoadReport) {
+    get_arrow_into(
+        ArrowDestination::new(),
+        source_conn,
+        origin_query,
+        queries,
+        pre_execution_queries,
+        post_execution_queries,
+        progress,
+    )?
+}
+
+/// Same as [`get_arrow_with_report`], but loads into `destination`, which can be configured
+/// beforehand, e.g. with [`ArrowDestination::set_dictionary_columns`].
+#[throws(ConnectorXOutError)]
+pub fn get_arrow_into(
+    destination: ArrowDestination,
+    source_conn: &SourceConn,
+    origin_query: Option<String>,
+    queries: &[CXQuery<String>],
+    pre_execution_queries: Option<&[String]>,
+    post_execution_queries: Option<&[String]>,
+    progress: Option<ProgressCallback>,
+) -> (ArrowDestination, LoadReport) {
+    load_arrow(
+        destination,
+        source_conn,
+        origin_query,
+        queries,
+        pre_execution_queries,
+        post_execution_queries,
+        progress,
+    )
+    .map_err(ConnectorXOutError::classify_timeout)?
+}
+
+#[allow(unreachable_code, unreachable_patterns, unused_variables, unused_mut)]
+#[throws(ConnectorXOutError)]
+fn load_arrow(
+    mut destination: ArrowDestination,
+    source_conn: &SourceConn,
+    origin_query: Option<String>,
+    queries: &[CXQuery<String>],
+    pre_execution_queries: Option<&[String]>,
+    post_execution_queries: Option<&[String]>,
+    progress: Option<ProgressCallback>,
+) -> (ArrowDestination, LoadReport) {
     let protocol = source_conn.proto.as_str();
-    debug!("Protocol: {}", protocol);
+    let _span = info_span!("get_arrow", source = ?source_conn.ty, protocol).entered();
+    destination.set_query_metadata(&source_conn.ty, origin_query.as_deref(), queries);
+    let queries = &tag_queries(queries, source_conn.tag.as_deref());
 
-    match source_conn.ty {
+    let mut report = match source_conn.ty {
         #[cfg(feature = "src_postgres")]
         SourceType::Postgres => {
             let (config, tls) = rewrite_tls_args(&source_conn.conn)?;
@@ -50,7 +128,9 @@ pub fn get_arrow(
                         source, &mut destination, queries, origin_query
                     );
                     dispatcher.set_pre_execution_queries(pre_execution_queries);
-                    dispatcher.run()?;
+                    dispatcher.set_post_execution_queries(post_execution_queries);
+                    dispatcher.set_progress_callback(progress);
+                    dispatcher.run_with_report(Some(protocol))?
                 }
                 ("csv", None) => {
                     let source =
@@ -63,7 +143,9 @@ pub fn get_arrow(
                         source, &mut destination, queries, origin_query
                     );
                     dispatcher.set_pre_execution_queries(pre_execution_queries);
-                    dispatcher.run()?;
+                    dispatcher.set_post_execution_queries(post_execution_queries);
+                    dispatcher.set_progress_callback(progress);
+                    dispatcher.run_with_report(Some(protocol))?
                 }
                 ("binary", Some(tls_conn)) => {
                     let source = PostgresSource::<PgBinaryProtocol, MakeTlsConnector>::new(
@@ -79,7 +161,9 @@ pub fn get_arrow(
                         source, &mut destination, queries, origin_query
                     );
                     dispatcher.set_pre_execution_queries(pre_execution_queries);
-                    dispatcher.run()?;
+                    dispatcher.set_post_execution_queries(post_execution_queries);
+                    dispatcher.set_progress_callback(progress);
+                    dispatcher.run_with_report(Some(protocol))?
                 }
                 ("binary", None) => {
                     let source = PostgresSource::<PgBinaryProtocol, NoTls>::new(
@@ -95,7 +179,9 @@ pub fn get_arrow(
                         source, &mut destination, queries, origin_query
                     );
                     dispatcher.set_pre_execution_queries(pre_execution_queries);
-                    dispatcher.run()?;
+                    dispatcher.set_post_execution_queries(post_execution_queries);
+                    dispatcher.set_progress_callback(progress);
+                    dispatcher.run_with_report(Some(protocol))?
                 }
                 ("cursor", Some(tls_conn)) => {
                     let source = PostgresSource::<CursorProtocol, MakeTlsConnector>::new(
@@ -111,7 +197,9 @@ pub fn get_arrow(
                         source, &mut destination, queries, origin_query
                     );
                     dispatcher.set_pre_execution_queries(pre_execution_queries);
-                    dispatcher.run()?;
+                    dispatcher.set_post_execution_queries(post_execution_queries);
+                    dispatcher.set_progress_callback(progress);
+                    dispatcher.run_with_report(Some(protocol))?
                 }
                 ("cursor", None) => {
                     let source =
@@ -124,7 +212,9 @@ pub fn get_arrow(
                         source, &mut destination, queries, origin_query
                     );
                     dispatcher.set_pre_execution_queries(pre_execution_queries);
-                    dispatcher.run()?;
+                    dispatcher.set_post_execution_queries(post_execution_queries);
+                    dispatcher.set_progress_callback(progress);
+                    dispatcher.run_with_report(Some(protocol))?
                 }
                 ("simple", Some(tls_conn)) => {
                     let sb = PostgresSource::<SimpleProtocol, MakeTlsConnector>::new(
@@ -141,7 +231,9 @@ pub fn get_arrow(
                     );
                     debug!("Running dispatcher");
                     dispatcher.set_pre_execution_queries(pre_execution_queries);
-                    dispatcher.run()?;
+                    dispatcher.set_post_execution_queries(post_execution_queries);
+                    dispatcher.set_progress_callback(progress);
+                    dispatcher.run_with_report(Some(protocol))?
                 }
                 ("simple", None) => {
                     let sb =
@@ -155,7 +247,9 @@ pub fn get_arrow(
                     );
                     debug!("Running dispatcher");
                     dispatcher.set_pre_execution_queries(pre_execution_queries);
-                    dispatcher.run()?;
+                    dispatcher.set_post_execution_queries(post_execution_queries);
+                    dispatcher.set_progress_callback(progress);
+                    dispatcher.run_with_report(Some(protocol))?
                 }
                 _ => unimplemented!("{} protocol not supported", protocol),
             }
@@ -173,7 +267,9 @@ pub fn get_arrow(
                         origin_query,
                     );
                 dispatcher.set_pre_execution_queries(pre_execution_queries);
-                dispatcher.run()?;
+                dispatcher.set_post_execution_queries(post_execution_queries);
+                dispatcher.set_progress_callback(progress);
+                dispatcher.run_with_report(Some(protocol))?
             }
             "text" => {
                 let source =
@@ -185,7 +281,9 @@ pub fn get_arrow(
                     origin_query,
                 );
                 dispatcher.set_pre_execution_queries(pre_execution_queries);
-                dispatcher.run()?;
+                dispatcher.set_post_execution_queries(post_execution_queries);
+                dispatcher.set_progress_callback(progress);
+                dispatcher.run_with_report(Some(protocol))?
             }
             _ => unimplemented!("{} protocol not supported", protocol),
         },
@@ -194,71 +292,934 @@ pub fn get_arrow(
             // remove the first "sqlite://" manually since url.path is not correct for windows
             let path = &source_conn.conn.as_str()[9..];
             let source = SQLiteSource::new(path, queries.len())?;
-            let dispatcher = Dispatcher::<_, _, SQLiteArrowTransport>::new(
+            let mut dispatcher = Dispatcher::<_, _, SQLiteArrowTransport>::new(
                 source,
                 &mut destination,
                 queries,
                 origin_query,
             );
-            dispatcher.run()?;
+            dispatcher.set_pre_execution_queries(pre_execution_queries);
+            dispatcher.set_post_execution_queries(post_execution_queries);
+            dispatcher.set_progress_callback(progress);
+            dispatcher.run_with_report(Some(protocol))?
         }
         #[cfg(feature = "src_mssql")]
         SourceType::MsSQL => {
             let rt = Arc::new(tokio::runtime::Runtime::new().expect("Failed to create runtime"));
             let source = MsSQLSource::new(rt, &source_conn.conn[..], queries.len())?;
-            let dispatcher = Dispatcher::<_, _, MsSQLArrowTransport>::new(
+            let mut dispatcher = Dispatcher::<_, _, MsSQLArrowTransport>::new(
                 source,
                 &mut destination,
                 queries,
                 origin_query,
             );
-            dispatcher.run()?;
+            dispatcher.set_pre_execution_queries(pre_execution_queries);
+            dispatcher.set_post_execution_queries(post_execution_queries);
+            dispatcher.set_progress_callback(progress);
+            dispatcher.run_with_report(Some(protocol))?
         }
         #[cfg(feature = "src_oracle")]
         SourceType::Oracle => {
             let source = OracleSource::new(&source_conn.conn[..], queries.len())?;
-            let dispatcher = Dispatcher::<_, _, OracleArrowTransport>::new(
+            let mut dispatcher = Dispatcher::<_, _, OracleArrowTransport>::new(
                 source,
                 &mut destination,
                 queries,
                 origin_query,
             );
-            dispatcher.run()?;
+            dispatcher.set_pre_execution_queries(pre_execution_queries);
+            dispatcher.set_post_execution_queries(post_execution_queries);
+            dispatcher.set_progress_callback(progress);
+            dispatcher.run_with_report(Some(protocol))?
         }
         #[cfg(feature = "src_bigquery")]
         SourceType::BigQuery => {
             let rt = Arc::new(tokio::runtime::Runtime::new().expect("Failed to create runtime"));
             let source = BigQuerySource::new(rt, &source_conn.conn[..])?;
-            let dispatcher = Dispatcher::<_, _, BigQueryArrowTransport>::new(
+            let mut dispatcher = Dispatcher::<_, _, BigQueryArrowTransport>::new(
                 source,
                 &mut destination,
                 queries,
                 origin_query,
             );
-            dispatcher.run()?;
+            dispatcher.set_pre_execution_queries(pre_execution_queries);
+            dispatcher.set_post_execution_queries(post_execution_queries);
+            dispatcher.set_progress_callback(progress);
+            dispatcher.run_with_report(Some(protocol))?
         }
         #[cfg(feature = "src_trino")]
         SourceType::Trino => {
             let rt = Arc::new(tokio::runtime::Runtime::new().expect("Failed to create runtime"));
             let source = TrinoSource::new(rt, &source_conn.conn[..])?;
-            let dispatcher = Dispatcher::<_, _, TrinoArrowTransport>::new(
+            let mut dispatcher = Dispatcher::<_, _, TrinoArrowTransport>::new(
                 source,
                 &mut destination,
                 queries,
                 origin_query,
             );
-            dispatcher.run()?;
+            dispatcher.set_pre_execution_queries(pre_execution_queries);
+            dispatcher.set_post_execution_queries(post_execution_queries);
+            dispatcher.set_progress_callback(progress);
+            dispatcher.run_with_report(Some(protocol))?
         }
         _ => throw!(ConnectorXOutError::SourceNotSupport(format!(
             "{:?}",
             source_conn.ty
         ))),
+    };
+    report.protocol = Some(protocol.to_string());
+
+    (destination, report)
+}
+
+/// One entry of [`get_arrow_batch`] and [`get_arrow_batch_stream`].
+pub enum BatchQuery {
+    /// The queries are loaded into one table as they are.
+    Queries(Vec<CXQuery<String>>),
+    /// The query is split on a column, the same way as [`partition`] does.
+    Partitioned(PartitionQuery),
+}
+
+struct BatchLoad {
+    name: String,
+    queries: Vec<CXQuery<String>>,
+    origin_query: Option<String>,
+}
+
+#[throws(ConnectorXOutError)]
+fn batch_loads(source_conn: &SourceConn, entries: &[(String, BatchQuery)]) -> Vec<BatchLoad> {
+    let mut loads = vec![];
+    for (name, entry) in entries {
+        if loads.iter().any(|load: &BatchLoad| &load.name == name) {
+            throw!(anyhow!("duplicated name in the batch: {}", name));
+        }
+        let (queries, origin_query) = match entry {
+            BatchQuery::Queries(queries) => (queries.clone(), None),
+            BatchQuery::Partitioned(part) => (
+                partition(part, source_conn)?,
+                Some(part.query().to_string()),
+            ),
+        };
+        loads.push(BatchLoad {
+            name: name.clone(),
+            queries: tag_queries(&queries, source_conn.tag.as_deref()),
+            origin_query,
+        });
     }
+    loads
+}
 
-    destination
+/// Load several unrelated queries against the same `source_conn` in one call. The partitions
+/// of all entries run on the global thread pool and share one connection pool, and the record
+/// batches are returned by the name of their entry.
+///
+/// The connection pool holds as many connections as the thread pool has threads, or as the
+/// largest entry has partitions if that is more. The entries are loaded in waves whose
+/// partitions fit into the pool.
+#[throws(ConnectorXOutError)]
+pub fn get_arrow_batch(
+    source_conn: &SourceConn,
+    entries: &[(String, BatchQuery)],
+    pre_execution_queries: Option<&[String]>,
+    post_execution_queries: Option<&[String]>,
+) -> HashMap<String, Vec<RecordBatch>> {
+    arrow_batch(
+        source_conn,
+        entries,
+        pre_execution_queries,
+        post_execution_queries,
+    )
+    .map_err(ConnectorXOutError::classify_timeout)?
+}
+
+#[allow(unreachable_code, unreachable_patterns, unused_variables, unused_mut)]
+#[throws(ConnectorXOutError)]
+fn arrow_batch(
+    source_conn: &SourceConn,
+    entries: &[(String, BatchQuery)],
+    pre_execution_queries: Option<&[String]>,
+    post_execution_queries: Option<&[String]>,
+) -> HashMap<String, Vec<RecordBatch>> {
+    let protocol = source_conn.proto.as_str();
+    let _span = info_span!("get_arrow_batch", source = ?source_conn.ty, protocol).entered();
+
+    let loads = batch_loads(source_conn, entries)?;
+    if loads.is_empty() {
+        return HashMap::new();
+    }
+    let largest = loads
+        .iter()
+        .map(|load| load.queries.len())
+        .max()
+        .unwrap_or(1);
+    let total = loads.iter().map(|load| load.queries.len()).sum::<usize>();
+    let nconn = total.min(rayon::current_num_threads().max(largest));
+    let nloads = loads.len();
+
+    match source_conn.ty {
+        #[cfg(feature = "src_postgres")]
+        SourceType::Postgres => {
+            let (config, tls) = rewrite_tls_args(&source_conn.conn)?;
+            match (protocol, tls) {
+                ("csv", Some(tls_conn)) => {
+                    let source = PostgresSource::<CSVProtocol, MakeTlsConnector>::new(
+                        config, tls_conn, nconn,
+                    )?;
+                    run_batch::<_, PostgresArrowTransport<CSVProtocol, MakeTlsConnector>>(
+                        fork_source(source, nloads),
+                        loads,
+                        nconn,
+                        pre_execution_queries,
+                        post_execution_queries,
+                    )?
+                }
+                ("csv", None) => {
+                    let source = PostgresSource::<CSVProtocol, NoTls>::new(config, NoTls, nconn)?;
+                    run_batch::<_, PostgresArrowTransport<CSVProtocol, NoTls>>(
+                        fork_source(source, nloads),
+                        loads,
+                        nconn,
+                        pre_execution_queries,
+                        post_execution_queries,
+                    )?
+                }
+                ("binary", Some(tls_conn)) => {
+                    let source = PostgresSource::<PgBinaryProtocol, MakeTlsConnector>::new(
+                        config, tls_conn, nconn,
+                    )?;
+                    run_batch::<_, PostgresArrowTransport<PgBinaryProtocol, MakeTlsConnector>>(
+                        fork_source(source, nloads),
+                        loads,
+                        nconn,
+                        pre_execution_queries,
+                        post_execution_queries,
+                    )?
+                }
+                ("binary", None) => {
+                    let source =
+                        PostgresSource::<PgBinaryProtocol, NoTls>::new(config, NoTls, nconn)?;
+                    run_batch::<_, PostgresArrowTransport<PgBinaryProtocol, NoTls>>(
+                        fork_source(source, nloads),
+                        loads,
+                        nconn,
+                        pre_execution_queries,
+                        post_execution_queries,
+                    )?
+                }
+                ("cursor", Some(tls_conn)) => {
+                    let source = PostgresSource::<CursorProtocol, MakeTlsConnector>::new(
+                        config, tls_conn, nconn,
+                    )?;
+                    run_batch::<_, PostgresArrowTransport<CursorProtocol, MakeTlsConnector>>(
+                        fork_source(source, nloads),
+                        loads,
+                        nconn,
+                        pre_execution_queries,
+                        post_execution_queries,
+                    )?
+                }
+                ("cursor", None) => {
+                    let source =
+                        PostgresSource::<CursorProtocol, NoTls>::new(config, NoTls, nconn)?;
+                    run_batch::<_, PostgresArrowTransport<CursorProtocol, NoTls>>(
+                        fork_source(source, nloads),
+                        loads,
+                        nconn,
+                        pre_execution_queries,
+                        post_execution_queries,
+                    )?
+                }
+                ("simple", Some(tls_conn)) => {
+                    let source = PostgresSource::<SimpleProtocol, MakeTlsConnector>::new(
+                        config, tls_conn, nconn,
+                    )?;
+                    run_batch::<_, PostgresArrowTransport<SimpleProtocol, MakeTlsConnector>>(
+                        fork_source(source, nloads),
+                        loads,
+                        nconn,
+                        pre_execution_queries,
+                        post_execution_queries,
+                    )?
+                }
+                ("simple", None) => {
+                    let source =
+                        PostgresSource::<SimpleProtocol, NoTls>::new(config, NoTls, nconn)?;
+                    run_batch::<_, PostgresArrowTransport<SimpleProtocol, NoTls>>(
+                        fork_source(source, nloads),
+                        loads,
+                        nconn,
+                        pre_execution_queries,
+                        post_execution_queries,
+                    )?
+                }
+                _ => unimplemented!("{} protocol not supported", protocol),
+            }
+        }
+        #[cfg(feature = "src_mysql")]
+        SourceType::MySQL => match protocol {
+            "binary" => {
+                let source = MySQLSource::<MySQLBinaryProtocol>::new(&source_conn.conn[..], nconn)?;
+                run_batch::<_, MySQLArrowTransport<MySQLBinaryProtocol>>(
+                    fork_source(source, nloads),
+                    loads,
+                    nconn,
+                    pre_execution_queries,
+                    post_execution_queries,
+                )?
+            }
+            "text" => {
+                let source = MySQLSource::<TextProtocol>::new(&source_conn.conn[..], nconn)?;
+                run_batch::<_, MySQLArrowTransport<TextProtocol>>(
+                    fork_source(source, nloads),
+                    loads,
+                    nconn,
+                    pre_execution_queries,
+                    post_execution_queries,
+                )?
+            }
+            _ => unimplemented!("{} protocol not supported", protocol),
+        },
+        #[cfg(feature = "src_sqlite")]
+        SourceType::SQLite => {
+            // remove the first "sqlite://" manually since url.path is not correct for windows
+            let path = &source_conn.conn.as_str()[9..];
+            let source = SQLiteSource::new(path, nconn)?;
+            run_batch::<_, SQLiteArrowTransport>(
+                fork_source(source, nloads),
+                loads,
+                nconn,
+                pre_execution_queries,
+                post_execution_queries,
+            )?
+        }
+        #[cfg(feature = "src_mssql")]
+        SourceType::MsSQL => {
+            let rt = Arc::new(tokio::runtime::Runtime::new().expect("Failed to create runtime"));
+            let source = MsSQLSource::new(rt, &source_conn.conn[..], nconn)?;
+            run_batch::<_, MsSQLArrowTransport>(
+                fork_source(source, nloads),
+                loads,
+                nconn,
+                pre_execution_queries,
+                post_execution_queries,
+            )?
+        }
+        #[cfg(feature = "src_oracle")]
+        SourceType::Oracle => {
+            let source = OracleSource::new(&source_conn.conn[..], nconn)?;
+            run_batch::<_, OracleArrowTransport>(
+                fork_source(source, nloads),
+                loads,
+                nconn,
+                pre_execution_queries,
+                post_execution_queries,
+            )?
+        }
+        #[cfg(feature = "src_bigquery")]
+        SourceType::BigQuery => {
+            let rt = Arc::new(tokio::runtime::Runtime::new().expect("Failed to create runtime"));
+            let source = BigQuerySource::new(rt, &source_conn.conn[..])?;
+            run_batch::<_, BigQueryArrowTransport>(
+                fork_source(source, nloads),
+                loads,
+                nconn,
+                pre_execution_queries,
+                post_execution_queries,
+            )?
+        }
+        #[cfg(feature = "src_trino")]
+        SourceType::Trino => {
+            // the session of a trino client is shared by all its queries, so every entry gets a
+            // client of its own to keep the post-execution queries of one from affecting another
+            let rt = Arc::new(tokio::runtime::Runtime::new().expect("Failed to create runtime"));
+            let sources = (0..nloads)
+                .map(|_| TrinoSource::new(rt.clone(), &source_conn.conn[..]))
+                .collect::<Result<Vec<_>, _>>()?;
+            run_batch::<_, TrinoArrowTransport>(
+                sources,
+                loads,
+                nconn,
+                pre_execution_queries,
+                post_execution_queries,
+            )?
+        }
+        _ => throw!(ConnectorXOutError::SourceNotSupport(format!(
+            "{:?}",
+            source_conn.ty
+        ))),
+    }
+}
+
+// `source` itself is handed to the last load
+fn fork_source<S: Source>(source: S, n: usize) -> Vec<S> {
+    let mut sources: Vec<S> = (1..n).map(|_| source.fork()).collect();
+    sources.push(source);
+    sources
+}
+
+fn run_batch<S, TP>(
+    sources: Vec<S>,
+    loads: Vec<BatchLoad>,
+    nconn: usize,
+    pre_execution_queries: Option<&[String]>,
+    post_execution_queries: Option<&[String]>,
+) -> Result<HashMap<String, Vec<RecordBatch>>, ConnectorXOutError>
+where
+    S: Source + Send,
+    TP: Transport<TSS = S::TypeSystem, TSD = ArrowTypeSystem, S = S, D = ArrowDestination>,
+    ConnectorXOutError: From<TP::Error>,
+{
+    let mut tables = HashMap::new();
+    let mut loads = sources.into_iter().zip_eq(loads).peekable();
+    while loads.peek().is_some() {
+        // every partition holds a connection from its preparation until it is loaded, so the
+        // partitions of a wave must not outnumber the connections
+        let (mut wave, mut nparts) = (vec![], 0);
+        while let Some((source, load)) =
+            loads.next_if(|(_, load)| wave.is_empty() || nparts + load.queries.len() <= nconn)
+        {
+            nparts += load.queries.len();
+            wave.push((source, load));
+        }
+        let loaded = wave
+            .into_par_iter()
+            .map(|(source, load)| {
+                debug!(name = load.name.as_str(), "Run batch entry");
+                let mut destination = ArrowDestination::new();
+                let mut dispatcher = Dispatcher::<_, _, TP>::new(
+                    source,
+                    &mut destination,
+                    &load.queries,
+                    load.origin_query,
+                );
+                dispatcher.set_pre_execution_queries(pre_execution_queries);
+                dispatcher.set_post_execution_queries(post_execution_queries);
+                dispatcher.run()?;
+                Ok((load.name, destination.arrow()?))
+            })
+            .collect::<Result<Vec<_>, ConnectorXOutError>>()?;
+        tables.extend(loaded);
+    }
+    Ok(tables)
+}
+
+/// Same as [`get_arrow_batch`], but returns a record batch iterator for each entry instead of
+/// loading them. The iterators are prepared up front and can be read in any order, so the
+/// connection pool holds a connection for every partition of every entry.
+#[throws(ConnectorXOutError)]
+pub fn get_arrow_batch_stream(
+    source_conn: &SourceConn,
+    entries: &[(String, BatchQuery)],
+    batch_size: usize,
+    pre_execution_queries: Option<&[String]>,
+    post_execution_queries: Option<&[String]>,
+) -> HashMap<String, Box<dyn RecordBatchIterator>> {
+    arrow_batch_stream(
+        source_conn,
+        entries,
+        batch_size,
+        pre_execution_queries,
+        post_execution_queries,
+    )
+    .map_err(ConnectorXOutError::classify_timeout)?
 }
 
 #[allow(unreachable_code, unreachable_patterns, unused_variables, unused_mut)]
+#[throws(ConnectorXOutError)]
+fn arrow_batch_stream(
+    source_conn: &SourceConn,
+    entries: &[(String, BatchQuery)],
+    batch_size: usize,
+    pre_execution_queries: Option<&[String]>,
+    post_execution_queries: Option<&[String]>,
+) -> HashMap<String, Box<dyn RecordBatchIterator>> {
+    let protocol = source_conn.proto.as_str();
+    let _span = info_span!("get_arrow_batch_stream", source = ?source_conn.ty, protocol).entered();
+
+    let loads = batch_loads(source_conn, entries)?;
+    if loads.is_empty() {
+        return HashMap::new();
+    }
+    let nconn = loads.iter().map(|load| load.queries.len()).sum::<usize>();
+    let nloads = loads.len();
+
+    match source_conn.ty {
+        #[cfg(feature = "src_postgres")]
+        SourceType::Postgres => {
+            let (config, tls) = rewrite_tls_args(&source_conn.conn)?;
+            match (protocol, tls) {
+                ("csv", Some(tls_conn)) => {
+                    let source = PostgresSource::<CSVProtocol, MakeTlsConnector>::new(
+                        config, tls_conn, nconn,
+                    )?;
+                    stream_batch::<_, PostgresArrowStreamTransport<CSVProtocol, MakeTlsConnector>>(
+                        fork_source(source, nloads),
+                        loads,
+                        batch_size,
+                        pre_execution_queries,
+                        post_execution_queries,
+                    )?
+                }
+                ("csv", None) => {
+                    let source = PostgresSource::<CSVProtocol, NoTls>::new(config, NoTls, nconn)?;
+                    stream_batch::<_, PostgresArrowStreamTransport<CSVProtocol, NoTls>>(
+                        fork_source(source, nloads),
+                        loads,
+                        batch_size,
+                        pre_execution_queries,
+                        post_execution_queries,
+                    )?
+                }
+                ("binary", Some(tls_conn)) => {
+                    let source = PostgresSource::<PgBinaryProtocol, MakeTlsConnector>::new(
+                        config, tls_conn, nconn,
+                    )?;
+                    stream_batch::<
+                        _,
+                        PostgresArrowStreamTransport<PgBinaryProtocol, MakeTlsConnector>,
+                    >(
+                        fork_source(source, nloads),
+                        loads,
+                        batch_size,
+                        pre_execution_queries,
+                        post_execution_queries,
+                    )?
+                }
+                ("binary", None) => {
+                    let source =
+                        PostgresSource::<PgBinaryProtocol, NoTls>::new(config, NoTls, nconn)?;
+                    stream_batch::<_, PostgresArrowStreamTransport<PgBinaryProtocol, NoTls>>(
+                        fork_source(source, nloads),
+                        loads,
+                        batch_size,
+                        pre_execution_queries,
+                        post_execution_queries,
+                    )?
+                }
+                ("cursor", Some(tls_conn)) => {
+                    let source = PostgresSource::<CursorProtocol, MakeTlsConnector>::new(
+                        config, tls_conn, nconn,
+                    )?;
+                    stream_batch::<_, PostgresArrowStreamTransport<CursorProtocol, MakeTlsConnector>>(
+                        fork_source(source, nloads),
+                        loads,
+                        batch_size,
+                        pre_execution_queries,
+                        post_execution_queries,
+                    )?
+                }
+                ("cursor", None) => {
+                    let source =
+                        PostgresSource::<CursorProtocol, NoTls>::new(config, NoTls, nconn)?;
+                    stream_batch::<_, PostgresArrowStreamTransport<CursorProtocol, NoTls>>(
+                        fork_source(source, nloads),
+                        loads,
+                        batch_size,
+                        pre_execution_queries,
+                        post_execution_queries,
+                    )?
+                }
+                _ => throw!(anyhow!("{} protocol not supported", protocol)),
+            }
+        }
+        #[cfg(feature = "src_mysql")]
+        SourceType::MySQL => match protocol {
+            "binary" => {
+                let source = MySQLSource::<MySQLBinaryProtocol>::new(&source_conn.conn[..], nconn)?;
+                stream_batch::<_, MySQLArrowStreamTransport<MySQLBinaryProtocol>>(
+                    fork_source(source, nloads),
+                    loads,
+                    batch_size,
+                    pre_execution_queries,
+                    post_execution_queries,
+                )?
+            }
+            "text" => {
+                let source = MySQLSource::<TextProtocol>::new(&source_conn.conn[..], nconn)?;
+                stream_batch::<_, MySQLArrowStreamTransport<TextProtocol>>(
+                    fork_source(source, nloads),
+                    loads,
+                    batch_size,
+                    pre_execution_queries,
+                    post_execution_queries,
+                )?
+            }
+            _ => throw!(anyhow!("{} protocol not supported", protocol)),
+        },
+        #[cfg(feature = "src_sqlite")]
+        SourceType::SQLite => {
+            // remove the first "sqlite://" manually since url.path is not correct for windows
+            let path = &source_conn.conn.as_str()[9..];
+            let source = SQLiteSource::new(path, nconn)?;
+            stream_batch::<_, SQLiteArrowStreamTransport>(
+                fork_source(source, nloads),
+                loads,
+                batch_size,
+                pre_execution_queries,
+                post_execution_queries,
+            )?
+        }
+        #[cfg(feature = "src_mssql")]
+        SourceType::MsSQL => {
+            let rt = Arc::new(tokio::runtime::Runtime::new().expect("Failed to create runtime"));
+            let source = MsSQLSource::new(rt, &source_conn.conn[..], nconn)?;
+            stream_batch::<_, MsSQLArrowStreamTransport>(
+                fork_source(source, nloads),
+                loads,
+                batch_size,
+                pre_execution_queries,
+                post_execution_queries,
+            )?
+        }
+        #[cfg(feature = "src_oracle")]
+        SourceType::Oracle => {
+            let source = OracleSource::new(&source_conn.conn[..], nconn)?;
+            stream_batch::<_, OracleArrowStreamTransport>(
+                fork_source(source, nloads),
+                loads,
+                batch_size,
+                pre_execution_queries,
+                post_execution_queries,
+            )?
+        }
+        #[cfg(feature = "src_bigquery")]
+        SourceType::BigQuery => {
+            let rt = Arc::new(tokio::runtime::Runtime::new().expect("Failed to create runtime"));
+            let source = BigQuerySource::new(rt, &source_conn.conn[..])?;
+            stream_batch::<_, BigQueryArrowStreamTransport>(
+                fork_source(source, nloads),
+                loads,
+                batch_size,
+                pre_execution_queries,
+                post_execution_queries,
+            )?
+        }
+        _ => throw!(ConnectorXOutError::SourceNotSupport(format!(
+            "{:?} for the record batch iterator",
+            source_conn.ty
+        ))),
+    }
+}
+
+fn stream_batch<S, TP>(
+    sources: Vec<S>,
+    loads: Vec<BatchLoad>,
+    batch_size: usize,
+    pre_execution_queries: Option<&[String]>,
+    post_execution_queries: Option<&[String]>,
+) -> Result<HashMap<String, Box<dyn RecordBatchIterator>>, ConnectorXOutError>
+where
+    S: Source + 'static,
+    TP: Transport<
+            TSS = S::TypeSystem,
+            TSD = ArrowStreamTypeSystem,
+            S = S,
+            D = ArrowStreamDestination,
+        > + Send
+        + 'static,
+    ConnectorXOutError: From<TP::Error>,
+{
+    sources
+        .into_iter()
+        .zip_eq(loads)
+        .map(|(mut source, load)| {
+            debug!(name = load.name.as_str(), "Prepare batch entry");
+            source.set_pre_execution_queries(pre_execution_queries);
+            source.set_post_execution_queries(post_execution_queries);
+            let batch_iter = ArrowBatchIter::<_, TP>::new(
+                source,
+                ArrowStreamDestination::new_with_batch_size(batch_size),
+                load.origin_query,
+                &load.queries,
+            )?;
+            Ok((
+                load.name,
+                Box::new(batch_iter) as Box<dyn RecordBatchIterator>,
+            ))
+        })
+        .collect()
+}
+
+/// Run `query` once as is, e.g. a stored procedure call, and load every result set it returns
+/// into a table of its own. The query is neither partitioned nor wrapped to fetch the metadata
+/// or the row count, so statements that are not a `SELECT` can be run. The result sets are read
+/// with the protocol of `source_conn` while they are loaded; on Postgres, the cursors returned by
+/// a function can only be read with the cursor protocol.
+#[allow(unreachable_code, unreachable_patterns, unused_variables, unused_mut)]
+#[throws(ConnectorXOutError)]
+pub fn get_arrow_raw(
+    source_conn: &SourceConn,
+    query: &CXQuery<String>,
+    pre_execution_queries: Option<&[String]>,
+    post_execution_queries: Option<&[String]>,
+) -> Vec<Vec<RecordBatch>> {
+    let protocol = source_conn.proto.as_str();
+    let _span = info_span!("get_arrow_raw", source = ?source_conn.ty, protocol).entered();
+    let queries = tag_queries(std::slice::from_ref(query), source_conn.tag.as_deref());
+
+    match source_conn.ty {
+        #[cfg(feature = "src_postgres")]
+        SourceType::Postgres => {
+            let (config, tls) = rewrite_tls_args(&source_conn.conn)?;
+            match (protocol, tls) {
+                ("csv", Some(tls_conn)) => {
+                    let source =
+                        PostgresSource::<CSVProtocol, MakeTlsConnector>::new(config, tls_conn, 1)?;
+                    run_raw::<_, PostgresArrowTransport<CSVProtocol, MakeTlsConnector>>(
+                        source,
+                        &queries,
+                        pre_execution_queries,
+                        post_execution_queries,
+                    )?
+                }
+                ("csv", None) => {
+                    let source = PostgresSource::<CSVProtocol, NoTls>::new(config, NoTls, 1)?;
+                    run_raw::<_, PostgresArrowTransport<CSVProtocol, NoTls>>(
+                        source,
+                        &queries,
+                        pre_execution_queries,
+                        post_execution_queries,
+                    )?
+                }
+                ("binary", Some(tls_conn)) => {
+                    let source = PostgresSource::<PgBinaryProtocol, MakeTlsConnector>::new(
+                        config, tls_conn, 1,
+                    )?;
+                    run_raw::<_, PostgresArrowTransport<PgBinaryProtocol, MakeTlsConnector>>(
+                        source,
+                        &queries,
+                        pre_execution_queries,
+                        post_execution_queries,
+                    )?
+                }
+                ("binary", None) => {
+                    let source = PostgresSource::<PgBinaryProtocol, NoTls>::new(config, NoTls, 1)?;
+                    run_raw::<_, PostgresArrowTransport<PgBinaryProtocol, NoTls>>(
+                        source,
+                        &queries,
+                        pre_execution_queries,
+                        post_execution_queries,
+                    )?
+                }
+                ("cursor", Some(tls_conn)) => {
+                    let source = PostgresSource::<CursorProtocol, MakeTlsConnector>::new(
+                        config, tls_conn, 1,
+                    )?;
+                    run_raw::<_, PostgresArrowTransport<CursorProtocol, MakeTlsConnector>>(
+                        source,
+                        &queries,
+                        pre_execution_queries,
+                        post_execution_queries,
+                    )?
+                }
+                ("cursor", None) => {
+                    let source = PostgresSource::<CursorProtocol, NoTls>::new(config, NoTls, 1)?;
+                    run_raw::<_, PostgresArrowTransport<CursorProtocol, NoTls>>(
+                        source,
+                        &queries,
+                        pre_execution_queries,
+                        post_execution_queries,
+                    )?
+                }
+                ("simple", Some(tls_conn)) => {
+                    let source = PostgresSource::<SimpleProtocol, MakeTlsConnector>::new(
+                        config, tls_conn, 1,
+                    )?;
+                    run_raw::<_, PostgresArrowTransport<SimpleProtocol, MakeTlsConnector>>(
+                        source,
+                        &queries,
+                        pre_execution_queries,
+                        post_execution_queries,
+                    )?
+                }
+                ("simple", None) => {
+                    let source = PostgresSource::<SimpleProtocol, NoTls>::new(config, NoTls, 1)?;
+                    run_raw::<_, PostgresArrowTransport<SimpleProtocol, NoTls>>(
+                        source,
+                        &queries,
+                        pre_execution_queries,
+                        post_execution_queries,
+                    )?
+                }
+                _ => throw!(anyhow!("{} protocol not supported", protocol)),
+            }
+        }
+        #[cfg(feature = "src_mysql")]
+        SourceType::MySQL => match protocol {
+            "binary" => {
+                let source = MySQLSource::<MySQLBinaryProtocol>::new(&source_conn.conn[..], 1)?;
+                run_raw::<_, MySQLArrowTransport<MySQLBinaryProtocol>>(
+                    source,
+                    &queries,
+                    pre_execution_queries,
+                    post_execution_queries,
+                )?
+            }
+            "text" => {
+                let source = MySQLSource::<TextProtocol>::new(&source_conn.conn[..], 1)?;
+                run_raw::<_, MySQLArrowTransport<TextProtocol>>(
+                    source,
+                    &queries,
+                    pre_execution_queries,
+                    post_execution_queries,
+                )?
+            }
+            _ => throw!(anyhow!("{} protocol not supported", protocol)),
+        },
+        #[cfg(feature = "src_mssql")]
+        SourceType::MsSQL => {
+            let rt = Arc::new(tokio::runtime::Runtime::new().expect("Failed to create runtime"));
+            let source = MsSQLSource::new(rt, &source_conn.conn[..], 1)?;
+            run_raw::<_, MsSQLArrowTransport>(
+                source,
+                &queries,
+                pre_execution_queries,
+                post_execution_queries,
+            )?
+        }
+        #[cfg(feature = "src_oracle")]
+        SourceType::Oracle => {
+            let source = OracleSource::new(&source_conn.conn[..], 1)?;
+            run_raw::<_, OracleArrowTransport>(
+                source,
+                &queries,
+                pre_execution_queries,
+                post_execution_queries,
+            )?
+        }
+        _ => throw!(ConnectorXOutError::SourceNotSupport(format!(
+            "{:?}",
+            source_conn.ty
+        ))),
+    }
+}
+
+fn run_raw<S, TP>(
+    mut source: S,
+    queries: &[CXQuery<String>],
+    pre_execution_queries: Option<&[String]>,
+    post_execution_queries: Option<&[String]>,
+) -> Result<Vec<Vec<RecordBatch>>, ConnectorXOutError>
+where
+    S: Source,
+    TP: Transport<TSS = S::TypeSystem, TSD = ArrowTypeSystem, S = S, D = ArrowDestination>,
+    ConnectorXOutError: From<TP::Error>,
+{
+    source.set_pre_execution_queries(pre_execution_queries);
+    source.set_post_execution_queries(post_execution_queries);
+    let destinations = load_result_sets::<_, _, TP>(source, queries, ArrowDestination::new)?;
+    debug!(nsets = destinations.len(), "Loaded result sets");
+
+    destinations
+        .into_iter()
+        .map(|destination| Ok(destination.arrow()?))
+        .collect()
+}
+
+/// Describe `query` and return the arrow schema its result is loaded with, without fetching any
+/// data. The fields carry the nullability of the columns, and the metadata describing the
+/// columns in the source, e.g. the declared type under the `connectorx.source_type` key.
+#[allow(unreachable_code, unreachable_patterns, unused_variables, unused_mut)]
+#[throws(ConnectorXOutError)]
+pub fn get_arrow_schema(source_conn: &SourceConn, query: &CXQuery<String>) -> Schema {
+    let _span = info_span!("get_arrow_schema", source = ?source_conn.ty).entered();
+    let queries = tag_queries(std::slice::from_ref(query), source_conn.tag.as_deref());
+
+    match source_conn.ty {
+        #[cfg(feature = "src_postgres")]
+        SourceType::Postgres => {
+            let (config, tls) = rewrite_tls_args(&source_conn.conn)?;
+            match tls {
+                Some(tls_conn) => {
+                    let source = PostgresSource::<CursorProtocol, MakeTlsConnector>::new(
+                        config, tls_conn, 1,
+                    )?;
+                    fetch_schema::<_, PostgresArrowTransport<CursorProtocol, MakeTlsConnector>>(
+                        source, &queries,
+                    )?
+                }
+                None => {
+                    let source = PostgresSource::<CursorProtocol, NoTls>::new(config, NoTls, 1)?;
+                    fetch_schema::<_, PostgresArrowTransport<CursorProtocol, NoTls>>(
+                        source, &queries,
+                    )?
+                }
+            }
+        }
+        #[cfg(feature = "src_mysql")]
+        SourceType::MySQL => {
+            let source = MySQLSource::<MySQLBinaryProtocol>::new(&source_conn.conn[..], 1)?;
+            fetch_schema::<_, MySQLArrowTransport<MySQLBinaryProtocol>>(source, &queries)?
+        }
+        #[cfg(feature = "src_sqlite")]
+        SourceType::SQLite => {
+            // remove the first "sqlite://" manually since url.path is not correct for windows
+            let path = &source_conn.conn.as_str()[9..];
+            let source = SQLiteSource::new(path, 1)?;
+            fetch_schema::<_, SQLiteArrowTransport>(source, &queries)?
+        }
+        #[cfg(feature = "src_mssql")]
+        SourceType::MsSQL => {
+            let rt = Arc::new(tokio::runtime::Runtime::new().expect("Failed to create runtime"));
+            let source = MsSQLSource::new(rt, &source_conn.conn[..], 1)?;
+            fetch_schema::<_, MsSQLArrowTransport>(source, &queries)?
+        }
+        #[cfg(feature = "src_oracle")]
+        SourceType::Oracle => {
+            let source = OracleSource::new(&source_conn.conn[..], 1)?;
+            fetch_schema::<_, OracleArrowTransport>(source, &queries)?
+        }
+        #[cfg(feature = "src_bigquery")]
+        SourceType::BigQuery => {
+            let rt = Arc::new(tokio::runtime::Runtime::new().expect("Failed to create runtime"));
+            let source = BigQuerySource::new(rt, &source_conn.conn[..])?;
+            fetch_schema::<_, BigQueryArrowTransport>(source, &queries)?
+        }
+        #[cfg(feature = "src_trino")]
+        SourceType::Trino => {
+            let rt = Arc::new(tokio::runtime::Runtime::new().expect("Failed to create runtime"));
+            let source = TrinoSource::new(rt, &source_conn.conn[..])?;
+            fetch_schema::<_, TrinoArrowTransport>(source, &queries)?
+        }
+        _ => throw!(ConnectorXOutError::SourceNotSupport(format!(
+            "{:?}",
+            source_conn.ty
+        ))),
+    }
+}
+
+fn fetch_schema<S, TP>(
+    mut source: S,
+    queries: &[CXQuery<String>],
+) -> Result<Schema, ConnectorXOutError>
+where
+    S: Source,
+    TP: Transport<TSS = S::TypeSystem, TSD = ArrowTypeSystem, S = S, D = ArrowDestination>,
+    ConnectorXOutError: From<TP::Error>,
+{
+    source.set_queries(queries);
+    source.fetch_metadata().map_err(TP::Error::from)?;
+    let columns = source.describe_columns().map_err(TP::Error::from)?;
+
+    let fields = source
+        .names()
+        .iter()
+        .zip(source.schema())
+        .zip(&columns)
+        .map(|((name, ts), column)| {
+            let dt = TP::convert_typesystem(ts)?;
+            let field = Realize::<FNewField>::realize(dt)?(name.as_str());
+            Ok(field.with_metadata(source_metadata(column, ts.is_nullable())))
+        })
+        .collect::<Result<Vec<Field>, ConnectorXOutError>>()?;
+    Ok(Schema::new(fields))
+}
+
+/// Create an iterator over the record batches of `queries`, see [`try_new_record_batch_iter`].
+///
+/// # Panics
+///
+/// Panics if the source cannot be set up or the schema of the queries cannot be fetched.
 pub fn new_record_batch_iter(
     source_conn: &SourceConn,
     origin_query: Option<String>,
@@ -266,131 +1227,168 @@ pub fn new_record_batch_iter(
     batch_size: usize,
     pre_execution_queries: Option<&[String]>,
 ) -> Box<dyn RecordBatchIterator> {
+    try_new_record_batch_iter(
+        source_conn,
+        origin_query,
+        queries,
+        batch_size,
+        pre_execution_queries,
+        None,
+    )
+    .unwrap()
+}
+
+/// Same as [`new_record_batch_iter`], but returns an error instead of panicking. The
+/// `post_execution_queries` are run on the connection of each partition once its rows are read.
+#[throws(ConnectorXOutError)]
+pub fn try_new_record_batch_iter(
+    source_conn: &SourceConn,
+    origin_query: Option<String>,
+    queries: &[CXQuery<String>],
+    batch_size: usize,
+    pre_execution_queries: Option<&[String]>,
+    post_execution_queries: Option<&[String]>,
+) -> Box<dyn RecordBatchIterator> {
+    record_batch_iter(
+        source_conn,
+        origin_query,
+        queries,
+        batch_size,
+        pre_execution_queries,
+        post_execution_queries,
+    )
+    .map_err(ConnectorXOutError::classify_timeout)?
+}
+
+#[allow(unreachable_code, unreachable_patterns, unused_variables, unused_mut)]
+fn record_batch_iter(
+    source_conn: &SourceConn,
+    origin_query: Option<String>,
+    queries: &[CXQuery<String>],
+    batch_size: usize,
+    pre_execution_queries: Option<&[String]>,
+    post_execution_queries: Option<&[String]>,
+) -> Result<Box<dyn RecordBatchIterator>, ConnectorXOutError> {
     let destination = ArrowStreamDestination::new_with_batch_size(batch_size);
     let protocol = source_conn.proto.as_str();
-    debug!("Protocol: {}", protocol);
+    debug!(source = ?source_conn.ty, protocol, "Create record batch iterator");
+    let queries = &tag_queries(queries, source_conn.tag.as_deref());
 
     match source_conn.ty {
         #[cfg(feature = "src_postgres")]
         SourceType::Postgres => {
-            let (config, tls) = rewrite_tls_args(&source_conn.conn).unwrap();
+            let (config, tls) = rewrite_tls_args(&source_conn.conn)?;
             match (protocol, tls) {
                 ("csv", Some(tls_conn)) => {
                     let mut source = PostgresSource::<CSVProtocol, MakeTlsConnector>::new(
                         config,
                         tls_conn,
                         queries.len(),
-                    )
-                    .unwrap();
+                    )?;
 
                     source.set_pre_execution_queries(pre_execution_queries);
+                    source.set_post_execution_queries(post_execution_queries);
 
                     let batch_iter =
                         ArrowBatchIter::<
                             _,
                             PostgresArrowStreamTransport<CSVProtocol, MakeTlsConnector>,
-                        >::new(source, destination, origin_query, queries)
-                        .unwrap();
-                    return Box::new(batch_iter);
+                        >::new(source, destination, origin_query, queries)?;
+                    return Ok(Box::new(batch_iter));
                 }
                 ("csv", None) => {
                     let mut source =
-                        PostgresSource::<CSVProtocol, NoTls>::new(config, NoTls, queries.len())
-                            .unwrap();
+                        PostgresSource::<CSVProtocol, NoTls>::new(config, NoTls, queries.len())?;
 
                     source.set_pre_execution_queries(pre_execution_queries);
+                    source.set_post_execution_queries(post_execution_queries);
 
                     let batch_iter = ArrowBatchIter::<
                         _,
                         PostgresArrowStreamTransport<CSVProtocol, NoTls>,
                     >::new(
                         source, destination, origin_query, queries
-                    )
-                    .unwrap();
-                    return Box::new(batch_iter);
+                    )?;
+                    return Ok(Box::new(batch_iter));
                 }
                 ("binary", Some(tls_conn)) => {
                     let mut source = PostgresSource::<PgBinaryProtocol, MakeTlsConnector>::new(
                         config,
                         tls_conn,
                         queries.len(),
-                    )
-                    .unwrap();
+                    )?;
 
                     source.set_pre_execution_queries(pre_execution_queries);
+                    source.set_post_execution_queries(post_execution_queries);
 
                     let batch_iter =
                         ArrowBatchIter::<
                             _,
                             PostgresArrowStreamTransport<PgBinaryProtocol, MakeTlsConnector>,
-                        >::new(source, destination, origin_query, queries)
-                        .unwrap();
-                    return Box::new(batch_iter);
+                        >::new(source, destination, origin_query, queries)?;
+                    return Ok(Box::new(batch_iter));
                 }
                 ("binary", None) => {
                     let mut source = PostgresSource::<PgBinaryProtocol, NoTls>::new(
                         config,
                         NoTls,
                         queries.len(),
-                    )
-                    .unwrap();
+                    )?;
 
                     source.set_pre_execution_queries(pre_execution_queries);
+                    source.set_post_execution_queries(post_execution_queries);
 
                     let batch_iter = ArrowBatchIter::<
                         _,
                         PostgresArrowStreamTransport<PgBinaryProtocol, NoTls>,
                     >::new(
                         source, destination, origin_query, queries
-                    )
-                    .unwrap();
-                    return Box::new(batch_iter);
+                    )?;
+                    return Ok(Box::new(batch_iter));
                 }
                 ("cursor", Some(tls_conn)) => {
                     let mut source = PostgresSource::<CursorProtocol, MakeTlsConnector>::new(
                         config,
                         tls_conn,
                         queries.len(),
-                    )
-                    .unwrap();
+                    )?;
 
                     source.set_pre_execution_queries(pre_execution_queries);
+                    source.set_post_execution_queries(post_execution_queries);
 
                     let batch_iter =
                         ArrowBatchIter::<
                             _,
                             PostgresArrowStreamTransport<CursorProtocol, MakeTlsConnector>,
-                        >::new(source, destination, origin_query, queries)
-                        .unwrap();
-                    return Box::new(batch_iter);
+                        >::new(source, destination, origin_query, queries)?;
+                    return Ok(Box::new(batch_iter));
                 }
                 ("cursor", None) => {
                     let mut source =
-                        PostgresSource::<CursorProtocol, NoTls>::new(config, NoTls, queries.len())
-                            .unwrap();
+                        PostgresSource::<CursorProtocol, NoTls>::new(config, NoTls, queries.len())?;
 
                     source.set_pre_execution_queries(pre_execution_queries);
+                    source.set_post_execution_queries(post_execution_queries);
 
                     let batch_iter = ArrowBatchIter::<
                         _,
                         PostgresArrowStreamTransport<CursorProtocol, NoTls>,
                     >::new(
                         source, destination, origin_query, queries
-                    )
-                    .unwrap();
-                    return Box::new(batch_iter);
+                    )?;
+                    return Ok(Box::new(batch_iter));
                 }
-                _ => unimplemented!("{} protocol not supported", protocol),
+                _ => throw!(anyhow!("{} protocol not supported", protocol)),
             }
         }
         #[cfg(feature = "src_mysql")]
         SourceType::MySQL => match protocol {
             "binary" => {
                 let mut source =
-                    MySQLSource::<MySQLBinaryProtocol>::new(&source_conn.conn[..], queries.len())
-                        .unwrap();
+                    MySQLSource::<MySQLBinaryProtocol>::new(&source_conn.conn[..], queries.len())?;
 
                 source.set_pre_execution_queries(pre_execution_queries);
+                source.set_post_execution_queries(post_execution_queries);
 
                 let batch_iter =
                     ArrowBatchIter::<_, MySQLArrowStreamTransport<MySQLBinaryProtocol>>::new(
@@ -398,80 +1396,90 @@ pub fn new_record_batch_iter(
                         destination,
                         origin_query,
                         queries,
-                    )
-                    .unwrap();
-                return Box::new(batch_iter);
+                    )?;
+                return Ok(Box::new(batch_iter));
             }
             "text" => {
                 let mut source =
-                    MySQLSource::<TextProtocol>::new(&source_conn.conn[..], queries.len()).unwrap();
+                    MySQLSource::<TextProtocol>::new(&source_conn.conn[..], queries.len())?;
 
                 source.set_pre_execution_queries(pre_execution_queries);
+                source.set_post_execution_queries(post_execution_queries);
 
                 let batch_iter = ArrowBatchIter::<_, MySQLArrowStreamTransport<TextProtocol>>::new(
                     source,
                     destination,
                     origin_query,
                     queries,
-                )
-                .unwrap();
-                return Box::new(batch_iter);
+                )?;
+                return Ok(Box::new(batch_iter));
             }
-            _ => unimplemented!("{} protocol not supported", protocol),
+            _ => throw!(anyhow!("{} protocol not supported", protocol)),
         },
         #[cfg(feature = "src_sqlite")]
         SourceType::SQLite => {
             // remove the first "sqlite://" manually since url.path is not correct for windows
             let path = &source_conn.conn.as_str()[9..];
-            let source = SQLiteSource::new(path, queries.len()).unwrap();
+            let mut source = SQLiteSource::new(path, queries.len())?;
+            source.set_pre_execution_queries(pre_execution_queries);
+            source.set_post_execution_queries(post_execution_queries);
+
             let batch_iter = ArrowBatchIter::<_, SQLiteArrowStreamTransport>::new(
                 source,
                 destination,
                 origin_query,
                 queries,
-            )
-            .unwrap();
-            return Box::new(batch_iter);
+            )?;
+            return Ok(Box::new(batch_iter));
         }
         #[cfg(feature = "src_mssql")]
         SourceType::MsSQL => {
             let rt = Arc::new(tokio::runtime::Runtime::new().expect("Failed to create runtime"));
-            let source = MsSQLSource::new(rt, &source_conn.conn[..], queries.len()).unwrap();
+            let mut source = MsSQLSource::new(rt, &source_conn.conn[..], queries.len())?;
+            source.set_pre_execution_queries(pre_execution_queries);
+            source.set_post_execution_queries(post_execution_queries);
+
             let batch_iter = ArrowBatchIter::<_, MsSQLArrowStreamTransport>::new(
                 source,
                 destination,
                 origin_query,
                 queries,
-            )
-            .unwrap();
-            return Box::new(batch_iter);
+            )?;
+            return Ok(Box::new(batch_iter));
         }
         #[cfg(feature = "src_oracle")]
         SourceType::Oracle => {
-            let source = OracleSource::new(&source_conn.conn[..], queries.len()).unwrap();
+            let mut source = OracleSource::new(&source_conn.conn[..], queries.len())?;
+            source.set_pre_execution_queries(pre_execution_queries);
+            source.set_post_execution_queries(post_execution_queries);
+
             let batch_iter = ArrowBatchIter::<_, OracleArrowStreamTransport>::new(
                 source,
                 destination,
                 origin_query,
                 queries,
-            )
-            .unwrap();
-            return Box::new(batch_iter);
+            )?;
+            return Ok(Box::new(batch_iter));
         }
         #[cfg(feature = "src_bigquery")]
         SourceType::BigQuery => {
             let rt = Arc::new(tokio::runtime::Runtime::new().expect("Failed to create runtime"));
-            let source = BigQuerySource::new(rt, &source_conn.conn[..]).unwrap();
+            let mut source = BigQuerySource::new(rt, &source_conn.conn[..])?;
+            source.set_pre_execution_queries(pre_execution_queries);
+            source.set_post_execution_queries(post_execution_queries);
+
             let batch_iter = ArrowBatchIter::<_, BigQueryArrowStreamTransport>::new(
                 source,
                 destination,
                 origin_query,
                 queries,
-            )
-            .unwrap();
-            return Box::new(batch_iter);
+            )?;
+            return Ok(Box::new(batch_iter));
         }
         _ => {}
     }
-    panic!("not supported!");
+    throw!(ConnectorXOutError::SourceNotSupport(format!(
+        "{:?} for the record batch iterator",
+        source_conn.ty
+    )));
 }
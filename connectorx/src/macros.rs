@@ -49,7 +49,27 @@ macro_rules! impl_typesystem {
             )*
         }
     ) => {
-        impl $crate::typesystem::TypeSystem for $TS {}
+        impl $crate::typesystem::TypeSystem for $TS {
+            fn type_name(&self) -> &'static str {
+                match self {
+                    $(
+                        $(
+                            $TS::$V(_) => stringify!($V),
+                        )+
+                    )*
+                }
+            }
+
+            fn is_nullable(&self) -> bool {
+                match self {
+                    $(
+                        $(
+                            $TS::$V(nullable) => *nullable,
+                        )+
+                    )*
+                }
+            }
+        }
 
         $(
             impl_typesystem!(@typeassoc $TS [$($V)+], $NT);
@@ -65,7 +85,7 @@ macro_rules! impl_typesystem {
                     $(
                         $TS::$V(false) => Ok(()),
                     )+
-                    _ => fehler::throw!($crate::errors::ConnectorXError::TypeCheckFailed(format!("{:?}", ts), std::any::type_name::<$NT>()))
+                    _ => fehler::throw!($crate::errors::ConnectorXError::TypeCheckFailed($crate::typesystem::TypeSystem::type_name(&ts).to_string(), std::any::type_name::<$NT>()))
                 }
             }
         }
@@ -76,7 +96,7 @@ macro_rules! impl_typesystem {
                     $(
                         $TS::$V(true) => Ok(()),
                     )+
-                    _ => fehler::throw!($crate::errors::ConnectorXError::TypeCheckFailed(format!("{:?}", ts), std::any::type_name::<$NT>()))
+                    _ => fehler::throw!($crate::errors::ConnectorXError::TypeCheckFailed($crate::typesystem::TypeSystem::type_name(&ts).to_string(), std::any::type_name::<$NT>()))
                 }
             }
         }
@@ -189,7 +209,7 @@ macro_rules! impl_transport {
                 )*
                 #[allow(unreachable_patterns)]
                 _ => fehler::throw!($crate::errors::ConnectorXError::NoConversionRule(
-                    format!("{:?}", ts), format!("{}", std::any::type_name::<Self::TSD>())
+                    $crate::typesystem::TypeSystem::type_name(&ts).to_string(), format!("{}", std::any::type_name::<Self::TSD>())
                 ))
             }
         }
@@ -220,7 +240,7 @@ macro_rules! impl_transport {
                 )*
                 #[allow(unreachable_patterns)]
                 _ => fehler::throw!($crate::errors::ConnectorXError::NoConversionRule(
-                    format!("{:?}", ts1), format!("{:?}", ts1))
+                    $crate::typesystem::TypeSystem::type_name(&ts1).to_string(), $crate::typesystem::TypeSystem::type_name(&ts2).to_string())
                 )
             }
 
@@ -249,7 +269,7 @@ macro_rules! impl_transport {
                 )*
                 #[allow(unreachable_patterns)]
                 _ => fehler::throw!($crate::errors::ConnectorXError::NoConversionRule(
-                    format!("{:?}", ts1), format!("{:?}", ts1))
+                    $crate::typesystem::TypeSystem::type_name(&ts1).to_string(), $crate::typesystem::TypeSystem::type_name(&ts2).to_string())
                 )
             }
 
@@ -1,11 +1,11 @@
 use crate::errors::{ConnectorXOutError, OutResult};
 use crate::source_router::{SourceConn, SourceType};
 #[cfg(feature = "src_bigquery")]
-use crate::sources::bigquery::BigQueryDialect;
+use crate::sources::bigquery::{query_request, BigQueryDialect};
 #[cfg(feature = "src_mssql")]
 use crate::sources::mssql::{mssql_config, FloatN, IntN, MsSQLTypeSystem};
 #[cfg(feature = "src_mysql")]
-use crate::sources::mysql::{MySQLSourceError, MySQLTypeSystem};
+use crate::sources::mysql::{mysql_opts, mysql_params, MySQLTypeSystem};
 #[cfg(feature = "src_oracle")]
 use crate::sources::oracle::{connect_oracle, OracleDialect, OracleSource};
 #[cfg(feature = "src_postgres")]
@@ -14,15 +14,18 @@ use crate::sources::postgres::{rewrite_tls_args, PostgresTypeSystem};
 use crate::sources::trino::TrinoDialect;
 #[cfg(feature = "src_sqlite")]
 use crate::sql::get_partition_range_query_sep;
-use crate::sql::{get_partition_range_query, single_col_partition_query, CXQuery};
+use crate::sql::{
+    get_partition_range_query, redact_query, single_col_partition_query, tag_query, CXParam,
+    CXQuery,
+};
 use anyhow::anyhow;
 use fehler::{throw, throws};
 #[cfg(feature = "src_bigquery")]
 use gcp_bigquery_client;
 #[cfg(feature = "src_mysql")]
-use r2d2_mysql::mysql::{prelude::Queryable, Opts, Pool, Row};
+use r2d2_mysql::mysql::{prelude::Queryable, Pool, Row};
 #[cfg(feature = "src_sqlite")]
-use rusqlite::{types::Type, Connection};
+use rusqlite::{params_from_iter, types::Type, Connection};
 #[cfg(feature = "src_postgres")]
 use rust_decimal::{prelude::ToPrimitive, Decimal};
 #[cfg(feature = "src_postgres")]
@@ -35,12 +38,14 @@ use sqlparser::dialect::MySqlDialect;
 use sqlparser::dialect::PostgreSqlDialect;
 #[cfg(feature = "src_sqlite")]
 use sqlparser::dialect::SQLiteDialect;
+use std::convert::TryFrom;
 #[cfg(feature = "src_mssql")]
 use tiberius::Client;
 #[cfg(any(feature = "src_bigquery", feature = "src_mssql", feature = "src_trino"))]
 use tokio::{net::TcpStream, runtime::Runtime};
 #[cfg(feature = "src_mssql")]
 use tokio_util::compat::TokioAsyncWriteCompatExt;
+use tracing::{debug, info_span};
 use url::Url;
 
 pub struct PartitionQuery {
@@ -49,6 +54,7 @@ pub struct PartitionQuery {
     min: Option<i64>,
     max: Option<i64>,
     num: usize,
+    params: Vec<CXParam>,
 }
 
 impl PartitionQuery {
@@ -59,21 +65,54 @@ impl PartitionQuery {
             min,
             max,
             num,
+            params: vec![],
         }
     }
+
+    /// Bind `params` to the placeholders of the query, they are carried over to the range query
+    /// and every partitioned query.
+    pub fn with_params(mut self, params: Vec<CXParam>) -> Self {
+        self.params = params;
+        self
+    }
+
+    /// The query being partitioned.
+    pub fn query(&self) -> &str {
+        &self.query
+    }
 }
 
 pub fn partition(part: &PartitionQuery, source_conn: &SourceConn) -> OutResult<Vec<CXQuery>> {
+    let _span = info_span!(
+        "partition_query",
+        source = ?source_conn.ty,
+        query = redact_query(&part.query),
+        column = part.column.as_str(),
+        num = part.num,
+    )
+    .entered();
     let mut queries = vec![];
     let num = part.num as i64;
     let (min, max) = match (part.min, part.max) {
-        (None, None) => get_col_range(source_conn, &part.query, &part.column)?,
+        (None, None) => {
+            let _span = info_span!("col_range").entered();
+            match &source_conn.tag {
+                Some(tag) => get_col_range(
+                    source_conn,
+                    &tag_query(&part.query, tag, None),
+                    &part.column,
+                    &part.params,
+                )?,
+                None => get_col_range(source_conn, &part.query, &part.column, &part.params)?,
+            }
+        }
         (Some(min), Some(max)) => (min, max),
         _ => throw!(anyhow!(
             "partition_query range can not be partially specified",
         )),
     };
 
+    debug!(min, max, "Partition range");
     let partition_size = (max - min + 1) / num;
 
     for i in 0..num {
@@ -83,27 +122,32 @@ pub fn partition(part: &PartitionQuery, source_conn: &SourceConn) -> OutResult<V
             false => min + (i + 1) * partition_size,
         };
         let partition_query = get_part_query(source_conn, &part.query, &part.column, lower, upper)?;
-        queries.push(partition_query);
+        queries.push(partition_query.bind(part.params.clone()));
     }
     Ok(queries)
 }
 
-pub fn get_col_range(source_conn: &SourceConn, query: &str, col: &str) -> OutResult<(i64, i64)> {
+pub fn get_col_range(
+    source_conn: &SourceConn,
+    query: &str,
+    col: &str,
+    params: &[CXParam],
+) -> OutResult<(i64, i64)> {
     match source_conn.ty {
         #[cfg(feature = "src_postgres")]
-        SourceType::Postgres => pg_get_partition_range(&source_conn.conn, query, col),
+        SourceType::Postgres => pg_get_partition_range(&source_conn.conn, query, col, params),
         #[cfg(feature = "src_sqlite")]
-        SourceType::SQLite => sqlite_get_partition_range(&source_conn.conn, query, col),
+        SourceType::SQLite => sqlite_get_partition_range(&source_conn.conn, query, col, params),
         #[cfg(feature = "src_mysql")]
-        SourceType::MySQL => mysql_get_partition_range(&source_conn.conn, query, col),
+        SourceType::MySQL => mysql_get_partition_range(&source_conn.conn, query, col, params),
         #[cfg(feature = "src_mssql")]
-        SourceType::MsSQL => mssql_get_partition_range(&source_conn.conn, query, col),
+        SourceType::MsSQL => mssql_get_partition_range(&source_conn.conn, query, col, params),
         #[cfg(feature = "src_oracle")]
-        SourceType::Oracle => oracle_get_partition_range(&source_conn.conn, query, col),
+        SourceType::Oracle => oracle_get_partition_range(&source_conn.conn, query, col, params),
         #[cfg(feature = "src_bigquery")]
-        SourceType::BigQuery => bigquery_get_partition_range(&source_conn.conn, query, col),
+        SourceType::BigQuery => bigquery_get_partition_range(&source_conn.conn, query, col, params),
         #[cfg(feature = "src_trino")]
-        SourceType::Trino => trino_get_partition_range(&source_conn.conn, query, col),
+        SourceType::Trino => trino_get_partition_range(&source_conn.conn, query, col, params),
         _ => unimplemented!("{:?} not implemented!", source_conn.ty),
     }
 }
@@ -152,16 +196,20 @@ pub fn get_part_query(
 
 #[cfg(feature = "src_postgres")]
 #[throws(ConnectorXOutError)]
-fn pg_get_partition_range(conn: &Url, query: &str, col: &str) -> (i64, i64) {
+fn pg_get_partition_range(conn: &Url, query: &str, col: &str, params: &[CXParam]) -> (i64, i64) {
     let (config, tls) = rewrite_tls_args(conn)?;
     let mut client = match tls {
         None => config.connect(postgres::NoTls)?,
         Some(tls_conn) => config.connect(tls_conn)?,
     };
     let range_query = get_partition_range_query(query, col, &PostgreSqlDialect {})?;
-    let row = client.query_one(range_query.as_str(), &[])?;
+    let params: Vec<&(dyn postgres::types::ToSql + Sync)> = params
+        .iter()
+        .map(|p| p as &(dyn postgres::types::ToSql + Sync))
+        .collect();
+    let row = client.query_one(range_query.as_str(), &params)?;
 
-    let col_type = PostgresTypeSystem::from(row.columns()[0].type_());
+    let col_type = PostgresTypeSystem::try_from(row.columns()[0].type_())?;
     let (min_v, max_v) = match col_type {
         PostgresTypeSystem::Int2(_) => {
             let min_v: Option<i16> = row.get(0);
@@ -206,14 +254,19 @@ fn pg_get_partition_range(conn: &Url, query: &str, col: &str) -> (i64, i64) {
 
 #[cfg(feature = "src_sqlite")]
 #[throws(ConnectorXOutError)]
-fn sqlite_get_partition_range(conn: &Url, query: &str, col: &str) -> (i64, i64) {
+fn sqlite_get_partition_range(
+    conn: &Url,
+    query: &str,
+    col: &str,
+    params: &[CXParam],
+) -> (i64, i64) {
     // remove the first "sqlite://" manually since url.path is not correct for windows and for relative path
     let conn = Connection::open(&conn.as_str()[9..])?;
     // SQLite only optimize min max queries when there is only one aggregation
     // https://www.sqlite.org/optoverview.html#minmax
     let (min_query, max_query) = get_partition_range_query_sep(query, col, &SQLiteDialect {})?;
     let mut error = None;
-    let min_v = conn.query_row(min_query.as_str(), [], |row| {
+    let min_v = conn.query_row(min_query.as_str(), params_from_iter(params), |row| {
         // declare type for count query will be None, only need to check the returned value type
         let col_type = row.get_ref(0)?.data_type();
         match col_type {
@@ -233,7 +286,7 @@ fn sqlite_get_partition_range(conn: &Url, query: &str, col: &str) -> (i64, i64)
         None => {}
         Some(e) => throw!(e),
     }
-    let max_v = conn.query_row(max_query.as_str(), [], |row| {
+    let max_v = conn.query_row(max_query.as_str(), params_from_iter(params), |row| {
         let col_type = row.get_ref(0)?.data_type();
         match col_type {
             Type::Integer => row.get(0),
@@ -258,16 +311,18 @@ fn sqlite_get_partition_range(conn: &Url, query: &str, col: &str) -> (i64, i64)
 
 #[cfg(feature = "src_mysql")]
 #[throws(ConnectorXOutError)]
-fn mysql_get_partition_range(conn: &Url, query: &str, col: &str) -> (i64, i64) {
-    let pool = Pool::new(Opts::from_url(conn.as_str()).map_err(MySQLSourceError::MySQLUrlError)?)?;
+fn mysql_get_partition_range(conn: &Url, query: &str, col: &str, params: &[CXParam]) -> (i64, i64) {
+    let pool = Pool::new(mysql_opts(conn)?)?;
     let mut conn = pool.get_conn()?;
     let range_query = get_partition_range_query(query, col, &MySqlDialect {})?;
-    let row: Row = conn
-        .query_first(range_query)?
-        .ok_or_else(|| anyhow!("mysql range: no row returns"))?;
+    let row: Row = match params {
+        [] => conn.query_first(range_query)?,
+        params => conn.exec_first(range_query, mysql_params(params))?,
+    }
+    .ok_or_else(|| anyhow!("mysql range: no row returns"))?;
 
     let col_type =
-        MySQLTypeSystem::from((&row.columns()[0].column_type(), &row.columns()[0].flags()));
+        MySQLTypeSystem::try_from((&row.columns()[0].column_type(), &row.columns()[0].flags()))?;
 
     let (min_v, max_v) = match col_type {
         MySQLTypeSystem::Tiny(_) => {
@@ -386,7 +441,7 @@ fn mysql_get_partition_range(conn: &Url, query: &str, col: &str) -> (i64, i64) {
 
 #[cfg(feature = "src_mssql")]
 #[throws(ConnectorXOutError)]
-fn mssql_get_partition_range(conn: &Url, query: &str, col: &str) -> (i64, i64) {
+fn mssql_get_partition_range(conn: &Url, query: &str, col: &str, params: &[CXParam]) -> (i64, i64) {
     let rt = Runtime::new().expect("Failed to create runtime");
     let config = mssql_config(conn)?;
     let tcp = rt.block_on(TcpStream::connect(config.get_addr()))?;
@@ -395,10 +450,12 @@ fn mssql_get_partition_range(conn: &Url, query: &str, col: &str) -> (i64, i64) {
     let mut client = rt.block_on(Client::connect(config, tcp.compat_write()))?;
 
     let range_query = get_partition_range_query(query, col, &MsSqlDialect {})?;
-    let query_result = rt.block_on(client.query(range_query.as_str(), &[]))?;
+    let params: Vec<&dyn tiberius::ToSql> =
+        params.iter().map(|p| p as &dyn tiberius::ToSql).collect();
+    let query_result = rt.block_on(client.query(range_query.as_str(), &params))?;
     let row = rt.block_on(query_result.into_row())?.unwrap();
 
-    let col_type = MsSQLTypeSystem::from(&row.columns()[0].column_type());
+    let col_type = MsSQLTypeSystem::try_from(&row.columns()[0].column_type())?;
     let (min_v, max_v) = match col_type {
         MsSQLTypeSystem::Tinyint(_) => {
             let min_v: u8 = row.get(0).unwrap_or(0);
@@ -450,11 +507,20 @@ fn mssql_get_partition_range(conn: &Url, query: &str, col: &str) -> (i64, i64) {
 
 #[cfg(feature = "src_oracle")]
 #[throws(ConnectorXOutError)]
-fn oracle_get_partition_range(conn: &Url, query: &str, col: &str) -> (i64, i64) {
+fn oracle_get_partition_range(
+    conn: &Url,
+    query: &str,
+    col: &str,
+    params: &[CXParam],
+) -> (i64, i64) {
     let source = OracleSource::new(conn.as_str(), 1)?;
     let conn = source.get_conn()?;
     let range_query = get_partition_range_query(query, col, &OracleDialect {})?;
-    let row = conn.query_row(range_query.as_str(), &[])?;
+    let params: Vec<&dyn r2d2_oracle::oracle::sql_type::ToSql> = params
+        .iter()
+        .map(|p| p as &dyn r2d2_oracle::oracle::sql_type::ToSql)
+        .collect();
+    let row = conn.query_row(range_query.as_str(), &params)?;
     let min_v: i64 = row.get(0).unwrap_or(0);
     let max_v: i64 = row.get(1).unwrap_or(0);
     (min_v, max_v)
@@ -462,7 +528,12 @@ fn oracle_get_partition_range(conn: &Url, query: &str, col: &str) -> (i64, i64)
 
 #[cfg(feature = "src_bigquery")]
 #[throws(ConnectorXOutError)] // TODO
-fn bigquery_get_partition_range(conn: &Url, query: &str, col: &str) -> (i64, i64) {
+fn bigquery_get_partition_range(
+    conn: &Url,
+    query: &str,
+    col: &str,
+    params: &[CXParam],
+) -> (i64, i64) {
     let rt = Runtime::new().expect("Failed to create runtime");
     let url = Url::parse(conn.as_str())?;
     let sa_key_path = url.path();
@@ -479,10 +550,8 @@ fn bigquery_get_partition_range(conn: &Url, query: &str, col: &str) -> (i64, i64
         .ok_or_else(|| anyhow!("Cannot get project_id as string from auth file"))?;
     let range_query = get_partition_range_query(query, col, &BigQueryDialect {})?;
 
-    let query_result = rt.block_on(client.job().query(
-        project_id,
-        gcp_bigquery_client::model::query_request::QueryRequest::new(range_query.as_str()),
-    ))?;
+    let range_query = CXQuery::Wrapped(range_query).bind(params.to_vec());
+    let query_result = rt.block_on(client.job().query(project_id, query_request(&range_query)))?;
     let mut rs = gcp_bigquery_client::model::query_response::ResultSet::new_from_query_response(
         query_result,
     );
@@ -495,10 +564,12 @@ fn bigquery_get_partition_range(conn: &Url, query: &str, col: &str) -> (i64, i64
 
 #[cfg(feature = "src_trino")]
 #[throws(ConnectorXOutError)]
-fn trino_get_partition_range(conn: &Url, query: &str, col: &str) -> (i64, i64) {
+fn trino_get_partition_range(conn: &Url, query: &str, col: &str, params: &[CXParam]) -> (i64, i64) {
     use prusto::{auth::Auth, ClientBuilder};
 
-    use crate::sources::trino::{TrinoDialect, TrinoPartitionQueryResult};
+    use crate::sources::trino::{
+        inline_query, with_timeouts, TrinoDialect, TrinoPartitionQueryResult,
+    };
 
     let rt = Runtime::new().expect("Failed to create runtime");
 
@@ -518,11 +589,12 @@ fn trino_get_partition_range(conn: &Url, query: &str, col: &str) -> (i64, i64) {
         Some(password) => builder.auth(Auth::Basic(username.to_owned(), Some(password.to_owned()))),
     };
 
-    let client = builder
+    let client = with_timeouts(builder, conn)?
         .build()
         .map_err(|e| anyhow!("Failed to build client: {}", e))?;
 
     let range_query = get_partition_range_query(query, col, &TrinoDialect {})?;
+    let range_query = inline_query(&CXQuery::Wrapped(range_query).bind(params.to_vec()))?;
     let query_result = rt.block_on(client.get_all::<TrinoPartitionQueryResult>(range_query));
 
     let query_result = match query_result {
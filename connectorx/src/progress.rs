@@ -0,0 +1,105 @@
+//! This module provides the progress callback hook used by the dispatchers to
+//! report how far a long running load has gone.
+
+use crate::constants::PROGRESS_REPORT_INTERVAL;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// A snapshot of the progress of a load.
+#[derive(Debug, Clone, Copy)]
+pub struct Progress {
+    /// Number of rows fetched so far over all partitions.
+    pub rows_done: usize,
+    /// Total number of rows, if counted in advance.
+    pub total_rows: Option<usize>,
+    pub partitions_done: usize,
+    pub total_partitions: usize,
+    /// Time elapsed since the partitions started running.
+    pub elapsed: Duration,
+}
+
+impl Progress {
+    /// Rows fetched per second since the partitions started running.
+    pub fn throughput(&self) -> f64 {
+        let secs = self.elapsed.as_secs_f64();
+        if secs > 0.0 {
+            self.rows_done as f64 / secs
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Callback invoked periodically with the progress of a load.
+pub type ProgressCallback = Arc<dyn Fn(Progress) + Send + Sync>;
+
+/// Shared by all the partitions of a load to aggregate their progress and throttle the callback.
+pub struct ProgressTracker {
+    callback: ProgressCallback,
+    interval: Duration,
+    total_rows: Option<usize>,
+    total_partitions: usize,
+    rows_done: AtomicUsize,
+    partitions_done: AtomicUsize,
+    start: Instant,
+    last_report: Mutex<Instant>,
+}
+
+impl ProgressTracker {
+    pub fn new(
+        callback: ProgressCallback,
+        total_rows: Option<usize>,
+        total_partitions: usize,
+    ) -> Self {
+        let start = Instant::now();
+        Self {
+            callback,
+            interval: PROGRESS_REPORT_INTERVAL,
+            total_rows,
+            total_partitions,
+            rows_done: AtomicUsize::new(0),
+            partitions_done: AtomicUsize::new(0),
+            start,
+            last_report: Mutex::new(start),
+        }
+    }
+
+    /// Record `n` more fetched rows. The callback is invoked at most once per interval.
+    pub fn add_rows(&self, n: usize) {
+        self.rows_done.fetch_add(n, Ordering::Relaxed);
+        let due = match self.last_report.try_lock() {
+            Ok(mut last) if last.elapsed() >= self.interval => {
+                *last = Instant::now();
+                true
+            }
+            _ => false,
+        };
+        if due {
+            self.report();
+        }
+    }
+
+    /// Record a finished partition. The callback is always invoked.
+    pub fn finish_partition(&self) {
+        self.partitions_done.fetch_add(1, Ordering::Relaxed);
+        if let Ok(mut last) = self.last_report.lock() {
+            *last = Instant::now();
+        }
+        self.report();
+    }
+
+    pub fn progress(&self) -> Progress {
+        Progress {
+            rows_done: self.rows_done.load(Ordering::Relaxed),
+            total_rows: self.total_rows,
+            partitions_done: self.partitions_done.load(Ordering::Relaxed),
+            total_partitions: self.total_partitions,
+            elapsed: self.start.elapsed(),
+        }
+    }
+
+    fn report(&self) {
+        (self.callback)(self.progress());
+    }
+}
@@ -0,0 +1,62 @@
+//! This module provides [`LoadReport`], a structured summary of a single load
+//! produced by [`crate::dispatcher::Dispatcher::run_with_report`].
+
+use std::time::Duration;
+
+/// Statistics of one partition of a load.
+#[derive(Debug, Clone, Default)]
+pub struct PartitionReport {
+    /// Index of the partition, in the order of the input queries.
+    pub index: usize,
+    /// The query issued for this partition.
+    pub query: String,
+    /// Number of rows fetched from the source.
+    pub rows: usize,
+    /// Number of bytes produced in the destination, 0 if the destination does not track it.
+    pub bytes: usize,
+    /// Time spent fetching rows from the source.
+    pub fetch_time: Duration,
+    /// Time spent converting and writing values into the destination.
+    pub convert_time: Duration,
+}
+
+/// Statistics of a whole load.
+#[derive(Debug, Clone, Default)]
+pub struct LoadReport {
+    /// The protocol used to talk to the source, if known.
+    pub protocol: Option<String>,
+    /// Time spent fetching the schema of the result.
+    pub metadata_time: Duration,
+    /// Time spent counting rows in advance. Zero if the destination does not need counts.
+    pub count_time: Duration,
+    /// Number of rows counted in advance, if the destination needs counts.
+    pub expected_rows: Option<usize>,
+    /// Wall time of the whole load.
+    pub total_time: Duration,
+    pub partitions: Vec<PartitionReport>,
+}
+
+impl LoadReport {
+    pub fn total_rows(&self) -> usize {
+        self.partitions.iter().map(|p| p.rows).sum()
+    }
+
+    pub fn total_bytes(&self) -> usize {
+        self.partitions.iter().map(|p| p.bytes).sum()
+    }
+
+    /// Fetch time summed over all partitions.
+    pub fn fetch_time(&self) -> Duration {
+        self.partitions.iter().map(|p| p.fetch_time).sum()
+    }
+
+    /// Convert time summed over all partitions.
+    pub fn convert_time(&self) -> Duration {
+        self.partitions.iter().map(|p| p.convert_time).sum()
+    }
+
+    /// The final queries issued, one per partition.
+    pub fn queries(&self) -> Vec<&str> {
+        self.partitions.iter().map(|p| p.query.as_str()).collect()
+    }
+}
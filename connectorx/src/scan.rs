@@ -0,0 +1,444 @@
+//! A structured alternative to raw SQL for reading a single table: the columns to select, a
+//! predicate, a limit and a random sample. [`TableScan`] is rendered into the dialect of the
+//! source through the sqlparser AST, its values are bound as parameters, and it can be
+//! partitioned on a column like any other query.
+
+use crate::errors::OutResult;
+use crate::partition::{partition, PartitionQuery};
+use crate::source_router::{SourceConn, SourceType};
+use crate::sql::{CXParam, CXQuery};
+use sqlparser::ast::{
+    BinaryOperator, Expr, Fetch, Function, FunctionArg, FunctionArgExpr, Ident, ObjectName,
+    OrderByExpr, Query, Select, SelectItem, SetExpr, TableFactor, TableWithJoins, Top,
+    UnaryOperator, Value, WildcardAdditionalOptions,
+};
+use tracing::debug;
+
+/// The comparison operators of a [`ScanFilter::Compare`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompareOp {
+    Eq,
+    NotEq,
+    Lt,
+    LtEq,
+    Gt,
+    GtEq,
+}
+
+/// A predicate on the columns of the scanned table.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScanFilter {
+    Compare(String, CompareOp, CXParam),
+    In(String, Vec<CXParam>),
+    IsNull(String),
+    IsNotNull(String),
+    And(Box<ScanFilter>, Box<ScanFilter>),
+    Or(Box<ScanFilter>, Box<ScanFilter>),
+    Not(Box<ScanFilter>),
+}
+
+impl ScanFilter {
+    pub fn compare<P: Into<CXParam>>(column: &str, op: CompareOp, value: P) -> Self {
+        ScanFilter::Compare(column.into(), op, value.into())
+    }
+
+    pub fn and(self, other: ScanFilter) -> Self {
+        ScanFilter::And(Box::new(self), Box::new(other))
+    }
+
+    pub fn or(self, other: ScanFilter) -> Self {
+        ScanFilter::Or(Box::new(self), Box::new(other))
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn not(self) -> Self {
+        ScanFilter::Not(Box::new(self))
+    }
+}
+
+/// How many rows a [`Sample`] draws.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SampleSize {
+    /// Keep every row with this probability, between 0 and 1.
+    Fraction(f64),
+    /// Keep this many rows.
+    Rows(usize),
+}
+
+/// A random sample of the rows of the scanned table. Fractions are sampled natively where the
+/// source can: `TABLESAMPLE BERNOULLI` for Postgres and Trino, `TABLESAMPLE SYSTEM` for MsSQL and
+/// BigQuery, and `SAMPLE` for Oracle, while MySQL and SQLite keep the rows for which a random
+/// number is below the fraction. Row counts order the rows randomly and take the first ones.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sample {
+    size: SampleSize,
+    seed: Option<u32>,
+}
+
+impl Sample {
+    pub fn fraction(fraction: f64) -> Self {
+        Self {
+            size: SampleSize::Fraction(fraction),
+            seed: None,
+        }
+    }
+
+    pub fn rows(rows: usize) -> Self {
+        Self {
+            size: SampleSize::Rows(rows),
+            seed: None,
+        }
+    }
+
+    /// Draw the same sample every time, as long as the table does not change. The seed is taken
+    /// by Postgres, MsSQL and Oracle for fractions, and by MySQL, and ignored by the others.
+    pub fn with_seed(mut self, seed: u32) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    fn clause(&self, ty: &SourceType) -> SampleClause {
+        let repeatable = |keyword: &str| match self.seed {
+            Some(seed) => format!(" {} ({})", keyword, seed),
+            None => String::new(),
+        };
+
+        match (self.size, ty) {
+            (SampleSize::Fraction(f), SourceType::Postgres) => SampleClause::TableSample(format!(
+                "TABLESAMPLE BERNOULLI ({}){}",
+                percent(f),
+                repeatable("REPEATABLE")
+            )),
+            (SampleSize::Fraction(f), SourceType::MsSQL) => SampleClause::TableSample(format!(
+                "TABLESAMPLE SYSTEM ({} PERCENT){}",
+                percent(f),
+                repeatable("REPEATABLE")
+            )),
+            (SampleSize::Fraction(f), SourceType::BigQuery) => {
+                SampleClause::TableSample(format!("TABLESAMPLE SYSTEM ({} PERCENT)", percent(f)))
+            }
+            (SampleSize::Fraction(f), SourceType::Trino) => {
+                SampleClause::TableSample(format!("TABLESAMPLE BERNOULLI ({})", percent(f)))
+            }
+            (SampleSize::Fraction(f), SourceType::Oracle) => {
+                SampleClause::TableSample(format!("SAMPLE ({}){}", percent(f), repeatable("SEED")))
+            }
+            (SampleSize::Fraction(f), SourceType::MySQL) => {
+                SampleClause::Predicate(Expr::BinaryOp {
+                    left: Box::new(random(ty, self.seed)),
+                    op: BinaryOperator::Lt,
+                    right: Box::new(number(f)),
+                })
+            }
+            // RANDOM() of SQLite is a signed 64-bit integer
+            (SampleSize::Fraction(f), _) => SampleClause::Predicate(Expr::BinaryOp {
+                left: Box::new(Expr::BinaryOp {
+                    left: Box::new(function("ABS", vec![random(ty, self.seed)])),
+                    op: BinaryOperator::Modulo,
+                    right: Box::new(number(1_000_000)),
+                }),
+                op: BinaryOperator::Lt,
+                right: Box::new(number((f * 1_000_000.) as i64)),
+            }),
+            (SampleSize::Rows(n), _) => SampleClause::Shuffle(random(ty, self.seed), n),
+        }
+    }
+}
+
+enum SampleClause {
+    /// A clause following the table name.
+    TableSample(String),
+    /// A predicate added to the filter.
+    Predicate(Expr),
+    /// An expression to order the rows by, and the number of rows to keep.
+    Shuffle(Expr, usize),
+}
+
+/// Read the rows of a table, optionally restricted to some columns, a filter and a limit.
+#[derive(Debug, Clone)]
+pub struct TableScan {
+    schema: Option<String>,
+    table: String,
+    columns: Option<Vec<String>>,
+    filter: Option<ScanFilter>,
+    limit: Option<usize>,
+    sample: Option<Sample>,
+    partition: Option<(String, usize)>,
+}
+
+impl TableScan {
+    pub fn new(table: &str) -> Self {
+        Self {
+            schema: None,
+            table: table.into(),
+            columns: None,
+            filter: None,
+            limit: None,
+            sample: None,
+            partition: None,
+        }
+    }
+
+    /// The schema (or dataset for BigQuery) the table belongs to.
+    pub fn with_schema(mut self, schema: &str) -> Self {
+        self.schema = Some(schema.into());
+        self
+    }
+
+    /// Select only `columns`, in this order, instead of every column of the table.
+    pub fn with_columns<S: AsRef<str>>(mut self, columns: &[S]) -> Self {
+        self.columns = Some(columns.iter().map(|c| c.as_ref().to_string()).collect());
+        self
+    }
+
+    pub fn with_filter(mut self, filter: ScanFilter) -> Self {
+        self.filter = Some(filter);
+        self
+    }
+
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Read a random sample of the rows that pass the filter. A sample composes with partitioning
+    /// since the partitions only restrict the sampled query. Fractions keep every row on its own,
+    /// so the partitions add up to a sample of the table, but a row count sample is drawn again by
+    /// every partition, so it is only consistent across them if the source honors the seed.
+    pub fn with_sample(mut self, sample: Sample) -> Self {
+        self.sample = Some(sample);
+        self
+    }
+
+    /// Split the scan into `num` queries on ranges of the integer `column`, see [`partition`].
+    pub fn with_partition(mut self, column: &str, num: usize) -> Self {
+        self.partition = Some((column.into(), num));
+        self
+    }
+
+    /// Render the scan as a single query in the dialect of `ty`. The values of the filter are
+    /// bound to placeholders, so sources that cannot bind parameters (Trino, Postgres unless
+    /// loaded with the cursor protocol, MySQL with the text protocol) reject scans with a filter
+    /// on values.
+    pub fn query(&self, ty: &SourceType) -> CXQuery<String> {
+        let mut params = vec![];
+
+        let mut name = vec![];
+        if let Some(schema) = &self.schema {
+            name.push(ident(ty, schema));
+        }
+        name.push(ident(ty, &self.table));
+
+        let projection = match &self.columns {
+            Some(columns) => columns
+                .iter()
+                .map(|c| SelectItem::UnnamedExpr(Expr::Identifier(ident(ty, c))))
+                .collect(),
+            None => vec![SelectItem::Wildcard(WildcardAdditionalOptions::default())],
+        };
+        let mut relation = ObjectName(name);
+        let mut selection = self
+            .filter
+            .as_ref()
+            .map(|filter| filter_expr(ty, filter, &mut params));
+        let mut order_by = vec![];
+        let mut limit = self.limit;
+
+        match self.sample.map(|sample| sample.clause(ty)) {
+            // sqlparser has no node for the sampling clauses, so they are appended to the name
+            Some(SampleClause::TableSample(clause)) => {
+                relation = ObjectName(vec![Ident::new(format!("{} {}", relation, clause))]);
+            }
+            Some(SampleClause::Predicate(predicate)) => {
+                selection = Some(match selection {
+                    Some(filter) => Expr::BinaryOp {
+                        left: Box::new(Expr::Nested(Box::new(filter))),
+                        op: BinaryOperator::And,
+                        right: Box::new(Expr::Nested(Box::new(predicate))),
+                    },
+                    None => predicate,
+                });
+            }
+            Some(SampleClause::Shuffle(expr, n)) => {
+                order_by.push(OrderByExpr {
+                    expr,
+                    asc: None,
+                    nulls_first: None,
+                });
+                limit = Some(limit.map_or(n, |limit| limit.min(n)));
+            }
+            None => {}
+        }
+        let limit = limit.map(number);
+
+        // MsSQL has no LIMIT and Oracle only knows the standard FETCH FIRST
+        let (top, fetch, limit) = match ty {
+            SourceType::MsSQL => (
+                limit.map(|quantity| Top {
+                    with_ties: false,
+                    percent: false,
+                    quantity: Some(quantity),
+                }),
+                None,
+                None,
+            ),
+            SourceType::Oracle => (
+                None,
+                limit.map(|quantity| Fetch {
+                    with_ties: false,
+                    percent: false,
+                    quantity: Some(quantity),
+                }),
+                None,
+            ),
+            _ => (None, None, limit),
+        };
+
+        let query = Query {
+            with: None,
+            body: Box::new(SetExpr::Select(Box::new(Select {
+                distinct: None,
+                top,
+                projection,
+                into: None,
+                from: vec![TableWithJoins {
+                    relation: TableFactor::Table {
+                        name: relation,
+                        alias: None,
+                        args: None,
+                        with_hints: vec![],
+                        version: None,
+                    },
+                    joins: vec![],
+                }],
+                lateral_views: vec![],
+                selection,
+                group_by: vec![],
+                cluster_by: vec![],
+                distribute_by: vec![],
+                sort_by: vec![],
+                having: None,
+                named_window: vec![],
+                qualify: None,
+            }))),
+            order_by,
+            limit,
+            offset: None,
+            fetch,
+            locks: vec![],
+        };
+
+        debug!("Rendered table scan: {}", query);
+        CXQuery::naked(query.to_string()).bind(params)
+    }
+}
+
+/// Render `scan` for `source_conn` into the queries to load, one per partition if the scan is
+/// partitioned. The queries can be passed to [`get_arrow`](crate::get_arrow::get_arrow) as is.
+pub fn scan_queries(scan: &TableScan, source_conn: &SourceConn) -> OutResult<Vec<CXQuery>> {
+    let query = scan.query(&source_conn.ty);
+    match &scan.partition {
+        Some((column, num)) => {
+            let part = PartitionQuery::new(query.as_str(), column, None, None, *num)
+                .with_params(query.params().to_vec());
+            partition(&part, source_conn)
+        }
+        None => Ok(vec![query]),
+    }
+}
+
+// rounded to 6 decimals, so that e.g. 0.1 is not rendered as 10.000000000000002
+fn percent(fraction: f64) -> f64 {
+    (fraction * 1e8).round() / 1e6
+}
+
+fn number<N: ToString>(n: N) -> Expr {
+    Expr::Value(Value::Number(n.to_string(), false))
+}
+
+fn function(name: &str, args: Vec<Expr>) -> Expr {
+    Expr::Function(Function {
+        name: ObjectName(vec![Ident::new(name)]),
+        args: args
+            .into_iter()
+            .map(|arg| FunctionArg::Unnamed(FunctionArgExpr::Expr(arg)))
+            .collect(),
+        over: None,
+        distinct: false,
+        special: false,
+        order_by: vec![],
+    })
+}
+
+// a random value per row to order the rows by, MySQL and SQLite also compare it to a fraction
+fn random(ty: &SourceType, seed: Option<u32>) -> Expr {
+    match ty {
+        SourceType::MySQL => function("RAND", seed.into_iter().map(number).collect()),
+        SourceType::BigQuery => function("RAND", vec![]),
+        SourceType::MsSQL => function("NEWID", vec![]),
+        SourceType::Oracle => {
+            Expr::CompoundIdentifier(vec![Ident::new("DBMS_RANDOM"), Ident::new("VALUE")])
+        }
+        _ => function("RANDOM", vec![]),
+    }
+}
+
+fn ident(ty: &SourceType, value: &str) -> Ident {
+    let quote = match ty {
+        SourceType::MySQL | SourceType::BigQuery => '`',
+        _ => '"',
+    };
+    Ident::with_quote(quote, value)
+}
+
+// the placeholders are numbered in the order they appear in the rendered query
+fn placeholder(ty: &SourceType, params: &mut Vec<CXParam>, value: &CXParam) -> Expr {
+    params.push(value.clone());
+    let n = params.len();
+    let name = match ty {
+        SourceType::Postgres => format!("${}", n),
+        SourceType::MsSQL | SourceType::BigQuery => format!("@P{}", n),
+        SourceType::Oracle => format!(":{}", n),
+        _ => "?".to_string(),
+    };
+    Expr::Value(Value::Placeholder(name))
+}
+
+fn filter_expr(ty: &SourceType, filter: &ScanFilter, params: &mut Vec<CXParam>) -> Expr {
+    let column = |c: &str| Box::new(Expr::Identifier(ident(ty, c)));
+    match filter {
+        ScanFilter::Compare(c, op, value) => Expr::BinaryOp {
+            left: column(c),
+            op: match op {
+                CompareOp::Eq => BinaryOperator::Eq,
+                CompareOp::NotEq => BinaryOperator::NotEq,
+                CompareOp::Lt => BinaryOperator::Lt,
+                CompareOp::LtEq => BinaryOperator::LtEq,
+                CompareOp::Gt => BinaryOperator::Gt,
+                CompareOp::GtEq => BinaryOperator::GtEq,
+            },
+            right: Box::new(placeholder(ty, params, value)),
+        },
+        ScanFilter::In(c, values) => Expr::InList {
+            expr: column(c),
+            list: values.iter().map(|v| placeholder(ty, params, v)).collect(),
+            negated: false,
+        },
+        ScanFilter::IsNull(c) => Expr::IsNull(column(c)),
+        ScanFilter::IsNotNull(c) => Expr::IsNotNull(column(c)),
+        ScanFilter::And(l, r) => Expr::BinaryOp {
+            left: Box::new(Expr::Nested(Box::new(filter_expr(ty, l, params)))),
+            op: BinaryOperator::And,
+            right: Box::new(Expr::Nested(Box::new(filter_expr(ty, r, params)))),
+        },
+        ScanFilter::Or(l, r) => Expr::BinaryOp {
+            left: Box::new(Expr::Nested(Box::new(filter_expr(ty, l, params)))),
+            op: BinaryOperator::Or,
+            right: Box::new(Expr::Nested(Box::new(filter_expr(ty, r, params)))),
+        },
+        ScanFilter::Not(f) => Expr::UnaryOp {
+            op: UnaryOperator::Not,
+            expr: Box::new(Expr::Nested(Box::new(filter_expr(ty, f, params)))),
+        },
+    }
+}
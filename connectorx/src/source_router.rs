@@ -1,4 +1,4 @@
-use crate::constants::CONNECTORX_PROTOCOL;
+use crate::constants::{CONNECTORX_PROTOCOL, CONNECTORX_TAG};
 use crate::errors::{ConnectorXError, Result};
 use anyhow::anyhow;
 use fehler::throws;
@@ -23,6 +23,8 @@ pub struct SourceConn {
     pub ty: SourceType,
     pub conn: Url,
     pub proto: String,
+    /// Label tagging every query issued, set by the `cxtag` parameter of the connection string.
+    pub tag: Option<String>,
 }
 
 impl TryFrom<&str> for SourceConn {
@@ -37,10 +39,16 @@ impl TryFrom<&str> for SourceConn {
             None => "binary".to_string(),
         };
 
-        // create url by removing connectorx protocol
+        // parse connectorx query tag
+        let tag = old_url
+            .query_pairs()
+            .find(|p| p.0 == CONNECTORX_TAG)
+            .map(|(_, tag)| tag.to_string());
+
+        // create url by removing connectorx protocol and query tag
         let stripped_query: Vec<(_, _)> = old_url
             .query_pairs()
-            .filter(|p| &*p.0 != CONNECTORX_PROTOCOL)
+            .filter(|p| &*p.0 != CONNECTORX_PROTOCOL && &*p.0 != CONNECTORX_TAG)
             .collect();
         let mut url = old_url.clone();
         url.set_query(None);
@@ -51,27 +59,52 @@ impl TryFrom<&str> for SourceConn {
 
         // users from sqlalchemy may set engine in connection url (e.g. mssql+pymssql://...)
         // only for compatablility, we don't use the same engine
-        match url.scheme().split('+').collect::<Vec<&str>>()[0] {
-            "postgres" | "postgresql" => Ok(SourceConn::new(SourceType::Postgres, url, proto)),
-            "sqlite" => Ok(SourceConn::new(SourceType::SQLite, url, proto)),
-            "mysql" => Ok(SourceConn::new(SourceType::MySQL, url, proto)),
-            "mssql" => Ok(SourceConn::new(SourceType::MsSQL, url, proto)),
-            "oracle" => Ok(SourceConn::new(SourceType::Oracle, url, proto)),
-            "bigquery" => Ok(SourceConn::new(SourceType::BigQuery, url, proto)),
-            "duckdb" => Ok(SourceConn::new(SourceType::DuckDB, url, proto)),
-            "trino" => Ok(SourceConn::new(SourceType::Trino, url, proto)),
-            _ => Ok(SourceConn::new(SourceType::Unknown, url, proto)),
+        let mut source_conn = match url.scheme().split('+').collect::<Vec<&str>>()[0] {
+            "postgres" | "postgresql" => SourceConn::new(SourceType::Postgres, url, proto),
+            "sqlite" => SourceConn::new(SourceType::SQLite, url, proto),
+            "mysql" => SourceConn::new(SourceType::MySQL, url, proto),
+            "mssql" => SourceConn::new(SourceType::MsSQL, url, proto),
+            "oracle" => SourceConn::new(SourceType::Oracle, url, proto),
+            "bigquery" => SourceConn::new(SourceType::BigQuery, url, proto),
+            "duckdb" => SourceConn::new(SourceType::DuckDB, url, proto),
+            "trino" => SourceConn::new(SourceType::Trino, url, proto),
+            _ => SourceConn::new(SourceType::Unknown, url, proto),
+        };
+        if let Some(tag) = tag {
+            source_conn.set_tag(&tag);
         }
+        Ok(source_conn)
     }
 }
 
 impl SourceConn {
     pub fn new(ty: SourceType, conn: Url, proto: String) -> Self {
-        Self { ty, conn, proto }
+        Self {
+            ty,
+            conn,
+            proto,
+            tag: None,
+        }
     }
     pub fn set_protocol(&mut self, protocol: &str) {
         self.proto = protocol.to_string();
     }
+
+    /// Tag every query issued with `tag`. Besides the comment prefixed to the queries, the tag is
+    /// used as the application name of the connections on Postgres and MsSQL, unless one is set.
+    pub fn set_tag(&mut self, tag: &str) {
+        let app_param = match self.ty {
+            SourceType::Postgres => Some("application_name"),
+            SourceType::MsSQL => Some("appname"),
+            _ => None,
+        };
+        if let Some(app_param) = app_param {
+            if !self.conn.query_pairs().any(|p| p.0 == app_param) {
+                self.conn.query_pairs_mut().append_pair(app_param, tag);
+            }
+        }
+        self.tag = Some(tag.to_string());
+    }
 }
 
 #[throws(ConnectorXError)]
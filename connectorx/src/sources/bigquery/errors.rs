@@ -1,9 +1,13 @@
 use gcp_bigquery_client::error::BQError;
+use std::time::Duration;
 use thiserror::Error;
 use url;
 
 #[derive(Error, Debug)]
 pub enum BigQuerySourceError {
+    #[error("Statement timed out after {0:?}")]
+    Timeout(Duration),
+
     #[error(transparent)]
     ConnectorXError(#[from] crate::errors::ConnectorXError),
 
@@ -29,3 +33,10 @@ pub enum BigQuerySourceError {
     #[error(transparent)]
     Other(#[from] anyhow::Error),
 }
+
+impl BigQuerySourceError {
+    /// Whether the error is raised because the statement or connect timeout elapsed.
+    pub fn is_timeout(&self) -> bool {
+        matches!(self, BigQuerySourceError::Timeout(_))
+    }
+}
@@ -7,23 +7,35 @@ pub use self::errors::BigQuerySourceError;
 use crate::{
     data_order::DataOrder,
     errors::ConnectorXError,
-    sources::{PartitionParser, Produce, Source, SourcePartition},
-    sql::{count_query, limit1_query, CXQuery},
+    sources::{
+        timeout::Timeouts, ColumnDescription, PartitionParser, Produce, Source, SourcePartition,
+    },
+    sql::{count_query, limit1_query, CXParam, CXQuery},
 };
 use anyhow::anyhow;
+use base64::{engine::general_purpose::STANDARD, Engine};
 use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
 use fehler::{throw, throws};
 use gcp_bigquery_client::{
     model::{
+        field_type::FieldType,
         get_query_results_parameters::GetQueryResultsParameters,
-        get_query_results_response::GetQueryResultsResponse, query_request::QueryRequest,
-        query_response::ResultSet,
+        get_query_results_response::GetQueryResultsResponse,
+        query_parameter::QueryParameter,
+        query_parameter_type::QueryParameterType,
+        query_parameter_value::QueryParameterValue,
+        query_request::QueryRequest,
+        query_response::{QueryResponse, ResultSet},
+        table_field_schema::TableFieldSchema,
     },
     Client,
 };
 use sqlparser::dialect::Dialect;
+use std::convert::TryFrom;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::runtime::Runtime;
+use tracing::{debug, warn};
 pub use typesystem::BigQueryTypeSystem;
 use url::Url;
 
@@ -45,6 +57,146 @@ impl Dialect for BigQueryDialect {
     }
 }
 
+// bigquery only takes named parameters from the api, they are named `@P1`, `@P2`, ... after
+// their positions like in mssql
+pub(crate) fn query_request(query: &CXQuery<String>) -> QueryRequest {
+    let mut request = QueryRequest::new(query.as_str());
+    if !query.params().is_empty() {
+        request.parameter_mode = Some("NAMED".to_string());
+        request.query_parameters = Some(
+            query
+                .params()
+                .iter()
+                .enumerate()
+                .map(|(i, param)| query_parameter(format!("P{}", i + 1), param))
+                .collect(),
+        );
+    }
+    request
+}
+
+fn query_parameter(name: String, param: &CXParam) -> QueryParameter {
+    let (ty, value) = match param {
+        CXParam::Null => ("STRING", None),
+        CXParam::Bool(v) => ("BOOL", Some(v.to_string())),
+        CXParam::I64(v) => ("INT64", Some(v.to_string())),
+        CXParam::F64(v) => ("FLOAT64", Some(v.to_string())),
+        CXParam::Str(v) => ("STRING", Some(v.clone())),
+        CXParam::Bytes(v) => ("BYTES", Some(STANDARD.encode(v))),
+        CXParam::Date(v) => ("DATE", Some(v.format("%Y-%m-%d").to_string())),
+        CXParam::DateTime(v) => (
+            "DATETIME",
+            Some(v.format("%Y-%m-%d %H:%M:%S%.f").to_string()),
+        ),
+    };
+    QueryParameter {
+        name: Some(name),
+        parameter_type: Some(QueryParameterType {
+            array_type: None,
+            struct_types: None,
+            r#type: ty.to_string(),
+        }),
+        parameter_value: Some(QueryParameterValue {
+            array_values: None,
+            struct_values: None,
+            value,
+        }),
+    }
+}
+
+// run `query` as a job through the jobs.query api, which waits `timeoutMs` for the job to
+// complete; an incomplete job means the statement timeout elapsed, in which case the job is
+// cancelled since it would keep running on the server otherwise
+#[throws(BigQuerySourceError)]
+fn run_query(
+    rt: &Runtime,
+    client: &Client,
+    project_id: &str,
+    query: &CXQuery<String>,
+    timeout: Option<Duration>,
+) -> QueryResponse {
+    let mut request = query_request(query);
+    request.timeout_ms = timeout.map(|t| t.as_millis() as i32);
+    let response = rt.block_on(client.job().query(project_id, request))?;
+    match timeout {
+        Some(timeout) if response.job_complete == Some(false) => {
+            if let Some(job) = &response.job_reference {
+                if let Some(job_id) = &job.job_id {
+                    let location = job.location.as_deref();
+                    if let Err(e) =
+                        rt.block_on(client.job().cancel_job(project_id, job_id, location))
+                    {
+                        warn!(job_id = %job_id, error = %e, "cannot cancel the timed out job");
+                    }
+                }
+            }
+            throw!(BigQuerySourceError::Timeout(timeout))
+        }
+        _ => response,
+    }
+}
+
+// bigquery jobs do not share a session, so the pre-execution queries are prepended to every
+// partition query as a multi-statement script, whose result is the one of its last statement
+fn with_pre_execution_queries(
+    query: &CXQuery<String>,
+    pre_execution_queries: &[String],
+) -> CXQuery<String> {
+    if pre_execution_queries.is_empty() {
+        return query.clone();
+    }
+    query.map(|q| format!("{};\n{}", pre_execution_queries.join(";\n"), q))
+}
+
+// the names, types and declared types of the columns from the fields of the result schema
+#[allow(clippy::type_complexity)]
+#[throws(BigQuerySourceError)]
+fn describe_fields(
+    fields: &[TableFieldSchema],
+) -> (Vec<String>, Vec<BigQueryTypeSystem>, Vec<ColumnDescription>) {
+    let names = fields.iter().map(|field| field.name.clone()).collect();
+    let types = fields
+        .iter()
+        .map(|field| BigQueryTypeSystem::try_from(&field.r#type))
+        .collect::<Result<_, _>>()?;
+    (names, types, fields.iter().map(describe_field).collect())
+}
+
+// the declared type of a field, e.g. `ARRAY<INT64>` for a repeated integer
+fn describe_field(field: &TableFieldSchema) -> ColumnDescription {
+    let source_type = match field.r#type {
+        FieldType::String => "STRING",
+        FieldType::Bytes => "BYTES",
+        FieldType::Integer => "INTEGER",
+        FieldType::Int64 => "INT64",
+        FieldType::Float => "FLOAT",
+        FieldType::Float64 => "FLOAT64",
+        FieldType::Numeric => "NUMERIC",
+        FieldType::Bignumeric => "BIGNUMERIC",
+        FieldType::Boolean => "BOOLEAN",
+        FieldType::Bool => "BOOL",
+        FieldType::Timestamp => "TIMESTAMP",
+        FieldType::Date => "DATE",
+        FieldType::Time => "TIME",
+        FieldType::Datetime => "DATETIME",
+        FieldType::Record => "RECORD",
+        FieldType::Struct => "STRUCT",
+        FieldType::Geography => "GEOGRAPHY",
+        FieldType::Json => "JSON",
+        FieldType::Interval => "INTERVAL",
+    };
+    let (source_type, nullable) = match field.mode.as_deref() {
+        Some("REQUIRED") => (source_type.to_string(), false),
+        Some("REPEATED") => (format!("ARRAY<{}>", source_type), false),
+        _ => (source_type.to_string(), true),
+    };
+    ColumnDescription {
+        source_type,
+        nullable: Some(nullable),
+        ..Default::default()
+    }
+}
+
 pub struct BigQuerySource {
     rt: Arc<Runtime>,
     client: Arc<Client>,
@@ -53,6 +205,10 @@ pub struct BigQuerySource {
     queries: Vec<CXQuery<String>>,
     names: Vec<String>,
     schema: Vec<BigQueryTypeSystem>,
+    columns: Vec<ColumnDescription>,
+    statement_timeout: Option<Duration>,
+    pre_execution_queries: Option<Vec<String>>,
+    post_execution_queries: Option<Vec<String>>,
 }
 
 impl BigQuerySource {
@@ -79,6 +235,10 @@ impl BigQuerySource {
             queries: vec![],
             names: vec![],
             schema: vec![],
+            columns: vec![],
+            statement_timeout: Timeouts::from_url(&url)?.statement,
+            pre_execution_queries: None,
+            post_execution_queries: None,
         }
     }
 }
@@ -108,33 +268,75 @@ where
         self.origin_query = query;
     }
 
+    fn set_pre_execution_queries(&mut self, pre_execution_queries: Option<&[String]>) {
+        self.pre_execution_queries = pre_execution_queries.map(|s| s.to_vec());
+    }
+
+    fn set_post_execution_queries(&mut self, post_execution_queries: Option<&[String]>) {
+        self.post_execution_queries = post_execution_queries.map(|s| s.to_vec());
+    }
+
+    fn fork(&self) -> Self {
+        Self {
+            rt: self.rt.clone(),
+            client: self.client.clone(),
+            project_id: self.project_id.clone(),
+            origin_query: None,
+            queries: vec![],
+            names: vec![],
+            schema: vec![],
+            columns: vec![],
+            statement_timeout: self.statement_timeout,
+            pre_execution_queries: self.pre_execution_queries.clone(),
+            post_execution_queries: self.post_execution_queries.clone(),
+        }
+    }
+
     #[throws(BigQuerySourceError)]
     fn fetch_metadata(&mut self) {
         assert!(!self.queries.is_empty());
-        let job = self.client.job();
+
+        // a dry run validates and plans the query without running it
+        let mut request = query_request(&self.queries[0]);
+        request.dry_run = Some(true);
+        match self
+            .rt
+            .block_on(self.client.job().query(self.project_id.as_str(), request))
+        {
+            Ok(rs) => {
+                if let Some(fields) = rs.schema.and_then(|schema| schema.fields) {
+                    let (names, types, columns) = describe_fields(&fields)?;
+                    self.names = names;
+                    self.schema = types;
+                    self.columns = columns;
+                    return;
+                }
+            }
+            Err(e) => {
+                debug!(error = %e, "cannot dry run query, fall back to limit 1");
+            }
+        }
+
         for (_, query) in self.queries.iter().enumerate() {
             let l1query = limit1_query(query, &BigQueryDialect {})?;
-            let rs = self.rt.block_on(job.query(
+            let rs = run_query(
+                &self.rt,
+                &self.client,
                 self.project_id.as_str(),
-                QueryRequest::new(l1query.as_str()),
-            ))?;
-            let (names, types) = rs
+                &l1query,
+                self.statement_timeout,
+            )?;
+            let fields = rs
                 .schema
                 .as_ref()
                 .ok_or_else(|| anyhow!("TableSchema is none"))?
                 .fields
                 .as_ref()
-                .ok_or_else(|| anyhow!("TableFieldSchema is none"))?
-                .iter()
-                .map(|col| {
-                    (
-                        col.clone().name,
-                        BigQueryTypeSystem::from(&col.clone().r#type),
-                    )
-                })
-                .unzip();
+                .ok_or_else(|| anyhow!("TableFieldSchema is none"))?;
+            let (names, types, columns) = describe_fields(fields)?;
             self.names = names;
             self.schema = types;
+            self.columns = columns;
         }
     }
 
@@ -142,11 +344,15 @@ where
     fn result_rows(&mut self) -> Option<usize> {
         match &self.origin_query {
             Some(q) => {
-                let cxq = CXQuery::Naked(q.clone());
+                // the partitioned queries are bound to the parameters of the origin query
+                let cxq = CXQuery::Naked(q.clone()).bind(self.queries[0].params().to_vec());
                 let cquery = count_query(&cxq, &BigQueryDialect {})?;
-                let job = self.client.job();
-                let mut rs = ResultSet::new_from_query_response(self.rt.block_on(
-                    job.query(self.project_id.as_str(), QueryRequest::new(cquery.as_str())),
+                let mut rs = ResultSet::new_from_query_response(run_query(
+                    &self.rt,
+                    &self.client,
+                    self.project_id.as_str(),
+                    &cquery,
+                    self.statement_timeout,
                 )?);
                 rs.next_row();
                 let nrows = rs
@@ -166,6 +372,13 @@ where
         self.schema.clone()
     }
 
+    /// The columns are described with the type of their field, e.g. `NUMERIC`, which is
+    /// nullable unless its mode is `REQUIRED`.
+    #[throws(BigQuerySourceError)]
+    fn describe_columns(&mut self) -> Vec<ColumnDescription> {
+        self.columns.clone()
+    }
+
     #[throws(BigQuerySourceError)]
     fn partition(self) -> Vec<Self::Partition> {
         let mut ret = vec![];
@@ -176,6 +389,9 @@ where
                 self.project_id.clone(),
                 &query,
                 &self.schema,
+                self.statement_timeout,
+                self.pre_execution_queries.clone().unwrap_or_default(),
+                self.post_execution_queries.clone().unwrap_or_default(),
             ));
         }
         ret
@@ -190,15 +406,22 @@ pub struct BigQuerySourcePartition {
     schema: Vec<BigQueryTypeSystem>,
     nrows: usize,
     ncols: usize,
+    statement_timeout: Option<Duration>,
+    pre_execution_queries: Vec<String>,
+    post_execution_queries: Vec<String>,
 }
 
 impl BigQuerySourcePartition {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         handle: Arc<Runtime>,
         client: Arc<Client>,
         project_id: String,
         query: &CXQuery<String>,
         schema: &[BigQueryTypeSystem],
+        statement_timeout: Option<Duration>,
+        pre_execution_queries: Vec<String>,
+        post_execution_queries: Vec<String>,
     ) -> Self {
         Self {
             rt: handle,
@@ -208,6 +431,9 @@ impl BigQuerySourcePartition {
             schema: schema.to_vec(),
             nrows: 0,
             ncols: schema.len(),
+            statement_timeout,
+            pre_execution_queries,
+            post_execution_queries,
         }
     }
 }
@@ -220,11 +446,13 @@ impl SourcePartition for BigQuerySourcePartition {
     #[throws(BigQuerySourceError)]
     fn result_rows(&mut self) {
         let cquery = count_query(&self.query, &BigQueryDialect {})?;
-        let job = self.client.job();
-        let mut rs =
-            ResultSet::new_from_query_response(self.rt.block_on(
-                job.query(self.project_id.as_str(), QueryRequest::new(cquery.as_str())),
-            )?);
+        let mut rs = ResultSet::new_from_query_response(run_query(
+            &self.rt,
+            &self.client,
+            self.project_id.as_str(),
+            &with_pre_execution_queries(&cquery, &self.pre_execution_queries),
+            self.statement_timeout,
+        )?);
         rs.next_row();
         let nrows = rs
             .get_i64(0)?
@@ -235,10 +463,13 @@ impl SourcePartition for BigQuerySourcePartition {
     #[throws(BigQuerySourceError)]
     fn parser(&mut self) -> Self::Parser<'_> {
         let job = self.client.job();
-        let qry = self.rt.block_on(job.query(
+        let qry = run_query(
+            &self.rt,
+            &self.client,
             self.project_id.as_str(),
-            QueryRequest::new(self.query.as_str()),
-        ))?;
+            &with_pre_execution_queries(&self.query, &self.pre_execution_queries),
+            self.statement_timeout,
+        )?;
         let job_info = qry
             .job_reference
             .as_ref()
@@ -272,6 +503,21 @@ impl SourcePartition for BigQuerySourcePartition {
     fn ncols(&self) -> usize {
         self.ncols
     }
+
+    // each post-execution query runs as a job of its own, e.g. to drop the tables created
+    // by the pre-execution queries
+    #[throws(BigQuerySourceError)]
+    fn finish(&mut self) {
+        for post_query in &self.post_execution_queries {
+            run_query(
+                &self.rt,
+                &self.client,
+                self.project_id.as_str(),
+                &CXQuery::Naked(post_query.clone()),
+                self.statement_timeout,
+            )?;
+        }
+    }
 }
 
 pub struct BigQuerySourceParser {
@@ -1,5 +1,9 @@
+use super::errors::BigQuerySourceError;
+use crate::errors::ConnectorXError;
 use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use fehler::{throw, throws};
 use gcp_bigquery_client::model::field_type::FieldType;
+use std::convert::TryFrom;
 
 #[derive(Copy, Clone, Debug)]
 pub enum BigQueryTypeSystem {
@@ -33,8 +37,11 @@ impl_typesystem! {
     }
 }
 
-impl<'a> From<&'a FieldType> for BigQueryTypeSystem {
-    fn from(ty: &'a FieldType) -> BigQueryTypeSystem {
+impl<'a> TryFrom<&'a FieldType> for BigQueryTypeSystem {
+    type Error = BigQuerySourceError;
+
+    #[throws(BigQuerySourceError)]
+    fn try_from(ty: &'a FieldType) -> BigQueryTypeSystem {
         use BigQueryTypeSystem::*;
         match ty {
             FieldType::Bool => Bool(true),
@@ -51,7 +58,7 @@ impl<'a> From<&'a FieldType> for BigQueryTypeSystem {
             FieldType::Datetime => Datetime(true),
             FieldType::Time => Time(true),
             FieldType::Timestamp => Timestamp(true),
-            _ => unimplemented!("{}", format!("{:?}", ty)),
+            _ => throw!(ConnectorXError::UnsupportedType(format!("{:?}", ty))),
         }
     }
 }
@@ -17,13 +17,14 @@ pub mod oracle;
 pub mod postgres;
 #[cfg(feature = "src_sqlite")]
 pub mod sqlite;
+pub mod timeout;
 #[cfg(feature = "src_trino")]
 pub mod trino;
 
 use crate::data_order::DataOrder;
 use crate::errors::ConnectorXError;
 use crate::sql::CXQuery;
-use crate::typesystem::{TypeAssoc, TypeSystem};
+use crate::typesystem::{FieldType, TypeAssoc, TypeSystem};
 use std::fmt::Debug;
 
 pub trait Source {
@@ -41,21 +42,103 @@ pub trait Source {
 
     fn set_origin_query(&mut self, query: Option<String>);
 
-    fn set_pre_execution_queries(&mut self, _pre_execution_queries: Option<&[String]>) {
-        unimplemented!("pre_execution_queries is not implemented in this source type");
+    /// Set the queries that run on the connection of each partition before its query. Sources
+    /// that cannot run them accept no queries and reject others.
+    fn set_pre_execution_queries(&mut self, pre_execution_queries: Option<&[String]>) {
+        if pre_execution_queries.is_some_and(|queries| !queries.is_empty()) {
+            unimplemented!("pre_execution_queries is not implemented in this source type");
+        }
+    }
+
+    /// Set the queries that run on the connection of each partition after its data is fetched,
+    /// e.g. to reset session settings or drop temporary objects created by the pre-execution
+    /// queries. Sources that cannot run them accept no queries and reject others.
+    fn set_post_execution_queries(&mut self, post_execution_queries: Option<&[String]>) {
+        if post_execution_queries.is_some_and(|queries| !queries.is_empty()) {
+            unimplemented!("post_execution_queries is not implemented in this source type");
+        }
+    }
+
+    /// Create a source of the same database that shares the connection pool (and the session
+    /// settings) of this one, so that several queries can be loaded with one pool.
+    fn fork(&self) -> Self
+    where
+        Self: Sized,
+    {
+        unimplemented!("fork is not implemented in this source type");
+    }
+
+    /// Prepare to run the first query once as is (e.g. a stored procedure call), without wrapping
+    /// it for metadata, counting or partitioning. The query runs once the parser of the returned
+    /// partition is created, which then reads the result sets it returns one after another, see
+    /// [`PartitionParser::next_result_set`].
+    fn raw_partition(self) -> Result<Self::Partition, Self::Error>
+    where
+        Self: Sized,
+    {
+        unimplemented!("raw_partition is not implemented in this source type");
     }
 
     fn fetch_metadata(&mut self) -> Result<(), Self::Error>;
     /// Get total number of rows if available
     fn result_rows(&mut self) -> Result<Option<usize>, Self::Error>;
 
+    /// Estimate the total number of rows from the query plan without running the queries.
+    /// Returns `None` if the source cannot provide an estimate.
+    fn estimate_rows(&mut self) -> Result<Option<usize>, Self::Error> {
+        Ok(None)
+    }
+
     fn names(&self) -> Vec<String>;
 
     fn schema(&self) -> Vec<Self::TypeSystem>;
 
+    /// Describe the columns of the result as declared in the source, e.g. `numeric(12,2)`.
+    /// Must be called after `fetch_metadata`. By default the description is derived from the
+    /// schema, sources that know the declared types and their modifiers override this.
+    fn describe_columns(&mut self) -> Result<Vec<ColumnDescription>, Self::Error> {
+        Ok(self
+            .schema()
+            .into_iter()
+            .map(ColumnDescription::from_typesystem)
+            .collect())
+    }
+
     fn partition(self) -> Result<Vec<Self::Partition>, Self::Error>;
 }
 
+/// The type of a result column as declared in the source.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ColumnDescription {
+    /// Name of the type in the source, e.g. `numeric(12,2)` or `Int4`.
+    pub source_type: String,
+    /// Whether the column is nullable, `None` if unknown.
+    pub nullable: Option<bool>,
+    /// Precision of decimals, or the fractional second precision of times and timestamps.
+    pub precision: Option<u32>,
+    /// Scale of decimals.
+    pub scale: Option<i32>,
+    /// Maximum length of strings and bit strings.
+    pub length: Option<u32>,
+    /// Dimension of vectors.
+    pub dimension: Option<u32>,
+    /// Time zone of timestamps with a time zone, e.g. `Europe/Berlin`.
+    pub time_zone: Option<String>,
+    /// Names and types of the fields of composite types.
+    pub fields: Option<Vec<(String, FieldType)>>,
+}
+
+impl ColumnDescription {
+    /// Describe a column by the variant of the typesystem, e.g. `Int4(true)` becomes `Int4`.
+    pub fn from_typesystem<TS: TypeSystem>(ts: TS) -> Self {
+        Self {
+            source_type: ts.type_name().to_string(),
+            nullable: Some(ts.is_nullable()),
+            ..Default::default()
+        }
+    }
+}
+
 /// In general, a `DataSource` abstracts the data source as a stream, which can produce
 /// a sequence of values of variate types by repetitively calling the function `produce`.
 pub trait SourcePartition {
@@ -70,6 +153,12 @@ pub trait SourcePartition {
 
     fn parser(&mut self) -> Result<Self::Parser<'_>, Self::Error>;
 
+    /// Called once the parser of this partition is exhausted and dropped. Sources run their
+    /// post-execution queries here.
+    fn finish(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
     /// Number of rows this `DataSource` got.
     /// Sometimes it is not possible for the source to know how many rows it gets before reading the whole data.
     fn nrows(&self) -> usize;
@@ -96,6 +185,16 @@ pub trait PartitionParser<'a>: Send {
     /// There might be rows that are not consumed yet when calling the next fetch_next.
     /// The function might be called even after the last batch is fetched.
     fn fetch_next(&mut self) -> Result<(usize, bool), Self::Error>;
+
+    /// Move on to the next result set of a raw query (see [`Source::raw_partition`]) and return
+    /// the names and types of its columns, or `None` once every result set is read. It is called
+    /// before the first result set too, and only after all rows of the previous one are fetched.
+    #[allow(clippy::type_complexity)]
+    fn next_result_set(
+        &mut self,
+    ) -> Result<Option<(Vec<String>, Vec<Self::TypeSystem>)>, Self::Error> {
+        unimplemented!("result sets are not implemented in this source type");
+    }
 }
 
 /// A type implemented `Produce<T>` means that it can produce a value `T` by consuming part of it's raw data buffer.
@@ -1,4 +1,5 @@
 use std::string::FromUtf8Error;
+use std::time::Duration;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -6,6 +7,9 @@ pub enum MsSQLSourceError {
     #[error("Cannot get # of rows in the partition")]
     GetNRowsFailed,
 
+    #[error("Statement timed out after {0:?}")]
+    Timeout(Duration),
+
     #[error(transparent)]
     ConnectorXError(#[from] crate::errors::ConnectorXError),
 
@@ -28,3 +32,14 @@ pub enum MsSQLSourceError {
     #[error(transparent)]
     Other(#[from] anyhow::Error),
 }
+
+impl MsSQLSourceError {
+    /// Whether the error is raised because the statement or connect timeout elapsed.
+    pub fn is_timeout(&self) -> bool {
+        matches!(
+            self,
+            MsSQLSourceError::Timeout(_)
+                | MsSQLSourceError::MsSQLRuntimeError(bb8::RunError::TimedOut)
+        )
+    }
+}
@@ -9,9 +9,10 @@ use crate::constants::DB_BUFFER_SIZE;
 use crate::{
     data_order::DataOrder,
     errors::ConnectorXError,
-    sources::{PartitionParser, Produce, Source, SourcePartition},
-    sql::{count_query, CXQuery},
-    utils::DummyBox,
+    sources::{
+        timeout::Timeouts, ColumnDescription, PartitionParser, Produce, Source, SourcePartition,
+    },
+    sql::{count_query, redact_query, CXParam, CXQuery},
 };
 use anyhow::anyhow;
 use bb8::{Pool, PooledConnection};
@@ -20,14 +21,19 @@ use chrono::{DateTime, Utc};
 use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
 use fehler::{throw, throws};
 use futures::StreamExt;
-use log::debug;
-use owning_ref::OwningHandle;
 use rust_decimal::Decimal;
 use sqlparser::dialect::MsSqlDialect;
 use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::future::Future;
 use std::sync::Arc;
-use tiberius::{AuthMethod, Config, EncryptionLevel, QueryItem, QueryStream, Row};
-use tokio::runtime::{Handle, Runtime};
+use std::time::{Duration, Instant};
+use tiberius::{
+    AuthMethod, ColumnData, Config, EncryptionLevel, QueryItem, QueryStream, ResultMetadata, Row,
+    ToSql,
+};
+use tokio::runtime::Runtime;
+use tracing::debug;
 use url::Url;
 use urlencoding::decode;
 use uuid_old::Uuid;
@@ -40,6 +46,163 @@ pub struct MsSQLSource {
     queries: Vec<CXQuery<String>>,
     names: Vec<String>,
     schema: Vec<MsSQLTypeSystem>,
+    // the declared types of the columns, if the query could be described
+    columns: Vec<ColumnDescription>,
+    statement_timeout: Option<Duration>,
+    pre_execution_queries: Option<Vec<String>>,
+    post_execution_queries: Option<Vec<String>>,
+}
+
+// tiberius has no statement timeout, so bound the time for `fut` to complete on the client side
+fn block_on_timeout<F: Future>(
+    rt: &Runtime,
+    timeout: Option<Duration>,
+    fut: F,
+) -> Result<F::Output, MsSQLSourceError> {
+    block_on_deadline(rt, timeout, Instant::now(), fut)
+}
+
+// same as `block_on_timeout`, but the timeout counts from `start`, so that it bounds a whole fetch
+// made of several calls rather than each of them
+fn block_on_deadline<F: Future>(
+    rt: &Runtime,
+    timeout: Option<Duration>,
+    start: Instant,
+    fut: F,
+) -> Result<F::Output, MsSQLSourceError> {
+    match timeout {
+        Some(timeout) => {
+            let remaining = timeout.saturating_sub(start.elapsed());
+            rt.block_on(async { tokio::time::timeout(remaining, fut).await })
+                .map_err(|_| MsSQLSourceError::Timeout(timeout))
+        }
+        None => Ok(rt.block_on(fut)),
+    }
+}
+
+fn sql_params(params: &[CXParam]) -> Vec<&dyn ToSql> {
+    params.iter().map(|p| p as &dyn ToSql).collect()
+}
+
+impl ToSql for CXParam {
+    fn to_sql(&self) -> ColumnData<'_> {
+        match self {
+            CXParam::Null => ColumnData::String(None),
+            CXParam::Bool(v) => ColumnData::Bit(Some(*v)),
+            CXParam::I64(v) => ColumnData::I64(Some(*v)),
+            CXParam::F64(v) => ColumnData::F64(Some(*v)),
+            CXParam::Str(v) => ColumnData::String(Some(v.as_str().into())),
+            CXParam::Bytes(v) => ColumnData::Binary(Some(v.as_slice().into())),
+            CXParam::Date(v) => v.to_sql(),
+            CXParam::DateTime(v) => v.to_sql(),
+        }
+    }
+}
+
+// the declaration of the `@P1`, `@P2`, ... parameters for `sp_describe_first_result_set`
+fn params_declaration(params: &[CXParam]) -> String {
+    params
+        .iter()
+        .enumerate()
+        .map(|(i, param)| {
+            let ty = match param {
+                CXParam::Null | CXParam::Str(_) => "nvarchar(max)",
+                CXParam::Bool(_) => "bit",
+                CXParam::I64(_) => "bigint",
+                CXParam::F64(_) => "float",
+                CXParam::Bytes(_) => "varbinary(max)",
+                CXParam::Date(_) => "date",
+                CXParam::DateTime(_) => "datetime2",
+            };
+            format!("@P{} {}", i + 1, ty)
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Describe the columns of `query` with `sp_describe_first_result_set`, which compiles the query
+/// without running it. Returns `None` if a column cannot be mapped to [`MsSQLTypeSystem`].
+#[allow(clippy::type_complexity)]
+#[throws(MsSQLSourceError)]
+fn describe_first_result_set(
+    rt: &Runtime,
+    conn: &mut Conn<'_>,
+    query: &CXQuery<String>,
+) -> Option<(Vec<String>, Vec<MsSQLTypeSystem>, Vec<ColumnDescription>)> {
+    let tsql = query.as_str();
+    let declaration = params_declaration(query.params());
+    let rows = rt.block_on(async {
+        let stream = if declaration.is_empty() {
+            conn.query("EXEC sp_describe_first_result_set @tsql = @P1", &[&tsql])
+                .await?
+        } else {
+            conn.query(
+                "EXEC sp_describe_first_result_set @tsql = @P1, @params = @P2",
+                &[&tsql, &declaration.as_str()],
+            )
+            .await?
+        };
+        stream.into_first_result().await
+    })?;
+
+    let mut names = vec![];
+    let mut types = vec![];
+    let mut columns = vec![];
+    for row in rows {
+        if row.try_get::<bool, _>("is_hidden")?.unwrap_or(false) {
+            continue;
+        }
+        let nullable = row.try_get::<bool, _>("is_nullable")?.unwrap_or(true);
+        let (ty, column) = match row.try_get::<&str, _>("system_type_name")? {
+            Some(name) => (
+                MsSQLTypeSystem::from_type_name(name, nullable),
+                describe_column(&row, name, nullable)?,
+            ),
+            None => return None,
+        };
+        match ty {
+            Some(ty) => types.push(ty),
+            None => return None,
+        }
+        names.push(row.try_get::<&str, _>("name")?.unwrap_or("").to_string());
+        columns.push(column);
+    }
+    if names.is_empty() {
+        return None;
+    }
+    Some((names, types, columns))
+}
+
+// the declared type of a column described by `sp_describe_first_result_set`, e.g. `decimal(12,2)`
+#[throws(MsSQLSourceError)]
+fn describe_column(row: &Row, source_type: &str, nullable: bool) -> ColumnDescription {
+    let mut description = ColumnDescription {
+        source_type: source_type.to_string(),
+        nullable: Some(nullable),
+        ..Default::default()
+    };
+    let base = source_type.split('(').next().unwrap_or(source_type);
+    match base {
+        "decimal" | "numeric" => {
+            description.precision = row.try_get::<u8, _>("precision")?.map(u32::from);
+            description.scale = row.try_get::<u8, _>("scale")?.map(i32::from);
+        }
+        // the scale of the times is their fractional second precision
+        "time" | "datetime2" | "datetimeoffset" => {
+            description.precision = row.try_get::<u8, _>("scale")?.map(u32::from);
+        }
+        "char" | "varchar" | "binary" | "varbinary" | "nchar" | "nvarchar" => {
+            // the length is in bytes, the characters of the unicode types take two, and it is
+            // -1 for `max`
+            let bytes = row.try_get::<i16, _>("max_length")?.unwrap_or(-1);
+            if bytes >= 0 {
+                let width = if base.starts_with('n') { 2 } else { 1 };
+                description.length = Some(bytes as u32 / width);
+            }
+        }
+        _ => {}
+    }
+    description
 }
 
 #[throws(MsSQLSourceError)]
@@ -113,8 +276,13 @@ impl MsSQLSource {
     pub fn new(rt: Arc<Runtime>, conn: &str, nconn: usize) -> Self {
         let url = Url::parse(conn)?;
         let config = mssql_config(&url)?;
+        let timeouts = Timeouts::from_url(&url)?;
         let manager = bb8_tiberius::ConnectionManager::new(config);
-        let pool = rt.block_on(Pool::builder().max_size(nconn as u32).build(manager))?;
+        let mut builder = Pool::builder().max_size(nconn as u32);
+        if let Some(timeout) = timeouts.connect {
+            builder = builder.connection_timeout(timeout);
+        }
+        let pool = rt.block_on(builder.build(manager))?;
 
         Self {
             rt,
@@ -123,6 +291,10 @@ impl MsSQLSource {
             queries: vec![],
             names: vec![],
             schema: vec![],
+            columns: vec![],
+            statement_timeout: timeouts.statement,
+            pre_execution_queries: None,
+            post_execution_queries: None,
         }
     }
 }
@@ -151,59 +323,123 @@ where
         self.origin_query = query;
     }
 
+    fn set_pre_execution_queries(&mut self, pre_execution_queries: Option<&[String]>) {
+        self.pre_execution_queries = pre_execution_queries.map(|s| s.to_vec());
+    }
+
+    fn set_post_execution_queries(&mut self, post_execution_queries: Option<&[String]>) {
+        self.post_execution_queries = post_execution_queries.map(|s| s.to_vec());
+    }
+
+    fn fork(&self) -> Self {
+        Self {
+            rt: self.rt.clone(),
+            pool: self.pool.clone(),
+            origin_query: None,
+            queries: vec![],
+            names: vec![],
+            schema: vec![],
+            columns: vec![],
+            statement_timeout: self.statement_timeout,
+            pre_execution_queries: self.pre_execution_queries.clone(),
+            post_execution_queries: self.post_execution_queries.clone(),
+        }
+    }
+
+    /// The query runs as a batch, e.g. `EXEC proc @P1`. Every result set of the batch starts with
+    /// the metadata of its columns, which is followed by its rows.
+    #[throws(MsSQLSourceError)]
+    fn raw_partition(self) -> Self::Partition {
+        assert!(!self.queries.is_empty());
+
+        MsSQLSourcePartition::new(
+            self.pool,
+            self.rt,
+            &self.queries[0],
+            &[],
+            self.statement_timeout,
+            self.pre_execution_queries.unwrap_or_default(),
+            self.post_execution_queries.unwrap_or_default(),
+        )
+    }
+
     #[throws(MsSQLSourceError)]
     fn fetch_metadata(&mut self) {
         assert!(!self.queries.is_empty());
 
         let mut conn = self.rt.block_on(self.pool.get())?;
         let first_query = &self.queries[0];
-        let (names, types) = match self.rt.block_on(conn.query(first_query.as_str(), &[])) {
-            Ok(mut stream) => match self.rt.block_on(async { stream.columns().await }) {
-                Ok(Some(columns)) => columns
-                    .iter()
-                    .map(|col| {
-                        (
-                            col.name().to_string(),
-                            MsSQLTypeSystem::from(&col.column_type()),
-                        )
-                    })
-                    .unzip(),
-                Ok(None) => {
-                    throw!(anyhow!(
-                        "MsSQL returned no columns for query: {}",
-                        first_query
-                    ));
-                }
-                Err(e) => {
-                    throw!(anyhow!("Error fetching columns: {}", e));
+
+        match describe_first_result_set(&self.rt, &mut conn, first_query) {
+            Ok(Some((names, types, columns))) => {
+                self.names = names;
+                self.schema = types;
+                self.columns = columns;
+                return;
+            }
+            Ok(None) => {}
+            Err(e) => {
+                debug!(error = %e, "cannot describe query, fall back to running it");
+            }
+        }
+
+        let start = Instant::now();
+        let (names, types) = match block_on_deadline(
+            &self.rt,
+            self.statement_timeout,
+            start,
+            conn.query(first_query.as_str(), &sql_params(first_query.params())),
+        )? {
+            Ok(mut stream) => {
+                match block_on_deadline(&self.rt, self.statement_timeout, start, stream.columns())?
+                {
+                    Ok(Some(columns)) => columns
+                        .iter()
+                        .map(|col| {
+                            Ok((
+                                col.name().to_string(),
+                                MsSQLTypeSystem::try_from(&col.column_type())?,
+                            ))
+                        })
+                        .collect::<Result<Vec<_>, MsSQLSourceError>>()?
+                        .into_iter()
+                        .unzip(),
+                    Ok(None) => {
+                        throw!(anyhow!(
+                            "MsSQL returned no columns for query: {}",
+                            first_query
+                        ));
+                    }
+                    Err(e) => {
+                        throw!(anyhow!("Error fetching columns: {}", e));
+                    }
                 }
-            },
+            }
             Err(e) => {
-                debug!(
-                    "cannot get metadata for '{}', try next query: {}",
-                    first_query, e
-                );
+                debug!(query = %redact_query(first_query.as_str()), error = %e, "cannot get metadata");
                 throw!(e);
             }
         };
 
         self.names = names;
         self.schema = types;
+        self.columns = vec![];
     }
 
     #[throws(MsSQLSourceError)]
     fn result_rows(&mut self) -> Option<usize> {
         match &self.origin_query {
             Some(q) => {
-                let cxq = CXQuery::Naked(q.clone());
+                // the partitioned queries are bound to the parameters of the origin query
+                let cxq = CXQuery::Naked(q.clone()).bind(self.queries[0].params().to_vec());
                 let cquery = count_query(&cxq, &MsSqlDialect {})?;
                 let mut conn = self.rt.block_on(self.pool.get())?;
 
-                let stream = self.rt.block_on(conn.query(cquery.as_str(), &[]))?;
-                let row = self
-                    .rt
-                    .block_on(stream.into_row())?
-                    .ok_or_else(|| anyhow!("MsSQL failed to get the count of query: {}", q))?;
+                let params = sql_params(cquery.params());
+                let row = block_on_timeout(&self.rt, self.statement_timeout, async {
+                    conn.query(cquery.as_str(), &params).await?.into_row().await
+                })??
+                .ok_or_else(|| anyhow!("MsSQL failed to get the count of query: {}", q))?;
 
                 let row: i32 = row.get(0).ok_or(MsSQLSourceError::GetNRowsFailed)?; // the count in mssql is i32
                 Some(row as usize)
@@ -220,6 +456,21 @@ where
         self.schema.clone()
     }
 
+    /// The columns are described with their declared type if `sp_describe_first_result_set` could
+    /// describe the query, and with their type on the wire otherwise.
+    #[throws(MsSQLSourceError)]
+    fn describe_columns(&mut self) -> Vec<ColumnDescription> {
+        if self.columns.is_empty() {
+            self.schema
+                .iter()
+                .copied()
+                .map(ColumnDescription::from_typesystem)
+                .collect()
+        } else {
+            self.columns.clone()
+        }
+    }
+
     #[throws(MsSQLSourceError)]
     fn partition(self) -> Vec<Self::Partition> {
         let mut ret = vec![];
@@ -229,6 +480,9 @@ where
                 self.rt.clone(),
                 &query,
                 &self.schema,
+                self.statement_timeout,
+                self.pre_execution_queries.clone().unwrap_or_default(),
+                self.post_execution_queries.clone().unwrap_or_default(),
             ));
         }
         ret
@@ -242,6 +496,10 @@ pub struct MsSQLSourcePartition {
     schema: Vec<MsSQLTypeSystem>,
     nrows: usize,
     ncols: usize,
+    statement_timeout: Option<Duration>,
+    pre_execution_queries: Vec<String>,
+    post_execution_queries: Vec<String>,
+    conn: Option<Conn<'static>>,
 }
 
 impl MsSQLSourcePartition {
@@ -250,6 +508,9 @@ impl MsSQLSourcePartition {
         handle: Arc<Runtime>,
         query: &CXQuery<String>,
         schema: &[MsSQLTypeSystem],
+        statement_timeout: Option<Duration>,
+        pre_execution_queries: Vec<String>,
+        post_execution_queries: Vec<String>,
     ) -> Self {
         Self {
             rt: handle,
@@ -258,7 +519,29 @@ impl MsSQLSourcePartition {
             schema: schema.to_vec(),
             nrows: 0,
             ncols: schema.len(),
+            statement_timeout,
+            pre_execution_queries,
+            post_execution_queries,
+            conn: None,
+        }
+    }
+
+    /// The connection of the partition. It is checked out and set up with the pre-execution
+    /// queries on first use, so that counting and fetching the rows run in the same session.
+    #[throws(MsSQLSourceError)]
+    fn conn(&mut self) -> &mut Conn<'static> {
+        if self.conn.is_none() {
+            let mut conn = self.rt.block_on(self.pool.get_owned())?;
+            for pre_query in &self.pre_execution_queries {
+                block_on_timeout(
+                    &self.rt,
+                    self.statement_timeout,
+                    conn.execute(pre_query.as_str(), &[]),
+                )??;
+            }
+            self.conn = Some(conn);
         }
+        self.conn.as_mut().unwrap()
     }
 }
 
@@ -270,13 +553,14 @@ impl SourcePartition for MsSQLSourcePartition {
     #[throws(MsSQLSourceError)]
     fn result_rows(&mut self) {
         let cquery = count_query(&self.query, &MsSqlDialect {})?;
-        let mut conn = self.rt.block_on(self.pool.get())?;
+        let (rt, statement_timeout) = (self.rt.clone(), self.statement_timeout);
+        let conn = self.conn()?;
 
-        let stream = self.rt.block_on(conn.query(cquery.as_str(), &[]))?;
-        let row = self
-            .rt
-            .block_on(stream.into_row())?
-            .ok_or_else(|| anyhow!("MsSQL failed to get the count of query: {}", self.query))?;
+        let params = sql_params(cquery.params());
+        let row = block_on_timeout(&rt, statement_timeout, async {
+            conn.query(cquery.as_str(), &params).await?.into_row().await
+        })??
+        .ok_or_else(|| anyhow!("MsSQL failed to get the count of query: {}", self.query))?;
 
         let row: i32 = row.get(0).ok_or(MsSQLSourceError::GetNRowsFailed)?; // the count in mssql is i32
         self.nrows = row as usize;
@@ -284,19 +568,27 @@ impl SourcePartition for MsSQLSourcePartition {
 
     #[throws(MsSQLSourceError)]
     fn parser<'a>(&'a mut self) -> Self::Parser<'a> {
-        let conn = self.rt.block_on(self.pool.get())?;
-        let rows: OwningHandle<Box<Conn<'a>>, DummyBox<QueryStream<'a>>> =
-            OwningHandle::new_with_fn(Box::new(conn), |conn: *const Conn<'a>| unsafe {
-                let conn = &mut *(conn as *mut Conn<'a>);
-
-                DummyBox(
-                    self.rt
-                        .block_on(conn.query(self.query.as_str(), &[]))
-                        .unwrap(),
-                )
-            });
+        self.conn()?;
+        let Self {
+            rt,
+            query,
+            schema,
+            conn,
+            statement_timeout,
+            ..
+        } = self;
+        let conn = conn.as_mut().unwrap();
+        // the statement timeout covers fetching the whole result set, not only its first rows
+        let start = Instant::now();
+        let params = sql_params(query.params());
+        let stream = block_on_deadline(
+            rt,
+            *statement_timeout,
+            start,
+            conn.query(query.as_str(), &params),
+        )??;
 
-        MsSQLSourceParser::new(self.rt.handle(), rows, &self.schema)
+        MsSQLSourceParser::new(rt, stream, schema, *statement_timeout, start)
     }
 
     fn nrows(&self) -> usize {
@@ -306,23 +598,46 @@ impl SourcePartition for MsSQLSourcePartition {
     fn ncols(&self) -> usize {
         self.ncols
     }
+
+    #[throws(MsSQLSourceError)]
+    fn finish(&mut self) {
+        if let Some(mut conn) = self.conn.take() {
+            for post_query in &self.post_execution_queries {
+                block_on_timeout(
+                    &self.rt,
+                    self.statement_timeout,
+                    conn.execute(post_query.as_str(), &[]),
+                )??;
+            }
+        }
+    }
 }
 
 pub struct MsSQLSourceParser<'a> {
-    rt: &'a Handle,
-    iter: OwningHandle<Box<Conn<'a>>, DummyBox<QueryStream<'a>>>,
+    rt: &'a Runtime,
+    iter: QueryStream<'a>,
     rowbuf: Vec<Row>,
     ncols: usize,
     current_col: usize,
     current_row: usize,
     is_finished: bool,
+    statement_timeout: Option<Duration>,
+    start: Instant,
+    // whether the rows of a result set are being read, its end is the metadata of the next one
+    in_set: bool,
+    // the metadata that ended the previous result set
+    next_set: Option<ResultMetadata>,
+    // whether the stream is exhausted
+    done: bool,
 }
 
 impl<'a> MsSQLSourceParser<'a> {
     fn new(
-        rt: &'a Handle,
-        iter: OwningHandle<Box<Conn<'a>>, DummyBox<QueryStream<'a>>>,
+        rt: &'a Runtime,
+        iter: QueryStream<'a>,
         schema: &[MsSQLTypeSystem],
+        statement_timeout: Option<Duration>,
+        start: Instant,
     ) -> Self {
         Self {
             rt,
@@ -332,6 +647,31 @@ impl<'a> MsSQLSourceParser<'a> {
             current_row: 0,
             current_col: 0,
             is_finished: false,
+            statement_timeout,
+            start,
+            in_set: false,
+            next_set: None,
+            done: false,
+        }
+    }
+
+    #[throws(MsSQLSourceError)]
+    fn next_item(&mut self) -> Option<QueryItem> {
+        if self.done {
+            return None;
+        }
+        let item = block_on_deadline(
+            self.rt,
+            self.statement_timeout,
+            self.start,
+            self.iter.next(),
+        )?;
+        match item {
+            Some(item) => Some(item.map_err(MsSQLSourceError::MsSQLError)?),
+            None => {
+                self.done = true;
+                None
+            }
         }
     }
 
@@ -363,20 +703,57 @@ impl<'a> PartitionParser<'a> for MsSQLSourceParser<'a> {
         }
 
         for _ in 0..DB_BUFFER_SIZE {
-            if let Some(item) = self.rt.block_on(self.iter.next()) {
-                match item.map_err(MsSQLSourceError::MsSQLError)? {
-                    QueryItem::Row(row) => self.rowbuf.push(row),
-                    _ => continue,
+            match self.next_item()? {
+                Some(QueryItem::Row(row)) => self.rowbuf.push(row),
+                Some(QueryItem::Metadata(meta)) if self.in_set => {
+                    // the next result set starts
+                    self.next_set = Some(meta);
+                    self.is_finished = true;
+                    break;
+                }
+                Some(QueryItem::Metadata(_)) => self.in_set = true,
+                None => {
+                    self.is_finished = true;
+                    break;
                 }
-            } else {
-                self.is_finished = true;
-                break;
             }
         }
         self.current_row = 0;
         self.current_col = 0;
         (self.rowbuf.len(), self.is_finished)
     }
+
+    #[throws(MsSQLSourceError)]
+    fn next_result_set(&mut self) -> Option<(Vec<String>, Vec<MsSQLTypeSystem>)> {
+        let mut meta = self.next_set.take();
+        while meta.is_none() {
+            match self.next_item()? {
+                Some(QueryItem::Metadata(next)) => meta = Some(next),
+                Some(QueryItem::Row(_)) => {} // the rows of the previous set are fetched already
+                None => return None,
+            }
+        }
+        let (names, schema): (Vec<String>, Vec<MsSQLTypeSystem>) = meta
+            .unwrap()
+            .columns()
+            .iter()
+            .map(|col| {
+                Ok((
+                    col.name().to_string(),
+                    MsSQLTypeSystem::try_from(&col.column_type())?,
+                ))
+            })
+            .collect::<Result<Vec<_>, MsSQLSourceError>>()?
+            .into_iter()
+            .unzip();
+        self.rowbuf.clear();
+        self.ncols = schema.len();
+        self.current_row = 0;
+        self.current_col = 0;
+        self.is_finished = false;
+        self.in_set = true;
+        Some((names, schema))
+    }
 }
 
 macro_rules! impl_produce {
@@ -1,5 +1,9 @@
+use super::errors::MsSQLSourceError;
+use crate::errors::ConnectorXError;
 use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use fehler::{throw, throws};
 use rust_decimal::Decimal;
+use std::convert::TryFrom;
 use tiberius::{ColumnData, ColumnType, FromSql};
 use uuid_old::Uuid;
 
@@ -60,8 +64,11 @@ impl_typesystem! {
     }
 }
 
-impl<'a> From<&'a ColumnType> for MsSQLTypeSystem {
-    fn from(ty: &'a ColumnType) -> MsSQLTypeSystem {
+impl<'a> TryFrom<&'a ColumnType> for MsSQLTypeSystem {
+    type Error = MsSQLSourceError;
+
+    #[throws(MsSQLSourceError)]
+    fn try_from(ty: &'a ColumnType) -> MsSQLTypeSystem {
         use MsSQLTypeSystem::*;
 
         match ty {
@@ -96,11 +103,53 @@ impl<'a> From<&'a ColumnType> for MsSQLTypeSystem {
             ColumnType::DatetimeOffsetn => Datetimeoffset(true),
             ColumnType::Money => Money(true),
             ColumnType::Money4 => SmallMoney(true),
-            _ => unimplemented!("{}", format!("{:?}", ty)),
+            _ => throw!(ConnectorXError::UnsupportedType(format!("{:?}", ty))),
         }
     }
 }
 
+impl MsSQLTypeSystem {
+    /// The type of a column described by `sp_describe_first_result_set`, e.g. `nvarchar(50)`,
+    /// following the type the column has on the wire. Returns `None` for unsupported types.
+    pub fn from_type_name(name: &str, nullable: bool) -> Option<Self> {
+        use MsSQLTypeSystem::*;
+
+        let base = name.split('(').next().unwrap_or(name).trim().to_lowercase();
+        let ty = match (base.as_str(), nullable) {
+            ("tinyint", false) => Tinyint(false),
+            ("smallint", false) => Smallint(false),
+            ("int", false) => Int(false),
+            ("bigint", false) => Bigint(false),
+            ("tinyint" | "smallint" | "int" | "bigint", true) => Intn(true),
+            ("real", false) => Float24(false),
+            ("float", false) => Float53(false),
+            ("real" | "float", true) => Floatn(true),
+            ("bit", _) => Bit(nullable),
+            ("nvarchar", _) => Nvarchar(nullable),
+            ("varchar", _) => Varchar(nullable),
+            ("nchar", _) => Nchar(nullable),
+            ("char", _) => Char(nullable),
+            ("ntext", _) => Ntext(nullable),
+            ("text", _) => Text(nullable),
+            ("binary", _) => Binary(nullable),
+            ("varbinary", _) => Varbinary(nullable),
+            ("image", _) => Image(nullable),
+            ("uniqueidentifier", _) => Uniqueidentifier(nullable),
+            ("decimal", _) => Decimal(nullable),
+            ("numeric", _) => Numeric(nullable),
+            ("datetime" | "smalldatetime", _) => Datetime(nullable),
+            ("datetime2", _) => Datetime2(nullable),
+            ("date", _) => Date(nullable),
+            ("time", _) => Time(nullable),
+            ("datetimeoffset", _) => Datetimeoffset(nullable),
+            ("money", _) => Money(nullable),
+            ("smallmoney", _) => SmallMoney(nullable),
+            _ => return None,
+        };
+        Some(ty)
+    }
+}
+
 pub struct IntN(pub i64);
 impl<'a> FromSql<'a> for IntN {
     fn from_sql(value: &'a ColumnData<'static>) -> Result<Option<Self>, tiberius::error::Error> {
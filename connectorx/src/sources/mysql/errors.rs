@@ -1,3 +1,4 @@
+use crate::sources::timeout::is_pool_timeout;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -18,3 +19,17 @@ pub enum MySQLSourceError {
     #[error(transparent)]
     Other(#[from] anyhow::Error),
 }
+
+impl MySQLSourceError {
+    /// Whether the error is raised because the statement or connect timeout elapsed.
+    pub fn is_timeout(&self) -> bool {
+        use r2d2_mysql::mysql::{DriverError, Error};
+        match self {
+            MySQLSourceError::MySQLPoolError(e) => is_pool_timeout(e),
+            // ER_QUERY_TIMEOUT, raised when max_execution_time is exceeded
+            MySQLSourceError::MySQLError(Error::MySqlError(e)) => e.code == 3024,
+            MySQLSourceError::MySQLError(Error::DriverError(DriverError::ConnectTimeout)) => true,
+            _ => false,
+        }
+    }
+}
@@ -8,13 +8,16 @@ use crate::constants::DB_BUFFER_SIZE;
 use crate::{
     data_order::DataOrder,
     errors::ConnectorXError,
-    sources::{PartitionParser, Produce, Source, SourcePartition},
-    sql::{count_query, limit1_query, CXQuery},
+    sources::{
+        timeout::Timeouts, ColumnDescription, PartitionParser, Produce, Source, SourcePartition,
+    },
+    sql::{
+        count_query, inline_params, limit1_query, redact_query, CXParam, CXQuery, PlaceholderStyle,
+    },
 };
 use anyhow::anyhow;
-use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use chrono::{Duration, NaiveDate, NaiveDateTime};
 use fehler::{throw, throws};
-use log::{debug, warn};
 use r2d2::{Pool, PooledConnection};
 use r2d2_mysql::{
     mysql::{
@@ -22,16 +25,20 @@ use r2d2_mysql::{
             ColumnFlags as MySQLColumnFlags, ColumnType as MySQLColumnType, UTF8MB4_GENERAL_CI,
             UTF8_GENERAL_CI,
         },
-        prelude::Queryable,
-        Binary, Opts, OptsBuilder, QueryResult, Row, Text,
+        prelude::{Protocol, Queryable},
+        Binary, Column, Opts, OptsBuilder, Params, QueryResult, Row, Text, Value as MySQLValue,
     },
     MySqlConnectionManager,
 };
 use rust_decimal::Decimal;
 use serde_json::Value;
 use sqlparser::dialect::MySqlDialect;
+use std::collections::HashMap;
+use std::convert::TryFrom;
 use std::marker::PhantomData;
+use tracing::{debug, warn};
 pub use typesystem::MySQLTypeSystem;
+use url::Url;
 
 type MysqlConn = PooledConnection<MySqlConnectionManager>;
 
@@ -40,8 +47,150 @@ pub enum TextProtocol {}
 
 #[throws(MySQLSourceError)]
 fn get_total_rows(conn: &mut MysqlConn, query: &CXQuery<String>) -> usize {
-    conn.query_first(&count_query(query, &MySqlDialect {})?)?
-        .ok_or_else(|| anyhow!("mysql failed to get the count of query: {}", query))?
+    let cquery = count_query(query, &MySqlDialect {})?;
+    // queries without parameters keep using the text protocol, since not every statement can be
+    // prepared by mysql
+    match cquery.params() {
+        [] => conn.query_first(cquery.as_str())?,
+        params => conn.exec_first(cquery.as_str(), mysql_params(params))?,
+    }
+    .ok_or_else(|| anyhow!("mysql failed to get the count of query: {}", query))?
+}
+
+pub(crate) fn mysql_params(params: &[CXParam]) -> Params {
+    if params.is_empty() {
+        return Params::Empty;
+    }
+    Params::Positional(params.iter().map(mysql_value).collect())
+}
+
+fn mysql_value(param: &CXParam) -> MySQLValue {
+    match param {
+        CXParam::Null => MySQLValue::NULL,
+        CXParam::Bool(v) => MySQLValue::from(*v),
+        CXParam::I64(v) => MySQLValue::Int(*v),
+        CXParam::F64(v) => MySQLValue::Double(*v),
+        CXParam::Str(v) => MySQLValue::from(v.as_str()),
+        CXParam::Bytes(v) => MySQLValue::Bytes(v.clone()),
+        CXParam::Date(v) => MySQLValue::from(*v),
+        CXParam::DateTime(v) => MySQLValue::from(*v),
+    }
+}
+
+/// Build the connection options from a connection string. `connect_timeout` is mapped to the tcp
+/// connect timeout and `statement_timeout` to the `max_execution_time` of the session.
+#[throws(MySQLSourceError)]
+pub fn mysql_opts(url: &Url) -> OptsBuilder {
+    let timeouts = Timeouts::from_url(url)?;
+    let opts = OptsBuilder::from_opts(Opts::from_url(Timeouts::strip(url).as_str())?)
+        .tcp_connect_timeout(timeouts.connect);
+    match timeouts.statement {
+        Some(timeout) => opts.init(vec![format!(
+            "SET SESSION max_execution_time = {}",
+            timeout.as_millis()
+        )]),
+        None => opts,
+    }
+}
+
+// the type of a column in the result of a prepared statement, utf8 blobs are read as text
+#[throws(MySQLSourceError)]
+fn column_type(col: &Column, server_version_post_5_5_3: bool) -> MySQLTypeSystem {
+    let col_type = col.column_type();
+    let col_flags = col.flags();
+    let charset = col.character_set();
+    let charset_is_utf8 = (server_version_post_5_5_3 && charset == UTF8MB4_GENERAL_CI)
+        || (!server_version_post_5_5_3 && charset == UTF8_GENERAL_CI);
+    if charset_is_utf8
+        && (col_type == MySQLColumnType::MYSQL_TYPE_LONG_BLOB
+            || col_type == MySQLColumnType::MYSQL_TYPE_BLOB
+            || col_type == MySQLColumnType::MYSQL_TYPE_MEDIUM_BLOB
+            || col_type == MySQLColumnType::MYSQL_TYPE_TINY_BLOB)
+    {
+        return MySQLTypeSystem::Char(!col_flags.contains(MySQLColumnFlags::NOT_NULL_FLAG));
+    }
+    MySQLTypeSystem::try_from((&col_type, &col_flags))?
+}
+
+// the name of the type of a column on the wire, for the columns that are not from a table
+fn column_type_name(col: &Column) -> &'static str {
+    let binary = col.character_set() == 63;
+    match col.column_type() {
+        MySQLColumnType::MYSQL_TYPE_DECIMAL | MySQLColumnType::MYSQL_TYPE_NEWDECIMAL => "decimal",
+        MySQLColumnType::MYSQL_TYPE_TINY => "tinyint",
+        MySQLColumnType::MYSQL_TYPE_SHORT => "smallint",
+        MySQLColumnType::MYSQL_TYPE_INT24 => "mediumint",
+        MySQLColumnType::MYSQL_TYPE_LONG => "int",
+        MySQLColumnType::MYSQL_TYPE_LONGLONG => "bigint",
+        MySQLColumnType::MYSQL_TYPE_FLOAT => "float",
+        MySQLColumnType::MYSQL_TYPE_DOUBLE => "double",
+        MySQLColumnType::MYSQL_TYPE_NULL => "null",
+        MySQLColumnType::MYSQL_TYPE_TIMESTAMP | MySQLColumnType::MYSQL_TYPE_TIMESTAMP2 => {
+            "timestamp"
+        }
+        MySQLColumnType::MYSQL_TYPE_DATE | MySQLColumnType::MYSQL_TYPE_NEWDATE => "date",
+        MySQLColumnType::MYSQL_TYPE_TIME | MySQLColumnType::MYSQL_TYPE_TIME2 => "time",
+        MySQLColumnType::MYSQL_TYPE_DATETIME | MySQLColumnType::MYSQL_TYPE_DATETIME2 => "datetime",
+        MySQLColumnType::MYSQL_TYPE_YEAR => "year",
+        MySQLColumnType::MYSQL_TYPE_BIT => "bit",
+        MySQLColumnType::MYSQL_TYPE_JSON => "json",
+        MySQLColumnType::MYSQL_TYPE_ENUM => "enum",
+        MySQLColumnType::MYSQL_TYPE_SET => "set",
+        MySQLColumnType::MYSQL_TYPE_GEOMETRY => "geometry",
+        MySQLColumnType::MYSQL_TYPE_VARCHAR | MySQLColumnType::MYSQL_TYPE_VAR_STRING if binary => {
+            "varbinary"
+        }
+        MySQLColumnType::MYSQL_TYPE_VARCHAR | MySQLColumnType::MYSQL_TYPE_VAR_STRING => "varchar",
+        MySQLColumnType::MYSQL_TYPE_STRING if binary => "binary",
+        MySQLColumnType::MYSQL_TYPE_STRING => "char",
+        MySQLColumnType::MYSQL_TYPE_TINY_BLOB if binary => "tinyblob",
+        MySQLColumnType::MYSQL_TYPE_TINY_BLOB => "tinytext",
+        MySQLColumnType::MYSQL_TYPE_MEDIUM_BLOB if binary => "mediumblob",
+        MySQLColumnType::MYSQL_TYPE_MEDIUM_BLOB => "mediumtext",
+        MySQLColumnType::MYSQL_TYPE_LONG_BLOB if binary => "longblob",
+        MySQLColumnType::MYSQL_TYPE_LONG_BLOB => "longtext",
+        MySQLColumnType::MYSQL_TYPE_BLOB if binary => "blob",
+        MySQLColumnType::MYSQL_TYPE_BLOB => "text",
+        _ => "unknown",
+    }
+}
+
+// Move `result` on to the next result set with columns and describe them. The statements
+// without columns, e.g. the call that closes the results of a procedure, only report a status.
+#[allow(clippy::type_complexity)]
+#[throws(MySQLSourceError)]
+fn next_result_set<T: Protocol>(
+    result: &mut QueryResult<'_, '_, '_, T>,
+    server_version_post_5_5_3: bool,
+) -> Option<(Vec<String>, Vec<MySQLTypeSystem>)> {
+    loop {
+        let columns = result.columns();
+        if !columns.as_ref().is_empty() {
+            break Some(
+                columns
+                    .as_ref()
+                    .iter()
+                    .map(|col| {
+                        Ok((
+                            col.name_str().to_string(),
+                            column_type(col, server_version_post_5_5_3)?,
+                        ))
+                    })
+                    .collect::<Result<Vec<_>, MySQLSourceError>>()?
+                    .into_iter()
+                    .unzip(),
+            );
+        }
+        // the rows of the previous set are fetched, so `result` is on a status or an error
+        match result.iter() {
+            Some(mut status) => {
+                if let Some(Err(e)) = status.next() {
+                    throw!(e);
+                }
+            }
+            None => break None,
+        }
+    }
 }
 
 pub struct MySQLSource<P> {
@@ -50,17 +199,22 @@ pub struct MySQLSource<P> {
     queries: Vec<CXQuery<String>>,
     names: Vec<String>,
     schema: Vec<MySQLTypeSystem>,
+    columns: Vec<Column>,
     pre_execution_queries: Option<Vec<String>>,
+    post_execution_queries: Option<Vec<String>>,
     _protocol: PhantomData<P>,
 }
 
 impl<P> MySQLSource<P> {
     #[throws(MySQLSourceError)]
     pub fn new(conn: &str, nconn: usize) -> Self {
-        let manager = MySqlConnectionManager::new(OptsBuilder::from_opts(Opts::from_url(conn)?));
-        let pool = r2d2::Pool::builder()
-            .max_size(nconn as u32)
-            .build(manager)?;
+        let url = Url::parse(conn).map_err(|e| anyhow!("parse error: {}", e))?;
+        let manager = MySqlConnectionManager::new(mysql_opts(&url)?);
+        let mut builder = r2d2::Pool::builder().max_size(nconn as u32);
+        if let Some(timeout) = Timeouts::from_url(&url)?.connect {
+            builder = builder.connection_timeout(timeout);
+        }
+        let pool = builder.build(manager)?;
 
         Self {
             pool,
@@ -68,7 +222,9 @@ impl<P> MySQLSource<P> {
             queries: vec![],
             names: vec![],
             schema: vec![],
+            columns: vec![],
             pre_execution_queries: None,
+            post_execution_queries: None,
             _protocol: PhantomData,
         }
     }
@@ -104,6 +260,41 @@ where
         self.pre_execution_queries = pre_execution_queries.map(|s| s.to_vec());
     }
 
+    fn set_post_execution_queries(&mut self, post_execution_queries: Option<&[String]>) {
+        self.post_execution_queries = post_execution_queries.map(|s| s.to_vec());
+    }
+
+    fn fork(&self) -> Self {
+        Self {
+            pool: self.pool.clone(),
+            origin_query: None,
+            queries: vec![],
+            names: vec![],
+            schema: vec![],
+            columns: vec![],
+            pre_execution_queries: self.pre_execution_queries.clone(),
+            post_execution_queries: self.post_execution_queries.clone(),
+            _protocol: PhantomData,
+        }
+    }
+
+    /// The query runs as a prepared statement with the binary protocol, e.g. `CALL proc(?)`, and
+    /// with its parameters inlined with the text protocol. The status of the call that closes the
+    /// results of a procedure carries no columns and is skipped.
+    #[throws(MySQLSourceError)]
+    fn raw_partition(self) -> Self::Partition {
+        assert!(!self.queries.is_empty());
+
+        let mut conn = self.pool.get()?;
+        if let Some(pre_queries) = &self.pre_execution_queries {
+            for pre_query in pre_queries {
+                conn.query_drop(pre_query)?;
+            }
+        }
+
+        MySQLSourcePartition::new(conn, &self.queries[0], &[], self.post_execution_queries)
+    }
+
     #[throws(MySQLSourceError)]
     fn fetch_metadata(&mut self) {
         assert!(!self.queries.is_empty());
@@ -119,32 +310,17 @@ where
                     .columns()
                     .iter()
                     .map(|col| {
-                        let col_name = col.name_str().to_string();
-                        let col_type = col.column_type();
-                        let col_flags = col.flags();
-                        let charset = col.character_set();
-                        let charset_is_utf8 = (server_version_post_5_5_3
-                            && charset == UTF8MB4_GENERAL_CI)
-                            || (!server_version_post_5_5_3 && charset == UTF8_GENERAL_CI);
-                        if charset_is_utf8
-                            && (col_type == MySQLColumnType::MYSQL_TYPE_LONG_BLOB
-                                || col_type == MySQLColumnType::MYSQL_TYPE_BLOB
-                                || col_type == MySQLColumnType::MYSQL_TYPE_MEDIUM_BLOB
-                                || col_type == MySQLColumnType::MYSQL_TYPE_TINY_BLOB)
-                        {
-                            return (
-                                col_name,
-                                MySQLTypeSystem::Char(
-                                    !col_flags.contains(MySQLColumnFlags::NOT_NULL_FLAG),
-                                ),
-                            );
-                        }
-                        let d = MySQLTypeSystem::from((&col_type, &col_flags));
-                        (col_name, d)
+                        Ok((
+                            col.name_str().to_string(),
+                            column_type(col, server_version_post_5_5_3)?,
+                        ))
                     })
+                    .collect::<Result<Vec<_>, MySQLSourceError>>()?
+                    .into_iter()
                     .unzip();
                 self.names = names;
                 self.schema = types;
+                self.columns = stmt.columns().to_vec();
             }
             Err(e) => {
                 warn!(
@@ -153,28 +329,37 @@ where
                 );
                 for (i, query) in self.queries.iter().enumerate() {
                     // assuming all the partition queries yield same schema
-                    match conn
-                        .query_first::<Row, _>(limit1_query(query, &MySqlDialect {})?.as_str())
-                    {
+                    let l1query = limit1_query(query, &MySqlDialect {})?;
+                    let row = match l1query.params() {
+                        [] => conn.query_first::<Row, _>(l1query.as_str()),
+                        params => conn.exec_first(l1query.as_str(), mysql_params(params)),
+                    };
+                    match row {
                         Ok(Some(row)) => {
                             let (names, types) = row
                                 .columns_ref()
                                 .iter()
                                 .map(|col| {
-                                    (
+                                    Ok((
                                         col.name_str().to_string(),
-                                        MySQLTypeSystem::from((&col.column_type(), &col.flags())),
-                                    )
+                                        MySQLTypeSystem::try_from((
+                                            &col.column_type(),
+                                            &col.flags(),
+                                        ))?,
+                                    ))
                                 })
+                                .collect::<Result<Vec<_>, MySQLSourceError>>()?
+                                .into_iter()
                                 .unzip();
                             self.names = names;
                             self.schema = types;
+                            self.columns = row.columns_ref().to_vec();
                             return;
                         }
                         Ok(None) => {}
                         Err(e) if i == self.queries.len() - 1 => {
                             // tried the last query but still get an error
-                            debug!("cannot get metadata for '{}', try next query: {}", query, e);
+                            debug!(query = %redact_query(query.as_str()), error = %e, "cannot get metadata");
                             throw!(e)
                         }
                         Err(_) => {}
@@ -196,6 +381,7 @@ where
                     .unzip();
                 self.names = names;
                 self.schema = types;
+                self.columns = iter.columns().as_ref().to_vec();
             }
         }
     }
@@ -204,7 +390,8 @@ where
     fn result_rows(&mut self) -> Option<usize> {
         match &self.origin_query {
             Some(q) => {
-                let cxq = CXQuery::Naked(q.clone());
+                // the partitioned queries are bound to the parameters of the origin query
+                let cxq = CXQuery::Naked(q.clone()).bind(self.queries[0].params().to_vec());
                 let mut conn = self.pool.get()?;
                 let nrows = get_total_rows(&mut conn, &cxq)?;
                 Some(nrows)
@@ -221,6 +408,75 @@ where
         self.schema.clone()
     }
 
+    /// Table columns are described by their `column_type` in `information_schema.columns`, e.g.
+    /// `decimal(12,2) unsigned`. The other columns are described by their type on the wire.
+    #[throws(MySQLSourceError)]
+    fn describe_columns(&mut self) -> Vec<ColumnDescription> {
+        // the table columns are looked up at once
+        let origins: Vec<MySQLValue> = self
+            .columns
+            .iter()
+            .filter(|col| !col.org_table_ref().is_empty())
+            .flat_map(|col| {
+                vec![
+                    MySQLValue::from(col.schema_ref()),
+                    MySQLValue::from(col.org_table_ref()),
+                    MySQLValue::from(col.org_name_ref()),
+                ]
+            })
+            .collect();
+        let mut declared = HashMap::new();
+        if !origins.is_empty() {
+            let query = format!(
+                "SELECT table_schema, table_name, column_name, column_type, is_nullable \
+                 FROM information_schema.columns \
+                 WHERE (table_schema, table_name, column_name) IN ({})",
+                vec!["(?, ?, ?)"; origins.len() / 3].join(", ")
+            );
+            let mut conn = self.pool.get()?;
+            let rows: Vec<(String, String, String, String, String)> = conn.exec(query, origins)?;
+            for (schema, table, column, column_type, is_nullable) in rows {
+                declared.insert((schema, table, column), (column_type, is_nullable == "YES"));
+            }
+        }
+
+        self.columns
+            .iter()
+            .map(|col| {
+                let origin = (
+                    col.schema_str().into_owned(),
+                    col.org_table_str().into_owned(),
+                    col.org_name_str().into_owned(),
+                );
+                let mut description = match declared.get(&origin) {
+                    Some((column_type, nullable)) => ColumnDescription {
+                        source_type: column_type.clone(),
+                        nullable: Some(*nullable),
+                        ..Default::default()
+                    },
+                    None => ColumnDescription {
+                        source_type: column_type_name(col).to_string(),
+                        nullable: Some(!col.flags().contains(MySQLColumnFlags::NOT_NULL_FLAG)),
+                        ..Default::default()
+                    },
+                };
+                if let MySQLColumnType::MYSQL_TYPE_DECIMAL
+                | MySQLColumnType::MYSQL_TYPE_NEWDECIMAL = col.column_type()
+                {
+                    // the display length counts the decimal point and the sign
+                    let scale = u32::from(col.decimals());
+                    let unsigned = col.flags().contains(MySQLColumnFlags::UNSIGNED_FLAG);
+                    let precision = col
+                        .column_length()
+                        .saturating_sub(u32::from(scale > 0) + u32::from(!unsigned));
+                    description.precision = Some(precision);
+                    description.scale = Some(scale as i32);
+                }
+                description
+            })
+            .collect()
+    }
+
     #[throws(MySQLSourceError)]
     fn partition(self) -> Vec<Self::Partition> {
         let mut ret = vec![];
@@ -233,7 +489,12 @@ where
                 }
             }
 
-            ret.push(MySQLSourcePartition::new(conn, &query, &self.schema));
+            ret.push(MySQLSourcePartition::new(
+                conn,
+                &query,
+                &self.schema,
+                self.post_execution_queries.clone(),
+            ));
         }
         ret
     }
@@ -245,20 +506,36 @@ pub struct MySQLSourcePartition<P> {
     schema: Vec<MySQLTypeSystem>,
     nrows: usize,
     ncols: usize,
+    post_execution_queries: Option<Vec<String>>,
     _protocol: PhantomData<P>,
 }
 
 impl<P> MySQLSourcePartition<P> {
-    pub fn new(conn: MysqlConn, query: &CXQuery<String>, schema: &[MySQLTypeSystem]) -> Self {
+    pub fn new(
+        conn: MysqlConn,
+        query: &CXQuery<String>,
+        schema: &[MySQLTypeSystem],
+        post_execution_queries: Option<Vec<String>>,
+    ) -> Self {
         Self {
             conn,
             query: query.clone(),
             schema: schema.to_vec(),
             nrows: 0,
             ncols: schema.len(),
+            post_execution_queries,
             _protocol: PhantomData,
         }
     }
+
+    #[throws(MySQLSourceError)]
+    fn run_post_execution_queries(&mut self) {
+        if let Some(post_queries) = &self.post_execution_queries {
+            for post_query in post_queries {
+                self.conn.query_drop(post_query)?;
+            }
+        }
+    }
 }
 
 impl SourcePartition for MySQLSourcePartition<BinaryProtocol> {
@@ -273,9 +550,14 @@ impl SourcePartition for MySQLSourcePartition<BinaryProtocol> {
 
     #[throws(MySQLSourceError)]
     fn parser(&mut self) -> Self::Parser<'_> {
+        let server_version_post_5_5_3 = self.conn.server_version() >= (5, 5, 3);
         let stmt = self.conn.prep(self.query.as_str())?;
-        let iter = self.conn.exec_iter(stmt, ())?;
-        MySQLBinarySourceParser::new(iter, &self.schema)
+        let iter = self
+            .conn
+            .exec_iter(stmt, mysql_params(self.query.params()))?;
+        let mut parser = MySQLBinarySourceParser::new(iter, &self.schema);
+        parser.server_version_post_5_5_3 = server_version_post_5_5_3;
+        parser
     }
 
     fn nrows(&self) -> usize {
@@ -285,6 +567,11 @@ impl SourcePartition for MySQLSourcePartition<BinaryProtocol> {
     fn ncols(&self) -> usize {
         self.ncols
     }
+
+    #[throws(MySQLSourceError)]
+    fn finish(&mut self) {
+        self.run_post_execution_queries()?;
+    }
 }
 
 impl SourcePartition for MySQLSourcePartition<TextProtocol> {
@@ -299,9 +586,20 @@ impl SourcePartition for MySQLSourcePartition<TextProtocol> {
 
     #[throws(MySQLSourceError)]
     fn parser(&mut self) -> Self::Parser<'_> {
-        let query = self.query.clone();
+        let server_version_post_5_5_3 = self.conn.server_version() >= (5, 5, 3);
+        // the text protocol cannot bind parameters, so they are inlined as escaped literals
+        let no_backslash_escape = self.conn.no_backslash_escape();
+        let query = inline_params(
+            self.query.as_str(),
+            self.query.params(),
+            PlaceholderStyle::QuestionMark,
+            !no_backslash_escape,
+            |param| mysql_value(param).as_sql(no_backslash_escape),
+        )?;
         let iter = self.conn.query_iter(query)?;
-        MySQLTextSourceParser::new(iter, &self.schema)
+        let mut parser = MySQLTextSourceParser::new(iter, &self.schema);
+        parser.server_version_post_5_5_3 = server_version_post_5_5_3;
+        parser
     }
 
     fn nrows(&self) -> usize {
@@ -311,6 +609,11 @@ impl SourcePartition for MySQLSourcePartition<TextProtocol> {
     fn ncols(&self) -> usize {
         self.ncols
     }
+
+    #[throws(MySQLSourceError)]
+    fn finish(&mut self) {
+        self.run_post_execution_queries()?;
+    }
 }
 
 pub struct MySQLBinarySourceParser<'a> {
@@ -320,6 +623,7 @@ pub struct MySQLBinarySourceParser<'a> {
     current_col: usize,
     current_row: usize,
     is_finished: bool,
+    server_version_post_5_5_3: bool,
 }
 
 impl<'a> MySQLBinarySourceParser<'a> {
@@ -331,6 +635,7 @@ impl<'a> MySQLBinarySourceParser<'a> {
             current_row: 0,
             current_col: 0,
             is_finished: false,
+            server_version_post_5_5_3: false,
         }
     }
 
@@ -374,6 +679,19 @@ impl<'a> PartitionParser<'a> for MySQLBinarySourceParser<'a> {
 
         (self.rowbuf.len(), self.is_finished)
     }
+
+    #[throws(MySQLSourceError)]
+    fn next_result_set(&mut self) -> Option<(Vec<String>, Vec<MySQLTypeSystem>)> {
+        let result_set = next_result_set(&mut self.iter, self.server_version_post_5_5_3)?;
+        if let Some((_, schema)) = &result_set {
+            self.rowbuf.clear();
+            self.ncols = schema.len();
+            self.current_row = 0;
+            self.current_col = 0;
+            self.is_finished = false;
+        }
+        result_set
+    }
 }
 
 macro_rules! impl_produce_binary {
@@ -416,7 +734,6 @@ impl_produce_binary!(
     f32,
     f64,
     NaiveDate,
-    NaiveTime,
     NaiveDateTime,
     Decimal,
     String,
@@ -431,6 +748,7 @@ pub struct MySQLTextSourceParser<'a> {
     current_col: usize,
     current_row: usize,
     is_finished: bool,
+    server_version_post_5_5_3: bool,
 }
 
 impl<'a> MySQLTextSourceParser<'a> {
@@ -442,6 +760,7 @@ impl<'a> MySQLTextSourceParser<'a> {
             current_row: 0,
             current_col: 0,
             is_finished: false,
+            server_version_post_5_5_3: false,
         }
     }
 
@@ -483,6 +802,19 @@ impl<'a> PartitionParser<'a> for MySQLTextSourceParser<'a> {
         self.current_col = 0;
         (self.rowbuf.len(), self.is_finished)
     }
+
+    #[throws(MySQLSourceError)]
+    fn next_result_set(&mut self) -> Option<(Vec<String>, Vec<MySQLTypeSystem>)> {
+        let result_set = next_result_set(&mut self.iter, self.server_version_post_5_5_3)?;
+        if let Some((_, schema)) = &result_set {
+            self.rowbuf.clear();
+            self.ncols = schema.len();
+            self.current_row = 0;
+            self.current_col = 0;
+            self.is_finished = false;
+        }
+        result_set
+    }
 }
 
 macro_rules! impl_produce_text {
@@ -525,10 +857,85 @@ impl_produce_text!(
     f32,
     f64,
     NaiveDate,
-    NaiveTime,
     NaiveDateTime,
     Decimal,
     String,
     Vec<u8>,
     Value,
 );
+
+// `TIME` ranges from -838:59:59 to 838:59:59, which `NaiveTime` cannot hold. The binary protocol
+// sends its parts, the text protocol a string such as `-838:59:59.000000`.
+#[throws(MySQLSourceError)]
+fn duration_from_value(value: MySQLValue) -> Option<Duration> {
+    match value {
+        MySQLValue::NULL => None,
+        MySQLValue::Time(negative, days, hours, minutes, seconds, micros) => {
+            let duration = Duration::days(days as i64)
+                + Duration::hours(hours as i64)
+                + Duration::minutes(minutes as i64)
+                + Duration::seconds(seconds as i64)
+                + Duration::microseconds(micros as i64);
+            Some(if negative { -duration } else { duration })
+        }
+        MySQLValue::Bytes(bytes) => {
+            let s = std::str::from_utf8(&bytes).map_err(|e| anyhow!(e))?;
+            let (negative, time) = match s.strip_prefix('-') {
+                Some(time) => (true, time),
+                None => (false, s),
+            };
+            let (time, fraction) = time.split_once('.').unwrap_or((time, ""));
+            let parts = time
+                .split(':')
+                .map(|p| p.parse::<i64>())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|_| ConnectorXError::cannot_produce::<Duration>(Some(s.into())))?;
+            let (hours, minutes, seconds) = match parts[..] {
+                [hours, minutes, seconds] => (hours, minutes, seconds),
+                _ => throw!(ConnectorXError::cannot_produce::<Duration>(Some(s.into()))),
+            };
+            let micros = format!("{:0<6}", fraction)[..6]
+                .parse::<i64>()
+                .map_err(|_| ConnectorXError::cannot_produce::<Duration>(Some(s.into())))?;
+            let duration = Duration::hours(hours)
+                + Duration::minutes(minutes)
+                + Duration::seconds(seconds)
+                + Duration::microseconds(micros);
+            Some(if negative { -duration } else { duration })
+        }
+        v => throw!(ConnectorXError::cannot_produce::<Duration>(Some(format!(
+            "{:?}",
+            v
+        )))),
+    }
+}
+
+macro_rules! impl_produce_duration {
+    ($($parser: ident,)+) => {
+        $(
+            impl<'r, 'a> Produce<'r, Duration> for $parser<'a> {
+                type Error = MySQLSourceError;
+
+                #[throws(MySQLSourceError)]
+                fn produce(&'r mut self) -> Duration {
+                    let (ridx, cidx) = self.next_loc()?;
+                    let value = self.rowbuf[ridx].take(cidx).ok_or_else(|| anyhow!("mysql cannot parse at position: ({}, {})", ridx, cidx))?;
+                    duration_from_value(value)?.ok_or_else(|| anyhow!("Cannot parse NULL in NOT NULL column."))?
+                }
+            }
+
+            impl<'r, 'a> Produce<'r, Option<Duration>> for $parser<'a> {
+                type Error = MySQLSourceError;
+
+                #[throws(MySQLSourceError)]
+                fn produce(&'r mut self) -> Option<Duration> {
+                    let (ridx, cidx) = self.next_loc()?;
+                    let value = self.rowbuf[ridx].take(cidx).ok_or_else(|| anyhow!("mysql cannot parse at position: ({}, {})", ridx, cidx))?;
+                    duration_from_value(value)?
+                }
+            }
+        )+
+    };
+}
+
+impl_produce_duration!(MySQLBinarySourceParser, MySQLTextSourceParser,);
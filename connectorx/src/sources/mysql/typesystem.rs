@@ -1,7 +1,11 @@
-use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use super::errors::MySQLSourceError;
+use crate::errors::ConnectorXError;
+use chrono::{Duration, NaiveDate, NaiveDateTime};
+use fehler::{throw, throws};
 use r2d2_mysql::mysql::consts::{ColumnFlags, ColumnType};
 use rust_decimal::Decimal;
 use serde_json::Value;
+use std::convert::TryFrom;
 
 #[derive(Copy, Clone, Debug)]
 pub enum MySQLTypeSystem {
@@ -49,7 +53,7 @@ impl_typesystem! {
         { ULongLong => u64 }
         { Datetime | Timestamp => NaiveDateTime }
         { Date => NaiveDate }
-        { Time => NaiveTime }
+        { Time => Duration }
         { Decimal => Decimal }
         { Char | VarChar | Enum => String }
         { TinyBlob | Blob | MediumBlob | LongBlob | Bit => Vec<u8>}
@@ -57,8 +61,11 @@ impl_typesystem! {
     }
 }
 
-impl<'a> From<(&'a ColumnType, &'a ColumnFlags)> for MySQLTypeSystem {
-    fn from(col: (&'a ColumnType, &'a ColumnFlags)) -> MySQLTypeSystem {
+impl<'a> TryFrom<(&'a ColumnType, &'a ColumnFlags)> for MySQLTypeSystem {
+    type Error = MySQLSourceError;
+
+    #[throws(MySQLSourceError)]
+    fn try_from(col: (&'a ColumnType, &'a ColumnFlags)) -> MySQLTypeSystem {
         use MySQLTypeSystem::*;
         let (ty, flag) = col;
         let null_ok = !flag.contains(ColumnFlags::NOT_NULL_FLAG);
@@ -118,7 +125,7 @@ impl<'a> From<(&'a ColumnType, &'a ColumnFlags)> for MySQLTypeSystem {
             ColumnType::MYSQL_TYPE_JSON => Json(null_ok),
             ColumnType::MYSQL_TYPE_VARCHAR => VarChar(null_ok),
             ColumnType::MYSQL_TYPE_BIT => Bit(null_ok),
-            _ => unimplemented!("{}", format!("{:?}", ty)),
+            _ => throw!(ConnectorXError::UnsupportedType(format!("{:?}", ty))),
         }
     }
 }
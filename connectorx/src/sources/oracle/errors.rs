@@ -1,3 +1,4 @@
+use crate::sources::timeout::is_pool_timeout;
 use std::string::FromUtf8Error;
 use thiserror::Error;
 
@@ -22,3 +23,18 @@ pub enum OracleSourceError {
     #[error(transparent)]
     Other(#[from] anyhow::Error),
 }
+
+impl OracleSourceError {
+    /// Whether the error is raised because the statement or connect timeout elapsed.
+    pub fn is_timeout(&self) -> bool {
+        use r2d2_oracle::oracle::ErrorKind;
+        match self {
+            OracleSourceError::OraclePoolError(e) => is_pool_timeout(e),
+            // DPI-1067: call timeout exceeded
+            OracleSourceError::OracleError(e) => {
+                e.kind() == ErrorKind::DpiError && e.db_error().map(|e| e.code()) == Some(1067)
+            }
+            _ => false,
+        }
+    }
+}
@@ -1,7 +1,9 @@
 mod errors;
 mod typesystem;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use std::convert::TryFrom;
+use std::time::Duration;
 
 pub use self::errors::OracleSourceError;
 pub use self::typesystem::OracleTypeSystem;
@@ -9,21 +11,29 @@ use crate::constants::{DB_BUFFER_SIZE, ORACLE_ARRAY_SIZE};
 use crate::{
     data_order::DataOrder,
     errors::ConnectorXError,
-    sources::{PartitionParser, Produce, Source, SourcePartition},
-    sql::{count_query, limit1_query_oracle, CXQuery},
+    sources::{
+        timeout::Timeouts, ColumnDescription, PartitionParser, Produce, Source, SourcePartition,
+    },
+    sql::{
+        count_query, describe_query_oracle, limit1_query_oracle, redact_query, CXParam, CXQuery,
+    },
+    typesystem::Interval,
     utils::DummyBox,
 };
 use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
 use fehler::{throw, throws};
-use log::debug;
 use owning_ref::OwningHandle;
 use r2d2::{Pool, PooledConnection};
 use r2d2_oracle::oracle::ResultSet;
 use r2d2_oracle::{
-    oracle::{Connector, Row, Statement},
+    oracle::{
+        sql_type::{FromSql, IntervalYM, OracleType, RefCursor, ToSql},
+        ColumnInfo, Connection, Connector, Row, SqlValue, Statement,
+    },
     OracleConnectionManager,
 };
 use sqlparser::dialect::Dialect;
+use tracing::debug;
 use url::Url;
 use urlencoding::decode;
 
@@ -44,13 +54,50 @@ impl Dialect for OracleDialect {
     }
 }
 
+fn sql_params(params: &[CXParam]) -> Vec<&dyn ToSql> {
+    params.iter().map(|p| p as &dyn ToSql).collect()
+}
+
+impl ToSql for CXParam {
+    fn oratype(&self, conn: &Connection) -> r2d2_oracle::oracle::Result<OracleType> {
+        match self {
+            CXParam::Null => None::<String>.oratype(conn),
+            // booleans are only available in PL/SQL, so they are bound as 1 and 0
+            CXParam::Bool(_) => 0i64.oratype(conn),
+            CXParam::I64(v) => v.oratype(conn),
+            CXParam::F64(v) => v.oratype(conn),
+            CXParam::Str(v) => v.oratype(conn),
+            CXParam::Bytes(v) => v.oratype(conn),
+            CXParam::Date(v) => v.oratype(conn),
+            CXParam::DateTime(v) => v.oratype(conn),
+        }
+    }
+
+    fn to_sql(&self, val: &mut SqlValue) -> r2d2_oracle::oracle::Result<()> {
+        match self {
+            CXParam::Null => val.set_null(),
+            CXParam::Bool(v) => (*v as i64).to_sql(val),
+            CXParam::I64(v) => v.to_sql(val),
+            CXParam::F64(v) => v.to_sql(val),
+            CXParam::Str(v) => v.to_sql(val),
+            CXParam::Bytes(v) => v.to_sql(val),
+            CXParam::Date(v) => v.to_sql(val),
+            CXParam::DateTime(v) => v.to_sql(val),
+        }
+    }
+}
+
 pub struct OracleSource {
     pool: Pool<OracleManager>,
     origin_query: Option<String>,
     queries: Vec<CXQuery<String>>,
     names: Vec<String>,
     schema: Vec<OracleTypeSystem>,
+    columns: Vec<ColumnDescription>,
     current_schema: Option<String>,
+    call_timeout: Option<Duration>,
+    pre_execution_queries: Option<Vec<String>>,
+    post_execution_queries: Option<Vec<String>>,
 }
 
 #[throws(OracleSourceError)]
@@ -82,10 +129,13 @@ impl OracleSource {
     pub fn new(conn: &str, nconn: usize) -> Self {
         let conn = Url::parse(conn)?;
         let connector = connect_oracle(&conn)?;
+        let timeouts = Timeouts::from_url(&conn)?;
         let manager = OracleConnectionManager::from_connector(connector);
-        let pool = r2d2::Pool::builder()
-            .max_size(nconn as u32)
-            .build(manager)?;
+        let mut builder = r2d2::Pool::builder().max_size(nconn as u32);
+        if let Some(timeout) = timeouts.connect {
+            builder = builder.connection_timeout(timeout);
+        }
+        let pool = builder.build(manager)?;
 
         let params: HashMap<String, String> = conn.query_pairs().into_owned().collect();
         let current_schema = params.get("schema").cloned();
@@ -96,7 +146,11 @@ impl OracleSource {
             queries: vec![],
             names: vec![],
             schema: vec![],
+            columns: vec![],
             current_schema,
+            call_timeout: timeouts.statement,
+            pre_execution_queries: None,
+            post_execution_queries: None,
         }
     }
     pub fn get_conn(&self) -> Result<OracleConn, OracleSourceError> {
@@ -104,6 +158,8 @@ impl OracleSource {
         if let Some(schema) = &self.current_schema {
             conn.set_current_schema(schema)?;
         }
+        // connections are reused by the pool, so always reset the call timeout
+        conn.set_call_timeout(self.call_timeout)?;
         Ok(conn)
     }
 }
@@ -133,42 +189,95 @@ where
         self.origin_query = query;
     }
 
+    fn set_pre_execution_queries(&mut self, pre_execution_queries: Option<&[String]>) {
+        self.pre_execution_queries = pre_execution_queries.map(|s| s.to_vec());
+    }
+
+    fn set_post_execution_queries(&mut self, post_execution_queries: Option<&[String]>) {
+        self.post_execution_queries = post_execution_queries.map(|s| s.to_vec());
+    }
+
+    fn fork(&self) -> Self {
+        Self {
+            pool: self.pool.clone(),
+            origin_query: None,
+            queries: vec![],
+            names: vec![],
+            schema: vec![],
+            columns: vec![],
+            current_schema: self.current_schema.clone(),
+            call_timeout: self.call_timeout,
+            pre_execution_queries: self.pre_execution_queries.clone(),
+            post_execution_queries: self.post_execution_queries.clone(),
+        }
+    }
+
+    /// A procedure call (e.g. `BEGIN proc(:1, :2); END;`) binds the query parameters first, and
+    /// every placeholder after them as a `SYS_REFCURSOR` out parameter. Each opened cursor yields
+    /// a result set, followed by the implicit results returned with `DBMS_SQL.RETURN_RESULT`.
+    #[throws(OracleSourceError)]
+    fn raw_partition(self) -> Self::Partition {
+        assert!(!self.queries.is_empty());
+
+        let conn = self.get_conn()?;
+        if let Some(pre_queries) = &self.pre_execution_queries {
+            for pre_query in pre_queries {
+                conn.execute(pre_query, &[])?;
+            }
+        }
+
+        let mut partition =
+            OracleSourcePartition::new(conn, &self.queries[0], &[], self.post_execution_queries);
+        partition.raw = true;
+        partition
+    }
+
     #[throws(OracleSourceError)]
     fn fetch_metadata(&mut self) {
         assert!(!self.queries.is_empty());
 
         let conn = self.get_conn()?;
+        let dquery = describe_query_oracle(&self.queries[0]);
+        match conn.query(dquery.as_str(), &sql_params(dquery.params())) {
+            Ok(rows) => {
+                let (names, types) = describe_rows(&rows)?;
+                self.names = names;
+                self.schema = types;
+                self.columns = rows.column_info().iter().map(describe_column).collect();
+                return;
+            }
+            Err(e) => {
+                debug!(error = %e, "cannot describe query, fall back to limit 1");
+            }
+        }
+
         for (i, query) in self.queries.iter().enumerate() {
             // assuming all the partition queries yield same schema
             // without rownum = 1, derived type might be wrong
             // example: select avg(test_int), test_char from test_table group by test_char
             // -> (NumInt, Char) instead of (NumtFloat, Char)
-            match conn.query(limit1_query_oracle(query)?.as_str(), &[]) {
+            let l1query = limit1_query_oracle(query)?;
+            match conn.query(l1query.as_str(), &sql_params(l1query.params())) {
                 Ok(rows) => {
-                    let (names, types) = rows
-                        .column_info()
-                        .iter()
-                        .map(|col| {
-                            (
-                                col.name().to_string(),
-                                OracleTypeSystem::from(col.oracle_type()),
-                            )
-                        })
-                        .unzip();
+                    let (names, types) = describe_rows(&rows)?;
                     self.names = names;
                     self.schema = types;
+                    self.columns = rows.column_info().iter().map(describe_column).collect();
                     return;
                 }
                 Err(e) if i == self.queries.len() - 1 => {
                     // tried the last query but still get an error
-                    debug!("cannot get metadata for '{}': {}", query, e);
+                    debug!(query = %redact_query(query.as_str()), error = %e, "cannot get metadata");
                     throw!(e);
                 }
                 Err(_) => {}
             }
         }
         // tried all queries but all get empty result set
-        let iter = conn.query(self.queries[0].as_str(), &[])?;
+        let iter = conn.query(
+            self.queries[0].as_str(),
+            &sql_params(self.queries[0].params()),
+        )?;
         let (names, types) = iter
             .column_info()
             .iter()
@@ -176,17 +285,20 @@ where
             .unzip();
         self.names = names;
         self.schema = types;
+        self.columns = iter.column_info().iter().map(describe_column).collect();
     }
 
     #[throws(OracleSourceError)]
     fn result_rows(&mut self) -> Option<usize> {
         match &self.origin_query {
             Some(q) => {
-                let cxq = CXQuery::Naked(q.clone());
+                // the partitioned queries are bound to the parameters of the origin query
+                let cxq = CXQuery::Naked(q.clone()).bind(self.queries[0].params().to_vec());
                 let conn = self.get_conn()?;
 
-                let nrows = conn
-                    .query_row_as::<usize>(count_query(&cxq, &OracleDialect {})?.as_str(), &[])?;
+                let cquery = count_query(&cxq, &OracleDialect {})?;
+                let nrows =
+                    conn.query_row_as::<usize>(cquery.as_str(), &sql_params(cquery.params()))?;
                 Some(nrows)
             }
             None => None,
@@ -201,12 +313,30 @@ where
         self.schema.clone()
     }
 
+    /// The columns are described with their declared type, e.g. `NUMBER(12,2)`.
+    #[throws(OracleSourceError)]
+    fn describe_columns(&mut self) -> Vec<ColumnDescription> {
+        self.columns.clone()
+    }
+
     #[throws(OracleSourceError)]
     fn partition(self) -> Vec<Self::Partition> {
         let mut ret = vec![];
         for query in &self.queries {
             let conn = self.get_conn()?;
-            ret.push(OracleSourcePartition::new(conn, &query, &self.schema));
+
+            if let Some(pre_queries) = &self.pre_execution_queries {
+                for pre_query in pre_queries {
+                    conn.execute(pre_query, &[])?;
+                }
+            }
+
+            ret.push(OracleSourcePartition::new(
+                conn,
+                query,
+                &self.schema,
+                self.post_execution_queries.clone(),
+            ));
         }
         ret
     }
@@ -218,16 +348,26 @@ pub struct OracleSourcePartition {
     schema: Vec<OracleTypeSystem>,
     nrows: usize,
     ncols: usize,
+    post_execution_queries: Option<Vec<String>>,
+    // whether the query runs as is, see `Source::raw_partition`
+    raw: bool,
 }
 
 impl OracleSourcePartition {
-    pub fn new(conn: OracleConn, query: &CXQuery<String>, schema: &[OracleTypeSystem]) -> Self {
+    pub fn new(
+        conn: OracleConn,
+        query: &CXQuery<String>,
+        schema: &[OracleTypeSystem],
+        post_execution_queries: Option<Vec<String>>,
+    ) -> Self {
         Self {
             conn,
             query: query.clone(),
             schema: schema.to_vec(),
             nrows: 0,
             ncols: schema.len(),
+            post_execution_queries,
+            raw: false,
         }
     }
 }
@@ -239,17 +379,21 @@ impl SourcePartition for OracleSourcePartition {
 
     #[throws(OracleSourceError)]
     fn result_rows(&mut self) {
+        let cquery = count_query(&self.query, &OracleDialect {})?;
         self.nrows = self
             .conn
-            .query_row_as::<usize>(count_query(&self.query, &OracleDialect {})?.as_str(), &[])?;
+            .query_row_as::<usize>(cquery.as_str(), &sql_params(cquery.params()))?;
     }
 
     #[throws(OracleSourceError)]
     fn parser(&mut self) -> Self::Parser<'_> {
         let query = self.query.clone();
+        if self.raw {
+            return OracleTextSourceParser::raw(&self.conn, query.as_str(), query.params())?;
+        }
 
         // let iter = self.conn.query(query.as_str(), &[])?;
-        OracleTextSourceParser::new(&self.conn, query.as_str(), &self.schema)?
+        OracleTextSourceParser::new(&self.conn, query.as_str(), query.params(), &self.schema)?
     }
 
     fn nrows(&self) -> usize {
@@ -259,12 +403,87 @@ impl SourcePartition for OracleSourcePartition {
     fn ncols(&self) -> usize {
         self.ncols
     }
+
+    #[throws(OracleSourceError)]
+    fn finish(&mut self) {
+        if let Some(post_queries) = &self.post_execution_queries {
+            for post_query in post_queries {
+                self.conn.execute(post_query, &[])?;
+            }
+        }
+    }
 }
 
 unsafe impl<'a> Send for OracleTextSourceParser<'a> {}
 
+// the names and types of the columns of a result set
+#[throws(OracleSourceError)]
+fn describe_rows(rows: &ResultSet<Row>) -> (Vec<String>, Vec<OracleTypeSystem>) {
+    rows.column_info()
+        .iter()
+        .map(|col| {
+            Ok((
+                col.name().to_string(),
+                OracleTypeSystem::try_from(col.oracle_type())?,
+            ))
+        })
+        .collect::<Result<Vec<_>, OracleSourceError>>()?
+        .into_iter()
+        .unzip()
+}
+
+// the declared type of a column, e.g. `VARCHAR2(10)`
+fn describe_column(col: &ColumnInfo) -> ColumnDescription {
+    let mut description = ColumnDescription {
+        source_type: col.oracle_type().to_string(),
+        nullable: Some(col.nullable()),
+        ..Default::default()
+    };
+    match *col.oracle_type() {
+        // a number without a precision is a floating point number
+        OracleType::Number(precision, scale) if precision > 0 => {
+            description.precision = Some(u32::from(precision));
+            description.scale = Some(i32::from(scale));
+        }
+        OracleType::Varchar2(length)
+        | OracleType::NVarchar2(length)
+        | OracleType::Char(length)
+        | OracleType::NChar(length)
+        | OracleType::Raw(length) => description.length = Some(length),
+        OracleType::Timestamp(precision)
+        | OracleType::TimestampTZ(precision)
+        | OracleType::TimestampLTZ(precision) => description.precision = Some(u32::from(precision)),
+        _ => {}
+    }
+    description
+}
+
+// the owner of the rows of a result set
+enum RowsOwner {
+    Statement(Statement),
+    Cursor(RefCursor),
+}
+
+type OracleRows<'a> = OwningHandle<Box<RowsOwner>, DummyBox<ResultSet<'a, Row>>>;
+
+#[throws(OracleSourceError)]
+fn query_rows<'a>(owner: RowsOwner) -> OracleRows<'a> {
+    OwningHandle::try_new(Box::new(owner), |owner: *const RowsOwner| unsafe {
+        match &mut *(owner as *mut RowsOwner) {
+            RowsOwner::Statement(stmt) => stmt.query(&[]),
+            RowsOwner::Cursor(cursor) => cursor.query(),
+        }
+        .map(DummyBox)
+    })?
+}
+
 pub struct OracleTextSourceParser<'a> {
-    rows: OwningHandle<Box<Statement>, DummyBox<ResultSet<'a, Row>>>,
+    rows: Option<OracleRows<'a>>,
+    // the cursors opened by a procedure call, which are read one after another
+    cursors: VecDeque<RefCursor>,
+    // the procedure call, which is kept until its cursors are read
+    call: Option<Statement>,
+    result_set: Option<(Vec<String>, Vec<OracleTypeSystem>)>,
     rowbuf: Vec<Row>,
     ncols: usize,
     current_col: usize,
@@ -274,19 +493,26 @@ pub struct OracleTextSourceParser<'a> {
 
 impl<'a> OracleTextSourceParser<'a> {
     #[throws(OracleSourceError)]
-    pub fn new(conn: &'a OracleConn, query: &str, schema: &[OracleTypeSystem]) -> Self {
-        let stmt = conn
+    pub fn new(
+        conn: &'a OracleConn,
+        query: &str,
+        params: &[CXParam],
+        schema: &[OracleTypeSystem],
+    ) -> Self {
+        let mut stmt = conn
             .statement(query)
             .prefetch_rows(ORACLE_ARRAY_SIZE)
             .fetch_array_size(ORACLE_ARRAY_SIZE)
             .build()?;
-        let rows: OwningHandle<Box<Statement>, DummyBox<ResultSet<'a, Row>>> =
-            OwningHandle::new_with_fn(Box::new(stmt), |stmt: *const Statement| unsafe {
-                DummyBox((*(stmt as *mut Statement)).query(&[]).unwrap())
-            });
+        for (i, param) in params.iter().enumerate() {
+            stmt.bind(i + 1, param)?;
+        }
 
         Self {
-            rows,
+            rows: Some(query_rows(RowsOwner::Statement(stmt))?),
+            cursors: VecDeque::new(),
+            call: None,
+            result_set: None,
             rowbuf: Vec::with_capacity(DB_BUFFER_SIZE),
             ncols: schema.len(),
             current_row: 0,
@@ -295,6 +521,50 @@ impl<'a> OracleTextSourceParser<'a> {
         }
     }
 
+    /// Run `query` as is and read its result sets, see [`Source::raw_partition`].
+    #[throws(OracleSourceError)]
+    pub fn raw(conn: &'a OracleConn, query: &str, params: &[CXParam]) -> Self {
+        let mut stmt = conn
+            .statement(query)
+            .prefetch_rows(ORACLE_ARRAY_SIZE)
+            .fetch_array_size(ORACLE_ARRAY_SIZE)
+            .build()?;
+        for (i, param) in params.iter().enumerate() {
+            stmt.bind(i + 1, param)?;
+        }
+        let mut parser = Self {
+            rows: None,
+            cursors: VecDeque::new(),
+            call: None,
+            result_set: None,
+            rowbuf: Vec::with_capacity(DB_BUFFER_SIZE),
+            ncols: 0,
+            current_row: 0,
+            current_col: 0,
+            is_finished: false,
+        };
+        if stmt.is_query() {
+            let rows = query_rows(RowsOwner::Statement(stmt))?;
+            parser.result_set = Some(describe_rows(&rows)?);
+            parser.rows = Some(rows);
+        } else {
+            for i in params.len() + 1..=stmt.bind_count() {
+                stmt.bind(i, &OracleType::RefCursor)?;
+            }
+            stmt.execute(&[])?;
+            for i in params.len() + 1..=stmt.bind_count() {
+                if let Some(cursor) = stmt.bind_value::<_, Option<RefCursor>>(i)? {
+                    parser.cursors.push_back(cursor);
+                }
+            }
+            while let Some(cursor) = stmt.implicit_result()? {
+                parser.cursors.push_back(cursor);
+            }
+            parser.call = Some(stmt);
+        }
+        parser
+    }
+
     #[throws(OracleSourceError)]
     fn next_loc(&mut self) -> (usize, usize) {
         let ret = (self.current_row, self.current_col);
@@ -318,11 +588,12 @@ impl<'a> PartitionParser<'a> for OracleTextSourceParser<'a> {
             return (0, self.is_finished);
         }
 
+        let rows = self.rows.as_mut().expect("no rows to fetch");
         if !self.rowbuf.is_empty() {
             self.rowbuf.drain(..);
         }
         for _ in 0..DB_BUFFER_SIZE {
-            if let Some(item) = (*self.rows).next() {
+            if let Some(item) = (**rows).next() {
                 self.rowbuf.push(item?);
             } else {
                 self.is_finished = true;
@@ -333,6 +604,35 @@ impl<'a> PartitionParser<'a> for OracleTextSourceParser<'a> {
         self.current_col = 0;
         (self.rowbuf.len(), self.is_finished)
     }
+
+    #[throws(OracleSourceError)]
+    fn next_result_set(&mut self) -> Option<(Vec<String>, Vec<OracleTypeSystem>)> {
+        let result_set = match self.result_set.take() {
+            Some(result_set) => result_set,
+            None => {
+                // the rows of the previous cursor are released before the next one is read
+                self.rows = None;
+                match self.cursors.pop_front() {
+                    Some(cursor) => {
+                        let rows = query_rows(RowsOwner::Cursor(cursor))?;
+                        let result_set = describe_rows(&rows)?;
+                        self.rows = Some(rows);
+                        result_set
+                    }
+                    None => {
+                        self.call = None;
+                        return None;
+                    }
+                }
+            }
+        };
+        self.rowbuf.clear();
+        self.ncols = result_set.1.len();
+        self.current_row = 0;
+        self.current_col = 0;
+        self.is_finished = false;
+        Some(result_set)
+    }
 }
 
 macro_rules! impl_produce_text {
@@ -370,5 +670,18 @@ impl_produce_text!(
     NaiveDate,
     NaiveDateTime,
     DateTime<Utc>,
+    chrono::Duration,
+    Interval,
     Vec<u8>,
 );
+
+impl FromSql for Interval {
+    fn from_sql(val: &SqlValue) -> r2d2_oracle::oracle::Result<Interval> {
+        let it = IntervalYM::from_sql(val)?;
+        Ok(Interval {
+            months: it.years() * 12 + it.months(),
+            days: 0,
+            nanoseconds: 0,
+        })
+    }
+}
@@ -1,5 +1,10 @@
-use chrono::{DateTime, NaiveDateTime, Utc};
+use super::errors::OracleSourceError;
+use crate::errors::ConnectorXError;
+use crate::typesystem::Interval;
+use chrono::{DateTime, Duration, NaiveDateTime, Utc};
+use fehler::{throw, throws};
 use r2d2_oracle::oracle::sql_type::OracleType;
+use std::convert::TryFrom;
 
 #[derive(Copy, Clone, Debug)]
 pub enum OracleTypeSystem {
@@ -19,6 +24,8 @@ pub enum OracleTypeSystem {
     TimestampTz(bool),
     TimestampNano(bool),
     TimestampTzNano(bool),
+    IntervalDS(bool),
+    IntervalYM(bool),
 }
 
 impl_typesystem! {
@@ -30,11 +37,16 @@ impl_typesystem! {
         { Clob | VarChar | Char | NVarChar | NChar => String }
         { Date | Timestamp | TimestampNano => NaiveDateTime }
         { TimestampTz | TimestampTzNano => DateTime<Utc> }
+        { IntervalDS => Duration }
+        { IntervalYM => Interval }
     }
 }
 
-impl<'a> From<&'a OracleType> for OracleTypeSystem {
-    fn from(ty: &'a OracleType) -> OracleTypeSystem {
+impl<'a> TryFrom<&'a OracleType> for OracleTypeSystem {
+    type Error = OracleSourceError;
+
+    #[throws(OracleSourceError)]
+    fn try_from(ty: &'a OracleType) -> OracleTypeSystem {
         use OracleTypeSystem::*;
         match ty {
             OracleType::Number(0, 0) => NumFloat(true),
@@ -58,7 +70,9 @@ impl<'a> From<&'a OracleType> for OracleTypeSystem {
             | OracleType::TimestampTZ(8)
             | OracleType::TimestampTZ(9) => TimestampTzNano(true),
             OracleType::TimestampTZ(_) => TimestampTz(true),
-            _ => unimplemented!("{}", format!("Type {:?} not implemented for oracle!", ty)),
+            OracleType::IntervalDS(_, _) => IntervalDS(true),
+            OracleType::IntervalYM(_) => IntervalYM(true),
+            _ => throw!(ConnectorXError::UnsupportedType(format!("{:?}", ty))),
         }
     }
 }
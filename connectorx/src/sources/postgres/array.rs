@@ -0,0 +1,282 @@
+//! Arrays of any element type, read from the binary format by the binary and the cursor protocols
+//! and from the text format by the CSV and the simple protocols.
+
+use crate::typesystem::{Interval, List};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use hex::decode;
+use postgres::types::{FromSql, Kind, Type};
+use rust_decimal::Decimal;
+use serde_json::{from_str, Value};
+use std::convert::TryFrom;
+use std::error::Error;
+use std::iter::Peekable;
+use std::str::Chars;
+use uuid::Uuid;
+
+// enum labels are read as text, the postgres crate has no type for them
+fn element_type(ty: &Type) -> Option<Type> {
+    match ty.kind() {
+        Kind::Array(member) => match member.kind() {
+            Kind::Enum(_) => Some(Type::TEXT),
+            _ => Some(member.clone()),
+        },
+        _ => None,
+    }
+}
+
+pub(super) fn read_i32(buf: &mut &[u8]) -> Result<i32, Box<dyn Error + Sync + Send>> {
+    if buf.len() < 4 {
+        return Err("unexpected end of value".into());
+    }
+    let (head, rest) = buf.split_at(4);
+    *buf = rest;
+    Ok(i32::from_be_bytes([head[0], head[1], head[2], head[3]]))
+}
+
+// an element of an array or a field of a composite value, prefixed with its length or -1 if it
+// is null
+pub(super) fn read_value<'a>(
+    buf: &mut &'a [u8],
+) -> Result<Option<&'a [u8]>, Box<dyn Error + Sync + Send>> {
+    match read_i32(buf)? {
+        n if n < 0 => Ok(None),
+        n => {
+            let n = n as usize;
+            if buf.len() < n {
+                return Err("unexpected end of value".into());
+            }
+            let (value, rest) = buf.split_at(n);
+            *buf = rest;
+            Ok(Some(value))
+        }
+    }
+}
+
+// the binary format of an array is its number of dimensions, whether it has nulls, the type of its
+// elements, the length and the lower bound of every dimension and then the elements
+impl<'a, T: FromSql<'a>> FromSql<'a> for List<T> {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        let member = element_type(ty).ok_or_else(|| format!("{} is not an array", ty))?;
+        let mut buf = raw;
+        let ndims = read_i32(&mut buf)?;
+        read_i32(&mut buf)?;
+        read_i32(&mut buf)?;
+        let mut dims = Vec::with_capacity(usize::try_from(ndims)?);
+        for _ in 0..ndims {
+            dims.push(usize::try_from(read_i32(&mut buf)?)?);
+            read_i32(&mut buf)?;
+        }
+        let len = match dims.is_empty() {
+            true => 0,
+            false => dims.iter().product(),
+        };
+        let mut values = Vec::with_capacity(len);
+        for _ in 0..len {
+            let value = read_value(&mut buf)?;
+            values.push(Option::<T>::from_sql_nullable(&member, value)?);
+        }
+        Ok(List { dims, values })
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        element_type(ty).is_some_and(|member| T::accepts(&member))
+    }
+}
+
+/// Parse the text format of an array, e.g. `{{1,2},{3,NULL}}` or `[0:1]={"a b",c}`, into its
+/// dimensions and its elements in row-major order. Quoted elements are unescaped and the
+/// unquoted `NULL`s are missing elements.
+pub(crate) fn parse_array_text(s: &str) -> Option<(Vec<usize>, Vec<Option<String>>)> {
+    // arrays whose lower bounds are not 1 are prefixed with their bounds
+    let s = match s.starts_with('[') {
+        true => &s[s.find('=')? + 1..],
+        false => s,
+    };
+    let mut chars = s.chars().peekable();
+    let mut dims = vec![];
+    let mut values = vec![];
+    parse_array_level(&mut chars, 0, &mut dims, &mut values, &mut None)?;
+    if chars.next().is_some() {
+        return None;
+    }
+    // the length of a dimension is 0 until one of its lists is closed, which only happens to the
+    // dimensions of an empty array
+    if dims.contains(&0) {
+        dims.clear();
+    }
+    Some((dims, values))
+}
+
+// `leaf` is the depth of the lists holding the elements, which is the same for all of them
+fn parse_array_level(
+    chars: &mut Peekable<Chars>,
+    depth: usize,
+    dims: &mut Vec<usize>,
+    values: &mut Vec<Option<String>>,
+    leaf: &mut Option<usize>,
+) -> Option<()> {
+    if chars.next()? != '{' {
+        return None;
+    }
+    if dims.len() <= depth {
+        dims.push(0);
+    }
+    if chars.peek() == Some(&'}') {
+        chars.next();
+        // only an array as a whole can be empty
+        return if depth == 0 { Some(()) } else { None };
+    }
+    let mut len = 0;
+    loop {
+        match chars.peek()? {
+            '{' if *leaf == Some(depth) => return None,
+            '{' => parse_array_level(chars, depth + 1, dims, values, leaf)?,
+            _ if leaf.is_some_and(|leaf| leaf != depth) => return None,
+            '"' => {
+                *leaf = Some(depth);
+                chars.next();
+                let mut value = String::new();
+                loop {
+                    match chars.next()? {
+                        '"' => break,
+                        '\\' => value.push(chars.next()?),
+                        c => value.push(c),
+                    }
+                }
+                values.push(Some(value));
+            }
+            _ => {
+                *leaf = Some(depth);
+                let mut value = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c == ',' || c == '}' {
+                        break;
+                    }
+                    value.push(c);
+                    chars.next();
+                }
+                let value = value.trim();
+                values.push(match value.eq_ignore_ascii_case("NULL") {
+                    true => None,
+                    false => Some(value.to_string()),
+                });
+            }
+        }
+        len += 1;
+        match chars.next()? {
+            ',' => continue,
+            '}' => break,
+            _ => return None,
+        }
+    }
+    // the lists of a dimension all have the same length
+    match dims[depth] {
+        0 => dims[depth] = len,
+        n if n != len => return None,
+        _ => {}
+    }
+    Some(())
+}
+
+/// Parse an array from its text format, see [`parse_array_text`].
+pub(crate) fn list_from_text<T: FromArrayText>(s: &str) -> Option<List<T>> {
+    let (dims, values) = parse_array_text(s)?;
+    let values = values
+        .into_iter()
+        .map(|v| match v {
+            Some(v) => T::from_array_text(&v).map(Some),
+            None => Some(None),
+        })
+        .collect::<Option<Vec<_>>>()?;
+    Some(List { dims, values })
+}
+
+/// Elements of arrays that can be read from the text format.
+pub(crate) trait FromArrayText: Sized {
+    fn from_array_text(s: &str) -> Option<Self>;
+}
+
+macro_rules! impl_from_array_text {
+    ($($t: ty,)+) => {
+        $(
+            impl FromArrayText for $t {
+                fn from_array_text(s: &str) -> Option<Self> {
+                    s.parse().ok()
+                }
+            }
+        )+
+    };
+}
+
+impl_from_array_text!(i16, i32, i64, f32, f64, String, Uuid, Interval,);
+
+impl FromArrayText for bool {
+    fn from_array_text(s: &str) -> Option<Self> {
+        match s {
+            "t" => Some(true),
+            "f" => Some(false),
+            _ => None,
+        }
+    }
+}
+
+impl FromArrayText for Decimal {
+    fn from_array_text(s: &str) -> Option<Self> {
+        match s {
+            "Infinity" => Some(Decimal::MAX),
+            "-Infinity" => Some(Decimal::MIN),
+            s => s.parse().ok(),
+        }
+    }
+}
+
+impl FromArrayText for Vec<u8> {
+    fn from_array_text(s: &str) -> Option<Self> {
+        decode(s.strip_prefix("\\x")?).ok()
+    }
+}
+
+impl FromArrayText for NaiveDate {
+    fn from_array_text(s: &str) -> Option<Self> {
+        match s {
+            "infinity" => Some(NaiveDate::MAX),
+            "-infinity" => Some(NaiveDate::MIN),
+            s => NaiveDate::parse_from_str(s, "%Y-%m-%d").ok(),
+        }
+    }
+}
+
+impl FromArrayText for NaiveDateTime {
+    fn from_array_text(s: &str) -> Option<Self> {
+        match s {
+            "infinity" => Some(NaiveDateTime::MAX),
+            "-infinity" => Some(NaiveDateTime::MIN),
+            s => NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S%.f").ok(),
+        }
+    }
+}
+
+impl FromArrayText for DateTime<Utc> {
+    fn from_array_text(s: &str) -> Option<Self> {
+        match s {
+            "infinity" => Some(DateTime::<Utc>::MAX_UTC),
+            "-infinity" => Some(DateTime::<Utc>::MIN_UTC),
+            // e.g. 1970-01-01 00:00:01+00
+            s => DateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S%.f%#z")
+                .ok()
+                .map(|dt| dt.with_timezone(&Utc)),
+        }
+    }
+}
+
+impl FromArrayText for NaiveTime {
+    fn from_array_text(s: &str) -> Option<Self> {
+        NaiveTime::parse_from_str(s, "%H:%M:%S%.f").ok()
+    }
+}
+
+impl FromArrayText for Value {
+    fn from_array_text(s: &str) -> Option<Self> {
+        from_str(s).ok()
+    }
+}
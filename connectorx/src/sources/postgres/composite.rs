@@ -0,0 +1,115 @@
+//! Composite values, e.g. `ROW(...)` expressions and user-defined types, read from the binary
+//! format by the binary and the cursor protocols.
+
+use super::array::{read_i32, read_value};
+use crate::typesystem::{Composite, FieldType, FieldValue, Interval, List};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use postgres::types::{FromSql, Kind, Type};
+use rust_decimal::Decimal;
+use serde_json::Value;
+use std::error::Error;
+use uuid::Uuid;
+
+/// The type of a field of a composite value of type `ty`, `None` if it is not supported.
+pub(crate) fn field_type(ty: &Type) -> Option<FieldType> {
+    let ty = match ty.name() {
+        "bool" => FieldType::Bool,
+        "int2" => FieldType::Int16,
+        "int4" => FieldType::Int32,
+        "int8" => FieldType::Int64,
+        "float4" => FieldType::Float32,
+        "float8" => FieldType::Float64,
+        "numeric" => FieldType::Decimal,
+        "text" | "bpchar" | "varchar" | "name" | "citext" => FieldType::Text,
+        "bytea" => FieldType::Binary,
+        "date" => FieldType::Date,
+        "time" => FieldType::Time,
+        "timestamp" => FieldType::Timestamp,
+        "timestamptz" => FieldType::TimestampTz,
+        "interval" => FieldType::Interval,
+        "uuid" => FieldType::Uuid,
+        "json" | "jsonb" => FieldType::Json,
+        _ => match ty.kind() {
+            Kind::Enum(_) => FieldType::Text,
+            Kind::Domain(base) => field_type(base)?,
+            Kind::Array(member) => FieldType::List(Box::new(field_type(member)?)),
+            Kind::Composite(fields) => FieldType::Composite(
+                fields
+                    .iter()
+                    .map(|f| Some((f.name().to_string(), field_type(f.type_())?)))
+                    .collect::<Option<_>>()?,
+            ),
+            _ => return None,
+        },
+    };
+    Some(ty)
+}
+
+impl<'a> FromSql<'a> for FieldValue {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        let field = field_type(ty).ok_or_else(|| format!("unsupported field type {}", ty))?;
+        let value = match field {
+            FieldType::Bool => FieldValue::Bool(bool::from_sql(ty, raw)?),
+            FieldType::Int16 => FieldValue::Int16(i16::from_sql(ty, raw)?),
+            FieldType::Int32 => FieldValue::Int32(i32::from_sql(ty, raw)?),
+            FieldType::Int64 => FieldValue::Int64(i64::from_sql(ty, raw)?),
+            FieldType::Float32 => FieldValue::Float32(f32::from_sql(ty, raw)?),
+            FieldType::Float64 => FieldValue::Float64(f64::from_sql(ty, raw)?),
+            FieldType::Decimal => FieldValue::Decimal(Decimal::from_sql(ty, raw)?),
+            FieldType::Text => FieldValue::Text(<&str>::from_sql(ty, raw)?.to_string()),
+            FieldType::Binary => FieldValue::Binary(Vec::<u8>::from_sql(ty, raw)?),
+            FieldType::Date => FieldValue::Date(NaiveDate::from_sql(ty, raw)?),
+            FieldType::Time => FieldValue::Time(NaiveTime::from_sql(ty, raw)?),
+            FieldType::Timestamp => FieldValue::Timestamp(NaiveDateTime::from_sql(ty, raw)?),
+            FieldType::TimestampTz => FieldValue::TimestampTz(DateTime::<Utc>::from_sql(ty, raw)?),
+            FieldType::Interval => FieldValue::Interval(Interval::from_sql(ty, raw)?),
+            FieldType::Uuid => FieldValue::Uuid(Uuid::from_sql(ty, raw)?),
+            FieldType::Json => FieldValue::Json(Value::from_sql(ty, raw)?),
+            FieldType::List(_) => {
+                let list = List::<FieldValue>::from_sql(ty, raw)?;
+                if list.dims.len() > 1 {
+                    return Err(
+                        "multidimensional arrays in composite values are not supported".into(),
+                    );
+                }
+                FieldValue::List(list.values)
+            }
+            FieldType::Composite(_) => FieldValue::Composite(Composite::from_sql(ty, raw)?),
+        };
+        Ok(value)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        field_type(ty).is_some()
+    }
+}
+
+// the binary format of a composite value is its number of fields and then the fields, each one
+// prefixed with its type
+impl<'a> FromSql<'a> for Composite {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        let fields = match ty.kind() {
+            Kind::Composite(fields) => fields,
+            _ => return Err(format!("{} is not a composite type", ty).into()),
+        };
+        let mut buf = raw;
+        let nfields = read_i32(&mut buf)?;
+        if nfields as usize != fields.len() {
+            return Err(format!("{} has {} fields, got {}", ty, fields.len(), nfields).into());
+        }
+        let mut values = Vec::with_capacity(fields.len());
+        for field in fields {
+            read_i32(&mut buf)?;
+            let value = read_value(&mut buf)?;
+            values.push(Option::<FieldValue>::from_sql_nullable(
+                field.type_(),
+                value,
+            )?);
+        }
+        Ok(Composite(values))
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        matches!(ty.kind(), Kind::Composite(_)) && field_type(ty).is_some()
+    }
+}
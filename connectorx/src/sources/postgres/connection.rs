@@ -1,4 +1,6 @@
+use crate::constants::STATEMENT_TIMEOUT;
 use crate::sources::postgres::errors::PostgresSourceError;
+use crate::sources::timeout::Timeouts;
 use openssl::ssl::{SslConnector, SslFiletype, SslMethod, SslVerifyMode};
 use postgres::{config::SslMode, Config};
 use postgres_openssl::MakeTlsConnector;
@@ -74,7 +76,12 @@ impl TryFrom<TlsConfig> for MakeTlsConnector {
 fn strip_bad_opts(url: &Url) -> Url {
     let stripped_query: Vec<(_, _)> = url
         .query_pairs()
-        .filter(|p| !matches!(&*p.0, "sslkey" | "sslcert" | "sslrootcert"))
+        .filter(|p| {
+            !matches!(
+                &*p.0,
+                "sslkey" | "sslcert" | "sslrootcert" | STATEMENT_TIMEOUT
+            )
+        })
         .collect();
 
     let mut url2 = url.clone();
@@ -108,7 +115,17 @@ pub fn rewrite_tls_args(
     };
 
     let stripped_url = strip_bad_opts(conn);
-    let pg_config: Config = stripped_url.as_str().parse().unwrap();
+    let mut pg_config: Config = stripped_url.as_str().parse().unwrap();
+
+    // statement_timeout is not a connection option, pass it as a runtime parameter instead
+    if let Some(timeout) = Timeouts::from_url(conn)?.statement {
+        let options = format!(
+            "{} -c statement_timeout={}",
+            pg_config.get_options().unwrap_or(""),
+            timeout.as_millis()
+        );
+        pg_config.options(options.trim_start());
+    }
 
     let tls_config = TlsConfig {
         pg_config: pg_config.clone(),
@@ -1,3 +1,4 @@
+use crate::sources::timeout::is_pool_timeout;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -27,3 +28,16 @@ pub enum PostgresSourceError {
     #[error(transparent)]
     Other(#[from] anyhow::Error),
 }
+
+impl PostgresSourceError {
+    /// Whether the error is raised because the statement or connect timeout elapsed.
+    pub fn is_timeout(&self) -> bool {
+        match self {
+            PostgresSourceError::PostgresPoolError(e) => is_pool_timeout(e),
+            PostgresSourceError::PostgresError(e) => {
+                e.code() == Some(&postgres::error::SqlState::QUERY_CANCELED)
+            }
+            _ => false,
+        }
+    }
+}
@@ -0,0 +1,61 @@
+//! hstore values read from the text format by the CSV and the simple protocols.
+
+use std::collections::HashMap;
+use std::iter::Peekable;
+use std::str::Chars;
+
+/// Parse the text format of an hstore, e.g. `"a"=>"1", "b"=>NULL`. The server quotes all the
+/// keys and values, unquoted ones are accepted as well and an unquoted `NULL` value is missing.
+pub(crate) fn hstore_from_text(s: &str) -> Option<HashMap<String, Option<String>>> {
+    let mut chars = s.chars().peekable();
+    let mut map = HashMap::new();
+    loop {
+        skip_whitespace(&mut chars);
+        if chars.peek().is_none() {
+            break;
+        }
+        let (key, _) = parse_token(&mut chars)?;
+        skip_whitespace(&mut chars);
+        if chars.next()? != '=' || chars.next()? != '>' {
+            return None;
+        }
+        skip_whitespace(&mut chars);
+        let value = match parse_token(&mut chars)? {
+            (value, false) if value.eq_ignore_ascii_case("NULL") => None,
+            (value, _) => Some(value),
+        };
+        map.insert(key, value);
+        skip_whitespace(&mut chars);
+        match chars.next() {
+            Some(',') => continue,
+            None => break,
+            _ => return None,
+        }
+    }
+    Some(map)
+}
+
+fn skip_whitespace(chars: &mut Peekable<Chars>) {
+    while chars.next_if(|c| c.is_whitespace()).is_some() {}
+}
+
+// a key or a value and whether it is quoted
+fn parse_token(chars: &mut Peekable<Chars>) -> Option<(String, bool)> {
+    let mut token = String::new();
+    if chars.next_if_eq(&'"').is_some() {
+        loop {
+            match chars.next()? {
+                '"' => return Some((token, true)),
+                '\\' => token.push(chars.next()?),
+                c => token.push(c),
+            }
+        }
+    }
+    while let Some(c) = chars.next_if(|&c| !c.is_whitespace() && c != '=' && c != ',') {
+        token.push(c);
+    }
+    match token.is_empty() {
+        true => None,
+        false => Some((token, false)),
+    }
+}
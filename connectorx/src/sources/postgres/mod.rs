@@ -1,7 +1,10 @@
 //! Source implementation for Postgres database, including the TLS support (client only).
 
+mod array;
+mod composite;
 mod connection;
 mod errors;
+mod hstore;
 mod typesystem;
 
 pub use self::errors::PostgresSourceError;
@@ -14,28 +17,35 @@ use crate::constants::DB_BUFFER_SIZE;
 use crate::{
     data_order::DataOrder,
     errors::ConnectorXError,
-    sources::{PartitionParser, Produce, Source, SourcePartition},
-    sql::{count_query, CXQuery},
+    sources::{ColumnDescription, PartitionParser, Produce, Source, SourcePartition},
+    sql::{count_query, inline_params, CXParam, CXQuery, PlaceholderStyle},
+    typesystem::{Composite, FieldType, Interval, List},
 };
 use anyhow::anyhow;
+use array::{list_from_text, FromArrayText};
 use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use composite::field_type;
 use csv::{ReaderBuilder, StringRecord, StringRecordsIntoIter};
 use fehler::{throw, throws};
-use hex::decode;
+use hex::{decode, encode};
+use hstore::hstore_from_text;
 use postgres::{
     binary_copy::{BinaryCopyOutIter, BinaryCopyOutRow},
     fallible_iterator::FallibleIterator,
     tls::{MakeTlsConnect, TlsConnect},
-    Config, CopyOutReader, Row, RowIter, SimpleQueryMessage, Socket,
+    types::{private::BytesMut, to_sql_checked, FromSql, IsNull, ToSql, Type},
+    Client, Column, Config, CopyOutReader, Row, RowIter, SimpleQueryMessage, Socket, Statement,
 };
 use r2d2::{Pool, PooledConnection};
 use r2d2_postgres::PostgresConnectionManager;
 use rust_decimal::Decimal;
 use serde_json::{from_str, Value};
 use sqlparser::dialect::PostgreSqlDialect;
-use std::collections::HashMap;
-use std::convert::TryFrom;
+use std::collections::{HashMap, VecDeque};
+use std::convert::{TryFrom, TryInto};
+use std::error::Error;
 use std::marker::PhantomData;
+use std::str::FromStr;
 use uuid::Uuid;
 
 /// Protocol - Binary based bulk load
@@ -78,19 +88,19 @@ macro_rules! impl_produce_unimplemented {
 }
 
 impl_produce_unimplemented!(
-    (PostgresCSVSourceParser<'_>, HashMap<String, Option<String>>, "Please use `cursor` protocol for hstore type"),
     (PostgresCSVSourceParser<'_>, Vector, "Please use `binary` protocol for vector type"),
     (PostgresCSVSourceParser<'_>, HalfVector, "Please use `binary` protocol for halfvector type"),
     (PostgresCSVSourceParser<'_>, Bit, "Please use `binary` protocol for bit type"),
     (PostgresCSVSourceParser<'_>, SparseVector, "Please use `binary` protocol for sparsevector type"),
+    (PostgresCSVSourceParser<'_>, Composite, "Please use `binary` or `cursor` protocol for composite types"),
 
 
-    (PostgresSimpleSourceParser,HashMap<String, Option<String>>, "unimplemented"),
     (PostgresSimpleSourceParser,Value, "unimplemented"),
     (PostgresSimpleSourceParser, Vector, "Please use `binary` protocol for vector type"),
     (PostgresSimpleSourceParser, HalfVector, "Please use `binary` protocol for halfvector type"),
     (PostgresSimpleSourceParser, Bit, "Please use `binary` protocol for bit type"),
     (PostgresSimpleSourceParser, SparseVector, "Please use `binary` protocol for sparsevector type"),
+    (PostgresSimpleSourceParser, Composite, "Please use `binary` or `cursor` protocol for composite types"),
 
 );
 
@@ -100,6 +110,188 @@ fn convert_row<'b, R: TryFrom<usize> + postgres::types::FromSql<'b> + Clone>(row
     nrows.expect("Could not parse int result from count_query")
 }
 
+// the row estimate of the planner for `query`, from the top node of `EXPLAIN (FORMAT JSON)`
+#[throws(PostgresSourceError)]
+fn get_estimated_rows<C>(conn: &mut PgConn<C>, query: &CXQuery<String>) -> usize
+where
+    C: MakeTlsConnect<Socket> + Clone + 'static + Sync + Send,
+    C::TlsConnect: Send,
+    C::Stream: Send,
+    <C::TlsConnect as TlsConnect<Socket>>::Future: Send,
+{
+    let row = conn.query_one(
+        format!("EXPLAIN (FORMAT JSON) {}", query).as_str(),
+        &sql_params(query.params()),
+    )?;
+    let plan: Value = row.get(0);
+    plan[0]["Plan"]["Plan Rows"]
+        .as_f64()
+        .ok_or_else(|| anyhow!("Cannot get the row estimate from the query plan"))? as usize
+}
+
+// `COPY` and the simple query protocol cannot bind parameters, so they are inlined as literals.
+// The literals are untyped, like the placeholders they replace, and take the type postgres infers
+// from their context.
+#[throws(PostgresSourceError)]
+fn inline_query(query: &CXQuery<String>) -> String {
+    inline_params(
+        query.as_str(),
+        query.params(),
+        PlaceholderStyle::Dollar,
+        false,
+        |param| match param {
+            CXParam::Null => "NULL".to_string(),
+            CXParam::Bool(v) => v.to_string(),
+            CXParam::I64(v) => format!("'{}'", v),
+            CXParam::F64(v) if v.is_nan() => "'NaN'".to_string(),
+            CXParam::F64(v) if v.is_infinite() => {
+                format!("'{}Infinity'", if *v < 0. { "-" } else { "" })
+            }
+            CXParam::F64(v) => format!("'{}'", v),
+            CXParam::Str(v) => format!("E'{}'", v.replace('\\', "\\\\").replace('\'', "''")),
+            CXParam::Bytes(v) => format!("E'\\\\x{}'::bytea", encode(v)),
+            CXParam::Date(v) => format!("'{}'", v),
+            // the same instant as the native binding, which reads a timestamptz in UTC
+            CXParam::DateTime(v) => format!("'{}+00'", v),
+        },
+    )?
+}
+
+fn sql_params(params: &[CXParam]) -> Vec<&(dyn ToSql + Sync)> {
+    params.iter().map(|p| p as &(dyn ToSql + Sync)).collect()
+}
+
+// the types of the placeholders are inferred by postgres, so the values are converted to them
+// instead of requiring an exact match
+impl ToSql for CXParam {
+    fn to_sql(
+        &self,
+        ty: &Type,
+        out: &mut BytesMut,
+    ) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        match self {
+            CXParam::Null => Ok(IsNull::Yes),
+            CXParam::Bool(v) => v.to_sql_checked(ty, out),
+            CXParam::I64(v) => match *ty {
+                Type::INT2 => i16::try_from(*v)?.to_sql(ty, out),
+                Type::INT4 => i32::try_from(*v)?.to_sql(ty, out),
+                Type::FLOAT4 => (*v as f32).to_sql(ty, out),
+                Type::FLOAT8 => (*v as f64).to_sql(ty, out),
+                Type::NUMERIC => Decimal::from(*v).to_sql(ty, out),
+                _ => v.to_sql_checked(ty, out),
+            },
+            CXParam::F64(v) => match *ty {
+                Type::FLOAT4 => (*v as f32).to_sql(ty, out),
+                Type::NUMERIC => Decimal::try_from(*v)?.to_sql(ty, out),
+                _ => v.to_sql_checked(ty, out),
+            },
+            CXParam::Str(v) => v.to_sql_checked(ty, out),
+            CXParam::Bytes(v) => v.to_sql_checked(ty, out),
+            CXParam::Date(v) => v.to_sql_checked(ty, out),
+            CXParam::DateTime(v) => match *ty {
+                Type::TIMESTAMPTZ => {
+                    DateTime::<Utc>::from_naive_utc_and_offset(*v, Utc).to_sql(ty, out)
+                }
+                _ => v.to_sql_checked(ty, out),
+            },
+        }
+    }
+
+    fn accepts(_: &Type) -> bool {
+        true
+    }
+
+    to_sql_checked!();
+}
+
+// whether the columns of a query are the cursors returned by a function
+fn returns_cursors(columns: &[Column]) -> bool {
+    !columns.is_empty() && columns.iter().all(|col| col.type_() == &Type::REFCURSOR)
+}
+
+// the name of a cursor returned by a function, `String` does not accept the `refcursor` type
+struct CursorName(String);
+
+impl<'a> FromSql<'a> for CursorName {
+    fn from_sql(_: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        Ok(CursorName(std::str::from_utf8(raw)?.to_string()))
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        ty == &Type::REFCURSOR
+    }
+}
+
+// the binary format of an interval is its microseconds, days and months
+impl<'a> FromSql<'a> for Interval {
+    fn from_sql(_: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        if raw.len() != 16 {
+            return Err(format!("invalid interval of {} bytes", raw.len()).into());
+        }
+        let micros = i64::from_be_bytes(raw[0..8].try_into()?);
+        Ok(Interval {
+            months: i32::from_be_bytes(raw[12..16].try_into()?),
+            days: i32::from_be_bytes(raw[8..12].try_into()?),
+            nanoseconds: micros
+                .checked_mul(1000)
+                .ok_or("interval is out of the range of nanoseconds")?,
+        })
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        ty == &Type::INTERVAL
+    }
+}
+
+// the text format of an interval in the default `IntervalStyle`, e.g. `-1 years 2 mons 3 days -04:05:06.5`
+impl FromStr for Interval {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut months: i64 = 0;
+        let mut days: i64 = 0;
+        let mut nanoseconds: i64 = 0;
+        let mut tokens = s.split_whitespace();
+        while let Some(token) = tokens.next() {
+            if token.contains(':') {
+                let (negative, time) = match token.strip_prefix('-') {
+                    Some(time) => (true, time),
+                    None => (false, token.trim_start_matches('+')),
+                };
+                let mut parts = time.splitn(3, ':');
+                let mut next = || {
+                    parts
+                        .next()
+                        .ok_or_else(|| anyhow!("invalid interval {}", s))
+                };
+                let hours: i64 = next()?.parse()?;
+                let minutes: i64 = next()?.parse()?;
+                let seconds = next()?;
+                let (seconds, fraction) = seconds.split_once('.').unwrap_or((seconds, ""));
+                let fraction = format!("{:0<9}", fraction);
+                let nanos = ((hours * 60 + minutes) * 60 + seconds.parse::<i64>()?)
+                    .checked_mul(1_000_000_000)
+                    .and_then(|n| n.checked_add(fraction[..9].parse().ok()?))
+                    .ok_or_else(|| anyhow!("interval {} is out of range", s))?;
+                nanoseconds = if negative { -nanos } else { nanos };
+            } else {
+                let n: i64 = token.parse()?;
+                match tokens.next() {
+                    Some(unit) if unit.starts_with("year") => months += n * 12,
+                    Some(unit) if unit.starts_with("mon") => months += n,
+                    Some(unit) if unit.starts_with("day") => days += n,
+                    _ => throw!(anyhow!("invalid interval {}", s)),
+                }
+            }
+        }
+        Ok(Interval {
+            months: months.try_into()?,
+            days: days.try_into()?,
+            nanoseconds,
+        })
+    }
+}
+
 #[throws(PostgresSourceError)]
 fn get_total_rows<C>(conn: &mut PgConn<C>, query: &CXQuery<String>) -> usize
 where
@@ -110,8 +302,9 @@ where
 {
     let dialect = PostgreSqlDialect {};
 
-    let row = conn.query_one(count_query(query, &dialect)?.as_str(), &[])?;
-    let col_type = PostgresTypeSystem::from(row.columns()[0].type_());
+    let cquery = count_query(query, &dialect)?;
+    let row = conn.query_one(cquery.as_str(), &sql_params(cquery.params()))?;
+    let col_type = PostgresTypeSystem::try_from(row.columns()[0].type_())?;
     match col_type {
         PostgresTypeSystem::Int2(_) => convert_row::<i16>(&row) as usize,
         PostgresTypeSystem::Int4(_) => convert_row::<i32>(&row) as usize,
@@ -122,6 +315,32 @@ where
     }
 }
 
+// decode the type modifier of a column, see the `*typmodout` functions of postgres
+fn describe_type_modifier(description: &mut ColumnDescription, ty: &Type, typmod: i32) {
+    if typmod < 0 {
+        return;
+    }
+    match *ty {
+        Type::NUMERIC => {
+            let typmod = typmod - 4;
+            description.precision = Some(((typmod >> 16) & 0xffff) as u32);
+            // the scale is an 11-bit signed integer
+            description.scale = Some(((typmod & 0x7ff) ^ 1024) - 1024);
+        }
+        Type::VARCHAR | Type::BPCHAR => description.length = Some((typmod - 4) as u32),
+        Type::BIT | Type::VARBIT => description.length = Some(typmod as u32),
+        Type::TIME | Type::TIMETZ | Type::TIMESTAMP | Type::TIMESTAMPTZ => {
+            description.precision = Some(typmod as u32)
+        }
+        // the pgvector types are not built in
+        _ => {
+            if let "vector" | "halfvec" | "sparsevec" = ty.name() {
+                description.dimension = Some(typmod as u32)
+            }
+        }
+    }
+}
+
 pub struct PostgresSource<P, C>
 where
     C: MakeTlsConnect<Socket> + Clone + 'static + Sync + Send,
@@ -135,7 +354,10 @@ where
     names: Vec<String>,
     schema: Vec<PostgresTypeSystem>,
     pg_schema: Vec<postgres::types::Type>,
+    // the type of each column, and its table oid and attribute number if it is a table column
+    columns: Vec<(Type, Option<(u32, i16)>)>,
     pre_execution_queries: Option<Vec<String>>,
+    post_execution_queries: Option<Vec<String>>,
     _protocol: PhantomData<P>,
 }
 
@@ -148,8 +370,12 @@ where
 {
     #[throws(PostgresSourceError)]
     pub fn new(config: Config, tls: C, nconn: usize) -> Self {
+        let mut builder = Pool::builder().max_size(nconn as u32);
+        if let Some(timeout) = config.get_connect_timeout() {
+            builder = builder.connection_timeout(*timeout);
+        }
         let manager = PostgresConnectionManager::new(config, tls);
-        let pool = Pool::builder().max_size(nconn as u32).build(manager)?;
+        let pool = builder.build(manager)?;
 
         Self {
             pool,
@@ -158,10 +384,39 @@ where
             names: vec![],
             schema: vec![],
             pg_schema: vec![],
+            columns: vec![],
             pre_execution_queries: None,
+            post_execution_queries: None,
             _protocol: PhantomData,
         }
     }
+
+    #[throws(PostgresSourceError)]
+    fn set_columns(&mut self, columns: &[Column]) {
+        let (names, pg_types): (Vec<String>, Vec<postgres::types::Type>) = columns
+            .iter()
+            .map(|col| (col.name().to_string(), col.type_().clone()))
+            .unzip();
+
+        self.names = names;
+        self.schema = pg_types
+            .iter()
+            .map(PostgresTypeSystem::try_from)
+            .collect::<Result<_, _>>()?;
+        self.pg_schema = self
+            .schema
+            .iter()
+            .zip(pg_types.iter())
+            .map(|(t1, t2)| PostgresTypePairs(t2, t1).into())
+            .collect();
+        self.columns = columns
+            .iter()
+            .map(|col| {
+                let origin = col.table_oid().zip(col.column_id());
+                (col.type_().clone(), origin)
+            })
+            .collect();
+    }
 }
 
 impl<P, C> Source for PostgresSource<P, C>
@@ -198,6 +453,50 @@ where
         self.pre_execution_queries = pre_execution_queries.map(|s| s.to_vec());
     }
 
+    fn set_post_execution_queries(&mut self, post_execution_queries: Option<&[String]>) {
+        self.post_execution_queries = post_execution_queries.map(|s| s.to_vec());
+    }
+
+    fn fork(&self) -> Self {
+        Self {
+            pool: self.pool.clone(),
+            origin_query: None,
+            queries: vec![],
+            names: vec![],
+            schema: vec![],
+            pg_schema: vec![],
+            columns: vec![],
+            pre_execution_queries: self.pre_execution_queries.clone(),
+            post_execution_queries: self.post_execution_queries.clone(),
+            _protocol: PhantomData,
+        }
+    }
+
+    /// Functions returning `refcursor`s yield one result set per cursor, which is read in the
+    /// same transaction, with the cursor protocol only. Any other query, e.g. a `SETOF` function,
+    /// yields its own rows with every protocol.
+    #[throws(PostgresSourceError)]
+    fn raw_partition(self) -> Self::Partition {
+        assert!(!self.queries.is_empty());
+
+        let mut conn = self.pool.get()?;
+        if let Some(pre_queries) = &self.pre_execution_queries {
+            for pre_query in pre_queries {
+                conn.query(pre_query.as_str(), &[])?;
+            }
+        }
+
+        let mut partition = PostgresSourcePartition::<P, C>::new(
+            conn,
+            &self.queries[0],
+            &[],
+            &[],
+            self.post_execution_queries,
+        );
+        partition.raw = true;
+        partition
+    }
+
     #[throws(PostgresSourceError)]
     fn fetch_metadata(&mut self) {
         assert!(!self.queries.is_empty());
@@ -206,28 +505,15 @@ where
         let first_query = &self.queries[0];
 
         let stmt = conn.prepare(first_query.as_str())?;
-
-        let (names, pg_types): (Vec<String>, Vec<postgres::types::Type>) = stmt
-            .columns()
-            .iter()
-            .map(|col| (col.name().to_string(), col.type_().clone()))
-            .unzip();
-
-        self.names = names;
-        self.schema = pg_types.iter().map(PostgresTypeSystem::from).collect();
-        self.pg_schema = self
-            .schema
-            .iter()
-            .zip(pg_types.iter())
-            .map(|(t1, t2)| PostgresTypePairs(t2, t1).into())
-            .collect();
+        self.set_columns(stmt.columns())?;
     }
 
     #[throws(PostgresSourceError)]
     fn result_rows(&mut self) -> Option<usize> {
         match &self.origin_query {
             Some(q) => {
-                let cxq = CXQuery::Naked(q.clone());
+                // the partitioned queries are bound to the parameters of the origin query
+                let cxq = CXQuery::Naked(q.clone()).bind(self.queries[0].params().to_vec());
                 let mut conn = self.pool.get()?;
                 let nrows = get_total_rows(&mut conn, &cxq)?;
                 Some(nrows)
@@ -236,6 +522,16 @@ where
         }
     }
 
+    #[throws(PostgresSourceError)]
+    fn estimate_rows(&mut self) -> Option<usize> {
+        let mut conn = self.pool.get()?;
+        let mut nrows = 0;
+        for query in &self.queries {
+            nrows += get_estimated_rows(&mut conn, query)?;
+        }
+        Some(nrows)
+    }
+
     fn names(&self) -> Vec<String> {
         self.names.clone()
     }
@@ -244,6 +540,85 @@ where
         self.schema.clone()
     }
 
+    /// Table columns are described by their attribute in `pg_attribute`, which holds the type
+    /// modifier (e.g. the precision and scale of a numeric) and the not null constraint. The
+    /// server does not report the type modifier of other columns, only their type.
+    /// Timestamps with a time zone are described with the time zone of the session, between the
+    /// pre- and post-execution queries, and composite types with their fields.
+    #[throws(PostgresSourceError)]
+    fn describe_columns(&mut self) -> Vec<ColumnDescription> {
+        let mut conn = self.pool.get()?;
+        let time_zone: Option<String> =
+            if self.columns.iter().any(|(ty, _)| *ty == Type::TIMESTAMPTZ) {
+                // the pre-execution queries may set the time zone of the session
+                if let Some(pre_queries) = &self.pre_execution_queries {
+                    for pre_query in pre_queries {
+                        conn.query(pre_query.as_str(), &[])?;
+                    }
+                }
+                let time_zone = conn.query_one("SHOW TimeZone", &[])?.get(0);
+                // and the post-execution queries clean the session up before it goes back to the
+                // pool, as after a partition
+                if let Some(post_queries) = &self.post_execution_queries {
+                    for post_query in post_queries {
+                        conn.query(post_query.as_str(), &[])?;
+                    }
+                }
+                Some(time_zone)
+            } else {
+                None
+            };
+        // the attributes of all the table columns are looked up at once
+        let (table_oids, column_ids): (Vec<u32>, Vec<i16>) = self
+            .columns
+            .iter()
+            .filter_map(|(_, origin)| *origin)
+            .unzip();
+        let mut attributes = HashMap::new();
+        if !table_oids.is_empty() {
+            for row in conn.query(
+                "SELECT attrelid, attnum, format_type(atttypid, atttypmod), atttypmod, attnotnull \
+                 FROM pg_attribute WHERE (attrelid, attnum) IN \
+                 (SELECT unnest($1::oid[]), unnest($2::int2[]))",
+                &[&table_oids, &column_ids],
+            )? {
+                attributes.insert((row.get::<_, u32>(0), row.get::<_, i16>(1)), row);
+            }
+        }
+        let mut descriptions = Vec::with_capacity(self.columns.len());
+        for (ty, origin) in &self.columns {
+            let attribute = origin.and_then(|origin| attributes.get(&origin));
+            let description = match attribute {
+                Some(row) => {
+                    let mut description = ColumnDescription {
+                        source_type: row.get(2),
+                        nullable: Some(!row.get::<_, bool>(4)),
+                        ..Default::default()
+                    };
+                    describe_type_modifier(&mut description, ty, row.get(3));
+                    description
+                }
+                None => ColumnDescription {
+                    source_type: ty.name().to_string(),
+                    ..Default::default()
+                },
+            };
+            let description = match (ty, field_type(ty)) {
+                (&Type::TIMESTAMPTZ, _) => ColumnDescription {
+                    time_zone: time_zone.clone(),
+                    ..description
+                },
+                (_, Some(FieldType::Composite(fields))) => ColumnDescription {
+                    fields: Some(fields),
+                    ..description
+                },
+                _ => description,
+            };
+            descriptions.push(description);
+        }
+        descriptions
+    }
+
     #[throws(PostgresSourceError)]
     fn partition(self) -> Vec<Self::Partition> {
         let mut ret = vec![];
@@ -261,6 +636,7 @@ where
                 &query,
                 &self.schema,
                 &self.pg_schema,
+                self.post_execution_queries.clone(),
             ));
         }
         ret
@@ -280,6 +656,9 @@ where
     pg_schema: Vec<postgres::types::Type>,
     nrows: usize,
     ncols: usize,
+    post_execution_queries: Option<Vec<String>>,
+    // whether the query runs as is, see `Source::raw_partition`
+    raw: bool,
     _protocol: PhantomData<P>,
 }
 
@@ -295,6 +674,7 @@ where
         query: &CXQuery<String>,
         schema: &[PostgresTypeSystem],
         pg_schema: &[postgres::types::Type],
+        post_execution_queries: Option<Vec<String>>,
     ) -> Self {
         Self {
             conn,
@@ -303,9 +683,60 @@ where
             pg_schema: pg_schema.to_vec(),
             nrows: 0,
             ncols: schema.len(),
+            post_execution_queries,
+            raw: false,
             _protocol: PhantomData,
         }
     }
+
+    // Prepare a raw query for the protocols that read the rows of the query itself, and take the
+    // schema from its columns. Returns `None` for the queries that are not raw.
+    #[allow(clippy::type_complexity)]
+    #[throws(PostgresSourceError)]
+    fn raw_result_set(
+        &mut self,
+        protocol: &'static str,
+    ) -> Option<(Vec<String>, Vec<PostgresTypeSystem>)> {
+        if !self.raw {
+            return None;
+        }
+        let stmt = self.conn.prepare(self.query.as_str())?;
+        if stmt.columns().is_empty() || returns_cursors(stmt.columns()) {
+            throw!(ConnectorXError::ResultSetsNotSupported(protocol));
+        }
+        let names = self.set_columns(stmt.columns())?;
+        Some((names, self.schema.clone()))
+    }
+
+    // take the schema of a raw query from its columns and return their names
+    #[throws(PostgresSourceError)]
+    fn set_columns(&mut self, columns: &[Column]) -> Vec<String> {
+        let (names, pg_types): (Vec<String>, Vec<postgres::types::Type>) = columns
+            .iter()
+            .map(|col| (col.name().to_string(), col.type_().clone()))
+            .unzip();
+        self.schema = pg_types
+            .iter()
+            .map(PostgresTypeSystem::try_from)
+            .collect::<Result<_, _>>()?;
+        self.pg_schema = self
+            .schema
+            .iter()
+            .zip(pg_types.iter())
+            .map(|(t1, t2)| PostgresTypePairs(t2, t1).into())
+            .collect();
+        self.ncols = self.schema.len();
+        names
+    }
+
+    #[throws(PostgresSourceError)]
+    fn run_post_execution_queries(&mut self) {
+        if let Some(post_queries) = &self.post_execution_queries {
+            for post_query in post_queries {
+                self.conn.query(post_query.as_str(), &[])?;
+            }
+        }
+    }
 }
 
 impl<C> SourcePartition for PostgresSourcePartition<BinaryProtocol, C>
@@ -326,11 +757,19 @@ where
 
     #[throws(PostgresSourceError)]
     fn parser(&mut self) -> Self::Parser<'_> {
-        let query = format!("COPY ({}) TO STDOUT WITH BINARY", self.query);
+        let result_set = self.raw_result_set(
+            "the postgres binary protocol for refcursors and statements without columns",
+        )?;
+        let query = format!(
+            "COPY ({}) TO STDOUT WITH BINARY",
+            inline_query(&self.query)?
+        );
         let reader = self.conn.copy_out(&*query)?; // unless reading the data, it seems like issue the query is fast
         let iter = BinaryCopyOutIter::new(reader, &self.pg_schema);
 
-        PostgresBinarySourcePartitionParser::new(iter, &self.schema)
+        let mut parser = PostgresBinarySourcePartitionParser::new(iter, &self.schema);
+        parser.result_set = result_set;
+        parser
     }
 
     fn nrows(&self) -> usize {
@@ -340,6 +779,11 @@ where
     fn ncols(&self) -> usize {
         self.ncols
     }
+
+    #[throws(PostgresSourceError)]
+    fn finish(&mut self) {
+        self.run_post_execution_queries()?;
+    }
 }
 
 impl<C> SourcePartition for PostgresSourcePartition<CSVProtocol, C>
@@ -360,14 +804,19 @@ where
 
     #[throws(PostgresSourceError)]
     fn parser(&mut self) -> Self::Parser<'_> {
-        let query = format!("COPY ({}) TO STDOUT WITH CSV", self.query);
+        let result_set = self.raw_result_set(
+            "the postgres csv protocol for refcursors and statements without columns",
+        )?;
+        let query = format!("COPY ({}) TO STDOUT WITH CSV", inline_query(&self.query)?);
         let reader = self.conn.copy_out(&*query)?; // unless reading the data, it seems like issue the query is fast
         let iter = ReaderBuilder::new()
             .has_headers(false)
             .from_reader(reader)
             .into_records();
 
-        PostgresCSVSourceParser::new(iter, &self.schema)
+        let mut parser = PostgresCSVSourceParser::new(iter, &self.schema);
+        parser.result_set = result_set;
+        parser
     }
 
     fn nrows(&self) -> usize {
@@ -377,6 +826,11 @@ where
     fn ncols(&self) -> usize {
         self.ncols
     }
+
+    #[throws(PostgresSourceError)]
+    fn finish(&mut self) {
+        self.run_post_execution_queries()?;
+    }
 }
 
 impl<C> SourcePartition for PostgresSourcePartition<CursorProtocol, C>
@@ -397,10 +851,32 @@ where
 
     #[throws(PostgresSourceError)]
     fn parser(&mut self) -> Self::Parser<'_> {
-        let iter = self
-            .conn
-            .query_raw::<_, bool, _>(self.query.as_str(), vec![])?; // unless reading the data, it seems like issue the query is fast
-        PostgresRawSourceParser::new(iter, &self.schema)
+        if !self.raw {
+            let params = self.query.params().iter().map(|p| p as &dyn ToSql);
+            let iter = self.conn.query_raw(self.query.as_str(), params)?; // unless reading the data, it seems like issue the query is fast
+            return PostgresRawSourceParser::new(iter, &self.schema);
+        }
+
+        let stmt = self.conn.prepare(self.query.as_str())?;
+        if stmt.columns().is_empty() || returns_cursors(stmt.columns()) {
+            // the cursors are closed at the end of the transaction, so they are read within it
+            self.conn.batch_execute("BEGIN")?;
+            let mut names = VecDeque::new();
+            for row in self.conn.query(&stmt, &sql_params(self.query.params()))? {
+                for cidx in 0..row.len() {
+                    if let Some(CursorName(name)) = row.try_get(cidx)? {
+                        names.push_back(name);
+                    }
+                }
+            }
+            return PostgresRawSourceParser::from_cursors(&mut self.conn, names);
+        }
+        let names = self.set_columns(stmt.columns())?;
+        let params = self.query.params().iter().map(|p| p as &dyn ToSql);
+        let iter = self.conn.query_raw(&stmt, params)?;
+        let mut parser = PostgresRawSourceParser::new(iter, &self.schema);
+        parser.result_set = Some((names, self.schema.clone()));
+        parser
     }
 
     fn nrows(&self) -> usize {
@@ -410,6 +886,11 @@ where
     fn ncols(&self) -> usize {
         self.ncols
     }
+
+    #[throws(PostgresSourceError)]
+    fn finish(&mut self) {
+        self.run_post_execution_queries()?;
+    }
 }
 pub struct PostgresBinarySourcePartitionParser<'a> {
     iter: BinaryCopyOutIter<'a>,
@@ -418,6 +899,7 @@ pub struct PostgresBinarySourcePartitionParser<'a> {
     current_col: usize,
     current_row: usize,
     is_finished: bool,
+    result_set: Option<(Vec<String>, Vec<PostgresTypeSystem>)>,
 }
 
 impl<'a> PostgresBinarySourcePartitionParser<'a> {
@@ -429,6 +911,7 @@ impl<'a> PostgresBinarySourcePartitionParser<'a> {
             current_row: 0,
             current_col: 0,
             is_finished: false,
+            result_set: None,
         }
     }
 
@@ -477,6 +960,12 @@ impl<'a> PartitionParser<'a> for PostgresBinarySourcePartitionParser<'a> {
 
         (self.rowbuf.len(), self.is_finished)
     }
+
+    #[throws(PostgresSourceError)]
+    fn next_result_set(&mut self) -> Option<(Vec<String>, Vec<PostgresTypeSystem>)> {
+        // the rows of the query itself are its only result set
+        self.result_set.take()
+    }
 }
 
 macro_rules! impl_produce {
@@ -522,6 +1011,7 @@ impl_produce!(
     &'r str,
     Vec<u8>,
     NaiveTime,
+    Interval,
     Uuid,
     Value,
     IpInet,
@@ -529,14 +1019,24 @@ impl_produce!(
     HalfVector,
     Bit,
     SparseVector,
-    Vec<Option<bool>>,
-    Vec<Option<i16>>,
-    Vec<Option<i32>>,
-    Vec<Option<i64>>,
-    Vec<Option<Decimal>>,
-    Vec<Option<f32>>,
-    Vec<Option<f64>>,
-    Vec<Option<String>>,
+    List<bool>,
+    List<i16>,
+    List<i32>,
+    List<i64>,
+    List<f32>,
+    List<f64>,
+    List<Decimal>,
+    List<String>,
+    List<Vec<u8>>,
+    List<NaiveDate>,
+    List<NaiveTime>,
+    List<NaiveDateTime>,
+    List<DateTime<Utc>>,
+    List<Interval>,
+    List<Uuid>,
+    List<Value>,
+    Composite,
+    HashMap<String, Option<String>>,
 );
 
 impl<'r> Produce<'r, NaiveDateTime> for PostgresBinarySourcePartitionParser<'_> {
@@ -638,24 +1138,6 @@ impl<'r> Produce<'r, Option<NaiveDate>> for PostgresBinarySourcePartitionParser<
     }
 }
 
-impl Produce<'_, HashMap<String, Option<String>>> for PostgresBinarySourcePartitionParser<'_> {
-    type Error = PostgresSourceError;
-    #[throws(PostgresSourceError)]
-    fn produce(&mut self) -> HashMap<String, Option<String>> {
-        unimplemented!("Please use `cursor` protocol for hstore type");
-    }
-}
-
-impl Produce<'_, Option<HashMap<String, Option<String>>>>
-    for PostgresBinarySourcePartitionParser<'_>
-{
-    type Error = PostgresSourceError;
-    #[throws(PostgresSourceError)]
-    fn produce(&mut self) -> Option<HashMap<String, Option<String>>> {
-        unimplemented!("Please use `cursor` protocol for hstore type");
-    }
-}
-
 pub struct PostgresCSVSourceParser<'a> {
     iter: StringRecordsIntoIter<CopyOutReader<'a>>,
     rowbuf: Vec<StringRecord>,
@@ -663,6 +1145,7 @@ pub struct PostgresCSVSourceParser<'a> {
     current_col: usize,
     current_row: usize,
     is_finished: bool,
+    result_set: Option<(Vec<String>, Vec<PostgresTypeSystem>)>,
 }
 
 impl<'a> PostgresCSVSourceParser<'a> {
@@ -677,6 +1160,7 @@ impl<'a> PostgresCSVSourceParser<'a> {
             current_row: 0,
             current_col: 0,
             is_finished: false,
+            result_set: None,
         }
     }
 
@@ -718,6 +1202,12 @@ impl<'a> PartitionParser<'a> for PostgresCSVSourceParser<'a> {
         self.current_col = 0;
         (self.rowbuf.len(), self.is_finished)
     }
+
+    #[throws(PostgresSourceError)]
+    fn next_result_set(&mut self) -> Option<(Vec<String>, Vec<PostgresTypeSystem>)> {
+        // the rows of the query itself are its only result set
+        self.result_set.take()
+    }
 }
 
 macro_rules! impl_csv_produce {
@@ -753,72 +1243,63 @@ macro_rules! impl_csv_produce {
     };
 }
 
-impl_csv_produce!(i8, i16, i32, i64, u32, f32, f64, Uuid, IpInet,);
+impl_csv_produce!(i8, i16, i32, i64, u32, f32, f64, Uuid, IpInet, Interval,);
 
-macro_rules! impl_csv_vec_produce {
-    ($($t: ty,)+) => {
-        $(
-            impl<'r, 'a> Produce<'r, Vec<Option<$t>>> for PostgresCSVSourceParser<'a> {
-                type Error = PostgresSourceError;
+impl<'r, T: FromArrayText> Produce<'r, List<T>> for PostgresCSVSourceParser<'_> {
+    type Error = PostgresSourceError;
 
-                #[throws(PostgresSourceError)]
-                fn produce(&mut self) -> Vec<Option<$t>> {
-                    let (ridx, cidx) = self.next_loc()?;
-                    let s = &self.rowbuf[ridx][cidx][..];
-                    match s {
-                        "{}" => vec![],
-                        _ if s.len() < 3 => throw!(ConnectorXError::cannot_produce::<$t>(Some(s.into()))),
-                        s => s[1..s.len() - 1]
-                            .split(",")
-                            .map(|v| {
-                                if v == "NULL" {
-                                    Ok(None)
-                                } else {
-                                    match v.parse() {
-                                        Ok(v) => Ok(Some(v)),
-                                        Err(e) => Err(e).map_err(|_| ConnectorXError::cannot_produce::<$t>(Some(s.into())))
-                                    }
-                                }
-                            })
-                            .collect::<Result<Vec<Option<$t>>, ConnectorXError>>()?,
-                    }
-                }
-            }
+    #[throws(PostgresSourceError)]
+    fn produce(&mut self) -> List<T> {
+        let (ridx, cidx) = self.next_loc()?;
+        let s = &self.rowbuf[ridx][cidx][..];
+        list_from_text(s)
+            .ok_or_else(|| ConnectorXError::cannot_produce::<List<T>>(Some(s.into())))?
+    }
+}
 
-            impl<'r, 'a> Produce<'r, Option<Vec<Option<$t>>>> for PostgresCSVSourceParser<'a> {
-                type Error = PostgresSourceError;
+impl<'r, T: FromArrayText> Produce<'r, Option<List<T>>> for PostgresCSVSourceParser<'_> {
+    type Error = PostgresSourceError;
 
-                #[throws(PostgresSourceError)]
-                fn produce(&mut self) -> Option<Vec<Option<$t>>> {
-                    let (ridx, cidx) = self.next_loc()?;
-                    let s = &self.rowbuf[ridx][cidx][..];
-                    match s {
-                        "" => None,
-                        "{}" => Some(vec![]),
-                        _ if s.len() < 3 => throw!(ConnectorXError::cannot_produce::<$t>(Some(s.into()))),
-                        s => Some(
-                            s[1..s.len() - 1]
-                                .split(",")
-                                .map(|v| {
-                                    if v == "NULL" {
-                                        Ok(None)
-                                    } else {
-                                        match v.parse() {
-                                            Ok(v) => Ok(Some(v)),
-                                            Err(e) => Err(e).map_err(|_| ConnectorXError::cannot_produce::<$t>(Some(s.into())))
-                                        }
-                                    }
-                                })
-                                .collect::<Result<Vec<Option<$t>>, ConnectorXError>>()?,
-                        ),
-                    }
-                }
-            }
-        )+
-    };
+    #[throws(PostgresSourceError)]
+    fn produce(&mut self) -> Option<List<T>> {
+        let (ridx, cidx) = self.next_loc()?;
+        match &self.rowbuf[ridx][cidx][..] {
+            "" => None,
+            s => Some(
+                list_from_text(s)
+                    .ok_or_else(|| ConnectorXError::cannot_produce::<List<T>>(Some(s.into())))?,
+            ),
+        }
+    }
 }
 
-impl_csv_vec_produce!(i8, i16, i32, i64, f32, f64, Decimal, String,);
+impl<'r> Produce<'r, HashMap<String, Option<String>>> for PostgresCSVSourceParser<'_> {
+    type Error = PostgresSourceError;
+
+    #[throws(PostgresSourceError)]
+    fn produce(&mut self) -> HashMap<String, Option<String>> {
+        let (ridx, cidx) = self.next_loc()?;
+        let s = &self.rowbuf[ridx][cidx][..];
+        hstore_from_text(s).ok_or_else(|| {
+            ConnectorXError::cannot_produce::<HashMap<String, Option<String>>>(Some(s.into()))
+        })?
+    }
+}
+
+impl<'r> Produce<'r, Option<HashMap<String, Option<String>>>> for PostgresCSVSourceParser<'_> {
+    type Error = PostgresSourceError;
+
+    #[throws(PostgresSourceError)]
+    fn produce(&mut self) -> Option<HashMap<String, Option<String>>> {
+        let (ridx, cidx) = self.next_loc()?;
+        match &self.rowbuf[ridx][cidx][..] {
+            "" => None,
+            s => Some(hstore_from_text(s).ok_or_else(|| {
+                ConnectorXError::cannot_produce::<HashMap<String, Option<String>>>(Some(s.into()))
+            })?),
+        }
+    }
+}
 
 impl Produce<'_, bool> for PostgresCSVSourceParser<'_> {
     type Error = PostgresSourceError;
@@ -855,55 +1336,6 @@ impl Produce<'_, Option<bool>> for PostgresCSVSourceParser<'_> {
     }
 }
 
-impl Produce<'_, Vec<Option<bool>>> for PostgresCSVSourceParser<'_> {
-    type Error = PostgresSourceError;
-
-    #[throws(PostgresSourceError)]
-    fn produce(&mut self) -> Vec<Option<bool>> {
-        let (ridx, cidx) = self.next_loc()?;
-        let s = &self.rowbuf[ridx][cidx][..];
-        match s {
-            "{}" => vec![],
-            _ if s.len() < 3 => throw!(ConnectorXError::cannot_produce::<bool>(Some(s.into()))),
-            s => s[1..s.len() - 1]
-                .split(',')
-                .map(|v| match v {
-                    "NULL" => Ok(None),
-                    "t" => Ok(Some(true)),
-                    "f" => Ok(Some(false)),
-                    _ => throw!(ConnectorXError::cannot_produce::<bool>(Some(s.into()))),
-                })
-                .collect::<Result<Vec<Option<bool>>, ConnectorXError>>()?,
-        }
-    }
-}
-
-impl Produce<'_, Option<Vec<Option<bool>>>> for PostgresCSVSourceParser<'_> {
-    type Error = PostgresSourceError;
-
-    #[throws(PostgresSourceError)]
-    fn produce(&mut self) -> Option<Vec<Option<bool>>> {
-        let (ridx, cidx) = self.next_loc()?;
-        let s = &self.rowbuf[ridx][cidx][..];
-        match s {
-            "" => None,
-            "{}" => Some(vec![]),
-            _ if s.len() < 3 => throw!(ConnectorXError::cannot_produce::<bool>(Some(s.into()))),
-            s => Some(
-                s[1..s.len() - 1]
-                    .split(',')
-                    .map(|v| match v {
-                        "NULL" => Ok(None),
-                        "t" => Ok(Some(true)),
-                        "f" => Ok(Some(false)),
-                        _ => throw!(ConnectorXError::cannot_produce::<bool>(Some(s.into()))),
-                    })
-                    .collect::<Result<Vec<Option<bool>>, ConnectorXError>>()?,
-            ),
-        }
-    }
-}
-
 impl<'r> Produce<'r, Decimal> for PostgresCSVSourceParser<'_> {
     type Error = PostgresSourceError;
 
@@ -1144,24 +1576,112 @@ impl<'r> Produce<'r, Option<Value>> for PostgresCSVSourceParser<'_> {
     }
 }
 
+// the cursors returned by a function, which are read in chunks within the transaction that
+// opened them
+struct RefCursors<'a> {
+    client: &'a mut Client,
+    names: VecDeque<String>,
+    // fetches the next rows of the current cursor
+    fetch: Option<Statement>,
+    in_transaction: bool,
+}
+
+impl RefCursors<'_> {
+    // move on to the next cursor and describe its columns, the transaction ends after the last one
+    #[allow(clippy::type_complexity)]
+    #[throws(PostgresSourceError)]
+    fn next_cursor(&mut self) -> Option<(Vec<String>, Vec<PostgresTypeSystem>)> {
+        match self.names.pop_front() {
+            Some(name) => {
+                let fetch = format!(
+                    "FETCH {} FROM \"{}\"",
+                    DB_BUFFER_SIZE,
+                    name.replace('"', "\"\"")
+                );
+                let stmt = self.client.prepare(fetch.as_str())?;
+                let columns = stmt
+                    .columns()
+                    .iter()
+                    .map(|col| {
+                        Ok((
+                            col.name().to_string(),
+                            PostgresTypeSystem::try_from(col.type_())?,
+                        ))
+                    })
+                    .collect::<Result<Vec<_>, PostgresSourceError>>()?
+                    .into_iter()
+                    .unzip();
+                self.fetch = Some(stmt);
+                Some(columns)
+            }
+            None => {
+                self.fetch = None;
+                if self.in_transaction {
+                    self.client.batch_execute("COMMIT")?;
+                    self.in_transaction = false;
+                }
+                None
+            }
+        }
+    }
+
+    #[throws(PostgresSourceError)]
+    fn fetch_rows(&mut self) -> Vec<Row> {
+        let fetch = self.fetch.as_ref().expect("no cursor to fetch from");
+        self.client.query(fetch, &[])?
+    }
+}
+
+impl Drop for RefCursors<'_> {
+    fn drop(&mut self) {
+        // the cursors were not read to the end
+        if self.in_transaction {
+            let _ = self.client.batch_execute("ROLLBACK");
+        }
+    }
+}
+
 pub struct PostgresRawSourceParser<'a> {
-    iter: RowIter<'a>,
+    iter: Option<RowIter<'a>>,
+    cursors: Option<RefCursors<'a>>,
     rowbuf: Vec<Row>,
     ncols: usize,
     current_col: usize,
     current_row: usize,
     is_finished: bool,
+    result_set: Option<(Vec<String>, Vec<PostgresTypeSystem>)>,
 }
 
 impl<'a> PostgresRawSourceParser<'a> {
     pub fn new(iter: RowIter<'a>, schema: &[PostgresTypeSystem]) -> Self {
         Self {
-            iter,
+            iter: Some(iter),
+            cursors: None,
             rowbuf: Vec::with_capacity(DB_BUFFER_SIZE),
             ncols: schema.len(),
             current_row: 0,
             current_col: 0,
             is_finished: false,
+            result_set: None,
+        }
+    }
+
+    // read the result sets of the cursors `names`, which were opened in the current transaction
+    fn from_cursors(client: &'a mut Client, names: VecDeque<String>) -> Self {
+        Self {
+            iter: None,
+            cursors: Some(RefCursors {
+                client,
+                names,
+                fetch: None,
+                in_transaction: true,
+            }),
+            rowbuf: Vec::with_capacity(DB_BUFFER_SIZE),
+            ncols: 0,
+            current_row: 0,
+            current_col: 0,
+            is_finished: false,
+            result_set: None,
         }
     }
 
@@ -1191,18 +1711,40 @@ impl<'a> PartitionParser<'a> for PostgresRawSourceParser<'a> {
         if !self.rowbuf.is_empty() {
             self.rowbuf.drain(..);
         }
-        for _ in 0..DB_BUFFER_SIZE {
-            if let Some(row) = self.iter.next()? {
-                self.rowbuf.push(row);
-            } else {
-                self.is_finished = true;
-                break;
+        if let Some(cursors) = self.cursors.as_mut() {
+            self.rowbuf = cursors.fetch_rows()?;
+            self.is_finished = self.rowbuf.len() < DB_BUFFER_SIZE;
+        } else {
+            let iter = self.iter.as_mut().expect("no rows to fetch");
+            for _ in 0..DB_BUFFER_SIZE {
+                if let Some(row) = iter.next()? {
+                    self.rowbuf.push(row);
+                } else {
+                    self.is_finished = true;
+                    break;
+                }
             }
         }
         self.current_row = 0;
         self.current_col = 0;
         (self.rowbuf.len(), self.is_finished)
     }
+
+    #[throws(PostgresSourceError)]
+    fn next_result_set(&mut self) -> Option<(Vec<String>, Vec<PostgresTypeSystem>)> {
+        let result_set = match self.cursors.as_mut() {
+            Some(cursors) => cursors.next_cursor()?,
+            None => self.result_set.take(),
+        };
+        if let Some((_, schema)) = &result_set {
+            self.rowbuf.clear();
+            self.ncols = schema.len();
+            self.current_row = 0;
+            self.current_col = 0;
+            self.is_finished = false;
+        }
+        result_set
+    }
 }
 
 macro_rules! impl_produce {
@@ -1248,6 +1790,7 @@ impl_produce!(
     &'r str,
     Vec<u8>,
     NaiveTime,
+    Interval,
     Uuid,
     Value,
     IpInet,
@@ -1256,14 +1799,23 @@ impl_produce!(
     Bit,
     SparseVector,
     HashMap<String, Option<String>>,
-    Vec<Option<bool>>,
-    Vec<Option<String>>,
-    Vec<Option<i16>>,
-    Vec<Option<i32>>,
-    Vec<Option<i64>>,
-    Vec<Option<f32>>,
-    Vec<Option<f64>>,
-    Vec<Option<Decimal>>,
+    List<bool>,
+    List<i16>,
+    List<i32>,
+    List<i64>,
+    List<f32>,
+    List<f64>,
+    List<Decimal>,
+    List<String>,
+    List<Vec<u8>>,
+    List<NaiveDate>,
+    List<NaiveTime>,
+    List<NaiveDateTime>,
+    List<DateTime<Utc>>,
+    List<Interval>,
+    List<Uuid>,
+    List<Value>,
+    Composite,
 );
 
 impl<'r> Produce<'r, DateTime<Utc>> for PostgresRawSourceParser<'_> {
@@ -1383,8 +1935,13 @@ where
 
     #[throws(PostgresSourceError)]
     fn parser(&mut self) -> Self::Parser<'_> {
-        let rows = self.conn.simple_query(self.query.as_str())?; // unless reading the data, it seems like issue the query is fast
-        PostgresSimpleSourceParser::new(rows, &self.schema)
+        let result_set = self.raw_result_set(
+            "the postgres simple protocol for refcursors and statements without columns",
+        )?;
+        let rows = self.conn.simple_query(&inline_query(&self.query)?)?; // unless reading the data, it seems like issue the query is fast
+        let mut parser = PostgresSimpleSourceParser::new(rows, &self.schema);
+        parser.result_set = result_set;
+        parser
     }
 
     fn nrows(&self) -> usize {
@@ -1394,6 +1951,11 @@ where
     fn ncols(&self) -> usize {
         self.ncols
     }
+
+    #[throws(PostgresSourceError)]
+    fn finish(&mut self) {
+        self.run_post_execution_queries()?;
+    }
 }
 
 pub struct PostgresSimpleSourceParser {
@@ -1401,6 +1963,7 @@ pub struct PostgresSimpleSourceParser {
     ncols: usize,
     current_col: usize,
     current_row: usize,
+    result_set: Option<(Vec<String>, Vec<PostgresTypeSystem>)>,
 }
 impl PostgresSimpleSourceParser {
     pub fn new(rows: Vec<SimpleQueryMessage>, schema: &[PostgresTypeSystem]) -> Self {
@@ -1409,6 +1972,7 @@ impl PostgresSimpleSourceParser {
             ncols: schema.len(),
             current_row: 0,
             current_col: 0,
+            result_set: None,
         }
     }
 
@@ -1437,6 +2001,12 @@ impl PartitionParser<'_> for PostgresSimpleSourceParser {
 
         (self.rows.len() - 1 - self.current_row, true) // last message is command complete
     }
+
+    #[throws(PostgresSourceError)]
+    fn next_result_set(&mut self) -> Option<(Vec<String>, Vec<PostgresTypeSystem>)> {
+        // the rows of the query itself are its only result set
+        self.result_set.take()
+    }
 }
 
 macro_rules! impl_simple_produce {
@@ -1496,7 +2066,7 @@ macro_rules! impl_simple_produce {
     };
 }
 
-impl_simple_produce!(i8, i16, i32, i64, u32, f32, f64, Uuid, IpInet,);
+impl_simple_produce!(i8, i16, i32, i64, u32, f32, f64, Uuid, IpInet, Interval,);
 
 impl<'r> Produce<'r, bool> for PostgresSimpleSourceParser {
     type Error = PostgresSourceError;
@@ -1710,116 +2280,46 @@ impl<'r> Produce<'r, Option<Vec<u8>>> for PostgresSimpleSourceParser {
     }
 }
 
-fn rem_first_and_last(value: &str) -> &str {
-    let mut chars = value.chars();
-    chars.next();
-    chars.next_back();
-    chars.as_str()
-}
-
-macro_rules! impl_simple_vec_produce {
-    ($($t: ty,)+) => {
-        $(
-            impl<'r> Produce<'r, Vec<Option<$t>>> for PostgresSimpleSourceParser {
-                type Error = PostgresSourceError;
+impl<'r> Produce<'r, HashMap<String, Option<String>>> for PostgresSimpleSourceParser {
+    type Error = PostgresSourceError;
 
-                #[throws(PostgresSourceError)]
-                fn produce(&'r mut self) -> Vec<Option<$t>> {
-                    let (ridx, cidx) = self.next_loc()?;
-                    let val = match &self.rows[ridx] {
-                        SimpleQueryMessage::Row(row) => match row.try_get(cidx)? {
-                            Some(s) => match s{
-                                "" => throw!(anyhow!("Cannot parse NULL in non-NULL column.")),
-                                "{}" => vec![],
-                                _ => rem_first_and_last(s).split(",").map(|v| {
-                                    if v == "NULL" {
-                                        Ok(None)
-                                    } else {
-                                        match v.parse() {
-                                            Ok(v) => Ok(Some(v)),
-                                            Err(e) => Err(e).map_err(|_| ConnectorXError::cannot_produce::<Vec<$t>>(Some(s.into())))
-                                        }
-                                    }
-                                }).collect::<Result<Vec<Option<$t>>, ConnectorXError>>()?
-                            },
-                            None => throw!(anyhow!("Cannot parse NULL in non-NULL column.")),
-                        },
-                        SimpleQueryMessage::CommandComplete(c) => {
-                            panic!("get command: {}", c);
-                        }
-                        _ => {
-                            panic!("what?");
-                        }
-                    };
-                    val
-                }
+    #[throws(PostgresSourceError)]
+    fn produce(&'r mut self) -> HashMap<String, Option<String>> {
+        let (ridx, cidx) = self.next_loc()?;
+        let val = match &self.rows[ridx] {
+            SimpleQueryMessage::Row(row) => match row.try_get(cidx)? {
+                Some(s) => hstore_from_text(s).ok_or_else(|| {
+                    ConnectorXError::cannot_produce::<HashMap<String, Option<String>>>(Some(
+                        s.into(),
+                    ))
+                })?,
+                None => throw!(anyhow!("Cannot parse NULL in non-NULL column.")),
+            },
+            SimpleQueryMessage::CommandComplete(c) => {
+                panic!("get command: {}", c);
             }
-
-            impl<'r, 'a> Produce<'r, Option<Vec<Option<$t>>>> for PostgresSimpleSourceParser {
-                type Error = PostgresSourceError;
-
-                #[throws(PostgresSourceError)]
-                fn produce(&'r mut self) -> Option<Vec<Option<$t>>> {
-                    let (ridx, cidx) = self.next_loc()?;
-                    let val = match &self.rows[ridx] {
-
-                        SimpleQueryMessage::Row(row) => match row.try_get(cidx)? {
-                            Some(s) => match s{
-                                "" => None,
-                                "{}" => Some(vec![]),
-                                _ => Some(rem_first_and_last(s).split(",").map(|v| {
-                                    if v == "NULL" {
-                                        Ok(None)
-                                    } else {
-                                        match v.parse() {
-                                            Ok(v) => Ok(Some(v)),
-                                            Err(e) => Err(e).map_err(|_| ConnectorXError::cannot_produce::<Vec<$t>>(Some(s.into())))
-                                        }
-                                    }
-                                }).collect::<Result<Vec<Option<$t>>, ConnectorXError>>()?)
-                            },
-                            None => None,
-                        },
-
-                        SimpleQueryMessage::CommandComplete(c) => {
-                            panic!("get command: {}", c);
-                        }
-                        _ => {
-                            panic!("what?");
-                        }
-                    };
-                    val
-                }
+            _ => {
+                panic!("what?");
             }
-        )+
-    };
+        };
+        val
+    }
 }
-impl_simple_vec_produce!(i16, i32, i64, f32, f64, Decimal, String,);
 
-impl<'r> Produce<'r, Vec<Option<bool>>> for PostgresSimpleSourceParser {
+impl<'r> Produce<'r, Option<HashMap<String, Option<String>>>> for PostgresSimpleSourceParser {
     type Error = PostgresSourceError;
 
     #[throws(PostgresSourceError)]
-    fn produce(&'r mut self) -> Vec<Option<bool>> {
+    fn produce(&'r mut self) -> Option<HashMap<String, Option<String>>> {
         let (ridx, cidx) = self.next_loc()?;
         let val = match &self.rows[ridx] {
             SimpleQueryMessage::Row(row) => match row.try_get(cidx)? {
-                Some(s) => match s {
-                    "" => throw!(anyhow!("Cannot parse NULL in non-NULL column.")),
-                    "{}" => vec![],
-                    _ => rem_first_and_last(s)
-                        .split(',')
-                        .map(|token| match token {
-                            "NULL" => Ok(None),
-                            "t" => Ok(Some(true)),
-                            "f" => Ok(Some(false)),
-                            _ => {
-                                throw!(ConnectorXError::cannot_produce::<Vec<bool>>(Some(s.into())))
-                            }
-                        })
-                        .collect::<Result<Vec<Option<bool>>, ConnectorXError>>()?,
-                },
-                None => throw!(anyhow!("Cannot parse NULL in non-NULL column.")),
+                Some(s) => Some(hstore_from_text(s).ok_or_else(|| {
+                    ConnectorXError::cannot_produce::<HashMap<String, Option<String>>>(Some(
+                        s.into(),
+                    ))
+                })?),
+                None => None,
             },
             SimpleQueryMessage::CommandComplete(c) => {
                 panic!("get command: {}", c);
@@ -1832,34 +2332,17 @@ impl<'r> Produce<'r, Vec<Option<bool>>> for PostgresSimpleSourceParser {
     }
 }
 
-impl<'r> Produce<'r, Option<Vec<Option<bool>>>> for PostgresSimpleSourceParser {
+impl<'r, T: FromArrayText> Produce<'r, List<T>> for PostgresSimpleSourceParser {
     type Error = PostgresSourceError;
 
     #[throws(PostgresSourceError)]
-    fn produce(&'r mut self) -> Option<Vec<Option<bool>>> {
+    fn produce(&'r mut self) -> List<T> {
         let (ridx, cidx) = self.next_loc()?;
         let val = match &self.rows[ridx] {
             SimpleQueryMessage::Row(row) => match row.try_get(cidx)? {
-                Some(s) => match s {
-                    "" => None,
-                    "{}" => Some(vec![]),
-                    _ => Some(
-                        rem_first_and_last(s)
-                            .split(',')
-                            .map(|token| match token {
-                                "NULL" => Ok(None),
-                                "t" => Ok(Some(true)),
-                                "f" => Ok(Some(false)),
-                                _ => {
-                                    throw!(ConnectorXError::cannot_produce::<Vec<bool>>(Some(
-                                        s.into()
-                                    )))
-                                }
-                            })
-                            .collect::<Result<Vec<Option<bool>>, ConnectorXError>>()?,
-                    ),
-                },
-                None => None,
+                Some(s) => list_from_text(s)
+                    .ok_or_else(|| ConnectorXError::cannot_produce::<List<T>>(Some(s.into())))?,
+                None => throw!(anyhow!("Cannot parse NULL in non-NULL column.")),
             },
             SimpleQueryMessage::CommandComplete(c) => {
                 panic!("get command: {}", c);
@@ -1872,6 +2355,31 @@ impl<'r> Produce<'r, Option<Vec<Option<bool>>>> for PostgresSimpleSourceParser {
     }
 }
 
+impl<'r, T: FromArrayText> Produce<'r, Option<List<T>>> for PostgresSimpleSourceParser {
+    type Error = PostgresSourceError;
+
+    #[throws(PostgresSourceError)]
+    fn produce(&'r mut self) -> Option<List<T>> {
+        let (ridx, cidx) = self.next_loc()?;
+        let val =
+            match &self.rows[ridx] {
+                SimpleQueryMessage::Row(row) => match row.try_get(cidx)? {
+                    Some(s) => Some(list_from_text(s).ok_or_else(|| {
+                        ConnectorXError::cannot_produce::<List<T>>(Some(s.into()))
+                    })?),
+                    None => None,
+                },
+                SimpleQueryMessage::CommandComplete(c) => {
+                    panic!("get command: {}", c);
+                }
+                _ => {
+                    panic!("what?");
+                }
+            };
+        val
+    }
+}
+
 impl<'r> Produce<'r, NaiveDate> for PostgresSimpleSourceParser {
     type Error = PostgresSourceError;
 
@@ -1,9 +1,14 @@
+use super::errors::PostgresSourceError;
+use crate::errors::ConnectorXError;
 use crate::sources::postgres::IpInet;
+use crate::typesystem::{Composite, Interval, List};
 use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use fehler::{throw, throws};
 use postgres::types::Type;
 use rust_decimal::Decimal;
 use serde_json::Value;
 use std::collections::HashMap;
+use std::convert::TryFrom;
 use uuid::Uuid;
 
 use pgvector::{Bit, HalfVector, SparseVector, Vector};
@@ -27,6 +32,16 @@ pub enum PostgresTypeSystem {
     Int8Array(bool),
     VarcharArray(bool),
     TextArray(bool),
+    ByteAArray(bool),
+    DateArray(bool),
+    TimeArray(bool),
+    TimestampArray(bool),
+    TimestampTzArray(bool),
+    IntervalArray(bool),
+    UUIDArray(bool),
+    JSONArray(bool),
+    JSONBArray(bool),
+    EnumArray(bool),
     Date(bool),
     Char(bool),
     BpChar(bool),
@@ -34,6 +49,7 @@ pub enum PostgresTypeSystem {
     Text(bool),
     ByteA(bool),
     Time(bool),
+    Interval(bool),
     Timestamp(bool),
     TimestampTz(bool),
     UUID(bool),
@@ -47,6 +63,7 @@ pub enum PostgresTypeSystem {
     HalfVec(bool),
     Bit(bool),
     SparseVec(bool),
+    Composite(bool),
 }
 
 impl_typesystem! {
@@ -59,19 +76,28 @@ impl_typesystem! {
         { Float4 => f32 }
         { Float8 => f64 }
         { Numeric => Decimal }
-        { BoolArray => Vec<Option<bool>> }
-        { Int2Array => Vec<Option<i16>> }
-        { Int4Array => Vec<Option<i32>> }
-        { Int8Array => Vec<Option<i64>> }
-        { Float4Array => Vec<Option<f32>> }
-        { Float8Array => Vec<Option<f64>> }
-        { NumericArray => Vec<Option<Decimal>> }
-        { VarcharArray | TextArray => Vec<Option<String>>}
+        { BoolArray => List<bool> }
+        { Int2Array => List<i16> }
+        { Int4Array => List<i32> }
+        { Int8Array => List<i64> }
+        { Float4Array => List<f32> }
+        { Float8Array => List<f64> }
+        { NumericArray => List<Decimal> }
+        { VarcharArray | TextArray | EnumArray => List<String> }
+        { ByteAArray => List<Vec<u8>> }
+        { DateArray => List<NaiveDate> }
+        { TimeArray => List<NaiveTime> }
+        { TimestampArray => List<NaiveDateTime> }
+        { TimestampTzArray => List<DateTime<Utc>> }
+        { IntervalArray => List<Interval> }
+        { UUIDArray => List<Uuid> }
+        { JSONArray | JSONBArray => List<Value> }
         { Bool => bool }
         { Char => i8 }
         { Text | BpChar | VarChar | Enum | Name => &'r str }
         { ByteA => Vec<u8> }
         { Time => NaiveTime }
+        { Interval => Interval }
         { Timestamp => NaiveDateTime }
         { TimestampTz => DateTime<Utc> }
         { Date => NaiveDate }
@@ -83,11 +109,15 @@ impl_typesystem! {
         { HalfVec => HalfVector }
         { Bit => Bit }
         { SparseVec => SparseVector }
+        { Composite => Composite }
     }
 }
 
-impl<'a> From<&'a Type> for PostgresTypeSystem {
-    fn from(ty: &'a Type) -> PostgresTypeSystem {
+impl<'a> TryFrom<&'a Type> for PostgresTypeSystem {
+    type Error = PostgresSourceError;
+
+    #[throws(PostgresSourceError)]
+    fn try_from(ty: &'a Type) -> PostgresTypeSystem {
         use PostgresTypeSystem::*;
         match ty.name() {
             "int2" => Int2(true),
@@ -105,7 +135,16 @@ impl<'a> From<&'a Type> for PostgresTypeSystem {
             "_float8" => Float8Array(true),
             "_numeric" => NumericArray(true),
             "_varchar" => VarcharArray(true),
-            "_text" => TextArray(true),
+            "_text" | "_bpchar" | "_name" | "_citext" => TextArray(true),
+            "_bytea" => ByteAArray(true),
+            "_date" => DateArray(true),
+            "_time" => TimeArray(true),
+            "_timestamp" => TimestampArray(true),
+            "_timestamptz" => TimestampTzArray(true),
+            "_interval" => IntervalArray(true),
+            "_uuid" => UUIDArray(true),
+            "_json" => JSONArray(true),
+            "_jsonb" => JSONBArray(true),
             "bool" => Bool(true),
             "char" => Char(true),
             "text" | "citext" | "ltree" | "lquery" | "ltxtquery" | "name" => Text(true),
@@ -113,6 +152,7 @@ impl<'a> From<&'a Type> for PostgresTypeSystem {
             "varchar" => VarChar(true),
             "bytea" => ByteA(true),
             "time" => Time(true),
+            "interval" => Interval(true),
             "timestamp" => Timestamp(true),
             "timestamptz" => TimestampTz(true),
             "date" => Date(true),
@@ -125,9 +165,17 @@ impl<'a> From<&'a Type> for PostgresTypeSystem {
             "halfvec" => HalfVec(true),
             "bit" => Bit(true),
             "sparsevec" => SparseVec(true),
+            // the fields of anonymous records are not known before they are read
+            "record" => throw!(ConnectorXError::UnsupportedType(String::from("record"))),
             _ => match ty.kind() {
                 postgres::types::Kind::Enum(_) => Enum(true),
-                _ => unimplemented!("{}", ty.name()),
+                postgres::types::Kind::Array(member)
+                    if matches!(member.kind(), postgres::types::Kind::Enum(_)) =>
+                {
+                    EnumArray(true)
+                }
+                postgres::types::Kind::Composite(_) => PostgresTypeSystem::Composite(true),
+                _ => throw!(ConnectorXError::UnsupportedType(ty.name().to_string())),
             },
         }
     }
@@ -141,7 +189,6 @@ impl From<PostgresTypePairs<'_>> for Type {
         use PostgresTypeSystem::*;
         match ty.1 {
             Enum(_) => Type::TEXT,
-            HSTORE(_) => Type::TEXT, // hstore is not supported in binary protocol (since no corresponding inner TYPE)
             _ => ty.0.clone(),
         }
     }
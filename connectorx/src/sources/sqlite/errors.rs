@@ -1,3 +1,4 @@
+use crate::sources::timeout::is_pool_timeout;
 use std::string::FromUtf8Error;
 use thiserror::Error;
 
@@ -22,3 +23,17 @@ pub enum SQLiteSourceError {
     #[error(transparent)]
     Other(#[from] anyhow::Error),
 }
+
+impl SQLiteSourceError {
+    /// Whether the error is raised because the statement or connect timeout elapsed.
+    pub fn is_timeout(&self) -> bool {
+        match self {
+            SQLiteSourceError::SQLitePoolError(e) => is_pool_timeout(e),
+            // the progress handler interrupts the statement once the deadline passed
+            SQLiteSourceError::SQLiteError(rusqlite::Error::SqliteFailure(e, _)) => {
+                e.code == rusqlite::ErrorCode::OperationInterrupted
+            }
+            _ => false,
+        }
+    }
+}
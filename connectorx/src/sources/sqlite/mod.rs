@@ -7,21 +7,28 @@ pub use self::errors::SQLiteSourceError;
 use crate::{
     data_order::DataOrder,
     errors::ConnectorXError,
-    sources::{PartitionParser, Produce, Source, SourcePartition},
-    sql::{count_query, limit1_query, CXQuery},
+    sources::{
+        timeout::Timeouts, ColumnDescription, PartitionParser, Produce, Source, SourcePartition,
+    },
+    sql::{count_query, limit1_query, redact_query, CXParam, CXQuery},
     utils::DummyBox,
 };
 use anyhow::anyhow;
 use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
 use fallible_streaming_iterator::FallibleStreamingIterator;
 use fehler::{throw, throws};
-use log::debug;
 use owning_ref::OwningHandle;
 use r2d2::{Pool, PooledConnection};
 use r2d2_sqlite::SqliteConnectionManager;
-use rusqlite::{Row, Rows, Statement};
+use rusqlite::{
+    params_from_iter,
+    types::{ToSqlOutput, Type, Value},
+    Connection, Row, Rows, Statement, ToSql,
+};
 use sqlparser::dialect::SQLiteDialect;
 use std::convert::TryFrom;
+use std::time::{Duration, Instant};
+use tracing::debug;
 pub use typesystem::SQLiteTypeSystem;
 use urlencoding::decode;
 
@@ -31,17 +38,34 @@ pub struct SQLiteSource {
     queries: Vec<CXQuery<String>>,
     names: Vec<String>,
     schema: Vec<SQLiteTypeSystem>,
+    // the declared types of the columns, `None` for expressions
+    decl_types: Vec<Option<String>>,
+    statement_timeout: Option<Duration>,
+    pre_execution_queries: Option<Vec<String>>,
+    post_execution_queries: Option<Vec<String>>,
 }
 
 impl SQLiteSource {
     #[throws(SQLiteSourceError)]
     pub fn new(conn: &str, nconn: usize) -> Self {
-        let decoded_conn = decode(conn)?.into_owned();
+        let mut decoded_conn = decode(conn)?.into_owned();
+        let mut timeouts = Timeouts::default();
+        if let Some((path, query)) = decoded_conn.rsplit_once('?') {
+            timeouts = Timeouts::from_query(query)?;
+            let query = Timeouts::strip_query(query);
+            decoded_conn = if query.is_empty() {
+                path.to_string()
+            } else {
+                format!("{}?{}", path, query)
+            };
+        }
         debug!("decoded conn: {}", decoded_conn);
         let manager = SqliteConnectionManager::file(decoded_conn);
-        let pool = r2d2::Pool::builder()
-            .max_size(nconn as u32)
-            .build(manager)?;
+        let mut builder = r2d2::Pool::builder().max_size(nconn as u32);
+        if let Some(timeout) = timeouts.connect {
+            builder = builder.connection_timeout(timeout);
+        }
+        let pool = builder.build(manager)?;
 
         Self {
             pool,
@@ -49,10 +73,22 @@ impl SQLiteSource {
             queries: vec![],
             names: vec![],
             schema: vec![],
+            decl_types: vec![],
+            statement_timeout: timeouts.statement,
+            pre_execution_queries: None,
+            post_execution_queries: None,
         }
     }
 }
 
+/// Interrupt the statements run on `conn` from now on once `timeout` elapsed.
+fn set_deadline(conn: &Connection, timeout: Option<Duration>) {
+    if let Some(timeout) = timeout {
+        let deadline = Instant::now() + timeout;
+        conn.progress_handler(1000, Some(move || Instant::now() > deadline));
+    }
+}
+
 impl Source for SQLiteSource
 where
     SQLiteSourcePartition: SourcePartition<TypeSystem = SQLiteTypeSystem>,
@@ -77,10 +113,61 @@ where
         self.origin_query = query;
     }
 
+    fn set_pre_execution_queries(&mut self, pre_execution_queries: Option<&[String]>) {
+        self.pre_execution_queries = pre_execution_queries.map(|s| s.to_vec());
+    }
+
+    fn set_post_execution_queries(&mut self, post_execution_queries: Option<&[String]>) {
+        self.post_execution_queries = post_execution_queries.map(|s| s.to_vec());
+    }
+
+    fn fork(&self) -> Self {
+        Self {
+            pool: self.pool.clone(),
+            origin_query: None,
+            queries: vec![],
+            names: vec![],
+            schema: vec![],
+            decl_types: vec![],
+            statement_timeout: self.statement_timeout,
+            pre_execution_queries: self.pre_execution_queries.clone(),
+            post_execution_queries: self.post_execution_queries.clone(),
+        }
+    }
+
     #[throws(SQLiteSourceError)]
     fn fetch_metadata(&mut self) {
         assert!(!self.queries.is_empty());
         let conn = self.pool.get()?;
+        set_deadline(&conn, self.statement_timeout);
+
+        // preparing a statement exposes the declared types of the columns from tables, only
+        // expressions need a row to infer their types from
+        let stmt = conn.prepare(self.queries[0].as_str())?;
+        self.decl_types = stmt
+            .columns()
+            .iter()
+            .map(|col| col.decl_type().map(str::to_string))
+            .collect();
+        let described: Option<Vec<SQLiteTypeSystem>> = stmt
+            .columns()
+            .iter()
+            .map(|col| {
+                col.decl_type()
+                    .and_then(|t| SQLiteTypeSystem::try_from((Some(t), Type::Null)).ok())
+            })
+            .collect();
+        if let Some(types) = described {
+            self.names = stmt
+                .column_names()
+                .into_iter()
+                .map(|s| s.to_string())
+                .collect();
+            self.schema = types;
+            return;
+        }
+        drop(stmt);
+
         let mut names = vec![];
         let mut types = vec![];
         let mut num_empty = 0;
@@ -89,7 +176,8 @@ where
         for (i, query) in self.queries.iter().enumerate() {
             let l1query = limit1_query(query, &SQLiteDialect {})?;
 
-            let is_sucess = conn.query_row(l1query.as_str(), [], |row| {
+            let params = params_from_iter(l1query.params());
+            let is_sucess = conn.query_row(l1query.as_str(), params, |row| {
                 for (j, col) in row.as_ref().columns().iter().enumerate() {
                     if j >= names.len() {
                         names.push(col.name().to_string());
@@ -122,8 +210,9 @@ where
                         return;
                     } else if i == self.queries.len() - 1 {
                         debug!(
-                            "cannot get metadata for '{}' due to null value: {:?}",
-                            query, types
+                            query = %redact_query(query.as_str()),
+                            ?types,
+                            "cannot get metadata due to null value"
                         );
                         throw!(SQLiteSourceError::InferTypeFromNull);
                     }
@@ -134,7 +223,7 @@ where
                     }
                     if i == self.queries.len() - 1 && num_empty < self.queries.len() {
                         // tried the last query but still get an error
-                        debug!("cannot get metadata for '{}': {}", query, e);
+                        debug!(query = %redact_query(query.as_str()), error = %e, "cannot get metadata");
                         throw!(e)
                     }
                 }
@@ -157,10 +246,13 @@ where
     fn result_rows(&mut self) -> Option<usize> {
         match &self.origin_query {
             Some(q) => {
-                let cxq = CXQuery::Naked(q.clone());
+                // the partitioned queries are bound to the parameters of the origin query
+                let cxq = CXQuery::Naked(q.clone()).bind(self.queries[0].params().to_vec());
                 let conn = self.pool.get()?;
+                set_deadline(&conn, self.statement_timeout);
+                let cquery = count_query(&cxq, &SQLiteDialect {})?;
                 let nrows =
-                    conn.query_row(count_query(&cxq, &SQLiteDialect {})?.as_str(), [], |row| {
+                    conn.query_row(cquery.as_str(), params_from_iter(cquery.params()), |row| {
                         Ok(row.get::<_, i64>(0)? as usize)
                     })?;
                 Some(nrows)
@@ -177,13 +269,42 @@ where
         self.schema.clone()
     }
 
+    /// Table columns are described with their declared type, e.g. `VARCHAR(10)`, and expressions
+    /// with the type inferred from their values.
+    #[throws(SQLiteSourceError)]
+    fn describe_columns(&mut self) -> Vec<ColumnDescription> {
+        self.schema
+            .iter()
+            .zip(&self.decl_types)
+            .map(|(&ts, decl_type)| match decl_type {
+                Some(decl_type) => ColumnDescription {
+                    source_type: decl_type.clone(),
+                    ..ColumnDescription::from_typesystem(ts)
+                },
+                None => ColumnDescription::from_typesystem(ts),
+            })
+            .collect()
+    }
+
     #[throws(SQLiteSourceError)]
     fn partition(self) -> Vec<Self::Partition> {
         let mut ret = vec![];
         for query in self.queries {
             let conn = self.pool.get()?;
 
-            ret.push(SQLiteSourcePartition::new(conn, &query, &self.schema));
+            if let Some(pre_queries) = &self.pre_execution_queries {
+                for pre_query in pre_queries {
+                    conn.execute_batch(pre_query)?;
+                }
+            }
+
+            ret.push(SQLiteSourcePartition::new(
+                conn,
+                &query,
+                &self.schema,
+                self.statement_timeout,
+                self.post_execution_queries.clone(),
+            ));
         }
         ret
     }
@@ -195,6 +316,8 @@ pub struct SQLiteSourcePartition {
     schema: Vec<SQLiteTypeSystem>,
     nrows: usize,
     ncols: usize,
+    statement_timeout: Option<Duration>,
+    post_execution_queries: Option<Vec<String>>,
 }
 
 impl SQLiteSourcePartition {
@@ -202,6 +325,8 @@ impl SQLiteSourcePartition {
         conn: PooledConnection<SqliteConnectionManager>,
         query: &CXQuery<String>,
         schema: &[SQLiteTypeSystem],
+        statement_timeout: Option<Duration>,
+        post_execution_queries: Option<Vec<String>>,
     ) -> Self {
         Self {
             conn,
@@ -209,6 +334,8 @@ impl SQLiteSourcePartition {
             schema: schema.to_vec(),
             nrows: 0,
             ncols: schema.len(),
+            statement_timeout,
+            post_execution_queries,
         }
     }
 }
@@ -220,16 +347,24 @@ impl SourcePartition for SQLiteSourcePartition {
 
     #[throws(SQLiteSourceError)]
     fn result_rows(&mut self) {
-        self.nrows = self.conn.query_row(
-            count_query(&self.query, &SQLiteDialect {})?.as_str(),
-            [],
-            |row| Ok(row.get::<_, i64>(0)? as usize),
-        )?;
+        set_deadline(&self.conn, self.statement_timeout);
+        let cquery = count_query(&self.query, &SQLiteDialect {})?;
+        self.nrows =
+            self.conn
+                .query_row(cquery.as_str(), params_from_iter(cquery.params()), |row| {
+                    Ok(row.get::<_, i64>(0)? as usize)
+                })?;
     }
 
     #[throws(SQLiteSourceError)]
     fn parser(&mut self) -> Self::Parser<'_> {
-        SQLiteSourcePartitionParser::new(&self.conn, self.query.as_str(), &self.schema)?
+        set_deadline(&self.conn, self.statement_timeout);
+        SQLiteSourcePartitionParser::new(
+            &self.conn,
+            self.query.as_str(),
+            self.query.params(),
+            &self.schema,
+        )?
     }
 
     fn nrows(&self) -> usize {
@@ -239,6 +374,15 @@ impl SourcePartition for SQLiteSourcePartition {
     fn ncols(&self) -> usize {
         self.ncols
     }
+
+    #[throws(SQLiteSourceError)]
+    fn finish(&mut self) {
+        if let Some(post_queries) = &self.post_execution_queries {
+            for post_query in post_queries {
+                self.conn.execute_batch(post_query)?;
+            }
+        }
+    }
 }
 
 unsafe impl<'a> Send for SQLiteSourcePartitionParser<'a> {}
@@ -256,16 +400,26 @@ impl<'a> SQLiteSourcePartitionParser<'a> {
     pub fn new(
         conn: &'a PooledConnection<SqliteConnectionManager>,
         query: &str,
+        params: &[CXParam],
         schema: &[SQLiteTypeSystem],
     ) -> Self {
-        let stmt: Statement<'a> = conn.prepare(query)?;
+        let mut stmt: Statement<'a> = conn.prepare(query)?;
+        if stmt.parameter_count() != params.len() {
+            throw!(rusqlite::Error::InvalidParameterCount(
+                params.len(),
+                stmt.parameter_count()
+            ));
+        }
+        for (i, param) in params.iter().enumerate() {
+            stmt.raw_bind_parameter(i + 1, param)?;
+        }
 
         // Safety: DummyBox borrows the on-heap stmt, which is owned by the OwningHandle.
         // No matter how we move the owning handle (thus the Box<Statment>), the Statement
         // keeps its address static on the heap, thus the borrow of MyRows keeps valid.
         let rows: OwningHandle<Box<Statement<'a>>, DummyBox<Rows<'a>>> =
             OwningHandle::new_with_fn(Box::new(stmt), |stmt: *const Statement<'a>| unsafe {
-                DummyBox((*(stmt as *mut Statement<'_>)).query([]).unwrap())
+                DummyBox((*(stmt as *mut Statement<'_>)).raw_query())
             });
         Self {
             rows,
@@ -355,3 +509,18 @@ impl_produce!(
     NaiveDateTime,
     Vec<u8>,
 );
+
+impl ToSql for CXParam {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(match self {
+            CXParam::Null => ToSqlOutput::Owned(Value::Null),
+            CXParam::Bool(v) => ToSqlOutput::Owned(Value::Integer(*v as i64)),
+            CXParam::I64(v) => ToSqlOutput::Owned(Value::Integer(*v)),
+            CXParam::F64(v) => ToSqlOutput::Owned(Value::Real(*v)),
+            CXParam::Str(v) => v.to_sql()?,
+            CXParam::Bytes(v) => v.to_sql()?,
+            CXParam::Date(v) => v.to_sql()?,
+            CXParam::DateTime(v) => v.to_sql()?,
+        })
+    }
+}
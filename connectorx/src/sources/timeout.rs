@@ -0,0 +1,77 @@
+//! Connect and statement timeouts shared by the database sources.
+
+use crate::constants::{CONNECT_TIMEOUT, STATEMENT_TIMEOUT};
+use anyhow::anyhow;
+use fehler::throws;
+use std::time::Duration;
+use url::{form_urlencoded, Url};
+
+/// Timeouts set by the `connect_timeout` (in seconds) and `statement_timeout` (in milliseconds)
+/// parameters of a connection string. Each source maps them to the mechanism of its backend.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Timeouts {
+    /// Maximum time to establish a connection.
+    pub connect: Option<Duration>,
+    /// Maximum time a single query may run.
+    pub statement: Option<Duration>,
+}
+
+impl Timeouts {
+    #[throws(anyhow::Error)]
+    pub fn from_url(url: &Url) -> Self {
+        Self::from_query(url.query().unwrap_or(""))?
+    }
+
+    /// Parse the timeouts from the query part of a connection string, e.g.
+    /// `a=1&statement_timeout=50`.
+    #[throws(anyhow::Error)]
+    pub fn from_query(query: &str) -> Self {
+        let mut timeouts = Self::default();
+        for (k, v) in form_urlencoded::parse(query.as_bytes()) {
+            let parse = || {
+                v.parse::<u64>()
+                    .map_err(|_| anyhow!("invalid value for {}: {}", k, v))
+            };
+            match &*k {
+                CONNECT_TIMEOUT => timeouts.connect = Some(Duration::from_secs(parse()?)),
+                STATEMENT_TIMEOUT => timeouts.statement = Some(Duration::from_millis(parse()?)),
+                _ => {}
+            }
+        }
+        timeouts
+    }
+
+    /// Remove the timeout parameters from `url`, for drivers that reject unknown parameters.
+    pub fn strip(url: &Url) -> Url {
+        let mut url = url.clone();
+        let query = Self::strip_query(url.query().unwrap_or(""));
+        url.set_query(Some(query.as_str()).filter(|q| !q.is_empty()));
+        url
+    }
+
+    /// Same as [`Timeouts::strip`], but on the query part of a connection string.
+    pub fn strip_query(query: &str) -> String {
+        form_urlencoded::Serializer::new(String::new())
+            .extend_pairs(
+                form_urlencoded::parse(query.as_bytes())
+                    .filter(|p| !matches!(&*p.0, CONNECT_TIMEOUT | STATEMENT_TIMEOUT)),
+            )
+            .finish()
+    }
+}
+
+/// Whether the pool failed because no connection could be checked out in time. r2d2 only fails
+/// on timeouts, but then carries the error of the last failed connection attempt, if any, e.g.
+/// bad credentials, which is only a timeout when the attempt itself timed out.
+#[cfg(feature = "r2d2")]
+pub fn is_pool_timeout(e: &r2d2::Error) -> bool {
+    const POOL_TIMEOUT: &str = "timed out waiting for connection";
+    match e.to_string().strip_prefix(POOL_TIMEOUT) {
+        Some("") => true,
+        Some(cause) => {
+            let cause = cause.to_lowercase();
+            cause.contains("timed out") || cause.contains("timeout")
+        }
+        None => false,
+    }
+}
@@ -23,3 +23,16 @@ pub enum TrinoSourceError {
     #[error(transparent)]
     Other(#[from] anyhow::Error),
 }
+
+impl TrinoSourceError {
+    /// Whether the error is raised because the statement or connect timeout elapsed.
+    pub fn is_timeout(&self) -> bool {
+        match self {
+            TrinoSourceError::PrustoError(prusto::error::Error::QueryError(e)) => {
+                e.error_name == "EXCEEDED_TIME_LIMIT"
+            }
+            TrinoSourceError::PrustoError(prusto::error::Error::HttpError(e)) => e.is_timeout(),
+            _ => false,
+        }
+    }
+}
@@ -1,22 +1,31 @@
-use std::{marker::PhantomData, sync::Arc};
+use std::{
+    marker::PhantomData,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc,
+    },
+};
 
-use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use chrono::{Duration, NaiveDate, NaiveDateTime, NaiveTime};
 use fehler::{throw, throws};
-use prusto::{auth::Auth, Client, ClientBuilder, DataSet, Presto, Row};
+use prusto::{auth::Auth, Client, ClientBuilder, DataSet, Presto, PrestoTy, Row};
 use serde_json::Value;
 use sqlparser::dialect::{Dialect, GenericDialect};
 use std::convert::TryFrom;
 use tokio::runtime::Runtime;
+use tracing::{debug, warn};
 
 use crate::{
     data_order::DataOrder,
     errors::ConnectorXError,
-    sources::Produce,
-    sql::{count_query, limit1_query, CXQuery},
+    sources::{timeout::Timeouts, ColumnDescription, Produce},
+    sql::{count_query, inline_params, limit1_query, CXParam, CXQuery, PlaceholderStyle},
+    typesystem::Interval,
 };
 
 pub use self::{errors::TrinoSourceError, typesystem::TrinoTypeSystem};
 use urlencoding::decode;
+use uuid::Uuid;
 
 use super::{PartitionParser, Source, SourcePartition};
 
@@ -25,12 +34,111 @@ use anyhow::anyhow;
 pub mod errors;
 pub mod typesystem;
 
+/// The SQL text of `query` with its parameters inlined as literals, since the trino client only
+/// sends plain statements.
+#[throws(ConnectorXError)]
+pub(crate) fn inline_query(query: &CXQuery<String>) -> String {
+    inline_params(
+        query.as_str(),
+        query.params(),
+        PlaceholderStyle::QuestionMark,
+        false,
+        |param| match param {
+            CXParam::Null => "NULL".to_string(),
+            CXParam::Bool(v) => v.to_string(),
+            CXParam::I64(v) => format!("({})", v),
+            CXParam::F64(v) if v.is_nan() => "nan()".to_string(),
+            CXParam::F64(v) if v.is_infinite() => {
+                format!("({}infinity())", if *v < 0. { "-" } else { "" })
+            }
+            CXParam::F64(v) => format!("DOUBLE '{}'", v),
+            CXParam::Str(v) => format!("'{}'", v.replace('\'', "''")),
+            CXParam::Bytes(v) => format!(
+                "X'{}'",
+                v.iter().map(|b| format!("{:02x}", b)).collect::<String>()
+            ),
+            CXParam::Date(v) => format!("DATE '{}'", v),
+            CXParam::DateTime(v) => format!("TIMESTAMP '{}'", v),
+        },
+    )?
+}
+
+// numbers the prepared statements used to describe queries, since the session of the client
+// is shared by every describe running concurrently
+static DESCRIBE_STMT_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Describe the output columns of `query` through a prepared statement, without running it.
+/// Returns `None` if a column type cannot be mapped to [`TrinoTypeSystem`].
+#[throws(TrinoSourceError)]
+#[allow(clippy::type_complexity)]
+fn describe_output(
+    rt: &Runtime,
+    client: &Client,
+    query: &str,
+) -> Option<(Vec<String>, Vec<TrinoTypeSystem>, Vec<String>)> {
+    let stmt = format!(
+        "cx_describe_{}",
+        DESCRIBE_STMT_ID.fetch_add(1, Ordering::Relaxed)
+    );
+    rt.block_on(client.execute(format!("PREPARE {} FROM {}", stmt, query)))
+        .map_err(TrinoSourceError::PrustoError)?;
+    let described = rt.block_on(client.get_all::<Row>(format!("DESCRIBE OUTPUT {}", stmt)));
+    // the statement is kept in the session of the shared client until deallocated
+    let _ = rt.block_on(client.execute(format!("DEALLOCATE PREPARE {}", stmt)));
+
+    let mut names = vec![];
+    let mut types = vec![];
+    let mut declared = vec![];
+    // columns: Column Name, Catalog, Schema, Table, Type, Type Size, Aliased
+    for row in described.map_err(TrinoSourceError::PrustoError)?.into_vec() {
+        let row = row.into_json();
+        let (name, ty) = match (row.first(), row.get(4)) {
+            (Some(Value::String(name)), Some(Value::String(ty))) => (name.clone(), ty),
+            _ => return None,
+        };
+        if ty.ends_with("with time zone") {
+            return None;
+        }
+        let base = ty.split('(').next().unwrap_or(ty);
+        match TrinoTypeSystem::try_from((Some(base), PrestoTy::Unknown)) {
+            Ok(ty) => types.push(ty),
+            Err(_) => return None,
+        }
+        names.push(name);
+        declared.push(ty.clone());
+    }
+    Some((names, types, declared))
+}
+
+// describe a column by its declared type, e.g. `decimal(12,2)` or `timestamp(3) with time zone`
+fn describe_type(ty: &str) -> ColumnDescription {
+    let (base, params) = match ty.split_once('(') {
+        Some((base, rest)) => (base, rest.split(')').next().unwrap_or_default()),
+        None => (ty, ""),
+    };
+    let mut params = params.split(',').map(|p| p.trim().parse::<u32>().ok());
+    let mut description = ColumnDescription {
+        source_type: ty.to_string(),
+        ..Default::default()
+    };
+    match base {
+        "decimal" => {
+            description.precision = params.next().flatten();
+            description.scale = params.next().flatten().map(|scale| scale as i32);
+        }
+        "varchar" | "char" => description.length = params.next().flatten(),
+        "time" | "timestamp" => description.precision = params.next().flatten(),
+        _ => {}
+    }
+    description
+}
+
 #[throws(TrinoSourceError)]
 fn get_total_rows(rt: Arc<Runtime>, client: Arc<Client>, query: &CXQuery<String>) -> usize {
     let cquery = count_query(query, &TrinoDialect {})?;
 
     let row = rt
-        .block_on(client.get_all::<Row>(cquery.to_string()))
+        .block_on(client.get_all::<Row>(inline_query(&cquery)?))
         .map_err(TrinoSourceError::PrustoError)?
         .split()
         .1[0]
@@ -66,6 +174,61 @@ impl Dialect for TrinoDialect {
     }
 }
 
+/// Map `connect_timeout` to the request timeout of the client and `statement_timeout` to the
+/// `query_max_run_time` session property.
+#[throws(TrinoSourceError)]
+pub fn with_timeouts(builder: ClientBuilder, url: &url::Url) -> ClientBuilder {
+    let timeouts = Timeouts::from_url(url)?;
+    let builder = match timeouts.connect {
+        Some(timeout) => builder.client_request_timeout(timeout),
+        None => builder,
+    };
+    match timeouts.statement {
+        Some(timeout) => {
+            builder.property("query_max_run_time", format!("{}ms", timeout.as_millis()))
+        }
+        None => builder,
+    }
+}
+
+/// Run `queries` through the shared client. Statements like `SET SESSION` update the session
+/// properties of the client, so they apply to all queries issued afterwards.
+#[throws(TrinoSourceError)]
+fn execute_session_queries(rt: &Runtime, client: &Client, queries: &[String]) {
+    for query in queries {
+        rt.block_on(client.execute(query.clone()))
+            .map_err(TrinoSourceError::PrustoError)?;
+    }
+}
+
+/// The post-execution queries of a source. All partitions share the session of one client, so
+/// the queries are run once by the last partition to finish, or when the source and all of its
+/// partitions are dropped if the load fails before that.
+pub struct TrinoPostExecution {
+    rt: Arc<Runtime>,
+    client: Arc<Client>,
+    queries: Vec<String>,
+    remaining: AtomicUsize,
+    done: AtomicBool,
+}
+
+impl TrinoPostExecution {
+    #[throws(TrinoSourceError)]
+    fn run(&self) {
+        if !self.done.swap(true, Ordering::AcqRel) {
+            execute_session_queries(&self.rt, &self.client, &self.queries)?;
+        }
+    }
+}
+
+impl Drop for TrinoPostExecution {
+    fn drop(&mut self) {
+        if let Err(e) = self.run() {
+            warn!(error = %e, "cannot run the post-execution queries");
+        }
+    }
+}
+
 pub struct TrinoSource {
     client: Arc<Client>,
     rt: Arc<Runtime>,
@@ -73,6 +236,12 @@ pub struct TrinoSource {
     queries: Vec<CXQuery<String>>,
     names: Vec<String>,
     schema: Vec<TrinoTypeSystem>,
+    // the declared types of the columns, e.g. `decimal(12,2)`
+    types: Vec<String>,
+    pre_execution_queries: Option<Vec<String>>,
+    post_execution_queries: Option<Vec<String>>,
+    // created once the session is set up, so that dropping the source on an error resets it
+    post_execution: Option<Arc<TrinoPostExecution>>,
 }
 
 impl TrinoSource {
@@ -107,7 +276,9 @@ impl TrinoSource {
             }
         };
 
-        let client = builder.build().map_err(TrinoSourceError::PrustoError)?;
+        let client = with_timeouts(builder, &url)?
+            .build()
+            .map_err(TrinoSourceError::PrustoError)?;
 
         Self {
             client: Arc::new(client),
@@ -116,8 +287,28 @@ impl TrinoSource {
             queries: vec![],
             names: vec![],
             schema: vec![],
+            types: vec![],
+            pre_execution_queries: None,
+            post_execution_queries: None,
+            post_execution: None,
         }
     }
+
+    fn post_execution(&mut self) -> Arc<TrinoPostExecution> {
+        let (rt, client) = (self.rt.clone(), self.client.clone());
+        let queries = self.post_execution_queries.clone().unwrap_or_default();
+        self.post_execution
+            .get_or_insert_with(|| {
+                Arc::new(TrinoPostExecution {
+                    rt,
+                    client,
+                    queries,
+                    remaining: AtomicUsize::new(0),
+                    done: AtomicBool::new(false),
+                })
+            })
+            .clone()
+    }
 }
 
 impl Source for TrinoSource
@@ -144,16 +335,41 @@ where
         self.origin_query = query;
     }
 
+    fn set_pre_execution_queries(&mut self, pre_execution_queries: Option<&[String]>) {
+        self.pre_execution_queries = pre_execution_queries.map(|s| s.to_vec());
+    }
+
+    fn set_post_execution_queries(&mut self, post_execution_queries: Option<&[String]>) {
+        self.post_execution_queries = post_execution_queries.map(|s| s.to_vec());
+    }
+
     #[throws(TrinoSourceError)]
     fn fetch_metadata(&mut self) {
         assert!(!self.queries.is_empty());
+        self.post_execution();
+        if let Some(pre_queries) = &self.pre_execution_queries {
+            execute_session_queries(&self.rt, &self.client, pre_queries)?;
+        }
 
         let first_query = &self.queries[0];
+        match describe_output(&self.rt, &self.client, &inline_query(first_query)?) {
+            Ok(Some((names, types, declared))) => {
+                self.names = names;
+                self.schema = types;
+                self.types = declared;
+                return;
+            }
+            Ok(None) => {}
+            Err(e) => {
+                debug!(error = %e, "cannot describe query, fall back to limit 1");
+            }
+        }
+
         let cxq = limit1_query(first_query, &GenericDialect {})?;
 
         let dataset: DataSet<Row> = self
             .rt
-            .block_on(self.client.get_all::<Row>(cxq.to_string()))
+            .block_on(self.client.get_all::<Row>(inline_query(&cxq)?))
             .map_err(TrinoSourceError::PrustoError)?;
 
         let schema = dataset.split().0;
@@ -161,6 +377,7 @@ where
         for (name, t) in schema {
             self.names.push(name.clone());
             self.schema.push(TrinoTypeSystem::try_from(t.clone())?);
+            self.types.push(t.full_type().into_owned());
         }
     }
 
@@ -168,7 +385,8 @@ where
     fn result_rows(&mut self) -> Option<usize> {
         match &self.origin_query {
             Some(q) => {
-                let cxq = CXQuery::Naked(q.clone());
+                // the partitioned queries are bound to the parameters of the origin query
+                let cxq = CXQuery::Naked(q.clone()).bind(self.queries[0].params().to_vec());
                 let nrows = get_total_rows(self.rt.clone(), self.client.clone(), &cxq)?;
                 Some(nrows)
             }
@@ -184,9 +402,20 @@ where
         self.schema.clone()
     }
 
+    /// The columns are described with their declared type. Trino does not tell whether they are
+    /// nullable.
     #[throws(TrinoSourceError)]
-    fn partition(self) -> Vec<Self::Partition> {
+    fn describe_columns(&mut self) -> Vec<ColumnDescription> {
+        self.types.iter().map(|ty| describe_type(ty)).collect()
+    }
+
+    #[throws(TrinoSourceError)]
+    fn partition(mut self) -> Vec<Self::Partition> {
         let mut ret = vec![];
+        let post_execution = self.post_execution();
+        post_execution
+            .remaining
+            .store(self.queries.len(), Ordering::Release);
 
         for query in self.queries {
             ret.push(TrinoSourcePartition::new(
@@ -194,6 +423,7 @@ where
                 query,
                 self.schema.clone(),
                 self.rt.clone(),
+                post_execution.clone(),
             )?);
         }
         ret
@@ -206,6 +436,7 @@ pub struct TrinoSourcePartition {
     schema: Vec<TrinoTypeSystem>,
     rt: Arc<Runtime>,
     nrows: usize,
+    post_execution: Arc<TrinoPostExecution>,
 }
 
 impl TrinoSourcePartition {
@@ -215,6 +446,7 @@ impl TrinoSourcePartition {
         query: CXQuery<String>,
         schema: Vec<TrinoTypeSystem>,
         rt: Arc<Runtime>,
+        post_execution: Arc<TrinoPostExecution>,
     ) -> Self {
         Self {
             client,
@@ -222,6 +454,7 @@ impl TrinoSourcePartition {
             schema: schema.to_vec(),
             rt,
             nrows: 0,
+            post_execution,
         }
     }
 }
@@ -253,6 +486,13 @@ impl SourcePartition for TrinoSourcePartition {
     fn ncols(&self) -> usize {
         self.schema.len()
     }
+
+    #[throws(TrinoSourceError)]
+    fn finish(&mut self) {
+        if self.post_execution.remaining.fetch_sub(1, Ordering::AcqRel) == 1 {
+            self.post_execution.run()?;
+        }
+    }
 }
 
 pub struct TrinoSourcePartitionParser<'a> {
@@ -275,7 +515,7 @@ impl<'a> TrinoSourcePartitionParser<'a> {
         schema: &[TrinoTypeSystem],
     ) -> Self {
         let results = rt
-            .block_on(client.get::<Row>(query.to_string()))
+            .block_on(client.get::<Row>(inline_query(&query)?))
             .map_err(TrinoSourceError::PrustoError)?;
 
         let rows = match results.data_set {
@@ -553,7 +793,92 @@ impl_produce_bool!(bool,);
 impl_produce_int!(i8, i16, i32, i64,);
 impl_produce_float!(f32, f64,);
 impl_produce_timestamp!(NaiveDateTime,);
-impl_produce_text!(String, char,);
+impl_produce_text!(String, char, Uuid,);
+
+/// Parse the `[-]D HH:MM:SS.fff` text Trino uses for `interval day to second`.
+fn parse_interval_day_to_second(s: &str) -> Option<Duration> {
+    let (negative, s) = match s.strip_prefix('-') {
+        Some(s) => (true, s),
+        None => (false, s),
+    };
+    let (days, time) = s.split_once(' ')?;
+    let mut parts = time.splitn(3, ':');
+    let hours: i64 = parts.next()?.parse().ok()?;
+    let minutes: i64 = parts.next()?.parse().ok()?;
+    let (seconds, fraction) = match parts.next()? {
+        x if x.contains('.') => x.split_once('.')?,
+        x => (x, ""),
+    };
+    if fraction.len() > 9 {
+        return None;
+    }
+    let nanoseconds: i64 = match fraction {
+        "" => 0,
+        _ => fraction.parse::<i64>().ok()? * 10i64.pow(9 - fraction.len() as u32),
+    };
+    let d = Duration::days(days.parse().ok()?)
+        + Duration::hours(hours)
+        + Duration::minutes(minutes)
+        + Duration::seconds(seconds.parse().ok()?)
+        + Duration::nanoseconds(nanoseconds);
+    Some(if negative { -d } else { d })
+}
+
+/// Parse the `[-]Y-M` text Trino uses for `interval year to month`.
+fn parse_interval_year_to_month(s: &str) -> Option<Interval> {
+    let (negative, s) = match s.strip_prefix('-') {
+        Some(s) => (true, s),
+        None => (false, s),
+    };
+    let (years, months) = s.split_once('-')?;
+    let months = years.parse::<i32>().ok()?.checked_mul(12)? + months.parse::<i32>().ok()?;
+    Some(Interval {
+        months: if negative { -months } else { months },
+        ..Interval::default()
+    })
+}
+
+macro_rules! impl_produce_interval {
+    ($($t: ty => $parse: ident,)+) => {
+        $(
+            impl<'r, 'a> Produce<'r, $t> for TrinoSourcePartitionParser<'a> {
+                type Error = TrinoSourceError;
+
+                #[throws(TrinoSourceError)]
+                fn produce(&'r mut self) -> $t {
+                    let (ridx, cidx) = self.next_loc()?;
+                    let value = &self.rows[ridx].value()[cidx];
+
+                    match value {
+                        Value::String(x) => $parse(x).ok_or_else(|| anyhow!("Trino cannot parse Interval at position: ({}, {}): {:?}", ridx, cidx, value))?,
+                        _ => throw!(anyhow!("Trino unknown value at position: ({}, {}): {:?}", ridx, cidx, value))
+                    }
+                }
+            }
+
+            impl<'r, 'a> Produce<'r, Option<$t>> for TrinoSourcePartitionParser<'a> {
+                type Error = TrinoSourceError;
+
+                #[throws(TrinoSourceError)]
+                fn produce(&'r mut self) -> Option<$t> {
+                    let (ridx, cidx) = self.next_loc()?;
+                    let value = &self.rows[ridx].value()[cidx];
+
+                    match value {
+                        Value::Null => None,
+                        Value::String(x) => Some($parse(x).ok_or_else(|| anyhow!("Trino cannot parse Interval at position: ({}, {}): {:?}", ridx, cidx, value))?),
+                        _ => throw!(anyhow!("Trino unknown value at position: ({}, {}): {:?}", ridx, cidx, value))
+                    }
+                }
+            }
+        )+
+    };
+}
+
+impl_produce_interval!(
+    Duration => parse_interval_day_to_second,
+    Interval => parse_interval_year_to_month,
+);
 
 impl<'r, 'a> Produce<'r, NaiveTime> for TrinoSourcePartitionParser<'a> {
     type Error = TrinoSourceError;
@@ -1,8 +1,10 @@
 use super::errors::TrinoSourceError;
-use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use crate::typesystem::Interval;
+use chrono::{Duration, NaiveDate, NaiveDateTime, NaiveTime};
 use fehler::{throw, throws};
 use prusto::{PrestoFloat, PrestoInt, PrestoTy};
 use std::convert::TryFrom;
+use uuid::Uuid;
 
 // TODO: implement Tuple, Row, Array and Map
 #[derive(Copy, Clone, Debug, PartialEq)]
@@ -19,6 +21,9 @@ pub enum TrinoTypeSystem {
     Real(bool),
     Varchar(bool),
     Char(bool),
+    Uuid(bool),
+    IntervalDayToSecond(bool),
+    IntervalYearToMonth(bool),
 }
 
 impl_typesystem! {
@@ -36,6 +41,9 @@ impl_typesystem! {
         { Real => f32 }
         { Varchar => String }
         { Char => char }
+        { Uuid => Uuid }
+        { IntervalDayToSecond => Duration }
+        { IntervalYearToMonth => Interval }
     }
 }
 
@@ -64,7 +72,9 @@ impl TryFrom<PrestoTy> for TrinoTypeSystem {
             PrestoTy::Map(_, _) => Varchar(true),
             PrestoTy::Decimal(_, _) => Double(true),
             PrestoTy::IpAddress => Varchar(true),
-            PrestoTy::Uuid => Varchar(true),
+            PrestoTy::Uuid => Uuid(true),
+            PrestoTy::IntervalDayToSecond => IntervalDayToSecond(true),
+            PrestoTy::IntervalYearToMonth => IntervalYearToMonth(true),
             _ => throw!(TrinoSourceError::InferTypeFromNull),
         }
     }
@@ -98,7 +108,9 @@ impl TryFrom<(Option<&str>, PrestoTy)> for TrinoTypeSystem {
                     "map" => Varchar(true),
                     "decimal" => Double(true),
                     "ipaddress" => Varchar(true),
-                    "uuid" => Varchar(true),
+                    "uuid" => Uuid(true),
+                    "interval day to second" => IntervalDayToSecond(true),
+                    "interval year to month" => IntervalYearToMonth(true),
                     _ => TrinoTypeSystem::try_from(ty)?,
                 }
             }
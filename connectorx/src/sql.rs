@@ -1,22 +1,74 @@
+use crate::constants::CONNECTORX_TAG;
 use crate::errors::ConnectorXError;
 #[cfg(feature = "src_oracle")]
 use crate::sources::oracle::OracleDialect;
+use chrono::{NaiveDate, NaiveDateTime};
 use fehler::{throw, throws};
-use log::{debug, trace, warn};
 use sqlparser::ast::{
     BinaryOperator, Expr, Function, FunctionArg, FunctionArgExpr, Ident, ObjectName, Query, Select,
     SelectItem, SetExpr, Statement, TableAlias, TableFactor, TableWithJoins, Value,
     WildcardAdditionalOptions,
 };
-use sqlparser::dialect::Dialect;
+use sqlparser::dialect::{Dialect, GenericDialect};
 use sqlparser::parser::Parser;
+use sqlparser::tokenizer::{Token, Tokenizer};
 #[cfg(feature = "src_oracle")]
 use std::any::Any;
+use tracing::{debug, trace, warn};
 
 #[derive(Debug, Clone)]
 pub enum CXQuery<Q = String> {
     Naked(Q),   // The query directly comes from the user
     Wrapped(Q), // The user query is already wrapped in a subquery
+    /// A query with parameters that the source binds natively when issuing it, or inlines as
+    /// literals on the protocols that cannot bind them. The placeholder syntax follows the
+    /// source: `$1` for Postgres, `?` for MySQL and SQLite, `@P1` for MsSQL, `:1` for Oracle and
+    /// `@P1` named parameters for BigQuery.
+    Bound(Box<CXQuery<Q>>, Vec<CXParam>),
+}
+
+/// A typed value bound to a placeholder of a [`CXQuery`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum CXParam {
+    Null,
+    Bool(bool),
+    I64(i64),
+    F64(f64),
+    Str(String),
+    Bytes(Vec<u8>),
+    Date(NaiveDate),
+    DateTime(NaiveDateTime),
+}
+
+macro_rules! impl_param_from {
+    ($($t:ty => $variant:ident),+) => {
+        $(
+            impl From<$t> for CXParam {
+                fn from(v: $t) -> Self {
+                    CXParam::$variant(v.into())
+                }
+            }
+        )+
+    };
+}
+
+impl_param_from!(
+    bool => Bool,
+    i32 => I64,
+    i64 => I64,
+    f32 => F64,
+    f64 => F64,
+    &str => Str,
+    String => Str,
+    Vec<u8> => Bytes,
+    NaiveDate => Date,
+    NaiveDateTime => DateTime
+);
+
+impl<T: Into<CXParam>> From<Option<T>> for CXParam {
+    fn from(v: Option<T>) -> Self {
+        v.map_or(CXParam::Null, Into::into)
+    }
 }
 
 impl<Q: std::fmt::Display> std::fmt::Display for CXQuery<Q> {
@@ -24,6 +76,7 @@ impl<Q: std::fmt::Display> std::fmt::Display for CXQuery<Q> {
         match self {
             CXQuery::Naked(q) => write!(f, "{}", q),
             CXQuery::Wrapped(q) => write!(f, "{}", q),
+            CXQuery::Bound(q, _) => write!(f, "{}", q),
         }
     }
 }
@@ -33,6 +86,7 @@ impl<Q: AsRef<str>> CXQuery<Q> {
         match self {
             CXQuery::Naked(q) => q.as_ref(),
             CXQuery::Wrapped(q) => q.as_ref(),
+            CXQuery::Bound(q, _) => q.as_str(),
         }
     }
 }
@@ -69,10 +123,7 @@ impl CXQuery<String> {
 
 impl<Q: AsRef<str>> AsRef<str> for CXQuery<Q> {
     fn as_ref(&self) -> &str {
-        match self {
-            CXQuery::Naked(q) => q.as_ref(),
-            CXQuery::Wrapped(q) => q.as_ref(),
-        }
+        self.as_str()
     }
 }
 
@@ -84,6 +135,28 @@ impl<Q> CXQuery<Q> {
         match self {
             CXQuery::Naked(q) => CXQuery::Naked(f(q)),
             CXQuery::Wrapped(q) => CXQuery::Wrapped(f(q)),
+            CXQuery::Bound(q, params) => CXQuery::Bound(Box::new(q.map(f)), params.clone()),
+        }
+    }
+
+    /// Attach `params` to the query, replacing the ones already bound.
+    pub fn bind(self, params: Vec<CXParam>) -> Self {
+        let query = match self {
+            CXQuery::Bound(q, _) => *q,
+            q => q,
+        };
+        if params.is_empty() {
+            query
+        } else {
+            CXQuery::Bound(Box::new(query), params)
+        }
+    }
+
+    /// The parameters bound to the query, empty if there are none.
+    pub fn params(&self) -> &[CXParam] {
+        match self {
+            CXQuery::Bound(_, params) => params,
+            _ => &[],
         }
     }
 }
@@ -93,10 +166,191 @@ impl<Q, E> CXQuery<Result<Q, E>> {
         match self {
             CXQuery::Naked(q) => q.map(CXQuery::Naked),
             CXQuery::Wrapped(q) => q.map(CXQuery::Wrapped),
+            CXQuery::Bound(q, params) => q.result().map(|q| CXQuery::Bound(Box::new(q), params)),
         }
     }
 }
 
+/// The placeholder syntax of a source, see [`inline_params`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlaceholderStyle {
+    /// `$1`, `$2`, ... refer to the parameters by position, as in Postgres.
+    Dollar,
+    /// Each `?` takes the next parameter, as in MySQL and Trino.
+    QuestionMark,
+}
+
+/// Substitute the placeholders of `sql` with the bound parameters rendered as literals by
+/// `literal`, for the protocols that cannot bind them natively (e.g. Postgres `COPY`). Quoted
+/// strings, quoted identifiers and comments are copied as they are. `backslash_escapes` tells
+/// whether a backslash escapes the next character in a quoted string.
+#[throws(ConnectorXError)]
+pub fn inline_params<F>(
+    sql: &str,
+    params: &[CXParam],
+    style: PlaceholderStyle,
+    backslash_escapes: bool,
+    literal: F,
+) -> String
+where
+    F: Fn(&CXParam) -> String,
+{
+    if params.is_empty() {
+        return sql.to_string();
+    }
+
+    fn is_ident(b: u8) -> bool {
+        b.is_ascii_alphanumeric() || b == b'_' || b == b'$' || b >= 0x80
+    }
+    // the delimiters are all ascii, so every index below is a char boundary
+    let bytes = sql.as_bytes();
+    let mut out = String::with_capacity(sql.len());
+    let (mut i, mut copied, mut next) = (0, 0, 0);
+    while i < bytes.len() {
+        let prev_ident = i > 0 && is_ident(bytes[i - 1]);
+        match (bytes[i], bytes.get(i + 1)) {
+            (q @ (b'\'' | b'"' | b'`'), _) => {
+                // postgres only honors backslashes in E'' strings
+                let escapes = q != b'`'
+                    && (backslash_escapes
+                        || (q == b'\'' && i > 0 && matches!(bytes[i - 1], b'E' | b'e')));
+                i += 1;
+                while i < bytes.len() {
+                    // an escaped character or a doubled quote
+                    if (escapes && bytes[i] == b'\\')
+                        || (bytes[i] == q && bytes.get(i + 1) == Some(&q))
+                    {
+                        i += 2;
+                    } else if bytes[i] == q {
+                        break;
+                    } else {
+                        i += 1;
+                    }
+                }
+                i += 1;
+            }
+            (b'-', Some(b'-')) => {
+                i = sql[i..].find('\n').map_or(bytes.len(), |pos| i + pos);
+            }
+            (b'/', Some(b'*')) => {
+                i = sql[i + 2..]
+                    .find("*/")
+                    .map_or(bytes.len(), |pos| i + 2 + pos + 2);
+            }
+            (b'$', Some(d)) if style == PlaceholderStyle::Dollar && !prev_ident => {
+                if d.is_ascii_digit() {
+                    let end = sql[i + 1..]
+                        .find(|c: char| !c.is_ascii_digit())
+                        .map_or(bytes.len(), |pos| i + 1 + pos);
+                    let placeholder = &sql[i..end];
+                    let param = placeholder[1..]
+                        .parse::<usize>()
+                        .ok()
+                        .and_then(|n| params.get(n.checked_sub(1)?))
+                        .ok_or_else(|| ConnectorXError::MissingParam(placeholder.to_string()))?;
+                    out.push_str(&sql[copied..i]);
+                    out.push_str(&literal(param));
+                    i = end;
+                    copied = end;
+                } else {
+                    // a dollar-quoted string, e.g. $tag$ ... $tag$
+                    match sql[i + 1..].find('$') {
+                        Some(pos) if sql[i + 1..i + 1 + pos].bytes().all(is_ident) => {
+                            let tag = &sql[i..i + 2 + pos];
+                            let body = i + tag.len();
+                            i = sql[body..]
+                                .find(tag)
+                                .map_or(bytes.len(), |pos| body + pos + tag.len());
+                        }
+                        _ => i += 1,
+                    }
+                }
+            }
+            (b'?', _) if style == PlaceholderStyle::QuestionMark => {
+                let param = params
+                    .get(next)
+                    .ok_or_else(|| ConnectorXError::MissingParam(format!("?{}", next + 1)))?;
+                out.push_str(&sql[copied..i]);
+                out.push_str(&literal(param));
+                next += 1;
+                i += 1;
+                copied = i;
+            }
+            _ => i += 1,
+        }
+    }
+    out.push_str(&sql[copied.min(bytes.len())..]);
+    out
+}
+
+/// Replace the literals in `sql` with `?` so that the query can be attached to logs and traces
+/// without leaking the values in it.
+pub fn redact_query(sql: &str) -> String {
+    match Tokenizer::new(&GenericDialect {}, sql).tokenize() {
+        Ok(tokens) => tokens
+            .into_iter()
+            .map(|token| match token {
+                Token::Number(..)
+                | Token::SingleQuotedString(_)
+                | Token::DollarQuotedString(_)
+                | Token::SingleQuotedByteStringLiteral(_)
+                | Token::NationalStringLiteral(_)
+                | Token::EscapedStringLiteral(_)
+                | Token::HexStringLiteral(_) => "?".to_string(),
+                token => token.to_string(),
+            })
+            .collect(),
+        Err(_) => "<unparsable query>".to_string(),
+    }
+}
+
+/// Prefix `sql` with a comment carrying the query tag and, for partition queries, the partition
+/// index, so that the queries can be traced back to the job that issued them.
+pub fn tag_query(sql: &str, tag: &str, partition: Option<usize>) -> String {
+    // the tag must neither close the comment nor open a nested one, and stripping a marker
+    // can join the characters around it into a new one, e.g. "*/*/"
+    let mut tag = tag.to_string();
+    while tag.contains("/*") || tag.contains("*/") {
+        tag = tag.replace("/*", "").replace("*/", "");
+    }
+    match partition {
+        Some(i) => format!("/* {}={} partition={} */ {}", CONNECTORX_TAG, tag, i, sql),
+        None => format!("/* {}={} */ {}", CONNECTORX_TAG, tag, sql),
+    }
+}
+
+/// Tag each query with `tag` and its partition index, see [`tag_query`].
+pub fn tag_queries(queries: &[CXQuery<String>], tag: Option<&str>) -> Vec<CXQuery<String>> {
+    match tag {
+        Some(tag) => queries
+            .iter()
+            .enumerate()
+            .map(|(i, q)| q.map(|sql| tag_query(sql, tag, Some(i))))
+            .collect(),
+        None => queries.to_vec(),
+    }
+}
+
+// carry the leading block comments of `sql` (e.g. query tags) over to its rewritten query,
+// since they are dropped by the parser
+fn keep_leading_comments(sql: &str, rewritten: String) -> String {
+    let mut end = 0;
+    loop {
+        let rest = sql[end..].trim_start();
+        if !rest.starts_with("/*") {
+            break;
+        }
+        match rest.find("*/") {
+            Some(pos) => end = sql.len() - rest.len() + pos + 2,
+            None => break,
+        }
+    }
+    match end {
+        0 => rewritten,
+        _ => format!("{} {}", sql[..end].trim_start(), rewritten),
+    }
+}
+
 // wrap a query into a derived table
 fn wrap_query(
     query: &mut Query,
@@ -178,6 +432,11 @@ impl QueryExt for Query {
 
 #[throws(ConnectorXError)]
 pub fn count_query<T: Dialect>(sql: &CXQuery<String>, dialect: &T) -> CXQuery<String> {
+    // the rewrite keeps the placeholders, so the parameters carry over as they are
+    if let CXQuery::Bound(q, params) = sql {
+        return count_query(q, dialect)?.bind(params.clone());
+    }
+
     trace!("Incoming query: {}", sql);
 
     const COUNT_TMP_TAB_NAME: &str = "CXTMPTAB_COUNT";
@@ -241,8 +500,11 @@ pub fn count_query<T: Dialect>(sql: &CXQuery<String>, dialect: &T) -> CXQuery<St
                     select.projection = projection;
                     Statement::Query(Box::new(query))
                 }
+                CXQuery::Bound(..) => {
+                    throw!(ConnectorXError::SqlQueryNotSupported(sql.to_string()))
+                }
             };
-            format!("{}", ast_count)
+            keep_leading_comments(sql.as_str(), format!("{}", ast_count))
         }
         Err(e) => {
             warn!("parser error: {:?}, manually compose query string", e);
@@ -260,6 +522,10 @@ pub fn count_query<T: Dialect>(sql: &CXQuery<String>, dialect: &T) -> CXQuery<St
 
 #[throws(ConnectorXError)]
 pub fn limit1_query<T: Dialect>(sql: &CXQuery<String>, dialect: &T) -> CXQuery<String> {
+    if let CXQuery::Bound(q, params) = sql {
+        return limit1_query(q, dialect)?.bind(params.clone());
+    }
+
     trace!("Incoming query: {}", sql);
 
     let sql = match Parser::parse_sql(dialect, sql.as_str()) {
@@ -275,7 +541,7 @@ pub fn limit1_query<T: Dialect>(sql: &CXQuery<String>, dialect: &T) -> CXQuery<S
                 _ => throw!(ConnectorXError::SqlQueryNotSupported(sql.to_string())),
             };
 
-            format!("{}", ast[0])
+            keep_leading_comments(sql.as_str(), format!("{}", ast[0]))
         }
         Err(e) => {
             warn!("parser error: {:?}, manually compose query string", e);
@@ -287,12 +553,22 @@ pub fn limit1_query<T: Dialect>(sql: &CXQuery<String>, dialect: &T) -> CXQuery<S
     CXQuery::Wrapped(sql)
 }
 
+/// Wrap the query with a predicate that is never true, so Oracle only parses it and describes
+/// its columns without producing rows.
+#[cfg(feature = "src_oracle")]
+pub fn describe_query_oracle(sql: &CXQuery<String>) -> CXQuery<String> {
+    trace!("Incoming oracle query: {}", sql);
+
+    CXQuery::Wrapped(format!("SELECT * FROM ({}) WHERE 1 = 0", sql)).bind(sql.params().to_vec())
+}
+
 #[throws(ConnectorXError)]
 #[cfg(feature = "src_oracle")]
 pub fn limit1_query_oracle(sql: &CXQuery<String>) -> CXQuery<String> {
     trace!("Incoming oracle query: {}", sql);
 
     CXQuery::Wrapped(format!("SELECT * FROM ({}) WHERE rownum = 1", sql))
+        .bind(sql.params().to_vec())
 
     // let ast = Parser::parse_sql(&OracleDialect {}, sql.as_str())?;
     // if ast.len() != 1 {
@@ -404,7 +680,7 @@ pub fn single_col_partition_query<T: Dialect>(
                 Some(selection),
                 table_alias,
             );
-            format!("{}", ast_part)
+            keep_leading_comments(sql, format!("{}", ast_part))
         }
         Err(e) => {
             warn!("parser error: {:?}, manually compose query string", e);
@@ -491,7 +767,7 @@ pub fn get_partition_range_query<T: Dialect>(sql: &str, col: &str, dialect: &T)
                 })),
             ];
             ast_range = wrap_query(&mut query, projection, None, table_alias);
-            format!("{}", ast_range)
+            keep_leading_comments(sql, format!("{}", ast_range))
         }
         Err(e) => {
             warn!("parser error: {:?}, manually compose query string", e);
@@ -576,7 +852,10 @@ pub fn get_partition_range_query_sep<T: Dialect>(
             }))];
             ast_range_min = wrap_query(&mut query.clone(), min_proj, None, RANGE_TMP_TAB_NAME);
             ast_range_max = wrap_query(&mut query, max_proj, None, RANGE_TMP_TAB_NAME);
-            (format!("{}", ast_range_min), format!("{}", ast_range_max))
+            (
+                keep_leading_comments(sql, format!("{}", ast_range_min)),
+                keep_leading_comments(sql, format!("{}", ast_range_max)),
+            )
         }
         Err(e) => {
             warn!("parser error: {:?}, manually compose query string", e);
@@ -0,0 +1,35 @@
+//! This module exports the `tracing` spans emitted by ConnectorX (load, metadata, count,
+//! partition, fetch_next and finalize) to OpenTelemetry.
+//!
+//! Applications that already install their own `tracing` subscriber only need [`otel_layer`];
+//! [`init_opentelemetry`] is a shortcut for those that do not.
+
+use opentelemetry::trace::Tracer;
+use tracing::Subscriber;
+use tracing_opentelemetry::{OpenTelemetryLayer, PreSampledTracer};
+use tracing_subscriber::{
+    layer::SubscriberExt,
+    registry::LookupSpan,
+    util::{SubscriberInitExt, TryInitError},
+};
+
+/// Create a `tracing` layer that forwards the spans to the given OpenTelemetry `tracer`.
+pub fn otel_layer<S, T>(tracer: T) -> OpenTelemetryLayer<S, T>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+    T: Tracer + PreSampledTracer + 'static,
+{
+    tracing_opentelemetry::layer().with_tracer(tracer)
+}
+
+/// Install a global subscriber that exports all the spans to the given OpenTelemetry `tracer`.
+/// Fails if a global subscriber has already been installed.
+pub fn init_opentelemetry<T>(tracer: T) -> Result<(), TryInitError>
+where
+    T: Tracer + PreSampledTracer + Send + Sync + 'static,
+    T::Span: Send + Sync,
+{
+    tracing_subscriber::registry()
+        .with(otel_layer(tracer))
+        .try_init()
+}
@@ -42,15 +42,15 @@ impl_transport!(
         { Float53[f64]                  => Float64[f64]              | conversion auto }
         { Floatn[FloatN]                => Float64[f64]              | conversion option }
         { Bit[bool]                     => Boolean[bool]             | conversion auto  }
-        { Nvarchar[&'r str]             => LargeUtf8[String]         | conversion owned }
-        { Varchar[&'r str]              => LargeUtf8[String]         | conversion none }
-        { Nchar[&'r str]                => LargeUtf8[String]         | conversion none }
-        { Char[&'r str]                 => LargeUtf8[String]         | conversion none }
-        { Text[&'r str]                 => LargeUtf8[String]         | conversion none }
-        { Ntext[&'r str]                => LargeUtf8[String]         | conversion none }
-        { Binary[&'r [u8]]              => LargeBinary[Vec<u8>]      | conversion owned }
-        { Varbinary[&'r [u8]]           => LargeBinary[Vec<u8>]      | conversion none }
-        { Image[&'r [u8]]               => LargeBinary[Vec<u8>]      | conversion none }
+        { Nvarchar[&'r str]             => LargeUtf8[&'r str]        | conversion auto }
+        { Varchar[&'r str]              => LargeUtf8[&'r str]        | conversion none }
+        { Nchar[&'r str]                => LargeUtf8[&'r str]        | conversion none }
+        { Char[&'r str]                 => LargeUtf8[&'r str]        | conversion none }
+        { Text[&'r str]                 => LargeUtf8[&'r str]        | conversion none }
+        { Ntext[&'r str]                => LargeUtf8[&'r str]        | conversion none }
+        { Binary[&'r [u8]]              => LargeBinary[&'r [u8]]     | conversion auto }
+        { Varbinary[&'r [u8]]           => LargeBinary[&'r [u8]]     | conversion none }
+        { Image[&'r [u8]]               => LargeBinary[&'r [u8]]     | conversion none }
         { Numeric[Decimal]              => Float64[f64]              | conversion option }
         { Decimal[Decimal]              => Float64[f64]              | conversion none }
         { Datetime[NaiveDateTime]       => Date64Micro[NaiveDateTimeWrapperMicro]     | conversion option }
@@ -58,7 +58,7 @@ impl_transport!(
         { Smalldatetime[NaiveDateTime]  => Date64Micro[NaiveDateTimeWrapperMicro]     | conversion none }
         { Date[NaiveDate]               => Date32[NaiveDate]         | conversion auto }
         { Datetimeoffset[DateTime<Utc>] => DateTimeTzMicro[DateTimeWrapperMicro] | conversion option }
-        { Uniqueidentifier[Uuid]        => LargeUtf8[String]         | conversion option }
+        { Uniqueidentifier[Uuid]        => Uuid[uuid::Uuid]          | conversion option }
         { Time[NaiveTime]               => Time64Micro[NaiveTimeWrapperMicro]         | conversion option }
         { SmallMoney[f32]               => Float32[f32]              | conversion none }
         { Money[f64]                    => Float64[f64]              | conversion none }
@@ -83,9 +83,9 @@ impl TypeConversion<DateTime<Utc>, DateTimeWrapperMicro> for MsSQLArrowTransport
     }
 }
 
-impl TypeConversion<Uuid, String> for MsSQLArrowTransport {
-    fn convert(val: Uuid) -> String {
-        val.to_string()
+impl TypeConversion<Uuid, uuid::Uuid> for MsSQLArrowTransport {
+    fn convert(val: Uuid) -> uuid::Uuid {
+        uuid::Uuid::from_bytes(*val.as_bytes())
     }
 }
 
@@ -2,7 +2,7 @@
 
 use crate::{
     destinations::arrow::{
-        typesystem::{ArrowTypeSystem, NaiveDateTimeWrapperMicro, NaiveTimeWrapperMicro},
+        typesystem::{ArrowTypeSystem, DictionaryWrapper, NaiveDateTimeWrapperMicro},
         ArrowDestination, ArrowDestinationError,
     },
     impl_transport,
@@ -11,10 +11,10 @@ use crate::{
     },
     typesystem::TypeConversion,
 };
-use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use chrono::{Duration, NaiveDate, NaiveDateTime};
 use num_traits::ToPrimitive;
 use rust_decimal::Decimal;
-use serde_json::{to_string, Value};
+use serde_json::Value;
 use std::marker::PhantomData;
 use thiserror::Error;
 
@@ -52,19 +52,19 @@ impl_transport!(
         { UInt24[u32]                => Int64[i64]              | conversion none }
         { ULongLong[u64]             => Float64[f64]            | conversion auto }
         { Date[NaiveDate]            => Date32[NaiveDate]       | conversion auto }
-        { Time[NaiveTime]            => Time64Micro[NaiveTimeWrapperMicro]       | conversion option }
+        { Time[Duration]             => Duration[Duration]      | conversion auto }
         { Datetime[NaiveDateTime]    => Date64Micro[NaiveDateTimeWrapperMicro]   | conversion option }
         { Year[i16]                  => Int64[i64]              | conversion none}
         { Timestamp[NaiveDateTime]   => Date64Micro[NaiveDateTimeWrapperMicro]   | conversion none }
         { Decimal[Decimal]           => Float64[f64]            | conversion option }
         { VarChar[String]            => LargeUtf8[String]       | conversion auto }
         { Char[String]               => LargeUtf8[String]       | conversion none }
-        { Enum[String]               => LargeUtf8[String]       | conversion none }
+        { Enum[String]               => Dictionary[DictionaryWrapper] | conversion option }
         { TinyBlob[Vec<u8>]          => LargeBinary[Vec<u8>]    | conversion auto }
         { Blob[Vec<u8>]              => LargeBinary[Vec<u8>]    | conversion none }
         { MediumBlob[Vec<u8>]        => LargeBinary[Vec<u8>]    | conversion none }
         { LongBlob[Vec<u8>]          => LargeBinary[Vec<u8>]    | conversion none }
-        { Json[Value]                => LargeUtf8[String]       | conversion option }
+        { Json[Value]                => Json[Value]             | conversion auto }
         { Bit[Vec<u8>]               => LargeBinary[Vec<u8>]    | conversion none }
     }
 );
@@ -88,26 +88,26 @@ impl_transport!(
         { UInt24[u32]                => Int64[i64]              | conversion none }
         { ULongLong[u64]             => Float64[f64]            | conversion auto }
         { Date[NaiveDate]            => Date32[NaiveDate]       | conversion auto }
-        { Time[NaiveTime]            => Time64Micro[NaiveTimeWrapperMicro]       | conversion option }
+        { Time[Duration]             => Duration[Duration]      | conversion auto }
         { Datetime[NaiveDateTime]    => Date64Micro[NaiveDateTimeWrapperMicro]   | conversion option }
         { Year[i16]                  => Int64[i64]              | conversion none}
         { Timestamp[NaiveDateTime]   => Date64Micro[NaiveDateTimeWrapperMicro]   | conversion none }
         { Decimal[Decimal]           => Float64[f64]            | conversion option }
         { VarChar[String]            => LargeUtf8[String]       | conversion auto }
         { Char[String]               => LargeUtf8[String]       | conversion none }
-        { Enum[String]               => LargeUtf8[String]       | conversion none }
+        { Enum[String]               => Dictionary[DictionaryWrapper] | conversion option }
         { TinyBlob[Vec<u8>]          => LargeBinary[Vec<u8>]    | conversion auto }
         { Blob[Vec<u8>]              => LargeBinary[Vec<u8>]    | conversion none }
         { MediumBlob[Vec<u8>]        => LargeBinary[Vec<u8>]    | conversion none }
         { LongBlob[Vec<u8>]          => LargeBinary[Vec<u8>]    | conversion none }
-        { Json[Value]                => LargeUtf8[String]       | conversion option }
+        { Json[Value]                => Json[Value]             | conversion auto }
         { Bit[Vec<u8>]               => LargeBinary[Vec<u8>]    | conversion none }
     }
 );
 
-impl<P> TypeConversion<NaiveTime, NaiveTimeWrapperMicro> for MySQLArrowTransport<P> {
-    fn convert(val: NaiveTime) -> NaiveTimeWrapperMicro {
-        NaiveTimeWrapperMicro(val)
+impl<P> TypeConversion<String, DictionaryWrapper> for MySQLArrowTransport<P> {
+    fn convert(val: String) -> DictionaryWrapper {
+        DictionaryWrapper(val)
     }
 }
 
@@ -124,12 +124,6 @@ impl<P> TypeConversion<Decimal, f64> for MySQLArrowTransport<P> {
     }
 }
 
-impl<P> TypeConversion<Value, String> for MySQLArrowTransport<P> {
-    fn convert(val: Value) -> String {
-        to_string(&val).unwrap()
-    }
-}
-
 impl<P> TypeConversion<i8, bool> for MySQLArrowTransport<P> {
     fn convert(val: i8) -> bool {
         val != 0
@@ -10,7 +10,7 @@ use crate::{
     },
     typesystem::TypeConversion,
 };
-use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use chrono::{Duration, NaiveDate, NaiveDateTime};
 use num_traits::ToPrimitive;
 use rust_decimal::Decimal;
 use serde_json::{to_string, Value};
@@ -51,7 +51,7 @@ impl_transport!(
         { UInt24[u32]                => Int64[i64]              | conversion none }
         { ULongLong[u64]             => Float64[f64]            | conversion auto }
         { Date[NaiveDate]            => Date32[NaiveDate]       | conversion auto }
-        { Time[NaiveTime]            => Time64[NaiveTime]       | conversion auto }
+        { Time[Duration]             => Duration[Duration]      | conversion auto }
         { Datetime[NaiveDateTime]    => Date64[NaiveDateTime]   | conversion auto }
         { Year[i16]                  => Int64[i64]              | conversion none}
         { Timestamp[NaiveDateTime]   => Date64[NaiveDateTime]   | conversion none }
@@ -87,7 +87,7 @@ impl_transport!(
         { UInt24[u32]                => Int64[i64]              | conversion none }
         { ULongLong[u64]             => Float64[f64]            | conversion auto }
         { Date[NaiveDate]            => Date32[NaiveDate]       | conversion auto }
-        { Time[NaiveTime]            => Time64[NaiveTime]       | conversion auto }
+        { Time[Duration]             => Duration[Duration]      | conversion auto }
         { Datetime[NaiveDateTime]    => Date64[NaiveDateTime]   | conversion auto }
         { Year[i16]                  => Int64[i64]              | conversion none}
         { Timestamp[NaiveDateTime]   => Date64[NaiveDateTime]   | conversion none }
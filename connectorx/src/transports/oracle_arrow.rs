@@ -5,9 +5,9 @@ use crate::{
     },
     impl_transport,
     sources::oracle::{OracleSource, OracleSourceError, OracleTypeSystem},
-    typesystem::TypeConversion,
+    typesystem::{Interval, TypeConversion},
 };
-use chrono::{DateTime, NaiveDateTime, Utc};
+use chrono::{DateTime, Duration, NaiveDateTime, Utc};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -46,6 +46,8 @@ impl_transport!(
         { TimestampNano[NaiveDateTime]   => Date64[NaiveDateTime]      | conversion auto }
         { TimestampTz[DateTime<Utc>] => DateTimeTz[DateTimeWrapperMicro]        | conversion option }
         { TimestampTzNano[DateTime<Utc>] => DateTimeTz[DateTime<Utc>]  | conversion auto }
+        { IntervalDS[Duration]       => Duration[Duration]         | conversion auto }
+        { IntervalYM[Interval]       => Interval[Interval]         | conversion auto }
     }
 );
 
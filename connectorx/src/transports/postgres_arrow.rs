@@ -2,7 +2,8 @@
 
 use crate::destinations::arrow::{
     typesystem::{
-        ArrowTypeSystem, DateTimeWrapperMicro, NaiveDateTimeWrapperMicro, NaiveTimeWrapperMicro,
+        ArrowTypeSystem, DateTimeWrapperMicro, DictionaryWrapper, NaiveDateTimeWrapperMicro,
+        NaiveTimeWrapperMicro,
     },
     ArrowDestination, ArrowDestinationError,
 };
@@ -10,15 +11,19 @@ use crate::sources::postgres::{
     BinaryProtocol, CSVProtocol, CursorProtocol, PostgresSource, PostgresSourceError,
     PostgresTypeSystem, SimpleProtocol,
 };
-use crate::typesystem::TypeConversion;
+use crate::typesystem::{
+    BitString, Composite, DenseVector, Interval, List, SparseVec, TypeConversion,
+};
 use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
 use cidr_02::IpInet;
+use half::f16;
 use num_traits::ToPrimitive;
 use pgvector::{Bit, HalfVector, SparseVector, Vector};
 use postgres::NoTls;
 use postgres_openssl::MakeTlsConnector;
 use rust_decimal::Decimal;
 use serde_json::Value;
+use std::collections::HashMap;
 use std::marker::PhantomData;
 use thiserror::Error;
 use uuid::Uuid;
@@ -46,42 +51,55 @@ macro_rules! impl_postgres_transport {
             systems = PostgresTypeSystem => ArrowTypeSystem,
             route = PostgresSource<$proto, $tls> => ArrowDestination,
             mappings = {
-                { Float4[f32]                        => Float32[f32]                           | conversion auto   }
-                { Float8[f64]                        => Float64[f64]                           | conversion auto   }
-                { Numeric[Decimal]                   => Decimal[Decimal]                       | conversion auto   }
-                { Int2[i16]                          => Int16[i16]                             | conversion auto   }
-                { Int4[i32]                          => Int32[i32]                             | conversion auto   }
-                { Int8[i64]                          => Int64[i64]                             | conversion auto   }
-                { UInt4[u32]                         => UInt32[u32]                            | conversion auto   }
-                { Bool[bool]                         => Boolean[bool]                          | conversion auto   }
-                { Text[&'r str]                      => LargeUtf8[String]                      | conversion owned  }
-                { BpChar[&'r str]                    => LargeUtf8[String]                      | conversion none   }
-                { VarChar[&'r str]                   => LargeUtf8[String]                      | conversion none   }
-                { Name[&'r str]                      => LargeUtf8[String]                      | conversion none   }
-                { Enum[&'r str]                      => LargeUtf8[String]                      | conversion none   }
-                { Timestamp[NaiveDateTime]           => Date64Micro[NaiveDateTimeWrapperMicro] | conversion option }
-                { Date[NaiveDate]                    => Date32[NaiveDate]                      | conversion auto   }
-                { Time[NaiveTime]                    => Time64Micro[NaiveTimeWrapperMicro]     | conversion option }
-                { TimestampTz[DateTime<Utc>]         => DateTimeTzMicro[DateTimeWrapperMicro]  | conversion option }
-                { UUID[Uuid]                         => LargeUtf8[String]                      | conversion option }
-                { Char[&'r str]                      => LargeUtf8[String]                      | conversion none   }
-                { ByteA[Vec<u8>]                     => LargeBinary[Vec<u8>]                   | conversion auto   }
-                { JSON[Value]                        => LargeUtf8[String]                      | conversion option }
-                { JSONB[Value]                       => LargeUtf8[String]                      | conversion none   }
-                { Inet[IpInet]                       => LargeUtf8[String]                      | conversion none   }
-                { BoolArray[Vec<Option<bool>>]       => BoolArray[Vec<Option<bool>>]           | conversion auto   }
-                { VarcharArray[Vec<Option<String>>]  => Utf8Array[Vec<Option<String>>]         | conversion auto   }
-                { TextArray[Vec<Option<String>>]     => Utf8Array[Vec<Option<String>>]         | conversion none   }
-                { Int2Array[Vec<Option<i16>>]        => Int16Array[Vec<Option<i16>>]           | conversion auto   }
-                { Int4Array[Vec<Option<i32>>]        => Int32Array[Vec<Option<i32>>]           | conversion auto   }
-                { Int8Array[Vec<Option<i64>>]        => Int64Array[Vec<Option<i64>>]           | conversion auto   }
-                { Float4Array[Vec<Option<f32>>]      => Float32Array[Vec<Option<f32>>]         | conversion auto   }
-                { Float8Array[Vec<Option<f64>>]      => Float64Array[Vec<Option<f64>>]         | conversion auto   }
-                { NumericArray[Vec<Option<Decimal>>] => DecimalArray[Vec<Option<Decimal>>]     | conversion auto   }
-                { Vector[Vector]                     => Float32Array[Vec<Option<f32>>]         | conversion option }
-                { HalfVec[HalfVector]                => Float32Array[Vec<Option<f32>>]         | conversion option }
-                { Bit[Bit]                           => LargeBinary[Vec<u8>]                   | conversion option }
-                { SparseVec[SparseVector]            => Float32Array[Vec<Option<f32>>]         | conversion option }
+                { Float4[f32]                           => Float32[f32]                           | conversion auto   }
+                { Float8[f64]                           => Float64[f64]                           | conversion auto   }
+                { Numeric[Decimal]                      => Decimal[Decimal]                       | conversion auto   }
+                { Int2[i16]                             => Int16[i16]                             | conversion auto   }
+                { Int4[i32]                             => Int32[i32]                             | conversion auto   }
+                { Int8[i64]                             => Int64[i64]                             | conversion auto   }
+                { UInt4[u32]                            => UInt32[u32]                            | conversion auto   }
+                { Bool[bool]                            => Boolean[bool]                          | conversion auto   }
+                { Text[&'r str]                         => LargeUtf8[&'r str]                     | conversion auto   }
+                { BpChar[&'r str]                       => LargeUtf8[&'r str]                     | conversion none   }
+                { VarChar[&'r str]                      => LargeUtf8[&'r str]                     | conversion none   }
+                { Name[&'r str]                         => LargeUtf8[&'r str]                     | conversion none   }
+                { Enum[&'r str]                         => Dictionary[DictionaryWrapper]          | conversion option }
+                { Timestamp[NaiveDateTime]              => Date64Micro[NaiveDateTimeWrapperMicro] | conversion option }
+                { Date[NaiveDate]                       => Date32[NaiveDate]                      | conversion auto   }
+                { Time[NaiveTime]                       => Time64Micro[NaiveTimeWrapperMicro]     | conversion option }
+                { Interval[Interval]                    => Interval[Interval]                     | conversion auto   }
+                { TimestampTz[DateTime<Utc>]            => DateTimeTzMicro[DateTimeWrapperMicro]  | conversion option }
+                { UUID[Uuid]                            => Uuid[Uuid]                             | conversion auto   }
+                { Char[&'r str]                         => LargeUtf8[&'r str]                     | conversion none   }
+                { ByteA[Vec<u8>]                        => LargeBinary[Vec<u8>]                   | conversion auto   }
+                { JSON[Value]                           => Json[Value]                            | conversion auto   }
+                { JSONB[Value]                          => Json[Value]                            | conversion none   }
+                { Inet[IpInet]                          => LargeUtf8[String]                      | conversion none   }
+                { BoolArray[List<bool>]                 => BoolArray[List<bool>]                  | conversion auto   }
+                { VarcharArray[List<String>]            => Utf8Array[List<String>]                | conversion auto   }
+                { TextArray[List<String>]               => Utf8Array[List<String>]                | conversion none   }
+                { EnumArray[List<String>]               => Utf8Array[List<String>]                | conversion none   }
+                { Int2Array[List<i16>]                  => Int16Array[List<i16>]                  | conversion auto   }
+                { Int4Array[List<i32>]                  => Int32Array[List<i32>]                  | conversion auto   }
+                { Int8Array[List<i64>]                  => Int64Array[List<i64>]                  | conversion auto   }
+                { Float4Array[List<f32>]                => Float32Array[List<f32>]                | conversion auto   }
+                { Float8Array[List<f64>]                => Float64Array[List<f64>]                | conversion auto   }
+                { NumericArray[List<Decimal>]           => DecimalArray[List<Decimal>]            | conversion auto   }
+                { ByteAArray[List<Vec<u8>>]             => BinaryArray[List<Vec<u8>>]             | conversion auto   }
+                { DateArray[List<NaiveDate>]            => Date32Array[List<NaiveDate>]           | conversion auto   }
+                { TimeArray[List<NaiveTime>]            => Time64Array[List<NaiveTime>]           | conversion auto   }
+                { TimestampArray[List<NaiveDateTime>]   => Date64Array[List<NaiveDateTime>]       | conversion auto   }
+                { TimestampTzArray[List<DateTime<Utc>>] => DateTimeTzArray[List<DateTime<Utc>>]   | conversion auto   }
+                { IntervalArray[List<Interval>]         => IntervalArray[List<Interval>]          | conversion auto   }
+                { UUIDArray[List<Uuid>]                 => UuidArray[List<Uuid>]                  | conversion auto   }
+                { JSONArray[List<Value>]                => JsonArray[List<Value>]                 | conversion auto   }
+                { JSONBArray[List<Value>]               => JsonArray[List<Value>]                 | conversion none   }
+                { Vector[Vector]                        => Float32Vector[DenseVector<f32>]        | conversion option }
+                { HalfVec[HalfVector]                   => Float16Vector[DenseVector<f16>]        | conversion option }
+                { Bit[Bit]                              => BitString[BitString]                   | conversion option }
+                { SparseVec[SparseVector]               => SparseVector[SparseVec]                | conversion option }
+                { Composite[Composite]                  => Struct[Composite]                      | conversion auto   }
+                { HSTORE[HashMap<String, Option<String>>] => Map[HashMap<String, Option<String>>] | conversion auto   }
             }
         );
     }
@@ -108,6 +126,12 @@ impl<P, C> TypeConversion<Option<IpInet>, Option<String>> for PostgresArrowTrans
     }
 }
 
+impl<'r, P, C> TypeConversion<&'r str, DictionaryWrapper> for PostgresArrowTransport<P, C> {
+    fn convert(val: &'r str) -> DictionaryWrapper {
+        DictionaryWrapper(val.to_owned())
+    }
+}
+
 impl<P, C> TypeConversion<NaiveTime, NaiveTimeWrapperMicro> for PostgresArrowTransport<P, C> {
     fn convert(val: NaiveTime) -> NaiveTimeWrapperMicro {
         NaiveTimeWrapperMicro(val)
@@ -128,12 +152,6 @@ impl<P, C> TypeConversion<DateTime<Utc>, DateTimeWrapperMicro> for PostgresArrow
     }
 }
 
-impl<P, C> TypeConversion<Uuid, String> for PostgresArrowTransport<P, C> {
-    fn convert(val: Uuid) -> String {
-        val.to_string()
-    }
-}
-
 impl<P, C> TypeConversion<Decimal, f64> for PostgresArrowTransport<P, C> {
     fn convert(val: Decimal) -> f64 {
         val.to_f64()
@@ -141,32 +159,30 @@ impl<P, C> TypeConversion<Decimal, f64> for PostgresArrowTransport<P, C> {
     }
 }
 
-impl<P, C> TypeConversion<Value, String> for PostgresArrowTransport<P, C> {
-    fn convert(val: Value) -> String {
-        val.to_string()
-    }
-}
-
-impl<P, C> TypeConversion<Vector, Vec<Option<f32>>> for PostgresArrowTransport<P, C> {
-    fn convert(val: Vector) -> Vec<Option<f32>> {
-        val.to_vec().into_iter().map(Some).collect()
+impl<P, C> TypeConversion<Vector, DenseVector<f32>> for PostgresArrowTransport<P, C> {
+    fn convert(val: Vector) -> DenseVector<f32> {
+        DenseVector(val.to_vec())
     }
 }
 
-impl<P, C> TypeConversion<HalfVector, Vec<Option<f32>>> for PostgresArrowTransport<P, C> {
-    fn convert(val: HalfVector) -> Vec<Option<f32>> {
-        val.to_vec().into_iter().map(|v| Some(v.to_f32())).collect()
+impl<P, C> TypeConversion<HalfVector, DenseVector<f16>> for PostgresArrowTransport<P, C> {
+    fn convert(val: HalfVector) -> DenseVector<f16> {
+        DenseVector(val.to_vec())
     }
 }
 
-impl<P, C> TypeConversion<Bit, Vec<u8>> for PostgresArrowTransport<P, C> {
-    fn convert(val: Bit) -> Vec<u8> {
-        val.as_bytes().into()
+impl<P, C> TypeConversion<Bit, BitString> for PostgresArrowTransport<P, C> {
+    fn convert(val: Bit) -> BitString {
+        BitString(val.as_bytes().into())
     }
 }
 
-impl<P, C> TypeConversion<SparseVector, Vec<Option<f32>>> for PostgresArrowTransport<P, C> {
-    fn convert(val: SparseVector) -> Vec<Option<f32>> {
-        val.to_vec().into_iter().map(Some).collect()
+impl<P, C> TypeConversion<SparseVector, SparseVec> for PostgresArrowTransport<P, C> {
+    fn convert(val: SparseVector) -> SparseVec {
+        SparseVec {
+            dim: val.dimensions(),
+            indices: val.indices().into(),
+            values: val.values().into(),
+        }
     }
 }
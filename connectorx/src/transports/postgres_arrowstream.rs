@@ -7,14 +7,16 @@ use crate::sources::postgres::{
     BinaryProtocol, CSVProtocol, CursorProtocol, PostgresSource, PostgresSourceError,
     PostgresTypeSystem, SimpleProtocol,
 };
-use crate::typesystem::TypeConversion;
+use crate::typesystem::{BitString, DenseVector, SparseVec, TypeConversion};
 use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
 use cidr_02::IpInet;
+use half::f16;
 use pgvector::{Bit, HalfVector, SparseVector, Vector};
 use postgres::NoTls;
 use postgres_openssl::MakeTlsConnector;
 use rust_decimal::Decimal;
 use serde_json::Value;
+use std::collections::HashMap;
 use std::marker::PhantomData;
 use thiserror::Error;
 use uuid::Uuid;
@@ -63,10 +65,11 @@ macro_rules! impl_postgres_transport {
                 { JSON[Value]                => LargeUtf8[String]                      | conversion option }
                 { JSONB[Value]               => LargeUtf8[String]                      | conversion none   }
                 { Inet[IpInet]               => LargeUtf8[String]                      | conversion none   }
-                { Vector[Vector]             => Float32Array[Vec<Option<f32>>]         | conversion option }
-                { HalfVec[HalfVector]        => Float32Array[Vec<Option<f32>>]         | conversion option }
-                { Bit[Bit]                   => LargeBinary[Vec<u8>]                   | conversion option }
-                { SparseVec[SparseVector]    => Float32Array[Vec<Option<f32>>]         | conversion option }
+                { Vector[Vector]             => Float32Vector[DenseVector<f32>]        | conversion option }
+                { HalfVec[HalfVector]        => Float16Vector[DenseVector<f16>]        | conversion option }
+                { Bit[Bit]                   => BitString[BitString]                   | conversion option }
+                { SparseVec[SparseVector]    => SparseVector[SparseVec]                | conversion option }
+                { HSTORE[HashMap<String, Option<String>>] => Map[HashMap<String, Option<String>>] | conversion auto }
             }
         );
     }
@@ -105,26 +108,30 @@ impl<P, C> TypeConversion<Value, String> for PostgresArrowTransport<P, C> {
     }
 }
 
-impl<P, C> TypeConversion<Vector, Vec<Option<f32>>> for PostgresArrowTransport<P, C> {
-    fn convert(val: Vector) -> Vec<Option<f32>> {
-        val.to_vec().into_iter().map(Some).collect()
+impl<P, C> TypeConversion<Vector, DenseVector<f32>> for PostgresArrowTransport<P, C> {
+    fn convert(val: Vector) -> DenseVector<f32> {
+        DenseVector(val.to_vec())
     }
 }
 
-impl<P, C> TypeConversion<HalfVector, Vec<Option<f32>>> for PostgresArrowTransport<P, C> {
-    fn convert(val: HalfVector) -> Vec<Option<f32>> {
-        val.to_vec().into_iter().map(|v| Some(v.to_f32())).collect()
+impl<P, C> TypeConversion<HalfVector, DenseVector<f16>> for PostgresArrowTransport<P, C> {
+    fn convert(val: HalfVector) -> DenseVector<f16> {
+        DenseVector(val.to_vec())
     }
 }
 
-impl<P, C> TypeConversion<Bit, Vec<u8>> for PostgresArrowTransport<P, C> {
-    fn convert(val: Bit) -> Vec<u8> {
-        val.as_bytes().into()
+impl<P, C> TypeConversion<Bit, BitString> for PostgresArrowTransport<P, C> {
+    fn convert(val: Bit) -> BitString {
+        BitString(val.as_bytes().into())
     }
 }
 
-impl<P, C> TypeConversion<SparseVector, Vec<Option<f32>>> for PostgresArrowTransport<P, C> {
-    fn convert(val: SparseVector) -> Vec<Option<f32>> {
-        val.to_vec().into_iter().map(Some).collect()
+impl<P, C> TypeConversion<SparseVector, SparseVec> for PostgresArrowTransport<P, C> {
+    fn convert(val: SparseVector) -> SparseVec {
+        SparseVec {
+            dim: val.dimensions(),
+            indices: val.indices().into(),
+            values: val.values().into(),
+        }
     }
 }
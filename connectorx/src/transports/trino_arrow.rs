@@ -4,13 +4,14 @@ use crate::{
     destinations::arrow::{typesystem::ArrowTypeSystem, ArrowDestination, ArrowDestinationError},
     impl_transport,
     sources::trino::{TrinoSource, TrinoSourceError, TrinoTypeSystem},
-    typesystem::TypeConversion,
+    typesystem::{Interval, TypeConversion},
 };
-use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use chrono::{Duration, NaiveDate, NaiveDateTime, NaiveTime};
 use num_traits::ToPrimitive;
 use rust_decimal::Decimal;
 use serde_json::{to_string, Value};
 use thiserror::Error;
+use uuid::Uuid;
 
 #[derive(Error, Debug)]
 pub enum TrinoArrowTransportError {
@@ -45,6 +46,9 @@ impl_transport!(
         { Real[f32]                  => Float64[f64]            | conversion auto }
         { Varchar[String]            => LargeUtf8[String]       | conversion auto }
         { Char[String]               => LargeUtf8[String]       | conversion none }
+        { Uuid[Uuid]                 => Uuid[Uuid]              | conversion auto }
+        { IntervalDayToSecond[Duration] => Duration[Duration]   | conversion auto }
+        { IntervalYearToMonth[Interval] => Interval[Interval]   | conversion auto }
     }
 );
 
@@ -7,12 +7,25 @@
 use crate::destinations::{Consume, Destination, DestinationPartition};
 use crate::errors::{ConnectorXError, Result as CXResult};
 use crate::sources::{PartitionParser, Produce, Source, SourcePartition};
+#[cfg(any(feature = "src_postgres", feature = "dst_arrow"))]
+use {
+    chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc},
+    rust_decimal::Decimal,
+    serde_json::Value,
+    uuid::Uuid,
+};
 
 #[doc(hidden)]
 /// `TypeSystem` describes all the types a source or destination support
 /// using enum variants.
 /// The variant can be used to type check with a static type `T` through the `check` method.
 pub trait TypeSystem: Copy + Clone + Send + Sync {
+    /// The name of the type, i.e. its variant without the nullability, e.g. `Int4`.
+    fn type_name(&self) -> &'static str;
+
+    /// Whether the values of the type can be null.
+    fn is_nullable(&self) -> bool;
+
     /// Check whether T is the same type as defined by self.
     fn check<T: TypeAssoc<Self>>(self) -> CXResult<()> {
         T::check(self)
@@ -141,3 +154,102 @@ where
     DestinationPartition::write(dst, val)?;
     Ok(())
 }
+
+/// A calendar interval, e.g. Postgres `interval` or Oracle `INTERVAL YEAR TO MONTH`. The months
+/// and days are kept apart from the time since their length varies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Interval {
+    pub months: i32,
+    pub days: i32,
+    pub nanoseconds: i64,
+}
+
+/// An array of nullable values, e.g. a Postgres array. The elements of a multidimensional array
+/// are kept in row-major order, with the length of every dimension in `dims`. An empty array has
+/// no dimensions.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct List<T> {
+    pub dims: Vec<usize>,
+    pub values: Vec<Option<T>>,
+}
+
+impl<T> From<Vec<Option<T>>> for List<T> {
+    fn from(values: Vec<Option<T>>) -> Self {
+        let dims = match values.len() {
+            0 => vec![],
+            n => vec![n],
+        };
+        List { dims, values }
+    }
+}
+
+/// A dense vector, e.g. a pgvector `vector` or `halfvec`. Its elements are never missing.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct DenseVector<T>(pub Vec<T>);
+
+/// A sparse vector of `dim` elements, e.g. a pgvector `sparsevec`, with the `values` of its
+/// nonzero elements at the zero-based `indices`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SparseVec {
+    pub dim: i32,
+    pub indices: Vec<i32>,
+    pub values: Vec<f32>,
+}
+
+/// A string of bits, e.g. a Postgres `bit(n)`, packed into bytes with the first bit in the most
+/// significant bit of the first byte.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct BitString(pub Vec<u8>);
+
+/// The type of a field of a [`Composite`]. Composite and list fields nest further types.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldType {
+    Bool,
+    Int16,
+    Int32,
+    Int64,
+    Float32,
+    Float64,
+    Decimal,
+    Text,
+    Binary,
+    Date,
+    Time,
+    Timestamp,
+    TimestampTz,
+    Interval,
+    Uuid,
+    Json,
+    List(Box<FieldType>),
+    Composite(Vec<(String, FieldType)>),
+}
+
+/// A value of a composite type, e.g. a Postgres row type, with its fields in the order of the
+/// type. A missing field is null.
+#[cfg(any(feature = "src_postgres", feature = "dst_arrow"))]
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Composite(pub Vec<Option<FieldValue>>);
+
+/// A value of a field of a [`Composite`], see [`FieldType`].
+#[cfg(any(feature = "src_postgres", feature = "dst_arrow"))]
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldValue {
+    Bool(bool),
+    Int16(i16),
+    Int32(i32),
+    Int64(i64),
+    Float32(f32),
+    Float64(f64),
+    Decimal(Decimal),
+    Text(String),
+    Binary(Vec<u8>),
+    Date(NaiveDate),
+    Time(NaiveTime),
+    Timestamp(NaiveDateTime),
+    TimestampTz(DateTime<Utc>),
+    Interval(Interval),
+    Uuid(Uuid),
+    Json(Value),
+    List(Vec<Option<FieldValue>>),
+    Composite(Composite),
+}
@@ -1,23 +1,35 @@
 use arrow::{
     array::{
-        BooleanArray, BooleanBuilder, Float64Array, Int64Array, Int64Builder, LargeListArray,
-        LargeListBuilder, StringArray, StringBuilder,
+        Array, BooleanArray, BooleanBuilder, FixedSizeBinaryArray, FixedSizeListArray,
+        Float16Array, Float32Array, Float64Array, Int32Array, Int64Array, Int64Builder,
+        LargeBinaryArray, LargeListArray, LargeListBuilder, ListArray, StringArray, StringBuilder,
+        StructArray,
     },
+    datatypes::{DataType, Field},
     record_batch::RecordBatch,
 };
 use connectorx::{
-    constants::RECORD_BATCH_SIZE,
-    destinations::arrow::{ArrowDestination, ArrowTypeSystem},
+    constants::{BIT_LENGTH_KEY, RECORD_BATCH_SIZE},
+    destinations::{
+        arrow::{ArrowDestination, ArrowTypeSystem},
+        arrowstream::{
+            ArrowDestination as ArrowStreamDestination, ArrowTypeSystem as ArrowStreamTypeSystem,
+        },
+    },
     prelude::*,
     sources::{
         dummy::{DummySource, DummyTypeSystem},
         postgres::{rewrite_tls_args, BinaryProtocol, PostgresSource},
+        ColumnDescription,
     },
     sql::CXQuery,
     transports::{DummyArrowTransport, PostgresArrowTransport},
+    typesystem::{BitString, DenseVector, SparseVec},
 };
+use half::f16;
 use postgres::NoTls;
 use std::env;
+use std::sync::{Arc, Mutex};
 use url::Url;
 
 #[test]
@@ -37,6 +49,281 @@ fn arrow_destination_col_major() {
     .unwrap();
 }
 
+#[test]
+fn arrow_destination_vectors() {
+    let mut destination = ArrowDestination::new();
+    // the dimensions of the vectors and the length of the bit strings of the first columns are
+    // known, as for table columns, those of the last ones are not
+    destination.set_column_descriptions(vec![
+        ColumnDescription {
+            dimension: Some(2),
+            ..Default::default()
+        },
+        ColumnDescription {
+            dimension: Some(2),
+            ..Default::default()
+        },
+        ColumnDescription {
+            length: Some(10),
+            ..Default::default()
+        },
+        ColumnDescription::default(),
+        ColumnDescription::default(),
+        ColumnDescription::default(),
+    ]);
+    destination
+        .allocate(
+            2,
+            &["dense", "half", "bits", "sparse", "dense_any", "bits_any"],
+            &[
+                ArrowTypeSystem::Float32Vector(true),
+                ArrowTypeSystem::Float16Vector(false),
+                ArrowTypeSystem::BitString(true),
+                ArrowTypeSystem::SparseVector(true),
+                ArrowTypeSystem::Float32Vector(true),
+                ArrowTypeSystem::BitString(false),
+            ],
+            DataOrder::RowMajor,
+        )
+        .unwrap();
+    {
+        let mut partitions = destination.partition(1).unwrap();
+        let writer = &mut partitions[0];
+        writer.write(Some(DenseVector(vec![1.0f32, 2.0]))).unwrap();
+        writer
+            .write(DenseVector(vec![f16::from_f32(0.5), f16::ONE]))
+            .unwrap();
+        writer
+            .write(Some(BitString(vec![0b1010_1010, 0b1000_0000])))
+            .unwrap();
+        writer
+            .write(Some(SparseVec {
+                dim: 5,
+                indices: vec![0, 4],
+                values: vec![1.0, 3.0],
+            }))
+            .unwrap();
+        writer
+            .write(Some(DenseVector(vec![1.0f32, 2.0, 3.0])))
+            .unwrap();
+        writer.write(BitString(vec![0b1110_0000])).unwrap();
+
+        writer.write(None::<DenseVector<f32>>).unwrap();
+        writer
+            .write(DenseVector(vec![f16::ZERO, f16::ZERO]))
+            .unwrap();
+        writer.write(None::<BitString>).unwrap();
+        writer.write(None::<SparseVec>).unwrap();
+        writer.write(Some(DenseVector(vec![4.0f32]))).unwrap();
+        writer.write(BitString(vec![])).unwrap();
+        writer.finalize().unwrap();
+    }
+
+    let item = |ty| Arc::new(Field::new("item", ty, false));
+    let schema = destination.arrow_schema();
+    assert_eq!(
+        schema.field(0).data_type(),
+        &DataType::FixedSizeList(item(DataType::Float32), 2)
+    );
+    assert_eq!(
+        schema.field(1).data_type(),
+        &DataType::FixedSizeList(item(DataType::Float16), 2)
+    );
+    assert_eq!(schema.field(2).data_type(), &DataType::FixedSizeBinary(2));
+    assert_eq!(
+        schema.field(2).metadata().get(BIT_LENGTH_KEY),
+        Some(&"10".to_string())
+    );
+    assert_eq!(
+        schema.field(3).data_type(),
+        &DataType::Struct(
+            vec![
+                Field::new("indices", DataType::List(item(DataType::Int32)), false),
+                Field::new("values", DataType::List(item(DataType::Float32)), false),
+                Field::new("dim", DataType::Int32, false),
+            ]
+            .into()
+        )
+    );
+    assert_eq!(
+        schema.field(4).data_type(),
+        &DataType::LargeList(item(DataType::Float32))
+    );
+    assert_eq!(schema.field(5).data_type(), &DataType::LargeBinary);
+    assert!(schema.field(5).metadata().is_empty());
+
+    let rbs = destination.arrow().unwrap();
+    let rb = &rbs[0];
+    let dense = rb
+        .column(0)
+        .as_any()
+        .downcast_ref::<FixedSizeListArray>()
+        .unwrap();
+    assert!(dense.is_valid(0) && dense.is_null(1));
+    let values = dense.value(0);
+    let values = values.as_any().downcast_ref::<Float32Array>().unwrap();
+    assert_eq!(values.values(), &[1.0, 2.0]);
+    let half = rb
+        .column(1)
+        .as_any()
+        .downcast_ref::<FixedSizeListArray>()
+        .unwrap();
+    let values = half.value(0);
+    let values = values.as_any().downcast_ref::<Float16Array>().unwrap();
+    assert_eq!(values.values(), &[f16::from_f32(0.5), f16::ONE]);
+    let bits = rb
+        .column(2)
+        .as_any()
+        .downcast_ref::<FixedSizeBinaryArray>()
+        .unwrap();
+    assert_eq!(bits.value(0), &[0b1010_1010, 0b1000_0000]);
+    assert!(bits.is_null(1));
+    let sparse = rb.column(3).as_any().downcast_ref::<StructArray>().unwrap();
+    assert!(sparse.is_valid(0) && sparse.is_null(1));
+    let indices = sparse
+        .column_by_name("indices")
+        .unwrap()
+        .as_any()
+        .downcast_ref::<ListArray>()
+        .unwrap()
+        .value(0);
+    let indices = indices.as_any().downcast_ref::<Int32Array>().unwrap();
+    assert_eq!(indices.values(), &[0, 4]);
+    let dims = sparse.column_by_name("dim").unwrap();
+    let dims = dims.as_any().downcast_ref::<Int32Array>().unwrap();
+    assert_eq!(dims.value(0), 5);
+    let dense_any = rb
+        .column(4)
+        .as_any()
+        .downcast_ref::<LargeListArray>()
+        .unwrap();
+    assert_eq!(dense_any.value_length(0), 3);
+    assert_eq!(dense_any.value_length(1), 1);
+    let bits_any = rb
+        .column(5)
+        .as_any()
+        .downcast_ref::<LargeBinaryArray>()
+        .unwrap();
+    assert_eq!(bits_any.value(0), &[0b1110_0000]);
+}
+
+#[test]
+fn arrow_stream_destination_vectors() {
+    let mut destination = ArrowStreamDestination::new();
+    destination.set_column_descriptions(vec![
+        ColumnDescription {
+            dimension: Some(2),
+            ..Default::default()
+        },
+        ColumnDescription {
+            length: Some(10),
+            ..Default::default()
+        },
+        ColumnDescription::default(),
+        ColumnDescription::default(),
+    ]);
+    destination
+        .allocate(
+            2,
+            &["dense", "bits", "sparse", "bits_any"],
+            &[
+                ArrowStreamTypeSystem::Float32Vector(true),
+                ArrowStreamTypeSystem::BitString(true),
+                ArrowStreamTypeSystem::SparseVector(false),
+                ArrowStreamTypeSystem::BitString(false),
+            ],
+            DataOrder::RowMajor,
+        )
+        .unwrap();
+    {
+        let mut partitions = destination.partition(1).unwrap();
+        let writer = &mut partitions[0];
+        writer.write(Some(DenseVector(vec![1.0f32, 2.0]))).unwrap();
+        writer
+            .write(Some(BitString(vec![0b1010_1010, 0b1000_0000])))
+            .unwrap();
+        writer
+            .write(SparseVec {
+                dim: 5,
+                indices: vec![0, 4],
+                values: vec![1.0, 3.0],
+            })
+            .unwrap();
+        writer.write(BitString(vec![0b1110_0000])).unwrap();
+
+        writer.write(None::<DenseVector<f32>>).unwrap();
+        writer.write(None::<BitString>).unwrap();
+        writer.write(SparseVec::default()).unwrap();
+        writer.write(BitString(vec![])).unwrap();
+        writer.finalize().unwrap();
+    }
+
+    let item = |ty| Arc::new(Field::new("item", ty, false));
+    let schema = destination.arrow_schema();
+    assert_eq!(
+        schema.field(0).data_type(),
+        &DataType::FixedSizeList(item(DataType::Float32), 2)
+    );
+    assert_eq!(schema.field(1).data_type(), &DataType::FixedSizeBinary(2));
+    assert_eq!(
+        schema.field(1).metadata().get(BIT_LENGTH_KEY),
+        Some(&"10".to_string())
+    );
+    assert!(matches!(schema.field(2).data_type(), DataType::Struct(_)));
+    assert_eq!(schema.field(3).data_type(), &DataType::LargeBinary);
+
+    let rbs = destination.arrow().unwrap();
+    let rb = &rbs[0];
+    let dense = rb
+        .column(0)
+        .as_any()
+        .downcast_ref::<FixedSizeListArray>()
+        .unwrap();
+    assert!(dense.is_valid(0) && dense.is_null(1));
+    let values = dense.value(0);
+    let values = values.as_any().downcast_ref::<Float32Array>().unwrap();
+    assert_eq!(values.values(), &[1.0, 2.0]);
+    let bits = rb
+        .column(1)
+        .as_any()
+        .downcast_ref::<FixedSizeBinaryArray>()
+        .unwrap();
+    assert_eq!(bits.value(0), &[0b1010_1010, 0b1000_0000]);
+    assert!(bits.is_null(1));
+    let sparse = rb.column(2).as_any().downcast_ref::<StructArray>().unwrap();
+    let dims = sparse.column_by_name("dim").unwrap();
+    let dims = dims.as_any().downcast_ref::<Int32Array>().unwrap();
+    assert_eq!(dims.values(), &[5, 0]);
+    let bits_any = rb
+        .column(3)
+        .as_any()
+        .downcast_ref::<LargeBinaryArray>()
+        .unwrap();
+    assert_eq!(bits_any.value(0), &[0b1110_0000]);
+}
+
+#[test]
+fn arrow_destination_vector_dimension() {
+    let mut destination = ArrowDestination::new();
+    destination.set_column_descriptions(vec![ColumnDescription {
+        dimension: Some(2),
+        ..Default::default()
+    }]);
+    destination
+        .allocate(
+            1,
+            &["dense"],
+            &[ArrowTypeSystem::Float32Vector(false)],
+            DataOrder::RowMajor,
+        )
+        .unwrap();
+    let mut partitions = destination.partition(1).unwrap();
+    // a vector of another dimension fails the load
+    assert!(partitions[0]
+        .write(DenseVector(vec![1.0f32, 2.0, 3.0]))
+        .is_err());
+}
+
 #[test]
 fn test_arrow() {
     let schema = [
@@ -143,6 +430,43 @@ fn test_arrow() {
     }
 }
 
+#[test]
+fn test_arrow_no_execution_queries() {
+    let schema = [DummyTypeSystem::I64(false)];
+    let queries = [CXQuery::naked("3,1")];
+    let mut destination = ArrowDestination::new();
+
+    // a source that cannot run execution queries accepts none
+    let mut dispatcher = Dispatcher::<_, _, DummyArrowTransport>::new(
+        DummySource::new(&["a"], &schema),
+        &mut destination,
+        &queries,
+        None,
+    );
+    dispatcher.set_pre_execution_queries(None);
+    dispatcher.set_post_execution_queries(Some(&[]));
+    dispatcher.run().expect("run dispatcher");
+
+    let records = destination.arrow().unwrap();
+    assert_eq!(3, records.iter().map(|rb| rb.num_rows()).sum::<usize>());
+}
+
+#[test]
+#[should_panic(expected = "pre_execution_queries is not implemented")]
+fn test_arrow_unsupported_execution_queries() {
+    let schema = [DummyTypeSystem::I64(false)];
+    let queries = [CXQuery::naked("3,1")];
+    let mut destination = ArrowDestination::new();
+
+    let mut dispatcher = Dispatcher::<_, _, DummyArrowTransport>::new(
+        DummySource::new(&["a"], &schema),
+        &mut destination,
+        &queries,
+        None,
+    );
+    dispatcher.set_pre_execution_queries(Some(&["SET search_path TO public".to_string()]));
+}
+
 #[test]
 fn test_arrow_large() {
     let schema = [
@@ -187,6 +511,75 @@ fn test_arrow_large() {
     );
 }
 
+#[test]
+fn test_arrow_report() {
+    let schema = [DummyTypeSystem::I64(true), DummyTypeSystem::String(true)];
+    let nrows = [4, 7];
+    let ncols = schema.len();
+    let queries: Vec<CXQuery> = nrows
+        .iter()
+        .map(|v| CXQuery::naked(format!("{},{}", v, ncols)))
+        .collect();
+    let mut destination = ArrowDestination::new();
+
+    let dispatcher = Dispatcher::<_, _, DummyArrowTransport>::new(
+        DummySource::new(&["a", "b"], &schema),
+        &mut destination,
+        &queries,
+        None,
+    );
+    let report = dispatcher
+        .run_with_report(Some("dummy"))
+        .expect("run dispatcher");
+
+    assert_eq!(Some("dummy"), report.protocol.as_deref());
+    assert_eq!(2, report.partitions.len());
+    assert_eq!(vec!["4,2", "7,2"], report.queries());
+    assert_eq!(
+        vec![4, 7],
+        report.partitions.iter().map(|p| p.rows).collect::<Vec<_>>()
+    );
+    assert_eq!(11, report.total_rows());
+    assert!(report.partitions.iter().all(|p| p.bytes > 0));
+
+    let records: Vec<RecordBatch> = destination.arrow().unwrap();
+    let bytes: usize = records.iter().map(|r| r.get_array_memory_size()).sum();
+    assert_eq!(bytes, report.total_bytes());
+}
+
+#[test]
+fn test_arrow_progress() {
+    let schema = [DummyTypeSystem::I64(true), DummyTypeSystem::String(true)];
+    let nrows = [4, 7, 5];
+    let ncols = schema.len();
+    let queries: Vec<CXQuery> = nrows
+        .iter()
+        .map(|v| CXQuery::naked(format!("{},{}", v, ncols)))
+        .collect();
+    let mut destination = ArrowDestination::new();
+
+    let seen = Arc::new(Mutex::new(vec![]));
+    let seen_cb = Arc::clone(&seen);
+    let mut dispatcher = Dispatcher::<_, _, DummyArrowTransport>::new(
+        DummySource::new(&["a", "b"], &schema),
+        &mut destination,
+        &queries,
+        None,
+    );
+    dispatcher.set_progress_callback(Some(Arc::new(move |p: Progress| {
+        seen_cb.lock().unwrap().push(p);
+    })));
+    dispatcher.run().expect("run dispatcher");
+
+    let seen = seen.lock().unwrap();
+    assert!(seen.len() >= 3);
+    let last = seen.last().unwrap();
+    assert_eq!(16, last.rows_done);
+    assert_eq!(3, last.partitions_done);
+    assert_eq!(3, last.total_partitions);
+    assert_eq!(None, last.total_rows);
+}
+
 #[test]
 fn test_postgres_arrow() {
     let _ = env_logger::builder().is_test(true).try_init();
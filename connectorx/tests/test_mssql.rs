@@ -3,13 +3,37 @@ use arrow::{
     record_batch::RecordBatch,
 };
 use connectorx::{
-    destinations::arrow::ArrowDestination, prelude::*, sources::mssql::MsSQLSource, sql::CXQuery,
+    destinations::arrow::ArrowDestination,
+    prelude::*,
+    sources::mssql::{MsSQLSource, MsSQLTypeSystem},
+    sql::CXQuery,
     transports::MsSQLArrowTransport,
 };
 use std::env;
 use std::sync::Arc;
 use tokio::runtime::Runtime;
 
+#[test]
+fn test_mssql_type_names() {
+    use MsSQLTypeSystem::*;
+
+    let ty = |name, nullable| MsSQLTypeSystem::from_type_name(name, nullable);
+    // nullable integers and floats are sent as variable length types
+    assert!(matches!(ty("int", false), Some(Int(false))));
+    assert!(matches!(ty("bigint", true), Some(Intn(true))));
+    assert!(matches!(ty("float", false), Some(Float53(false))));
+    assert!(matches!(ty("real", true), Some(Floatn(true))));
+    // the length, precision and scale are ignored
+    assert!(matches!(ty("nvarchar(50)", false), Some(Nvarchar(false))));
+    assert!(matches!(ty("NVARCHAR(max)", true), Some(Nvarchar(true))));
+    assert!(matches!(ty("decimal(10,2)", true), Some(Decimal(true))));
+    assert!(matches!(ty("datetime2(7)", false), Some(Datetime2(false))));
+    assert!(matches!(ty("smalldatetime", true), Some(Datetime(true))));
+    assert!(matches!(ty("bit", false), Some(Bit(false))));
+    assert!(ty("xml", true).is_none());
+    assert!(ty("geography", true).is_none());
+}
+
 #[test]
 #[ignore]
 fn test_mssql() {
@@ -33,6 +57,37 @@ fn test_mssql() {
     verify_arrow_results(result);
 }
 
+#[test]
+#[ignore]
+fn test_mssql_pre_execution_queries() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let dburl = env::var("MSSQL_URL").unwrap();
+
+    let queries = [CXQuery::naked(
+        "SELECT CAST(@@LANGUAGE AS VARCHAR(128)) AS lang",
+    )];
+    let pre_execution_queries = [String::from("SET LANGUAGE Deutsch")];
+    let post_execution_queries = [String::from("SET LANGUAGE us_english")];
+    let rt = Arc::new(Runtime::new().unwrap());
+
+    let builder = MsSQLSource::new(rt, &dburl, 1).unwrap();
+    let mut destination = ArrowDestination::new();
+    let mut dispatcher =
+        Dispatcher::<_, _, MsSQLArrowTransport>::new(builder, &mut destination, &queries, None);
+    dispatcher.set_pre_execution_queries(Some(&pre_execution_queries));
+    dispatcher.set_post_execution_queries(Some(&post_execution_queries));
+    dispatcher.run().unwrap();
+
+    let result = destination.arrow().unwrap();
+    assert!(result[0]
+        .column(0)
+        .as_any()
+        .downcast_ref::<StringArray>()
+        .unwrap()
+        .eq(&StringArray::from(vec!["Deutsch"])));
+}
+
 #[test]
 #[ignore]
 fn test_mssql_agg() {
@@ -1,14 +1,16 @@
 use arrow::{
-    array::{Float64Array, Int64Array, StringArray},
+    array::{DurationMicrosecondArray, Float64Array, Int64Array, StringArray},
     record_batch::RecordBatch,
 };
 use connectorx::{
     destinations::arrow::ArrowDestination,
     prelude::*,
     sources::mysql::{BinaryProtocol, MySQLSource, TextProtocol},
-    sql::CXQuery,
+    sql::{CXParam, CXQuery},
     transports::MySQLArrowTransport,
 };
+use std::collections::HashMap;
+use std::convert::TryFrom;
 use std::env;
 
 #[test]
@@ -161,6 +163,177 @@ fn test_mysql_partitioned_pre_execution_queries() {
     assert_eq!(result_map.get("wait_timeout"), Some(&2252.0));
 }
 
+#[test]
+fn test_mysql_time_duration() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let dburl = env::var("MYSQL_URL").unwrap();
+
+    let queries = [CXQuery::naked(
+        "SELECT CAST('12:34:56.789' AS TIME(6)) AS t UNION ALL SELECT CAST('-01:02:03' AS TIME(6)) \
+         UNION ALL SELECT CAST('838:59:59' AS TIME(6)) UNION ALL SELECT NULL",
+    )];
+    let expected = DurationMicrosecondArray::from(vec![
+        Some(45_296_789_000),
+        Some(-3_723_000_000),
+        Some(3_020_399_000_000),
+        None,
+    ]);
+
+    let builder = MySQLSource::<BinaryProtocol>::new(&dburl, 1).unwrap();
+    let mut destination = ArrowDestination::new();
+    let dispatcher = Dispatcher::<_, _, MySQLArrowTransport<BinaryProtocol>>::new(
+        builder,
+        &mut destination,
+        &queries,
+        None,
+    );
+    dispatcher.run().unwrap();
+    let result = destination.arrow().unwrap();
+    assert_eq!(
+        result[0]
+            .column(0)
+            .as_any()
+            .downcast_ref::<DurationMicrosecondArray>()
+            .unwrap(),
+        &expected
+    );
+
+    let builder = MySQLSource::<TextProtocol>::new(&dburl, 1).unwrap();
+    let mut destination = ArrowDestination::new();
+    let dispatcher = Dispatcher::<_, _, MySQLArrowTransport<TextProtocol>>::new(
+        builder,
+        &mut destination,
+        &queries,
+        None,
+    );
+    dispatcher.run().unwrap();
+    let result = destination.arrow().unwrap();
+    assert_eq!(
+        result[0]
+            .column(0)
+            .as_any()
+            .downcast_ref::<DurationMicrosecondArray>()
+            .unwrap(),
+        &expected
+    );
+
+    // the record batch iterator loads them as durations as well
+    let source_conn = SourceConn::try_from(dburl.as_str()).unwrap();
+    let mut iter =
+        try_new_record_batch_iter(&source_conn, None, &queries, 1024, None, None).unwrap();
+    iter.prepare();
+    let rb = iter.try_next_batch().unwrap().unwrap();
+    assert_eq!(
+        rb.column(0)
+            .as_any()
+            .downcast_ref::<DurationMicrosecondArray>()
+            .unwrap(),
+        &expected
+    );
+}
+
+#[test]
+fn test_mysql_bound_params() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let dburl = env::var("MYSQL_URL").unwrap();
+
+    // the text protocol inlines the parameters as escaped literals
+    let queries = [CXQuery::naked(
+        "SELECT CAST(? AS CHAR) AS s, CAST(? AS SIGNED) AS i, '?' AS q FROM test_table \
+         WHERE test_int < ?",
+    )
+    .bind(vec![
+        CXParam::from("it's a \\ ?"),
+        CXParam::from(-7),
+        CXParam::from(3),
+    ])];
+    for text in [false, true] {
+        let mut destination = ArrowDestination::new();
+        if text {
+            let builder = MySQLSource::<TextProtocol>::new(&dburl, 1).unwrap();
+            Dispatcher::<_, _, MySQLArrowTransport<TextProtocol>>::new(
+                builder,
+                &mut destination,
+                &queries,
+                None,
+            )
+            .run()
+            .unwrap();
+        } else {
+            let builder = MySQLSource::<BinaryProtocol>::new(&dburl, 1).unwrap();
+            Dispatcher::<_, _, MySQLArrowTransport<BinaryProtocol>>::new(
+                builder,
+                &mut destination,
+                &queries,
+                None,
+            )
+            .run()
+            .unwrap();
+        }
+        let rows: Vec<(String, i64, String)> = destination
+            .arrow()
+            .unwrap()
+            .iter()
+            .flat_map(|rb| {
+                let s = rb.column(0).as_any().downcast_ref::<StringArray>().unwrap();
+                let i = rb.column(1).as_any().downcast_ref::<Int64Array>().unwrap();
+                let q = rb.column(2).as_any().downcast_ref::<StringArray>().unwrap();
+                (0..rb.num_rows())
+                    .map(|r| (s.value(r).to_string(), i.value(r), q.value(r).to_string()))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        assert_eq!(
+            rows,
+            vec![(String::from("it's a \\ ?"), -7, String::from("?")); 2]
+        );
+    }
+}
+
+#[test]
+fn test_mysql_arrow_raw() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let dburl = env::var("MYSQL_URL").unwrap();
+    let mut source_conn = SourceConn::try_from(dburl.as_str()).unwrap();
+
+    let pre_execution_queries = [
+        String::from("DROP PROCEDURE IF EXISTS cx_three_sets"),
+        String::from(
+            "CREATE PROCEDURE cx_three_sets(IN lim INT)
+            BEGIN
+                SELECT test_int FROM test_table WHERE test_int < lim;
+                SELECT test_int, test_float FROM test_table;
+                WITH RECURSIVE seq(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM seq WHERE i < 100)
+                SELECT i FROM seq;
+            END",
+        ),
+    ];
+    let post_execution_queries = [String::from("DROP PROCEDURE cx_three_sets")];
+    let query = CXQuery::naked("CALL cx_three_sets(?)").bind(vec![CXParam::from(3)]);
+
+    let nrows = |set: &[RecordBatch]| set.iter().map(|rb| rb.num_rows()).sum::<usize>();
+    for protocol in ["binary", "text"] {
+        source_conn.set_protocol(protocol);
+        let sets = get_arrow_raw(
+            &source_conn,
+            &query,
+            Some(&pre_execution_queries),
+            Some(&post_execution_queries),
+        )
+        .unwrap();
+        // the status of the call is not a result set
+        assert_eq!(sets.len(), 3, "{}", protocol);
+        assert_eq!(nrows(&sets[0]), 2, "{}", protocol);
+        assert_eq!(sets[0][0].num_columns(), 1, "{}", protocol);
+        assert_eq!(nrows(&sets[1]), 6, "{}", protocol);
+        assert_eq!(sets[1][0].num_columns(), 2, "{}", protocol);
+        assert_eq!(nrows(&sets[2]), 100, "{}", protocol);
+    }
+}
+
 pub fn verify_arrow_results(result: Vec<RecordBatch>) {
     assert!(result.len() == 2);
 
@@ -225,3 +398,46 @@ pub fn verify_arrow_results(result: Vec<RecordBatch>) {
         }
     }
 }
+
+#[test]
+fn test_mysql_source_metadata() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let dburl = env::var("MYSQL_URL").unwrap();
+    let source_conn = SourceConn::try_from(dburl.as_str()).unwrap();
+    let queries = [CXQuery::naked(
+        "SELECT test_new_decimal, test_varchar, test_tiny + 1 AS test_expr FROM test_types",
+    )];
+    let mut destination = ArrowDestination::new();
+    destination.set_source_metadata(true);
+    let (destination, _) =
+        get_arrow_into(destination, &source_conn, None, &queries, None, None, None).unwrap();
+
+    let schema = destination.arrow_schema();
+    let metadata = |pairs: &[(&str, &str)]| -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    };
+    let expected = [
+        metadata(&[
+            ("connectorx.source_type", "decimal(15,2)"),
+            ("connectorx.source_nullable", "true"),
+            ("connectorx.precision", "15"),
+            ("connectorx.scale", "2"),
+        ]),
+        metadata(&[
+            ("connectorx.source_type", "varchar(15)"),
+            ("connectorx.source_nullable", "true"),
+        ]),
+        // expressions are described by their type on the wire
+        metadata(&[
+            ("connectorx.source_type", "bigint"),
+            ("connectorx.source_nullable", "true"),
+        ]),
+    ];
+    for (field, expected) in schema.fields().iter().zip(expected) {
+        assert_eq!(field.metadata(), &expected, "{}", field.name());
+    }
+}
@@ -1,6 +1,9 @@
 use connectorx::prelude::*;
-use connectorx::sources::oracle::OracleSource;
-use connectorx::sql::CXQuery;
+use connectorx::sources::oracle::{OracleDialect, OracleSource};
+use connectorx::sql::{
+    count_query, describe_query_oracle, get_partition_range_query, limit1_query_oracle,
+    single_col_partition_query, CXParam, CXQuery,
+};
 use std::env;
 
 #[test]
@@ -79,3 +82,44 @@ fn test_types() {
         rows
     );
 }
+
+#[test]
+fn test_oracle_placeholders_kept_by_rewrites() {
+    let params = vec![CXParam::from(1), CXParam::from("a")];
+    let query =
+        CXQuery::naked("SELECT test_int FROM test_table WHERE test_int > :1 AND test_char = :2")
+            .bind(params.clone());
+
+    let count = count_query(&query, &OracleDialect {}).unwrap();
+    assert_eq!(
+        "SELECT COUNT(*) FROM (SELECT test_int FROM test_table WHERE test_int > :1 AND test_char = :2) CXTMPTAB_COUNT",
+        count.as_str()
+    );
+    assert_eq!(params.as_slice(), count.params());
+
+    let describe = describe_query_oracle(&query);
+    assert_eq!(
+        "SELECT * FROM (SELECT test_int FROM test_table WHERE test_int > :1 AND test_char = :2) WHERE 1 = 0",
+        describe.as_str()
+    );
+    assert_eq!(params.as_slice(), describe.params());
+
+    let limit1 = limit1_query_oracle(&query).unwrap();
+    assert_eq!(
+        "SELECT * FROM (SELECT test_int FROM test_table WHERE test_int > :1 AND test_char = :2) WHERE rownum = 1",
+        limit1.as_str()
+    );
+    assert_eq!(params.as_slice(), limit1.params());
+
+    let range = get_partition_range_query(query.as_str(), "test_int", &OracleDialect {}).unwrap();
+    assert_eq!(
+        "SELECT MIN(CXTMPTAB_RANGE.test_int) as min, MAX(CXTMPTAB_RANGE.test_int) as max FROM (SELECT test_int FROM test_table WHERE test_int > :1 AND test_char = :2) CXTMPTAB_RANGE",
+        range
+    );
+    let part =
+        single_col_partition_query(query.as_str(), "test_int", 0, 10, &OracleDialect {}).unwrap();
+    assert_eq!(
+        "SELECT * FROM (SELECT test_int FROM test_table WHERE test_int > :1 AND test_char = :2) CXTMPTAB_PART WHERE CXTMPTAB_PART.test_int >= 0 AND CXTMPTAB_PART.test_int < 10",
+        part
+    );
+}
@@ -1,16 +1,24 @@
 use arrow::{
     array::{
-        Array, BooleanArray, BooleanBuilder, Date32Array, Decimal128Array, Decimal128Builder,
-        Float32Array, Float64Array, Int16Array, Int32Array, Int64Array, LargeBinaryArray,
-        LargeListArray, LargeListBuilder, StringArray, StringBuilder, Time64MicrosecondArray,
-        TimestampMicrosecondArray,
+        Array, BinaryArray, BinaryViewArray, BooleanArray, BooleanBuilder, Date32Array,
+        Decimal128Array, Decimal128Builder, DictionaryArray, FixedSizeBinaryArray,
+        FixedSizeListArray, Float16Array, Float32Array, Float64Array, Int16Array, Int32Array,
+        Int64Array, IntervalMonthDayNanoArray, LargeBinaryArray, LargeListArray, LargeListBuilder,
+        ListArray, MapArray, StringArray, StringBuilder, StringViewArray, StructArray,
+        Time64MicrosecondArray, TimestampMicrosecondArray, TimestampMillisecondArray,
+    },
+    datatypes::{
+        DataType, Field, Float32Type, Float64Type, Int16Type, Int32Type, Int64Type,
+        IntervalMonthDayNano, IntervalUnit, Schema, TimeUnit,
     },
-    datatypes::{Float32Type, Float64Type, Int16Type, Int32Type, Int64Type},
     record_batch::RecordBatch,
 };
-use chrono::naive::NaiveDate;
+use chrono::naive::{NaiveDate, NaiveDateTime};
+use chrono::{DateTime, Utc};
 use connectorx::{
+    constants::BIT_LENGTH_KEY,
     destinations::arrow::ArrowDestination,
+    partition::PartitionQuery,
     prelude::*,
     sources::{
         postgres::{
@@ -19,11 +27,14 @@ use connectorx::{
         },
         PartitionParser,
     },
-    sql::CXQuery,
+    sql::{CXParam, CXQuery},
     transports::PostgresArrowTransport,
 };
 use postgres::NoTls;
+use std::collections::HashMap;
+use std::convert::TryFrom;
 use std::env;
+use std::sync::Arc;
 use url::Url;
 
 #[test]
@@ -755,18 +766,25 @@ pub fn verify_arrow_type_results(result: Vec<RecordBatch>, protocol: &str) {
     // test_enum
     if protocol != "cursor" && protocol != "simple" {
         col += 1;
-        assert!(result[0]
+        let enums = result[0]
             .column(col)
             .as_any()
-            .downcast_ref::<StringArray>()
-            .unwrap()
-            .eq(&StringArray::from(vec![
+            .downcast_ref::<DictionaryArray<Int32Type>>()
+            .unwrap();
+        assert_eq!(
+            enums
+                .downcast_dict::<StringArray>()
+                .unwrap()
+                .into_iter()
+                .collect::<Vec<_>>(),
+            vec![
                 Some("happy"),
                 Some("very happy"),
                 Some("ecstatic"),
                 Some("ecstatic"),
                 None,
-            ])));
+            ]
+        );
     }
 
     // test_f4array
@@ -1085,72 +1103,83 @@ fn verfiy_pgvector_results(result: Vec<RecordBatch>, _protocol: &str) {
         .unwrap()
         .eq(&Int32Array::from(vec![1, 2])));
 
-    // Verify dense_vector column
+    // Verify dense_vector column, a vector(10)
     col += 1;
     let dense_vector = rb
         .column(col)
         .as_any()
-        .downcast_ref::<LargeListArray>()
+        .downcast_ref::<FixedSizeListArray>()
         .unwrap();
+    assert_eq!(dense_vector.value_length(), 10);
     let dense_vector_value = dense_vector.value(0);
     let dense_vector_values = dense_vector_value
         .as_any()
         .downcast_ref::<Float32Array>()
         .unwrap();
     let expected = vec![0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8, 0.9, 1.0];
-    for (i, val) in expected.iter().enumerate() {
-        assert_eq!(dense_vector_values.value(i), *val);
-    }
+    assert_eq!(dense_vector_values.values(), &expected[..]);
     assert!(dense_vector.is_null(1));
 
-    // Verify half_vector column
+    // Verify half_vector column, a halfvec(10)
     col += 1;
     let half_vector = rb
         .column(col)
         .as_any()
-        .downcast_ref::<LargeListArray>()
+        .downcast_ref::<FixedSizeListArray>()
         .unwrap();
+    assert_eq!(half_vector.value_length(), 10);
     let half_vector_value = half_vector.value(0);
     let half_vector_values = half_vector_value
         .as_any()
-        .downcast_ref::<Float32Array>()
+        .downcast_ref::<Float16Array>()
         .unwrap();
     let expected = vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0];
     for (i, val) in expected.iter().enumerate() {
-        assert_eq!(half_vector_values.value(i), *val);
+        assert_eq!(half_vector_values.value(i).to_f32(), *val);
     }
     assert!(half_vector.is_null(1));
 
-    // Verify binary_vector column
+    // Verify binary_vector column, a bit(10) in 2 bytes
     col += 1;
     let binary_vector = rb
         .column(col)
         .as_any()
-        .downcast_ref::<LargeBinaryArray>()
+        .downcast_ref::<FixedSizeBinaryArray>()
         .unwrap();
-    let binary_vector_value = binary_vector.value(0);
-    let expected = vec![170, 128];
-    for (i, val) in expected.iter().enumerate() {
-        assert_eq!(binary_vector_value[i], *val);
-    }
+    assert_eq!(binary_vector.value_length(), 2);
+    assert_eq!(
+        rb.schema().field(col).metadata().get(BIT_LENGTH_KEY),
+        Some(&"10".to_string())
+    );
+    assert_eq!(binary_vector.value(0), &[170, 128]);
     assert!(binary_vector.is_null(1));
 
-    // Verify sparse_vector column
+    // Verify sparse_vector column, its nonzero elements at zero-based indices
     col += 1;
     let sparse_vector = rb
         .column(col)
         .as_any()
-        .downcast_ref::<LargeListArray>()
+        .downcast_ref::<StructArray>()
         .unwrap();
-    let sparse_vector_value = sparse_vector.value(0);
-    let sparse_vector_value = sparse_vector_value
+    let indices = sparse_vector.column_by_name("indices").unwrap();
+    let indices = indices
         .as_any()
-        .downcast_ref::<Float32Array>()
-        .unwrap();
-    let expected = vec![1.0, 0.0, 2.0, 0.0, 3.0];
-    for (i, val) in expected.iter().enumerate() {
-        assert_eq!(sparse_vector_value.value(i), *val);
-    }
+        .downcast_ref::<ListArray>()
+        .unwrap()
+        .value(0);
+    let indices = indices.as_any().downcast_ref::<Int32Array>().unwrap();
+    assert_eq!(indices.values(), &[0, 2, 4]);
+    let values = sparse_vector.column_by_name("values").unwrap();
+    let values = values
+        .as_any()
+        .downcast_ref::<ListArray>()
+        .unwrap()
+        .value(0);
+    let values = values.as_any().downcast_ref::<Float32Array>().unwrap();
+    assert_eq!(values.values(), &[1.0, 2.0, 3.0]);
+    let dims = sparse_vector.column_by_name("dim").unwrap();
+    let dims = dims.as_any().downcast_ref::<Int32Array>().unwrap();
+    assert_eq!(dims.value(0), 5);
     assert!(sparse_vector.is_null(1));
 }
 
@@ -1256,6 +1285,1317 @@ fn test_postgres_partitioned_pre_execution_queries() {
     );
 }
 
+#[test]
+fn test_postgres_post_execution_queries() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let dburl = env::var("POSTGRES_URL").unwrap();
+
+    let queries = [
+        "SELECT CAST(setting AS INTEGER) AS setting FROM pg_settings WHERE name = 'statement_timeout'",
+        "SELECT CAST(setting AS INTEGER) AS setting FROM pg_settings WHERE name = 'statement_timeout'",
+    ];
+    let pre_execution_queries = [String::from("SET SESSION statement_timeout = 2151")];
+    // the setting of each session is recorded after the reset, since the sessions are closed
+    // with the pool of the source
+    let post_execution_queries = [
+        String::from("RESET statement_timeout"),
+        String::from("INSERT INTO cx_post_execution SELECT current_setting('statement_timeout')"),
+    ];
+
+    let url = Url::parse(dburl.as_str()).unwrap();
+    let (config, _tls) = rewrite_tls_args(&url).unwrap();
+    let mut client = config.connect(NoTls).unwrap();
+    client
+        .batch_execute(
+            "DROP TABLE IF EXISTS cx_post_execution; CREATE TABLE cx_post_execution (setting TEXT)",
+        )
+        .unwrap();
+    let builder = PostgresSource::<BinaryProtocol, NoTls>::new(config, NoTls, 2).unwrap();
+    let mut destination = ArrowDestination::new();
+    let mut dispatcher = Dispatcher::<_, _, PostgresArrowTransport<BinaryProtocol, NoTls>>::new(
+        builder,
+        &mut destination,
+        &queries,
+        None,
+    );
+    dispatcher.set_pre_execution_queries(Some(&pre_execution_queries));
+    dispatcher.set_post_execution_queries(Some(&post_execution_queries));
+    dispatcher.run().expect("run dispatcher");
+
+    let result = destination.arrow().unwrap();
+    assert!(result.len() == 2);
+    for rb in result {
+        assert!(rb
+            .column(0)
+            .as_any()
+            .downcast_ref::<Int32Array>()
+            .unwrap()
+            .eq(&Int32Array::from(vec![2151])));
+    }
+    let settings: Vec<String> = client
+        .query("SELECT setting FROM cx_post_execution", &[])
+        .unwrap()
+        .iter()
+        .map(|row| row.get(0))
+        .collect();
+    assert_eq!(settings, vec![String::from("0"); 2]);
+    client
+        .batch_execute("DROP TABLE cx_post_execution")
+        .unwrap();
+
+    // a failing post-execution query fails the load
+    let (config, _tls) = rewrite_tls_args(&url).unwrap();
+    let builder = PostgresSource::<BinaryProtocol, NoTls>::new(config, NoTls, 2).unwrap();
+    let mut destination = ArrowDestination::new();
+    let mut dispatcher = Dispatcher::<_, _, PostgresArrowTransport<BinaryProtocol, NoTls>>::new(
+        builder,
+        &mut destination,
+        &queries,
+        None,
+    );
+    let post_execution_queries = [String::from("SELECT * FROM cx_missing_table")];
+    dispatcher.set_post_execution_queries(Some(&post_execution_queries));
+    assert!(dispatcher.run().is_err());
+}
+
+#[test]
+fn test_postgres_arrow_batch() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let dburl = env::var("POSTGRES_URL").unwrap();
+    let source_conn = SourceConn::try_from(dburl.as_str()).unwrap();
+
+    let entries = [
+        (
+            String::from("table"),
+            BatchQuery::Partitioned(PartitionQuery::new(
+                "select * from test_table",
+                "test_int",
+                None,
+                None,
+                3,
+            )),
+        ),
+        (
+            String::from("small"),
+            BatchQuery::Queries(vec![CXQuery::naked(
+                "select test_int from test_table where test_int < 2",
+            )]),
+        ),
+    ];
+    let result = get_arrow_batch(&source_conn, &entries, None, None).unwrap();
+
+    assert_eq!(result.len(), 2);
+    let nrows = |name: &str| result[name].iter().map(|rb| rb.num_rows()).sum::<usize>();
+    assert_eq!(nrows("table"), 6);
+    assert_eq!(result["table"][0].num_columns(), 5);
+    assert_eq!(nrows("small"), 2);
+    assert_eq!(result["small"][0].num_columns(), 1);
+}
+
+#[test]
+fn test_postgres_arrow_batch_waves() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let dburl = env::var("POSTGRES_URL").unwrap();
+    let source_conn = SourceConn::try_from(dburl.as_str()).unwrap();
+
+    // more partitions than the connection pool holds, so the entries are loaded in waves
+    let entries = (0..rayon::current_num_threads() + 2)
+        .map(|i| {
+            (
+                format!("table{}", i),
+                BatchQuery::Partitioned(PartitionQuery::new(
+                    "select * from test_table",
+                    "test_int",
+                    None,
+                    None,
+                    2,
+                )),
+            )
+        })
+        .collect::<Vec<_>>();
+    let result = get_arrow_batch(&source_conn, &entries, None, None).unwrap();
+
+    assert_eq!(result.len(), entries.len());
+    for (name, _) in &entries {
+        let nrows = result[name].iter().map(|rb| rb.num_rows()).sum::<usize>();
+        assert_eq!(nrows, 6);
+    }
+}
+
+#[test]
+fn test_postgres_arrow_batch_stream() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let dburl = env::var("POSTGRES_URL").unwrap();
+    let source_conn = SourceConn::try_from(dburl.as_str()).unwrap();
+
+    let entries = [
+        (
+            String::from("table"),
+            BatchQuery::Partitioned(PartitionQuery::new(
+                "select * from test_table",
+                "test_int",
+                None,
+                None,
+                3,
+            )),
+        ),
+        (
+            String::from("small"),
+            BatchQuery::Queries(vec![CXQuery::naked(
+                "select test_int from test_table where test_int < 2",
+            )]),
+        ),
+    ];
+    let mut iters = get_arrow_batch_stream(&source_conn, &entries, 2, None, None).unwrap();
+    assert_eq!(iters.len(), 2);
+
+    // the iterators are independent of each other and can be read in any order
+    for (name, nrows, ncols) in [("small", 2, 1), ("table", 6, 5)] {
+        let iter = iters.get_mut(name).unwrap();
+        iter.prepare();
+        let mut total = 0;
+        while let Some(rb) = iter.try_next_batch().unwrap() {
+            assert_eq!(rb.num_columns(), ncols);
+            total += rb.num_rows();
+        }
+        assert_eq!(total, nrows);
+    }
+}
+
+#[test]
+fn test_postgres_arrow_batch_errors() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let dburl = env::var("POSTGRES_URL").unwrap();
+    let source_conn = SourceConn::try_from(dburl.as_str()).unwrap();
+    let entry = || BatchQuery::Queries(vec![CXQuery::naked("select test_int from test_table")]);
+
+    let entries = [(String::from("a"), entry()), (String::from("a"), entry())];
+    assert!(get_arrow_batch(&source_conn, &entries, None, None).is_err());
+    assert!(get_arrow_batch_stream(&source_conn, &entries, 1024, None, None).is_err());
+
+    let mut url = Url::parse(&dburl).unwrap();
+    url.query_pairs_mut()
+        .append_pair("statement_timeout", "100");
+    let source_conn = SourceConn::try_from(url.as_str()).unwrap();
+    let entries = [
+        (String::from("a"), entry()),
+        (
+            String::from("slow"),
+            BatchQuery::Queries(vec![CXQuery::naked("SELECT 1 AS a FROM pg_sleep(5)")]),
+        ),
+    ];
+    let err = get_arrow_batch(&source_conn, &entries, None, None).err().unwrap();
+    assert!(matches!(err, ConnectorXOutError::Timeout(_)), "{}", err);
+}
+
+#[test]
+fn test_postgres_arrow_raw() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let dburl = env::var("POSTGRES_URL").unwrap();
+    let mut source_conn = SourceConn::try_from(dburl.as_str()).unwrap();
+
+    // the function lives in the temporary schema of the session the query runs in
+    let pre_execution_queries = [String::from(
+        "CREATE FUNCTION pg_temp.three_cursors() RETURNS SETOF refcursor AS $$
+        DECLARE
+            small refcursor := 'small';
+            strs refcursor := 'strs';
+            large refcursor := 'large';
+        BEGIN
+            OPEN small FOR SELECT test_int FROM test_table WHERE test_int < 2;
+            RETURN NEXT small;
+            OPEN strs FOR SELECT test_str, test_float FROM test_table;
+            RETURN NEXT strs;
+            OPEN large FOR SELECT i FROM generate_series(1, 100) AS i;
+            RETURN NEXT large;
+        END
+        $$ LANGUAGE plpgsql",
+    )];
+    let post_execution_queries = [String::from("DROP FUNCTION pg_temp.three_cursors()")];
+    let query = CXQuery::naked("SELECT * FROM pg_temp.three_cursors()");
+
+    // the cursors are read with the cursor protocol only
+    source_conn.set_protocol("binary");
+    let err = get_arrow_raw(
+        &source_conn,
+        &query,
+        Some(&pre_execution_queries),
+        Some(&post_execution_queries),
+    )
+    .err()
+    .unwrap();
+    assert!(err.to_string().contains("binary protocol"), "{}", err);
+
+    source_conn.set_protocol("cursor");
+    let sets = get_arrow_raw(
+        &source_conn,
+        &query,
+        Some(&pre_execution_queries),
+        Some(&post_execution_queries),
+    )
+    .unwrap();
+
+    let nrows = |set: &[RecordBatch]| set.iter().map(|rb| rb.num_rows()).sum::<usize>();
+    assert_eq!(sets.len(), 3);
+    assert_eq!(nrows(&sets[0]), 2);
+    assert_eq!(sets[0][0].num_columns(), 1);
+    assert_eq!(nrows(&sets[1]), 6);
+    assert_eq!(sets[1][0].num_columns(), 2);
+    assert_eq!(nrows(&sets[2]), 100);
+
+    for protocol in ["binary", "csv", "cursor", "simple"] {
+        source_conn.set_protocol(protocol);
+        let sets = get_arrow_raw(
+            &source_conn,
+            &CXQuery::naked("SELECT * FROM generate_series(1, 100)"),
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(sets.len(), 1, "{}", protocol);
+        assert_eq!(nrows(&sets[0]), 100, "{}", protocol);
+    }
+}
+
+#[test]
+fn test_postgres_catalog() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let dburl = env::var("POSTGRES_URL").unwrap();
+    let source_conn = SourceConn::try_from(dburl.as_str()).unwrap();
+
+    let catalog = get_catalog(&source_conn, None).unwrap();
+    assert!(catalog.schemas.iter().any(|s| s == "public"));
+
+    let table = catalog
+        .tables
+        .iter()
+        .find(|t| t.schema == "public" && t.name == "test_table")
+        .unwrap();
+    assert_eq!(table.kind, TableKind::Table);
+    let columns: Vec<(&str, &str, Option<DataType>)> = table
+        .columns
+        .iter()
+        .map(|c| {
+            (
+                c.name.as_str(),
+                c.source_type.as_str(),
+                c.arrow_type.clone(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        columns,
+        vec![
+            ("test_int", "Int4", Some(DataType::Int32)),
+            ("test_nullint", "Int4", Some(DataType::Int32)),
+            ("test_str", "Text", Some(DataType::Utf8)),
+            ("test_float", "Float8", Some(DataType::Float64)),
+            ("test_bool", "Bool", Some(DataType::Boolean)),
+        ]
+    );
+    // a prepared statement does not tell the columns declared `NOT NULL`
+    assert!(table.columns.iter().all(|c| c.nullable));
+
+    let catalog = get_catalog(&source_conn, Some("public")).unwrap();
+    assert_eq!(catalog.schemas, vec![String::from("public")]);
+    assert!(catalog.tables.iter().all(|t| t.schema == "public"));
+    assert!(catalog.tables.iter().any(|t| t.name == "test_table"));
+}
+
+#[test]
+fn test_postgres_arrow_schema() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let dburl = env::var("POSTGRES_URL").unwrap();
+    let source_conn = SourceConn::try_from(dburl.as_str()).unwrap();
+    let query = CXQuery::naked("SELECT test_int, test_str, test_bool FROM test_table");
+    let schema = get_arrow_schema(&source_conn, &query).unwrap();
+
+    let field = |name: &str, dt: DataType, source_type: &str, nullable: &str| {
+        Field::new(name, dt, true).with_metadata(HashMap::from([
+            (
+                String::from("connectorx.source_type"),
+                String::from(source_type),
+            ),
+            (
+                String::from("connectorx.source_nullable"),
+                String::from(nullable),
+            ),
+        ]))
+    };
+    assert_eq!(
+        schema,
+        Schema::new(vec![
+            field("test_int", DataType::Int32, "integer", "false"),
+            field("test_str", DataType::Utf8, "text", "true"),
+            field("test_bool", DataType::Boolean, "boolean", "true"),
+        ])
+    );
+
+    // the type modifiers of the columns are kept
+    let query = CXQuery::naked("SELECT test_numeric FROM test_types");
+    let schema = get_arrow_schema(&source_conn, &query).unwrap();
+    let metadata = schema.field(0).metadata();
+    assert_eq!(metadata["connectorx.source_type"], "numeric(5,2)");
+    assert_eq!(metadata["connectorx.precision"], "5");
+    assert_eq!(metadata["connectorx.scale"], "2");
+}
+
+#[test]
+fn test_postgres_table_scan() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let dburl = env::var("POSTGRES_URL").unwrap();
+    let source_conn = SourceConn::try_from(dburl.as_str()).unwrap();
+    let scan = TableScan::new("test_table")
+        .with_columns(&["test_int", "test_str"])
+        .with_filter(
+            ScanFilter::compare("test_int", CompareOp::Lt, 4).and(ScanFilter::compare(
+                "test_str",
+                CompareOp::NotEq,
+                "str1",
+            )),
+        )
+        .with_partition("test_int", 2);
+    let queries = scan_queries(&scan, &source_conn).unwrap();
+    assert_eq!(queries.len(), 2);
+
+    let destination = get_arrow(&source_conn, None, &queries, None).unwrap();
+    let mut rbs = destination.arrow().unwrap();
+    rbs.sort_by_key(|rb| {
+        rb.column(0)
+            .as_any()
+            .downcast_ref::<Int32Array>()
+            .map(|a| a.value(0))
+    });
+    let values: Vec<(i32, String)> = rbs
+        .iter()
+        .flat_map(|rb| {
+            let ints = rb.column(0).as_any().downcast_ref::<Int32Array>().unwrap();
+            let strs = rb.column(1).as_any().downcast_ref::<StringArray>().unwrap();
+            (0..rb.num_rows())
+                .map(|i| (ints.value(i), strs.value(i).to_string()))
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    assert_eq!(
+        values,
+        vec![
+            (0, String::from("a")),
+            (2, String::from("str2")),
+            (3, String::from("b"))
+        ]
+    );
+}
+
+#[test]
+fn test_postgres_table_sample() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let dburl = env::var("POSTGRES_URL").unwrap();
+    let source_conn = SourceConn::try_from(dburl.as_str()).unwrap();
+    let nrows = |scan: TableScan| -> usize {
+        let queries = scan_queries(&scan, &source_conn).unwrap();
+        let destination = get_arrow(&source_conn, None, &queries, None).unwrap();
+        destination
+            .arrow()
+            .unwrap()
+            .iter()
+            .map(|rb| rb.num_rows())
+            .sum()
+    };
+
+    let scan = TableScan::new("test_table")
+        .with_sample(Sample::fraction(1.).with_seed(7))
+        .with_partition("test_int", 2);
+    assert_eq!(nrows(scan), 6);
+
+    let scan = TableScan::new("test_table").with_sample(Sample::rows(2));
+    assert_eq!(nrows(scan), 2);
+}
+
+#[test]
+fn test_postgres_dictionary_columns() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let dburl = env::var("POSTGRES_URL").unwrap();
+    let source_conn = SourceConn::try_from(dburl.as_str()).unwrap();
+    let queries = [CXQuery::naked(
+        "SELECT test_int, test_str FROM test_table ORDER BY test_int",
+    )];
+    let mut destination = ArrowDestination::new_with_batch_size(4);
+    destination.set_dictionary_columns(&["test_str"]);
+    let (destination, _) =
+        get_arrow_into(destination, &source_conn, None, &queries, None, None, None).unwrap();
+
+    let schema = destination.arrow_schema();
+    assert_eq!(schema.field(0).data_type(), &DataType::Int32);
+    assert_eq!(
+        schema.field(1).data_type(),
+        &DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8))
+    );
+
+    let rbs = destination.arrow().unwrap();
+    assert_eq!(rbs.len(), 2);
+    let values: Vec<Option<String>> = rbs
+        .iter()
+        .flat_map(|rb| {
+            rb.column(1)
+                .as_any()
+                .downcast_ref::<DictionaryArray<Int32Type>>()
+                .unwrap()
+                .downcast_dict::<StringArray>()
+                .unwrap()
+                .into_iter()
+                .map(|v| v.map(String::from))
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    assert_eq!(
+        values,
+        vec![
+            Some(String::from("a")),
+            Some(String::from("str1")),
+            Some(String::from("str2")),
+            Some(String::from("b")),
+            Some(String::from("c")),
+            None,
+        ]
+    );
+}
+
+#[test]
+fn test_postgres_dictionary_unknown_column() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let dburl = env::var("POSTGRES_URL").unwrap();
+    let source_conn = SourceConn::try_from(dburl.as_str()).unwrap();
+    let queries = [CXQuery::naked("SELECT test_int, test_str FROM test_table")];
+    let mut destination = ArrowDestination::new();
+    destination.set_dictionary_columns(&["test_string"]);
+    let result = get_arrow_into(destination, &source_conn, None, &queries, None, None, None);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_postgres_view_types() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let dburl = env::var("POSTGRES_URL").unwrap();
+    for protocol in ["binary", "csv", "cursor"] {
+        let mut source_conn = SourceConn::try_from(dburl.as_str()).unwrap();
+        source_conn.set_protocol(protocol);
+        let queries = [CXQuery::naked(
+            "SELECT test_str, test_str::bytea AS test_bytes FROM test_table ORDER BY test_int",
+        )];
+        let mut destination = ArrowDestination::new();
+        destination.set_view_types(true);
+        let (destination, _) =
+            get_arrow_into(destination, &source_conn, None, &queries, None, None, None).unwrap();
+
+        let schema = destination.arrow_schema();
+        assert_eq!(schema.field(0).data_type(), &DataType::Utf8View);
+        assert_eq!(schema.field(1).data_type(), &DataType::BinaryView);
+
+        let rbs = destination.arrow().unwrap();
+        assert_eq!(rbs.len(), 1);
+        let strs: Vec<Option<&str>> = rbs[0]
+            .column(0)
+            .as_any()
+            .downcast_ref::<StringViewArray>()
+            .unwrap()
+            .iter()
+            .collect();
+        assert_eq!(
+            strs,
+            vec![
+                Some("a"),
+                Some("str1"),
+                Some("str2"),
+                Some("b"),
+                Some("c"),
+                None
+            ]
+        );
+        let bytes: Vec<Option<&[u8]>> = rbs[0]
+            .column(1)
+            .as_any()
+            .downcast_ref::<BinaryViewArray>()
+            .unwrap()
+            .iter()
+            .collect();
+        assert_eq!(
+            bytes,
+            vec![
+                Some(&b"a"[..]),
+                Some(&b"str1"[..]),
+                Some(&b"str2"[..]),
+                Some(&b"b"[..]),
+                Some(&b"c"[..]),
+                None
+            ]
+        );
+    }
+}
+
+#[test]
+fn test_postgres_extension_types() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let dburl = env::var("POSTGRES_URL").unwrap();
+    for protocol in ["binary", "csv", "cursor"] {
+        let mut source_conn = SourceConn::try_from(dburl.as_str()).unwrap();
+        source_conn.set_protocol(protocol);
+        let queries = [CXQuery::naked(
+            "SELECT u::uuid AS test_uuid, j::jsonb AS test_jsonb FROM (VALUES \
+             ('86b494cc-96b2-11eb-9298-3e22fbb9fe9d', '{\"a\": 1}'), (NULL, NULL)) t(u, j)",
+        )];
+        let mut destination = ArrowDestination::new();
+        destination.set_extension_types(true);
+        let (destination, _) =
+            get_arrow_into(destination, &source_conn, None, &queries, None, None, None).unwrap();
+
+        let schema = destination.arrow_schema();
+        assert_eq!(schema.field(0).data_type(), &DataType::FixedSizeBinary(16));
+        assert_eq!(
+            schema.field(0).metadata().get("ARROW:extension:name"),
+            Some(&String::from("arrow.uuid"))
+        );
+        assert_eq!(schema.field(1).data_type(), &DataType::Utf8);
+        assert_eq!(
+            schema.field(1).metadata().get("ARROW:extension:name"),
+            Some(&String::from("arrow.json"))
+        );
+
+        let rbs = destination.arrow().unwrap();
+        assert_eq!(rbs.len(), 1);
+        let uuids: Vec<Option<&[u8]>> = rbs[0]
+            .column(0)
+            .as_any()
+            .downcast_ref::<FixedSizeBinaryArray>()
+            .unwrap()
+            .iter()
+            .collect();
+        assert_eq!(
+            uuids,
+            vec![
+                Some(
+                    &[
+                        0x86, 0xb4, 0x94, 0xcc, 0x96, 0xb2, 0x11, 0xeb, 0x92, 0x98, 0x3e, 0x22,
+                        0xfb, 0xb9, 0xfe, 0x9d
+                    ][..]
+                ),
+                None
+            ]
+        );
+        let jsons: Vec<Option<&str>> = rbs[0]
+            .column(1)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap()
+            .iter()
+            .collect();
+        assert_eq!(jsons, vec![Some(r#"{"a":1}"#), None]);
+    }
+}
+
+#[test]
+fn test_postgres_source_metadata() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let dburl = env::var("POSTGRES_URL").unwrap();
+    let source_conn = SourceConn::try_from(dburl.as_str()).unwrap();
+    let query = "SELECT test_numeric, test_varchar, test_timestamptz, test_jsonb, test_int, \
+                 test_numeric * 2 AS test_expr FROM test_types, test_table";
+    let queries = [CXQuery::naked(query)];
+    let mut destination = ArrowDestination::new();
+    destination.set_source_metadata(true);
+    let (destination, _) =
+        get_arrow_into(destination, &source_conn, None, &queries, None, None, None).unwrap();
+
+    let schema = destination.arrow_schema();
+    assert_eq!(
+        schema.metadata(),
+        &HashMap::from([
+            (String::from("connectorx.source"), String::from("postgres")),
+            (String::from("connectorx.query"), String::from(query)),
+        ])
+    );
+    let metadata = |pairs: &[(&str, &str)]| -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    };
+    let expected = [
+        metadata(&[
+            ("connectorx.source_type", "numeric(5,2)"),
+            ("connectorx.source_nullable", "true"),
+            ("connectorx.precision", "5"),
+            ("connectorx.scale", "2"),
+        ]),
+        metadata(&[
+            ("connectorx.source_type", "character varying(10)"),
+            ("connectorx.source_nullable", "true"),
+            ("connectorx.length", "10"),
+        ]),
+        metadata(&[
+            ("connectorx.source_type", "timestamp with time zone"),
+            ("connectorx.source_nullable", "true"),
+        ]),
+        metadata(&[
+            ("connectorx.source_type", "jsonb"),
+            ("connectorx.source_nullable", "true"),
+        ]),
+        metadata(&[
+            ("connectorx.source_type", "integer"),
+            ("connectorx.source_nullable", "false"),
+        ]),
+        // the type modifier and the constraints of expressions are unknown
+        metadata(&[
+            ("connectorx.source_type", "numeric"),
+            ("connectorx.source_nullable", "true"),
+        ]),
+    ];
+    for (field, expected) in schema.fields().iter().zip(expected) {
+        assert_eq!(field.metadata(), &expected, "{}", field.name());
+    }
+}
+
+#[test]
+fn test_postgres_timestamp_options() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let dburl = env::var("POSTGRES_URL").unwrap();
+    let queries = [CXQuery::naked(
+        "SELECT '2021-01-01 12:00:00+00'::timestamptz AS test_timestamptz, \
+         '2021-01-01 12:00:00'::timestamp AS test_timestamp",
+    )];
+    let source_conn = SourceConn::try_from(dburl.as_str()).unwrap();
+    let mut destination = ArrowDestination::new();
+    destination.set_timestamp_unit(TimeUnit::Millisecond);
+    destination.set_time_zone("Europe/Berlin");
+    destination.set_naive_time_zone("Europe/Berlin");
+    let (destination, _) =
+        get_arrow_into(destination, &source_conn, None, &queries, None, None, None).unwrap();
+
+    let schema = destination.arrow_schema();
+    let expected = DataType::Timestamp(TimeUnit::Millisecond, Some("Europe/Berlin".into()));
+    assert_eq!(schema.field(0).data_type(), &expected);
+    assert_eq!(schema.field(1).data_type(), &expected);
+    let rbs = destination.arrow().unwrap();
+    let value = |i: usize| {
+        rbs[0]
+            .column(i)
+            .as_any()
+            .downcast_ref::<TimestampMillisecondArray>()
+            .unwrap()
+            .value(0)
+    };
+    // 12:00 UTC, and 12:00 in Berlin which is 11:00 UTC
+    assert_eq!(value(0), 1_609_502_400_000);
+    assert_eq!(value(1), 1_609_498_800_000);
+
+    // the time zone of the session is set by the connection options
+    let mut url = Url::parse(dburl.as_str()).unwrap();
+    url.query_pairs_mut()
+        .append_pair("options", "-cTimeZone=Asia/Tokyo");
+    let source_conn = SourceConn::try_from(url.as_str()).unwrap();
+    let mut destination = ArrowDestination::new();
+    destination.set_session_time_zone(true);
+    let (destination, _) =
+        get_arrow_into(destination, &source_conn, None, &queries, None, None, None).unwrap();
+
+    let schema = destination.arrow_schema();
+    assert_eq!(
+        schema.field(0).data_type(),
+        &DataType::Timestamp(TimeUnit::Microsecond, Some("Asia/Tokyo".into()))
+    );
+    assert_eq!(
+        schema.field(1).data_type(),
+        &DataType::Timestamp(TimeUnit::Microsecond, None)
+    );
+    let rbs = destination.arrow().unwrap();
+    let instant = rbs[0]
+        .column(0)
+        .as_any()
+        .downcast_ref::<TimestampMicrosecondArray>()
+        .unwrap()
+        .value(0);
+    assert_eq!(instant, 1_609_502_400_000_000);
+
+    // or by the pre-execution queries
+    let source_conn = SourceConn::try_from(dburl.as_str()).unwrap();
+    let pre_execution_queries = [String::from("SET TimeZone = 'America/New_York'")];
+    let mut destination = ArrowDestination::new();
+    destination.set_session_time_zone(true);
+    let (destination, _) = get_arrow_into(
+        destination,
+        &source_conn,
+        None,
+        &queries,
+        Some(&pre_execution_queries),
+        None,
+        None,
+    )
+    .unwrap();
+    assert_eq!(
+        destination.arrow_schema().field(0).data_type(),
+        &DataType::Timestamp(TimeUnit::Microsecond, Some("America/New_York".into()))
+    );
+
+    // a POSIX time zone is not known to arrow, UTC is used instead
+    let pre_execution_queries = [String::from("SET TimeZone = '<+03>-03'")];
+    let mut destination = ArrowDestination::new();
+    destination.set_session_time_zone(true);
+    let (destination, _) = get_arrow_into(
+        destination,
+        &source_conn,
+        None,
+        &queries,
+        Some(&pre_execution_queries),
+        None,
+        None,
+    )
+    .unwrap();
+    assert_eq!(
+        destination.arrow_schema().field(0).data_type(),
+        &DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into()))
+    );
+    let rbs = destination.arrow().unwrap();
+    let instant = rbs[0]
+        .column(0)
+        .as_any()
+        .downcast_ref::<TimestampMicrosecondArray>()
+        .unwrap()
+        .value(0);
+    assert_eq!(instant, 1_609_502_400_000_000);
+}
+
+#[test]
+fn test_postgres_describe_columns_post_execution_queries() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let dburl = env::var("POSTGRES_URL").unwrap();
+    let url = Url::parse(dburl.as_str()).unwrap();
+    let (config, _tls) = rewrite_tls_args(&url).unwrap();
+    // one connection, so the forked source below gets the one the columns were described on
+    let mut source = PostgresSource::<BinaryProtocol, NoTls>::new(config, NoTls, 1).unwrap();
+    source.set_pre_execution_queries(Some(&[String::from("SET TimeZone = 'America/New_York'")]));
+    source.set_post_execution_queries(Some(&[String::from("SET TimeZone = 'Asia/Tokyo'")]));
+    source.set_queries(&[CXQuery::naked(
+        "SELECT '2021-01-01 12:00:00+00'::timestamptz AS test_timestamptz",
+    )]);
+    source.fetch_metadata().unwrap();
+    let columns = source.describe_columns().unwrap();
+    assert_eq!(columns[0].time_zone.as_deref(), Some("America/New_York"));
+
+    // the post-execution queries ran before the connection went back to the pool
+    let mut fork = source.fork();
+    fork.set_pre_execution_queries(None);
+    fork.set_post_execution_queries(None);
+    let queries = [CXQuery::naked(
+        "SELECT current_setting('TimeZone') AS time_zone",
+    )];
+    let mut destination = ArrowDestination::new();
+    let dispatcher = Dispatcher::<_, _, PostgresArrowTransport<BinaryProtocol, NoTls>>::new(
+        fork,
+        &mut destination,
+        &queries,
+        None,
+    );
+    dispatcher.run().unwrap();
+    let rbs = destination.arrow().unwrap();
+    let time_zone = rbs[0]
+        .column(0)
+        .as_any()
+        .downcast_ref::<StringArray>()
+        .unwrap()
+        .value(0)
+        .to_string();
+    assert_eq!(time_zone, "Asia/Tokyo");
+}
+
+#[test]
+fn test_postgres_interval() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let dburl = env::var("POSTGRES_URL").unwrap();
+    for protocol in ["binary", "csv", "cursor", "simple"] {
+        let mut source_conn = SourceConn::try_from(dburl.as_str()).unwrap();
+        source_conn.set_protocol(protocol);
+        let queries = [CXQuery::naked(
+            "SELECT n, i::interval AS test_interval FROM (VALUES (1, '1 year 2 mons 3 days 04:05:06.789'), \
+             (2, '-1 years -2 mons +3 days -04:05:06'), (3, '178956970 years 7 mons 100000 hours'), \
+             (4, '-1 day +00:00:00.000001'), (5, NULL)) t(n, i) ORDER BY n",
+        )];
+        let rbs = get_arrow(&source_conn, None, &queries, None)
+            .unwrap()
+            .arrow()
+            .unwrap();
+        assert_eq!(
+            rbs[0].schema().field(1).data_type(),
+            &DataType::Interval(IntervalUnit::MonthDayNano)
+        );
+        let values: Vec<Option<IntervalMonthDayNano>> = rbs
+            .iter()
+            .flat_map(|rb| {
+                rb.column(1)
+                    .as_any()
+                    .downcast_ref::<IntervalMonthDayNanoArray>()
+                    .unwrap()
+                    .iter()
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        assert_eq!(
+            values,
+            vec![
+                Some(IntervalMonthDayNano::new(14, 3, 14_706_789_000_000)),
+                Some(IntervalMonthDayNano::new(-14, 3, -14_706_000_000_000)),
+                Some(IntervalMonthDayNano::new(
+                    2_147_483_647,
+                    0,
+                    360_000_000_000_000_000
+                )),
+                Some(IntervalMonthDayNano::new(0, -1, 1_000)),
+                None,
+            ],
+            "{}",
+            protocol
+        );
+    }
+}
+
+#[test]
+fn test_postgres_arrays() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let dburl = env::var("POSTGRES_URL").unwrap();
+    for protocol in ["binary", "csv", "cursor", "simple"] {
+        let mut source_conn = SourceConn::try_from(dburl.as_str()).unwrap();
+        source_conn.set_protocol(protocol);
+        let queries = [CXQuery::naked(
+            "SELECT n, d::date[] AS test_date, b::bytea[] AS test_bytea, u::uuid[] AS test_uuid, \
+             j::jsonb[] AS test_jsonb, e::happiness[] AS test_enum, m::int4[] AS test_matrix FROM (VALUES \
+             (1, ARRAY['2021-01-01', NULL], ARRAY['\\x0102'], ARRAY['a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11'], \
+             ARRAY['{\"a\": 1}'], ARRAY['happy', 'very happy'], ARRAY[[1, 2], [3, NULL]]), \
+             (2, '{}', '{}', '{}', '{}', '{}', '{}'), \
+             (3, NULL, NULL, NULL, NULL, NULL, NULL)) t(n, d, b, u, j, e, m) ORDER BY n",
+        )];
+        let rbs = get_arrow(&source_conn, None, &queries, None)
+            .unwrap()
+            .arrow()
+            .unwrap();
+        let rb = &rbs[0];
+        assert_eq!(rb.num_rows(), 3, "{}", protocol);
+        let list = |i: usize| {
+            rb.column(i)
+                .as_any()
+                .downcast_ref::<LargeListArray>()
+                .unwrap()
+        };
+        for i in 1..rb.num_columns() {
+            assert!(list(i).is_valid(0) && list(i).is_valid(1), "{}", protocol);
+            assert_eq!(list(i).value(1).len(), 0, "{}", protocol);
+            assert!(list(i).is_null(2), "{}", protocol);
+        }
+
+        let dates = list(1).value(0);
+        let dates = dates.as_any().downcast_ref::<Date32Array>().unwrap();
+        assert_eq!(dates.iter().collect::<Vec<_>>(), vec![Some(18628), None]);
+        let bytes = list(2).value(0);
+        let bytes = bytes.as_any().downcast_ref::<BinaryArray>().unwrap();
+        assert_eq!(bytes.value(0), &[1, 2]);
+        let uuids = list(3).value(0);
+        let uuids = uuids.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(uuids.value(0), "a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11");
+        let jsons = list(4).value(0);
+        let jsons = jsons.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(jsons.value(0), "{\"a\":1}");
+        let labels = list(5).value(0);
+        let labels = labels.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(
+            labels.iter().collect::<Vec<_>>(),
+            vec![Some("happy"), Some("very happy")]
+        );
+
+        // multidimensional arrays are nested one list per dimension
+        assert_eq!(
+            rb.schema().field(6).data_type(),
+            &DataType::LargeList(Arc::new(Field::new(
+                "item",
+                DataType::LargeList(Arc::new(Field::new("item", DataType::Int32, true))),
+                true
+            )))
+        );
+        let matrix = list(6).value(0);
+        let matrix = matrix.as_any().downcast_ref::<LargeListArray>().unwrap();
+        assert_eq!(matrix.len(), 2);
+        let rows: Vec<Vec<Option<i32>>> = matrix
+            .iter()
+            .map(|row| {
+                row.unwrap()
+                    .as_any()
+                    .downcast_ref::<Int32Array>()
+                    .unwrap()
+                    .iter()
+                    .collect()
+            })
+            .collect();
+        assert_eq!(rows, vec![vec![Some(1), Some(2)], vec![Some(3), None]]);
+    }
+}
+
+#[test]
+fn test_postgres_temporal_arrays() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let dburl = env::var("POSTGRES_URL").unwrap();
+    // the infinite timestamps are only read from the text format of the arrays
+    for (protocol, infinite) in [
+        ("binary", false),
+        ("csv", true),
+        ("cursor", false),
+        ("simple", true),
+    ] {
+        let mut source_conn = SourceConn::try_from(dburl.as_str()).unwrap();
+        source_conn.set_protocol(protocol);
+        let (ts, tz) = match infinite {
+            true => ("'infinity', '-infinity'", "'infinity'"),
+            false => ("'2021-01-02 00:00:00', '2021-01-03 00:00:00'", "NULL"),
+        };
+        let queries = [CXQuery::naked(format!(
+            "SELECT ARRAY['2021-01-01 12:00:00', {}]::timestamp[] AS test_ts, \
+             ARRAY['2021-01-01 12:00:00+00', {}]::timestamptz[] AS test_tz, \
+             ARRAY['12:34:56.789', NULL]::time[] AS test_time",
+            ts, tz
+        ))];
+        let rbs = get_arrow(&source_conn, None, &queries, None)
+            .unwrap()
+            .arrow()
+            .unwrap();
+        let rb = &rbs[0];
+        let item = |i: usize| match rb.schema().field(i).data_type() {
+            DataType::LargeList(item) => item.data_type().clone(),
+            ty => panic!("{} is not a list", ty),
+        };
+        assert_eq!(
+            item(0),
+            DataType::Timestamp(TimeUnit::Microsecond, None),
+            "{}",
+            protocol
+        );
+        assert_eq!(
+            item(1),
+            DataType::Timestamp(TimeUnit::Microsecond, Some("+00:00".into())),
+            "{}",
+            protocol
+        );
+        assert_eq!(
+            item(2),
+            DataType::Time64(TimeUnit::Microsecond),
+            "{}",
+            protocol
+        );
+
+        let values = |i: usize| {
+            let list = rb
+                .column(i)
+                .as_any()
+                .downcast_ref::<LargeListArray>()
+                .unwrap()
+                .value(0);
+            match list.data_type() {
+                DataType::Time64(_) => list
+                    .as_any()
+                    .downcast_ref::<Time64MicrosecondArray>()
+                    .unwrap()
+                    .iter()
+                    .collect::<Vec<_>>(),
+                _ => list
+                    .as_any()
+                    .downcast_ref::<TimestampMicrosecondArray>()
+                    .unwrap()
+                    .iter()
+                    .collect::<Vec<_>>(),
+            }
+        };
+        let (ts, tz) = match infinite {
+            true => (
+                vec![
+                    Some(NaiveDateTime::MAX.and_utc().timestamp_micros()),
+                    Some(NaiveDateTime::MIN.and_utc().timestamp_micros()),
+                ],
+                Some(DateTime::<Utc>::MAX_UTC.timestamp_micros()),
+            ),
+            false => (
+                vec![Some(1_609_545_600_000_000), Some(1_609_632_000_000_000)],
+                None,
+            ),
+        };
+        assert_eq!(
+            values(0),
+            [vec![Some(1_609_502_400_000_000)], ts].concat(),
+            "{}",
+            protocol
+        );
+        assert_eq!(
+            values(1),
+            vec![Some(1_609_502_400_000_000), tz],
+            "{}",
+            protocol
+        );
+        assert_eq!(values(2), vec![Some(45_296_789_000), None], "{}", protocol);
+    }
+}
+
+#[test]
+fn test_postgres_array_depth() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let dburl = env::var("POSTGRES_URL").unwrap();
+    let source_conn = SourceConn::try_from(dburl.as_str()).unwrap();
+    let matrix = DataType::LargeList(Arc::new(Field::new(
+        "item",
+        DataType::LargeList(Arc::new(Field::new("item", DataType::Int32, true))),
+        true,
+    )));
+
+    // the depth of the first array holds for the batches without any array as well
+    let queries = [CXQuery::naked(
+        "SELECT m::int4[] AS test_matrix FROM (VALUES (1, ARRAY[[1, 2], [3, 4]]), (2, NULL), \
+         (3, '{}')) t(n, m) ORDER BY n",
+    )];
+    let destination = ArrowDestination::new_with_batch_size(1);
+    let (destination, _) =
+        get_arrow_into(destination, &source_conn, None, &queries, None, None, None).unwrap();
+    assert_eq!(destination.arrow_schema().field(0).data_type(), &matrix);
+    let rbs = destination.arrow().unwrap();
+    assert_eq!(rbs.iter().map(|rb| rb.num_rows()).sum::<usize>(), 3);
+    for rb in &rbs {
+        assert_eq!(rb.schema().field(0).data_type(), &matrix);
+        assert_eq!(rb.column(0).data_type(), &matrix);
+    }
+
+    // arrays of another depth in a later batch are rejected
+    let queries = [CXQuery::naked(
+        "SELECT m::int4[] AS test_matrix FROM (VALUES (1, ARRAY[[1, 2], [3, 4]]), \
+         (2, ARRAY[5, 6])) t(n, m) ORDER BY n",
+    )];
+    let destination = ArrowDestination::new_with_batch_size(1);
+    let result = get_arrow_into(destination, &source_conn, None, &queries, None, None, None);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_postgres_composite() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let dburl = env::var("POSTGRES_URL").unwrap();
+    for protocol in ["binary", "cursor"] {
+        let mut source_conn = SourceConn::try_from(dburl.as_str()).unwrap();
+        source_conn.set_protocol(protocol);
+        let queries = [CXQuery::naked(
+            "SELECT n, s AS test_shape FROM (VALUES \
+             (1, ROW('square', ROW(1, 0.5)::test_point, ARRAY[ROW(2, NULL)::test_point, NULL], \
+             'happy', '2021-01-01 00:00:00+00')::test_shape), \
+             (2, NULL), (3, ROW(NULL, NULL, NULL, NULL, NULL)::test_shape)) t(n, s) ORDER BY n",
+        )];
+        let rbs = get_arrow(&source_conn, None, &queries, None)
+            .unwrap()
+            .arrow()
+            .unwrap();
+        let point = DataType::Struct(
+            vec![
+                Field::new("x", DataType::Int32, true),
+                Field::new("y", DataType::Float64, true),
+            ]
+            .into(),
+        );
+        assert_eq!(
+            rbs[0].schema().field(1).data_type(),
+            &DataType::Struct(
+                vec![
+                    Field::new("name", DataType::Utf8, true),
+                    Field::new("origin", point.clone(), true),
+                    Field::new(
+                        "corners",
+                        DataType::LargeList(Arc::new(Field::new("item", point, true))),
+                        true
+                    ),
+                    Field::new("mood", DataType::Utf8, true),
+                    Field::new(
+                        "created",
+                        DataType::Timestamp(TimeUnit::Microsecond, Some("+00:00".into())),
+                        true
+                    ),
+                ]
+                .into()
+            ),
+            "{}",
+            protocol
+        );
+
+        let shapes = rbs[0]
+            .column(1)
+            .as_any()
+            .downcast_ref::<StructArray>()
+            .unwrap();
+        assert!(shapes.is_valid(0) && shapes.is_null(1) && shapes.is_valid(2));
+        let names = shapes.column_by_name("name").unwrap();
+        let names = names.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(
+            names.iter().collect::<Vec<_>>(),
+            vec![Some("square"), None, None]
+        );
+        let origins = shapes.column_by_name("origin").unwrap();
+        let origins = origins.as_any().downcast_ref::<StructArray>().unwrap();
+        assert!(origins.is_valid(0) && origins.is_null(2));
+        let y = origins.column_by_name("y").unwrap();
+        let y = y.as_any().downcast_ref::<Float64Array>().unwrap();
+        assert_eq!(y.value(0), 0.5);
+        let corners = shapes.column_by_name("corners").unwrap();
+        let corners = corners.as_any().downcast_ref::<LargeListArray>().unwrap();
+        let corners = corners.value(0);
+        let corners = corners.as_any().downcast_ref::<StructArray>().unwrap();
+        assert!(corners.is_valid(0) && corners.is_null(1));
+        let x = corners.column_by_name("x").unwrap();
+        let x = x.as_any().downcast_ref::<Int32Array>().unwrap();
+        assert_eq!(x.value(0), 2);
+        let created = shapes.column_by_name("created").unwrap();
+        let created = created
+            .as_any()
+            .downcast_ref::<TimestampMicrosecondArray>()
+            .unwrap();
+        assert_eq!(created.value(0), 1_609_459_200_000_000);
+    }
+}
+
+#[test]
+fn test_postgres_record() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let dburl = env::var("POSTGRES_URL").unwrap();
+    // the fields of an uncast row are only known from the values, so it cannot be loaded
+    for protocol in ["binary", "csv", "cursor", "simple"] {
+        let mut source_conn = SourceConn::try_from(dburl.as_str()).unwrap();
+        source_conn.set_protocol(protocol);
+        let queries = [CXQuery::naked("SELECT ROW(1, 'a') AS test_record")];
+        let err = get_arrow(&source_conn, None, &queries, None).err().unwrap();
+        assert!(
+            err.to_string()
+                .contains("Column type record is not supported"),
+            "{}: {}",
+            protocol,
+            err
+        );
+    }
+}
+
+#[test]
+fn test_postgres_hstore() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let dburl = env::var("POSTGRES_URL").unwrap();
+    for protocol in ["binary", "csv", "cursor", "simple"] {
+        let mut source_conn = SourceConn::try_from(dburl.as_str()).unwrap();
+        source_conn.set_protocol(protocol);
+        let queries = [CXQuery::naked(
+            "SELECT n, h::hstore AS test_hstore FROM (VALUES \
+             (1, 'b=>NULL, a=>1, \"c d\"=>\"x\\\"y\"'), (2, NULL), (3, 'e=>\"\"')) t(n, h) \
+             ORDER BY n",
+        )];
+        let rbs = get_arrow(&source_conn, None, &queries, None)
+            .unwrap()
+            .arrow()
+            .unwrap();
+        let maps = rbs[0]
+            .column(1)
+            .as_any()
+            .downcast_ref::<MapArray>()
+            .unwrap();
+        assert!(maps.is_valid(0) && maps.is_null(1) && maps.is_valid(2));
+
+        let entries: Vec<Vec<(String, Option<String>)>> = [0, 2]
+            .iter()
+            .map(|&i| {
+                let entries = maps.value(i);
+                let keys = entries.column(0).as_any().downcast_ref::<StringArray>();
+                let values = entries.column(1).as_any().downcast_ref::<StringArray>();
+                keys.unwrap()
+                    .iter()
+                    .zip(values.unwrap().iter())
+                    .map(|(k, v)| (k.unwrap().to_string(), v.map(|v| v.to_string())))
+                    .collect()
+            })
+            .collect();
+        assert_eq!(
+            entries,
+            vec![
+                vec![
+                    ("a".to_string(), Some("1".to_string())),
+                    ("b".to_string(), None),
+                    ("c d".to_string(), Some("x\"y".to_string())),
+                ],
+                vec![("e".to_string(), Some("".to_string()))],
+            ],
+            "{}",
+            protocol
+        );
+    }
+}
+
+#[test]
+fn test_postgres_json_maps() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let dburl = env::var("POSTGRES_URL").unwrap();
+    let source_conn = SourceConn::try_from(dburl.as_str()).unwrap();
+    let queries = [CXQuery::naked(
+        "SELECT n, j::jsonb AS test_json FROM (VALUES \
+         (1, '{\"b\": 2, \"a\": null}'), (2, NULL), (3, '{}')) t(n, j) ORDER BY n",
+    )];
+    let mut destination = ArrowDestination::new();
+    destination.set_json_maps(DataType::Int64).unwrap();
+    let (destination, _) =
+        get_arrow_into(destination, &source_conn, None, &queries, None, None, None).unwrap();
+    let rbs = destination.arrow().unwrap();
+    let maps = rbs[0]
+        .column(1)
+        .as_any()
+        .downcast_ref::<MapArray>()
+        .unwrap();
+    assert!(maps.is_valid(0) && maps.is_null(1) && maps.is_valid(2));
+    assert_eq!(maps.value_length(2), 0);
+    let entries = maps.value(0);
+    let keys = entries.column(0).as_any().downcast_ref::<StringArray>();
+    assert_eq!(
+        keys.unwrap().iter().collect::<Vec<_>>(),
+        vec![Some("a"), Some("b")]
+    );
+    let values = entries.column(1).as_any().downcast_ref::<Int64Array>();
+    assert_eq!(
+        values.unwrap().iter().collect::<Vec<_>>(),
+        vec![None, Some(2)]
+    );
+
+    // values of another type fail the load
+    let queries = [CXQuery::naked(
+        "SELECT '{\"a\": \"x\"}'::jsonb AS test_json",
+    )];
+    let mut destination = ArrowDestination::new();
+    destination.set_json_maps(DataType::Int64).unwrap();
+    assert!(get_arrow_into(destination, &source_conn, None, &queries, None, None, None).is_err());
+}
+
 fn build_decimal_array(vals: Vec<Option<i128>>) -> Decimal128Array {
     let mut builder = Decimal128Builder::new()
         .with_precision_and_scale(38, 10)
@@ -1270,3 +2610,91 @@ fn build_decimal_array(vals: Vec<Option<i128>>) -> Decimal128Array {
 
     builder.finish()
 }
+
+#[test]
+fn test_postgres_statement_timeout() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let mut url = Url::parse(&env::var("POSTGRES_URL").unwrap()).unwrap();
+    url.query_pairs_mut()
+        .append_pair("statement_timeout", "100");
+    let source_conn = SourceConn::try_from(url.as_str()).unwrap();
+    let queries = [CXQuery::naked("SELECT 1 AS a FROM pg_sleep(5)")];
+
+    let err = get_arrow(&source_conn, None, &queries, None).err().unwrap();
+    assert!(matches!(err, ConnectorXOutError::Timeout(_)), "{}", err);
+
+    // the query only runs once the iterator is prepared
+    let mut iter =
+        try_new_record_batch_iter(&source_conn, None, &queries, 1024, None, None).unwrap();
+    iter.prepare();
+    let err = iter.try_next_batch().err().unwrap();
+    assert!(matches!(err, ConnectorXOutError::Timeout(_)), "{}", err);
+
+    // other errors are left untouched
+    let queries = [CXQuery::naked("SELECT * FROM cx_missing_table")];
+    let err = get_arrow(&source_conn, None, &queries, None).err().unwrap();
+    assert!(!matches!(err, ConnectorXOutError::Timeout(_)), "{}", err);
+}
+
+#[test]
+fn test_postgres_bound_params() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let dburl = env::var("POSTGRES_URL").unwrap();
+    // the copy based and simple protocols inline the parameters as literals
+    for protocol in ["binary", "csv", "cursor", "simple"] {
+        let mut source_conn = SourceConn::try_from(dburl.as_str()).unwrap();
+        source_conn.set_protocol(protocol);
+        let queries = [CXQuery::naked(
+            "SELECT $1::text AS s, $3::int8 AS i, '$2' AS q, $2::bytea AS b FROM test_table \
+             WHERE test_int < $3 ORDER BY test_int",
+        )
+        .bind(vec![
+            CXParam::from("it's a \\ $1"),
+            CXParam::from(vec![0u8, 255]),
+            CXParam::from(2),
+        ])];
+        let rbs = get_arrow(&source_conn, None, &queries, None)
+            .unwrap()
+            .arrow()
+            .unwrap();
+        let rows: Vec<(String, i64, String, Vec<u8>)> = rbs
+            .iter()
+            .flat_map(|rb| {
+                let s = rb.column(0).as_any().downcast_ref::<StringArray>().unwrap();
+                let i = rb.column(1).as_any().downcast_ref::<Int64Array>().unwrap();
+                let q = rb.column(2).as_any().downcast_ref::<StringArray>().unwrap();
+                let b = rb
+                    .column(3)
+                    .as_any()
+                    .downcast_ref::<LargeBinaryArray>()
+                    .unwrap();
+                (0..rb.num_rows())
+                    .map(|r| {
+                        (
+                            s.value(r).to_string(),
+                            i.value(r),
+                            q.value(r).to_string(),
+                            b.value(r).to_vec(),
+                        )
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                (
+                    String::from("it's a \\ $1"),
+                    2,
+                    String::from("$2"),
+                    vec![0, 255]
+                );
+                2
+            ],
+            "{}",
+            protocol
+        );
+    }
+}
@@ -1,12 +1,37 @@
 use arrow::{
-    array::{Float64Array, Int64Array},
+    array::{DurationMicrosecondArray, Float64Array, Int64Array, IntervalMonthDayNanoArray},
+    datatypes::IntervalMonthDayNano,
     record_batch::RecordBatch,
 };
 use connectorx::{
-    destinations::arrow::ArrowDestination, prelude::*, sources::trino::TrinoSource, sql::CXQuery,
+    destinations::arrow::ArrowDestination,
+    prelude::*,
+    sources::trino::{TrinoSource, TrinoTypeSystem},
+    sql::CXQuery,
     transports::TrinoArrowTransport,
 };
-use std::{env, sync::Arc};
+use prusto::PrestoTy;
+use std::{convert::TryFrom, env, sync::Arc};
+
+#[test]
+fn test_trino_type_names() {
+    use TrinoTypeSystem::*;
+
+    // the declared type wins over the type of the value
+    let ty = |name| TrinoTypeSystem::try_from((Some(name), PrestoTy::Unknown)).ok();
+    assert_eq!(ty("integer"), Some(Integer(true)));
+    assert_eq!(ty("BIGINT"), Some(Bigint(true)));
+    assert_eq!(ty("real"), Some(Real(true)));
+    assert_eq!(ty("varchar"), Some(Varchar(true)));
+    assert_eq!(ty("json"), Some(Varchar(true)));
+    assert_eq!(ty("decimal"), Some(Double(true)));
+    assert_eq!(ty("timestamp"), Some(Timestamp(true)));
+    assert_eq!(
+        ty("interval day to second"),
+        Some(IntervalDayToSecond(true))
+    );
+    assert_eq!(ty("hyperloglog"), None);
+}
 
 #[test]
 #[ignore]
@@ -60,6 +85,47 @@ fn test_trino_text() {
     verify_arrow_results(result);
 }
 
+#[test]
+#[ignore]
+fn test_trino_interval() {
+    let _ = env_logger::builder().is_test(true).try_init();
+
+    let dburl = env::var("TRINO_URL").unwrap();
+
+    let queries = [CXQuery::naked(
+        "select interval '2' day + interval '03:04:05.678' hour to second as ds, \
+         interval '-1-2' year to month as ym \
+         union all select interval '-100000' hour, interval '178956970' year",
+    )];
+
+    let rt = Arc::new(tokio::runtime::Runtime::new().expect("Failed to create runtime"));
+    let builder = TrinoSource::new(rt, &dburl).unwrap();
+    let mut destination = ArrowDestination::new();
+    let dispatcher =
+        Dispatcher::<_, _, TrinoArrowTransport>::new(builder, &mut destination, &queries, None);
+    dispatcher.run().unwrap();
+
+    let result = destination.arrow().unwrap();
+    assert!(result[0]
+        .column(0)
+        .as_any()
+        .downcast_ref::<DurationMicrosecondArray>()
+        .unwrap()
+        .eq(&DurationMicrosecondArray::from(vec![
+            183_845_678_000,
+            -360_000_000_000_000
+        ])));
+    assert!(result[0]
+        .column(1)
+        .as_any()
+        .downcast_ref::<IntervalMonthDayNanoArray>()
+        .unwrap()
+        .eq(&IntervalMonthDayNanoArray::from(vec![
+            IntervalMonthDayNano::new(-14, 0, 0),
+            IntervalMonthDayNano::new(2_147_483_640, 0, 0),
+        ])));
+}
+
 pub fn verify_arrow_results(result: Vec<RecordBatch>) {
     assert!(result.len() == 2);
 
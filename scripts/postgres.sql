@@ -2,9 +2,12 @@ DROP TABLE IF EXISTS test_table;
 DROP TABLE IF EXISTS test_str;
 DROP TABLE IF EXISTS test_types;
 DROP TABLE IF EXISTS test_infinite_values;
+DROP TYPE IF EXISTS test_shape;
+DROP TYPE IF EXISTS test_point;
 DROP TYPE IF EXISTS happiness;
 DROP EXTENSION IF EXISTS citext;
 DROP EXTENSION IF EXISTS ltree;
+DROP EXTENSION IF EXISTS hstore;
 
 CREATE TABLE IF NOT EXISTS test_table(
     test_int INTEGER NOT NULL,
@@ -51,8 +54,11 @@ INSERT INTO test_str VALUES (7, 'Mixed', 'Ha好ち😁ðy̆');
 INSERT INTO test_str VALUES (8, '', NULL);
 
 CREATE TYPE happiness AS ENUM ('happy', 'very happy', 'ecstatic');
+CREATE TYPE test_point AS (x INTEGER, y DOUBLE PRECISION);
+CREATE TYPE test_shape AS (name TEXT, origin test_point, corners test_point[], mood happiness, created TIMESTAMPTZ);
 CREATE EXTENSION citext;
 CREATE EXTENSION ltree;
+CREATE EXTENSION hstore;
 CREATE TABLE IF NOT EXISTS test_types(
     test_bool BOOLEAN,
     test_date DATE,